@@ -1,3 +1,9 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
 use derive_more::Deref;
 use futures_util::future::join_all;
@@ -7,13 +13,81 @@ use eyre::Context;
 use itertools::{Either, Itertools};
 use tracing::{info, instrument};
 
-use hyperlane_core::{HyperlaneMessage, InterchainSecurityModule, ModuleType, H256, U256};
+use hyperlane_core::{
+    HyperlaneMessage, InterchainSecurityModule, ModuleType, ReprepareReason, H256, U256,
+};
 
 use super::{MessageMetadataBuilder, Metadata, MetadataBuilder};
 
 /// Bytes used to store one member of the (start, end) range tuple
 /// Copied from `AggregationIsmMetadata.sol`
 const METADATA_RANGE_SIZE: usize = 4;
+/// Calldata gas per zero byte, see EIP-2028
+const ZERO_BYTE_GAS: u64 = 4;
+/// Calldata gas per non-zero byte, see EIP-2028
+const NON_ZERO_BYTE_GAS: u64 = 16;
+/// How long the tracker remembers since when a message was deliverable,
+/// as a multiple of the max wait
+const VIABLE_SINCE_RETENTION_FACTOR: u32 = 10;
+
+/// Remembers what verifying with each sub-module of aggregation ISMs cost,
+/// and since when messages could have been delivered. This lets builders
+/// briefly wait for sub-modules whose metadata isn't available yet, e.g.
+/// because their validators haven't reached quorum, but that are usually
+/// cheaper than the ones that are available.
+#[derive(Debug)]
+pub struct AggregationCostTracker {
+    max_wait: Duration,
+    sub_module_costs: Mutex<HashMap<H256, U256>>,
+    viable_since: Mutex<HashMap<H256, Instant>>,
+}
+
+impl AggregationCostTracker {
+    /// A zero `max_wait` disables waiting for cheaper sub-modules
+    pub fn new(max_wait: Duration) -> Self {
+        Self {
+            max_wait,
+            sub_module_costs: Default::default(),
+            viable_since: Default::default(),
+        }
+    }
+
+    fn record_cost(&self, ism: H256, cost: U256) {
+        self.sub_module_costs.lock().unwrap().insert(ism, cost);
+    }
+
+    fn known_cost(&self, ism: &H256) -> Option<U256> {
+        self.sub_module_costs.lock().unwrap().get(ism).copied()
+    }
+
+    /// Whether the message can still wait for cheaper sub-modules, given
+    /// that it can be delivered now
+    fn can_wait(&self, message_id: H256) -> bool {
+        if self.max_wait.is_zero() {
+            return false;
+        }
+        let mut viable_since = self.viable_since.lock().unwrap();
+        let retention = self.max_wait * VIABLE_SINCE_RETENTION_FACTOR;
+        viable_since.retain(|_, since| since.elapsed() < retention);
+        viable_since
+            .entry(message_id)
+            .or_insert_with(Instant::now)
+            .elapsed()
+            < self.max_wait
+    }
+
+    /// Like `can_wait`, but without starting the wait of messages that
+    /// weren't waiting yet
+    fn would_wait(&self, message_id: H256) -> bool {
+        !self.max_wait.is_zero()
+            && self
+                .viable_since
+                .lock()
+                .unwrap()
+                .get(&message_id)
+                .map_or(true, |since| since.elapsed() < self.max_wait)
+    }
+}
 
 #[derive(Clone, Debug, new, Deref)]
 pub struct AggregationIsmMetadataBuilder {
@@ -74,6 +148,50 @@ impl AggregationIsmMetadataBuilder {
         buffer
     }
 
+    /// The cost of verifying with a sub-module: the gas used by its
+    /// `verify`, plus the calldata gas of its metadata
+    fn sub_module_cost(verify_gas: U256, metadata: &[u8]) -> U256 {
+        let calldata_gas: u64 = metadata
+            .iter()
+            .map(|byte| {
+                if *byte == 0 {
+                    ZERO_BYTE_GAS
+                } else {
+                    NON_ZERO_BYTE_GAS
+                }
+            })
+            .sum();
+        verify_gas.saturating_add(calldata_gas.into())
+    }
+
+    /// The total cost of the `n` cheapest of `costs`
+    fn n_cheapest_cost(costs: impl IntoIterator<Item = U256>, n: usize) -> U256 {
+        costs
+            .into_iter()
+            .sorted()
+            .take(n)
+            .fold(U256::zero(), U256::saturating_add)
+    }
+
+    /// Whether sub-modules whose metadata isn't available yet would make
+    /// for a cheaper combination than the available ones, going by what
+    /// they cost before
+    fn cheaper_when_pending_available(
+        available_costs: &[U256],
+        pending_costs: &[U256],
+        threshold: usize,
+    ) -> bool {
+        if pending_costs.is_empty() {
+            return false;
+        }
+        let available = Self::n_cheapest_cost(available_costs.iter().copied(), threshold);
+        let all = Self::n_cheapest_cost(
+            available_costs.iter().chain(pending_costs).copied(),
+            threshold,
+        );
+        all < available
+    }
+
     fn n_cheapest_metas(
         mut metas_and_gas: Vec<(SubModuleMetadata, U256)>,
         n: usize,
@@ -87,11 +205,45 @@ impl AggregationIsmMetadataBuilder {
         cheapest.into_iter().map(|(meta, _)| meta).collect()
     }
 
+    /// Returns, for each sub-module, the metadata the aggregation metadata
+    /// includes for it, if any. Unused sub-modules keep a zeroed range.
+    pub(super) fn sub_module_metadata(metadata: &[u8], ism_count: usize) -> Vec<Option<Vec<u8>>> {
+        let read_index = |offset: usize| {
+            metadata
+                .get(offset..offset + METADATA_RANGE_SIZE)
+                .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()) as usize)
+        };
+        (0..ism_count)
+            .map(|index| {
+                let range_start = METADATA_RANGE_SIZE * 2 * index;
+                let start = read_index(range_start).filter(|start| *start != 0)?;
+                let end = read_index(range_start + METADATA_RANGE_SIZE)?;
+                metadata.get(start..end).map(<[u8]>::to_vec)
+            })
+            .collect()
+    }
+
+    /// Returns why the aggregation can't reach its threshold, if more of its
+    /// sub-modules reject every message than it can do without
+    fn threshold_blocked(
+        blocked: &[ReprepareReason],
+        ism_count: usize,
+        threshold: usize,
+    ) -> Option<ReprepareReason> {
+        if blocked.len() > ism_count.saturating_sub(threshold) {
+            blocked.first().cloned()
+        } else {
+            None
+        }
+    }
+
     async fn cheapest_valid_metas(
+        &self,
         sub_modules: Vec<IsmAndMetadata>,
         message: &HyperlaneMessage,
         threshold: usize,
         err_isms: Vec<(H256, Option<ModuleType>)>,
+        pending_isms: Vec<H256>,
     ) -> Option<Vec<SubModuleMetadata>> {
         let gas_cost_results: Vec<_> = join_all(
             sub_modules
@@ -100,10 +252,18 @@ impl AggregationIsmMetadataBuilder {
         )
         .await;
         // Filter out the ISMs with a gas cost estimate
+        let costs = self.aggregation_costs();
         let metas_and_gas: Vec<_> = sub_modules
             .into_iter()
             .zip(gas_cost_results.into_iter())
-            .filter_map(|(module, gas_cost)| gas_cost.ok().flatten().map(|gc| (module.meta, gc)))
+            .filter_map(|(module, gas_cost)| {
+                let gas_cost = gas_cost.ok().flatten()?;
+                let cost = Self::sub_module_cost(gas_cost, &module.meta.metadata);
+                if !self.read_only {
+                    costs.record_cost(module.ism.address(), cost);
+                }
+                Some((module.meta, cost))
+            })
             .collect();
 
         let metas_and_gas_count = metas_and_gas.len();
@@ -111,6 +271,22 @@ impl AggregationIsmMetadataBuilder {
             info!(?err_isms, %metas_and_gas_count, %threshold, message_id=?message.id(), "Could not fetch all metadata, ISM metadata count did not reach aggregation threshold");
             return None;
         }
+
+        let available_costs = metas_and_gas.iter().map(|(_, cost)| *cost).collect_vec();
+        let pending_costs = pending_isms
+            .iter()
+            .filter_map(|ism| costs.known_cost(ism))
+            .collect_vec();
+        if Self::cheaper_when_pending_available(&available_costs, &pending_costs, threshold)
+            && if self.read_only {
+                costs.would_wait(message.id())
+            } else {
+                costs.can_wait(message.id())
+            }
+        {
+            info!(?pending_isms, %threshold, message_id=?message.id(), "Waiting for the metadata of cheaper sub-modules");
+            return None;
+        }
         Some(Self::n_cheapest_metas(metas_and_gas, threshold))
     }
 }
@@ -121,8 +297,10 @@ impl MetadataBuilder for AggregationIsmMetadataBuilder {
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn build(&self, ism_address: H256, message: &HyperlaneMessage) -> eyre::Result<Metadata> {
         const CTX: &str = "When fetching AggregationIsm metadata";
-        let ism = self.build_aggregation_ism(ism_address).await.context(CTX)?;
-        let (ism_addresses, threshold) = ism.modules_and_threshold(message).await.context(CTX)?;
+        let (ism_addresses, threshold) = self
+            .modules_and_threshold(ism_address, message)
+            .await
+            .context(CTX)?;
         let threshold = threshold as usize;
 
         let sub_modules_and_metas = join_all(
@@ -144,6 +322,37 @@ impl MetadataBuilder for AggregationIsmMetadataBuilder {
             return Ok(first_refusal);
         }
 
+        // Sub-modules that reject every message, e.g. paused ones, only block
+        // the aggregation if the others can't reach the threshold on their own
+        let blocked: Vec<_> = sub_modules_and_metas
+            .iter()
+            .filter_map(|result| match result {
+                Ok(sub_module_and_meta) => match &sub_module_and_meta.metadata {
+                    Metadata::Blocked(reason) => Some(reason.clone()),
+                    _ => None,
+                },
+                Err(_) => None,
+            })
+            .collect();
+        if let Some(reason) = Self::threshold_blocked(&blocked, ism_addresses.len(), threshold) {
+            info!(?reason, blocked_count=blocked.len(), %threshold, message_id=?message.id(), "Aggregation ISM threshold can't be reached");
+            return Ok(Metadata::Blocked(reason));
+        }
+
+        // Sub-modules whose metadata may become available later
+        let pending_isms = sub_modules_and_metas
+            .iter()
+            .zip(ism_addresses.iter())
+            .filter_map(|(result, ism_address)| match result {
+                Ok(sub_module_and_meta)
+                    if matches!(sub_module_and_meta.metadata, Metadata::CouldNotFetch) =>
+                {
+                    Some(*ism_address)
+                }
+                _ => None,
+            })
+            .collect_vec();
+
         // Partitions things into
         // 1. ok_sub_modules: ISMs with metadata with valid metadata
         // 2. err_sub_modules: ISMs with invalid metadata
@@ -162,12 +371,18 @@ impl MetadataBuilder for AggregationIsmMetadataBuilder {
                 },
                 Err(_) => Either::Right((*ism_address, None)),
             });
-        let maybe_aggregation_metadata =
-            Self::cheapest_valid_metas(ok_sub_modules, message, threshold, err_sub_modules)
-                .await
-                .map_or(Metadata::CouldNotFetch, |mut metas| {
-                    Metadata::Found(Self::format_metadata(&mut metas, ism_addresses.len()))
-                });
+        let maybe_aggregation_metadata = self
+            .cheapest_valid_metas(
+                ok_sub_modules,
+                message,
+                threshold,
+                err_sub_modules,
+                pending_isms,
+            )
+            .await
+            .map_or(Metadata::CouldNotFetch, |mut metas| {
+                Metadata::Found(Self::format_metadata(&mut metas, ism_addresses.len()))
+            });
         Ok(maybe_aggregation_metadata)
     }
 }
@@ -178,6 +393,39 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_sub_module_metadata() {
+        let mut metadatas = vec![
+            SubModuleMetadata::new(0, vec![1; 4]),
+            // Sub-modules like null ISMs are chosen with empty metadata
+            SubModuleMetadata::new(2, vec![]),
+        ];
+        let metadata = AggregationIsmMetadataBuilder::format_metadata(&mut metadatas, 3);
+        assert_eq!(
+            AggregationIsmMetadataBuilder::sub_module_metadata(&metadata, 3),
+            vec![Some(vec![1; 4]), None, Some(vec![])]
+        );
+    }
+
+    #[test]
+    fn test_threshold_blocked_only_when_threshold_unreachable() {
+        let blocked = vec![ReprepareReason::IsmPaused];
+        // 2 of 3: the two other sub-modules can still verify the message
+        assert_eq!(
+            AggregationIsmMetadataBuilder::threshold_blocked(&blocked, 3, 2),
+            None
+        );
+        // 3 of 3: every sub-module is needed
+        assert_eq!(
+            AggregationIsmMetadataBuilder::threshold_blocked(&blocked, 3, 3),
+            Some(ReprepareReason::IsmPaused)
+        );
+        assert_eq!(
+            AggregationIsmMetadataBuilder::threshold_blocked(&[], 3, 3),
+            None
+        );
+    }
+
     #[test]
     fn test_format_n_of_n_metadata_works_correctly() {
         let mut metadatas = vec![
@@ -246,6 +494,61 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_sub_module_cost_includes_calldata() {
+        assert_eq!(
+            AggregationIsmMetadataBuilder::sub_module_cost(100.into(), &[0, 1, 0, 2]),
+            U256::from(100 + 2 * ZERO_BYTE_GAS + 2 * NON_ZERO_BYTE_GAS)
+        );
+    }
+
+    #[test]
+    fn test_cheaper_when_pending_available() {
+        let costs = |costs: &[u64]| costs.iter().map(|cost| U256::from(*cost)).collect_vec();
+        // A pending sub-module that used to cost 10 would replace the one costing 50
+        assert!(
+            AggregationIsmMetadataBuilder::cheaper_when_pending_available(
+                &costs(&[20, 50, 100]),
+                &costs(&[10]),
+                2
+            )
+        );
+        // Pending sub-modules that are more expensive aren't worth waiting for
+        assert!(
+            !AggregationIsmMetadataBuilder::cheaper_when_pending_available(
+                &costs(&[20, 50]),
+                &costs(&[60]),
+                2
+            )
+        );
+        assert!(
+            !AggregationIsmMetadataBuilder::cheaper_when_pending_available(
+                &costs(&[20, 50]),
+                &[],
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn test_cost_tracker_waits_at_most_max_wait() {
+        let message_id = H256::repeat_byte(1);
+        assert!(!AggregationCostTracker::new(Duration::ZERO).can_wait(message_id));
+
+        let tracker = AggregationCostTracker::new(Duration::from_secs(60));
+        // Checking doesn't start the wait
+        assert!(tracker.would_wait(message_id));
+        assert!(tracker.viable_since.lock().unwrap().is_empty());
+        assert!(tracker.can_wait(message_id));
+        tracker
+            .viable_since
+            .lock()
+            .unwrap()
+            .insert(message_id, Instant::now() - Duration::from_secs(61));
+        assert!(!tracker.can_wait(message_id));
+        assert!(!tracker.would_wait(message_id));
+    }
+
     #[test]
     fn test_n_cheapest_metas_works() {
         let metas_and_gas = vec![
@@ -0,0 +1,145 @@
+#![allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+
+use async_trait::async_trait;
+use derive_more::Deref;
+use derive_new::new;
+use ethers::abi::Token;
+use eyre::Context;
+use hyperlane_core::{ArbL2ToL1Tx, HyperlaneMessage, H256};
+use tracing::{info, instrument};
+
+use super::{base::MessageMetadataBuilder, Metadata, MetadataBuilder};
+
+/// Builds metadata for ISMs that verify messages sent from Arbitrum to L1
+/// by executing the message's L2 to L1 transaction in the Arbitrum outbox.
+#[derive(Clone, Debug, new, Deref)]
+pub struct ArbL2ToL1MetadataBuilder {
+    base: MessageMetadataBuilder,
+}
+
+#[async_trait]
+impl MetadataBuilder for ArbL2ToL1MetadataBuilder {
+    #[instrument(err, skip(self, message))]
+    async fn build(&self, ism_address: H256, message: &HyperlaneMessage) -> eyre::Result<Metadata> {
+        const CTX: &str = "When fetching ArbL2ToL1 metadata";
+        let ism = self
+            .build_arb_l2_to_l1_ism(ism_address)
+            .await
+            .context(CTX)?;
+
+        // If the outbox transaction was already executed, e.g. by someone
+        // else, the ISM verifies the message without metadata
+        if ism.is_verified(message).await.context(CTX)? {
+            return Ok(Metadata::Found(vec![]));
+        }
+
+        let Some(block_number) = self.dispatched_block_number(message).context(CTX)? else {
+            info!(hyp_message=?message, "Could not fetch metadata: dispatch block of message is unknown");
+            return Ok(Metadata::CouldNotFetch);
+        };
+        let hook = ism.authorized_hook().await.context(CTX)?;
+        let prover = self.build_arbitrum_outbox_prover().await.context(CTX)?;
+        let Some(tx) = prover
+            .l2_to_l1_tx(block_number, hook, message.id())
+            .await
+            .context(CTX)?
+        else {
+            info!(hyp_message=?message, block_number, "Could not fetch metadata: no L2 to L1 transaction from the authorized hook found for message");
+            return Ok(Metadata::CouldNotFetch);
+        };
+
+        if ism.is_spent(tx.position).await.context(CTX)? {
+            info!(hyp_message=?message, position=?tx.position, "Could not fetch metadata: outbox transaction was executed but the message is not verified");
+            return Ok(Metadata::CouldNotFetch);
+        }
+
+        // The transaction can only be executed once a send root including it
+        // was confirmed, i.e. after the challenge period
+        let Some(confirmed_block) = ism.latest_confirmed_block().await.context(CTX)? else {
+            info!(hyp_message=?message, "Could not fetch metadata: no send root was confirmed recently");
+            return Ok(Metadata::CouldNotFetch);
+        };
+        let send_count = prover.send_count(confirmed_block).await.context(CTX)?;
+        let position = tx.position.as_u64();
+        if send_count <= position {
+            info!(
+                hyp_message=?message, position, send_count,
+                "Could not fetch metadata: challenge period of the L2 to L1 transaction has not passed"
+            );
+            return Ok(Metadata::CouldNotFetch);
+        }
+
+        let proof = prover
+            .outbox_proof(send_count, position)
+            .await
+            .context(CTX)?;
+        Ok(Metadata::Found(format_metadata(proof, &tx)))
+    }
+}
+
+/// Encodes the arguments of the outbox's `executeTransaction` call, as
+/// expected by the ISM.
+fn format_metadata(proof: Vec<H256>, tx: &ArbL2ToL1Tx) -> Vec<u8> {
+    ethers::abi::encode(&[
+        Token::Array(
+            proof
+                .into_iter()
+                .map(|node| Token::FixedBytes(node.to_fixed_bytes().into()))
+                .collect(),
+        ),
+        Token::Uint(tx.position.into()),
+        Token::Address(tx.caller.into()),
+        Token::Address(tx.destination.into()),
+        Token::Uint(tx.arb_block_num.into()),
+        Token::Uint(tx.eth_block_num.into()),
+        Token::Uint(tx.timestamp.into()),
+        Token::Uint(tx.callvalue.into()),
+        Token::Bytes(tx.data.clone()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::abi::{decode, ParamType};
+    use hyperlane_core::{H160, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_format_metadata_matches_execute_transaction_args() {
+        let tx = ArbL2ToL1Tx {
+            caller: H160::repeat_byte(1).into(),
+            destination: H160::repeat_byte(2).into(),
+            position: U256::from(42),
+            arb_block_num: U256::from(100),
+            eth_block_num: U256::from(10),
+            timestamp: U256::from(1_700_000_000),
+            callvalue: U256::zero(),
+            data: vec![0xab; 36],
+        };
+        let metadata = format_metadata(vec![H256::repeat_byte(3); 2], &tx);
+
+        let tokens = decode(
+            &[
+                ParamType::Array(Box::new(ParamType::FixedBytes(32))),
+                ParamType::Uint(256),
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Bytes,
+            ],
+            &metadata,
+        )
+        .unwrap();
+        assert_eq!(tokens[0].clone().into_array().unwrap().len(), 2);
+        assert_eq!(tokens[1].clone().into_uint().unwrap(), 42.into());
+        assert_eq!(
+            tokens[2].clone().into_address().unwrap(),
+            ethers::types::H160::repeat_byte(1)
+        );
+        assert_eq!(tokens[8].clone().into_bytes().unwrap(), tx.data);
+    }
+}
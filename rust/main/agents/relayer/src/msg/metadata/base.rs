@@ -13,10 +13,16 @@ use std::{
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::metadata::{
-        multisig::{MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder},
-        AggregationIsmMetadataBuilder, CcipReadIsmMetadataBuilder, NullMetadataBuilder,
-        RoutingIsmMetadataBuilder,
+        ism_cache::IsmCacheKey,
+        multisig::{
+            MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder,
+            WeightedMerkleRootMultisigMetadataBuilder, WeightedMessageIdMultisigMetadataBuilder,
+        },
+        AggregationCostTracker, AggregationIsmMetadataBuilder, ArbL2ToL1MetadataBuilder,
+        CcipReadClient, CcipReadIsmMetadataBuilder, IsmCache, NullMetadataBuilder,
+        OpL2ToL1MetadataBuilder, RoutingIsmMetadataBuilder,
     },
+    msg::op_withdrawal::OpWithdrawalScheduler,
     settings::matching_list::MatchingList,
 };
 use async_trait::async_trait;
@@ -31,9 +37,10 @@ use hyperlane_base::{
     CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer,
 };
 use hyperlane_core::{
-    accumulator::merkle::Proof, AggregationIsm, CcipReadIsm, Checkpoint, HyperlaneDomain,
-    HyperlaneMessage, InterchainSecurityModule, Mailbox, ModuleType, MultisigIsm, RoutingIsm,
-    ValidatorAnnounce, H160, H256,
+    accumulator::merkle::Proof, AggregationIsm, ArbL2ToL1Ism, ArbitrumOutboxProver, CcipReadIsm,
+    Checkpoint, HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, Mailbox, ModuleType,
+    MultisigIsm, NullIsm, OpL2ToL1Ism, OpWithdrawalProver, ReprepareReason, RoutingIsm,
+    ValidatorAnnounce, WeightedMultisigIsm, H160, H256,
 };
 
 use tokio::sync::RwLock;
@@ -57,6 +64,10 @@ pub enum Metadata {
     /// prohibit all metadata for the message from being built.
     /// Provides the reason for the refusal.
     Refused(String),
+    /// The ISM rejects the message whatever its metadata until the ISM's
+    /// on-chain state changes, e.g. because it is paused.
+    /// Provides the reason to retry the message with.
+    Blocked(ReprepareReason),
 }
 
 #[derive(Debug)]
@@ -191,6 +202,9 @@ pub struct MessageMetadataBuilder {
     /// of the recursion to avoid infinite loops.
     pub depth: u32,
     pub app_context: Option<String>,
+    /// Set when metadata is only built to be inspected. Building it then
+    /// has no side effects, e.g. no transactions are scheduled.
+    pub read_only: bool,
 }
 
 impl Deref for MessageMetadataBuilder {
@@ -225,10 +239,19 @@ impl MessageMetadataBuilder {
             base,
             depth: 0,
             app_context,
+            read_only: false,
         })
     }
 
-    fn clone_with_incremented_depth(&self) -> Result<MessageMetadataBuilder> {
+    /// A copy of this builder whose metadata building has no side effects
+    pub fn as_read_only(&self) -> Self {
+        Self {
+            read_only: true,
+            ..self.clone()
+        }
+    }
+
+    pub(super) fn clone_with_incremented_depth(&self) -> Result<MessageMetadataBuilder> {
         let mut cloned = self.clone();
         cloned.depth += 1;
         if cloned.depth > cloned.max_depth {
@@ -249,8 +272,8 @@ impl MessageMetadataBuilder {
             .await
             .context("When building ISM")?;
 
-        let module_type = ism
-            .module_type()
+        let module_type = self
+            .module_type(ism_address)
             .await
             .context("When fetching module type")?;
         let cloned = self.clone_with_incremented_depth()?;
@@ -262,10 +285,18 @@ impl MessageMetadataBuilder {
             ModuleType::MessageIdMultisig => {
                 Box::new(MessageIdMultisigMetadataBuilder::new(cloned))
             }
+            ModuleType::WeightedMerkleRootMultisig => {
+                Box::new(WeightedMerkleRootMultisigMetadataBuilder::new(cloned))
+            }
+            ModuleType::WeightedMessageIdMultisig => {
+                Box::new(WeightedMessageIdMultisigMetadataBuilder::new(cloned))
+            }
             ModuleType::Routing => Box::new(RoutingIsmMetadataBuilder::new(cloned)),
             ModuleType::Aggregation => Box::new(AggregationIsmMetadataBuilder::new(cloned)),
-            ModuleType::Null => Box::new(NullMetadataBuilder::new()),
+            ModuleType::Null => Box::new(NullMetadataBuilder::new(cloned)),
             ModuleType::CcipRead => Box::new(CcipReadIsmMetadataBuilder::new(cloned)),
+            ModuleType::ArbL2ToL1 => Box::new(ArbL2ToL1MetadataBuilder::new(cloned)),
+            ModuleType::OpL2ToL1 => Box::new(OpL2ToL1MetadataBuilder::new(cloned)),
             _ => return Err(MetadataBuilderError::UnsupportedModuleType(module_type).into()),
         };
         let meta = metadata_builder
@@ -284,7 +315,7 @@ impl MessageMetadataBuilder {
 #[allow(clippy::too_many_arguments)]
 #[derive(new)]
 pub struct BaseMetadataBuilder {
-    origin_domain: HyperlaneDomain,
+    origin_chain_setup: ChainConf,
     destination_chain_setup: ChainConf,
     origin_prover_sync: Arc<RwLock<MerkleTreeBuilder>>,
     origin_validator_announce: Arc<dyn ValidatorAnnounce>,
@@ -292,6 +323,10 @@ pub struct BaseMetadataBuilder {
     metrics: Arc<CoreMetrics>,
     db: HyperlaneRocksDB,
     app_context_classifier: IsmAwareAppContextClassifier,
+    ism_cache: Arc<IsmCache>,
+    ccip_read_client: Arc<CcipReadClient>,
+    aggregation_costs: Arc<AggregationCostTracker>,
+    op_withdrawals: Arc<OpWithdrawalScheduler>,
     #[new(value = "7")]
     max_depth: u32,
 }
@@ -300,15 +335,15 @@ impl Debug for BaseMetadataBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "BaseMetadataBuilder {{ origin_domain: {:?} destination_chain_setup: {:?}, validator_announce: {:?} }}",
-            self.origin_domain, self.destination_chain_setup, self.origin_validator_announce
+            "BaseMetadataBuilder {{ origin_chain_setup: {:?} destination_chain_setup: {:?}, validator_announce: {:?} }}",
+            self.origin_chain_setup, self.destination_chain_setup, self.origin_validator_announce
         )
     }
 }
 
 impl BaseMetadataBuilder {
     pub fn origin_domain(&self) -> &HyperlaneDomain {
-        &self.origin_domain
+        &self.origin_chain_setup.domain
     }
 
     pub fn destination_domain(&self) -> &HyperlaneDomain {
@@ -345,6 +380,115 @@ impl BaseMetadataBuilder {
         Ok(merkle_leaf)
     }
 
+    pub fn ism_cache(&self) -> &IsmCache {
+        &self.ism_cache
+    }
+
+    pub fn ccip_read_client(&self) -> &CcipReadClient {
+        &self.ccip_read_client
+    }
+
+    pub fn aggregation_costs(&self) -> &AggregationCostTracker {
+        &self.aggregation_costs
+    }
+
+    pub fn op_withdrawals(&self) -> &OpWithdrawalScheduler {
+        &self.op_withdrawals
+    }
+
+    /// The module type of the ISM at `address`, cached
+    pub async fn module_type(&self, address: H256) -> Result<ModuleType> {
+        self.ism_cache
+            .module_types
+            .get_or_fetch(address, || async {
+                Ok(self.build_ism(address).await?.module_type().await?)
+            })
+            .await
+    }
+
+    /// The ISM the routing ISM at `address` routes `message` to, cached
+    pub async fn route(&self, address: H256, message: &HyperlaneMessage) -> Result<H256> {
+        self.ism_cache
+            .routes
+            .get_or_fetch(IsmCacheKey::new(address, message), || async {
+                Ok(self
+                    .build_routing_ism(address)
+                    .await?
+                    .route(message)
+                    .await?)
+            })
+            .await
+    }
+
+    /// The sub-modules and threshold of the aggregation ISM at `address`
+    /// for `message`, cached per ISM
+    pub async fn modules_and_threshold(
+        &self,
+        address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<H256>, u8)> {
+        self.ism_cache
+            .modules_and_thresholds
+            .get_or_fetch(address, || async {
+                Ok(self
+                    .build_aggregation_ism(address)
+                    .await?
+                    .modules_and_threshold(message)
+                    .await?)
+            })
+            .await
+    }
+
+    /// The validators and threshold of the multisig ISM at `address` for
+    /// `message`, cached per ISM
+    pub async fn validators_and_threshold(
+        &self,
+        address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<H256>, u8)> {
+        self.ism_cache
+            .validators_and_thresholds
+            .get_or_fetch(address, || async {
+                Ok(self
+                    .build_multisig_ism(address)
+                    .await?
+                    .validators_and_threshold(message)
+                    .await?)
+            })
+            .await
+    }
+
+    /// The weighted validators and threshold weight of the weighted multisig
+    /// ISM at `address` for `message`, cached per ISM
+    pub async fn validators_and_threshold_weight(
+        &self,
+        address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<(H256, u128)>, u128)> {
+        self.ism_cache
+            .validators_and_threshold_weights
+            .get_or_fetch(address, || async {
+                Ok(self
+                    .build_weighted_multisig_ism(address)
+                    .await?
+                    .validators_and_threshold_weight(message)
+                    .await?)
+            })
+            .await
+    }
+
+    /// Whether the null ISM at `address` is paused, and the relayer it
+    /// trusts if any, cached
+    pub async fn null_ism_probe(&self, address: H256) -> Result<(bool, Option<H256>)> {
+        self.ism_cache
+            .null_ism_probes
+            .get_or_fetch(address, || async {
+                let ism = self.build_null_ism(address).await?;
+                Ok(tokio::try_join!(ism.paused(), ism.trusted_relayer())?)
+            })
+            .await
+    }
+
     pub async fn build_ism(&self, address: H256) -> Result<Box<dyn InterchainSecurityModule>> {
         self.destination_chain_setup
             .build_ism(address, &self.metrics)
@@ -363,6 +507,15 @@ impl BaseMetadataBuilder {
             .await
     }
 
+    pub async fn build_weighted_multisig_ism(
+        &self,
+        address: H256,
+    ) -> Result<Box<dyn WeightedMultisigIsm>> {
+        self.destination_chain_setup
+            .build_weighted_multisig_ism(address, &self.metrics)
+            .await
+    }
+
     pub async fn build_aggregation_ism(&self, address: H256) -> Result<Box<dyn AggregationIsm>> {
         self.destination_chain_setup
             .build_aggregation_ism(address, &self.metrics)
@@ -375,6 +528,43 @@ impl BaseMetadataBuilder {
             .await
     }
 
+    pub async fn build_arb_l2_to_l1_ism(&self, address: H256) -> Result<Box<dyn ArbL2ToL1Ism>> {
+        self.destination_chain_setup
+            .build_arb_l2_to_l1_ism(address, &self.metrics)
+            .await
+    }
+
+    pub async fn build_arbitrum_outbox_prover(&self) -> Result<Box<dyn ArbitrumOutboxProver>> {
+        self.origin_chain_setup
+            .build_arbitrum_outbox_prover(&self.metrics)
+            .await
+    }
+
+    pub async fn build_null_ism(&self, address: H256) -> Result<Box<dyn NullIsm>> {
+        self.destination_chain_setup
+            .build_null_ism(address, &self.metrics)
+            .await
+    }
+
+    pub async fn build_op_l2_to_l1_ism(&self, address: H256) -> Result<Box<dyn OpL2ToL1Ism>> {
+        self.destination_chain_setup
+            .build_op_l2_to_l1_ism(address, &self.metrics)
+            .await
+    }
+
+    pub async fn build_op_withdrawal_prover(&self) -> Result<Box<dyn OpWithdrawalProver>> {
+        self.origin_chain_setup
+            .build_op_withdrawal_prover(&self.metrics)
+            .await
+    }
+
+    /// The origin block number the message was dispatched in, if indexed
+    pub fn dispatched_block_number(&self, message: &HyperlaneMessage) -> Result<Option<u64>> {
+        Ok(self
+            .db
+            .retrieve_dispatched_block_number_by_nonce(&message.nonce)?)
+    }
+
     pub async fn build_checkpoint_syncer(
         &self,
         message: &HyperlaneMessage,
@@ -0,0 +1,543 @@
+use std::{
+    error::Error,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use hyperlane_base::CoreMetrics;
+use hyperlane_core::{utils::bytes_to_hex, OffchainLookup};
+use prometheus::{HistogramVec, IntCounterVec};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    Client, StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, warn};
+
+use crate::settings::CcipReadConf;
+
+#[derive(Serialize, Deserialize)]
+struct OffchainResponse {
+    data: String,
+}
+
+/// Why a gateway couldn't provide offchain data
+#[derive(Debug, thiserror::Error)]
+pub enum GatewayError {
+    /// The URL is malformed, or its host isn't allowed by the config
+    #[error("Gateway not allowed: {0}")]
+    NotAllowed(String),
+    /// The gateway didn't respond in time
+    #[error("Gateway timed out")]
+    Timeout,
+    /// The response was larger than allowed by the config
+    #[error("Gateway response exceeds {0} bytes")]
+    ResponseTooLarge(usize),
+    /// The gateway responded with an error status
+    #[error("Gateway responded with status {0}")]
+    Status(StatusCode),
+    /// The response isn't of the form `{"data": "0x..."}`
+    #[error("Invalid gateway response: {0}")]
+    InvalidResponse(String),
+    /// The request failed, e.g. because the gateway is down
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+impl GatewayError {
+    /// Whether the request may succeed if retried. Per EIP-3668, 4xx
+    /// responses mean the request itself is bad.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout | Self::Request(_) => true,
+            Self::Status(status) => status.is_server_error(),
+            Self::NotAllowed(_) | Self::ResponseTooLarge(_) | Self::InvalidResponse(_) => false,
+        }
+    }
+
+    fn status_label(&self) -> &'static str {
+        match self {
+            Self::NotAllowed(_) => "not_allowed",
+            Self::Timeout => "timeout",
+            Self::ResponseTooLarge(_) => "too_large",
+            Self::Status(_) => "error_status",
+            Self::InvalidResponse(_) => "invalid_response",
+            Self::Request(_) => "request_failed",
+        }
+    }
+}
+
+/// Metrics of the requests made to CCIP read gateways
+#[derive(Clone, Debug)]
+pub struct CcipReadMetrics {
+    /// Requests made to gateways.
+    ///
+    /// Labels:
+    /// - `gateway`: The host of the gateway if it is allowed by the config,
+    ///   `other` otherwise.
+    /// - `status`: `success`, `rejected` if the ISM rejected the data, or
+    ///   the kind of failure.
+    requests: IntCounterVec,
+    /// How long gateway requests took, including failed ones.
+    ///
+    /// Labels:
+    /// - `gateway`: The host of the gateway if it is allowed by the config,
+    ///   `other` otherwise.
+    latency: HistogramVec,
+}
+
+impl CcipReadMetrics {
+    pub fn new(metrics: &CoreMetrics) -> Self {
+        let requests = metrics
+            .new_int_counter(
+                "ccip_read_gateway_requests",
+                "Number of requests made to CCIP read gateways",
+                &["gateway", "status"],
+            )
+            .expect("failed to register ccip_read_gateway_requests metric");
+        let latency = metrics
+            .new_histogram(
+                "ccip_read_gateway_latency_seconds",
+                "Duration of requests made to CCIP read gateways",
+                &["gateway"],
+                vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0],
+            )
+            .expect("failed to register ccip_read_gateway_latency_seconds metric");
+        Self { requests, latency }
+    }
+}
+
+/// Queries CCIP read gateways following EIP-3668, bounding how long they
+/// can take, how much data they can return and which of them are queried.
+///
+/// Without allowed hosts, gateways can be any host an ISM deployer chooses,
+/// so gateways on private, loopback and link-local addresses are refused.
+#[derive(Debug)]
+pub struct CcipReadClient {
+    client: Client,
+    conf: CcipReadConf,
+    metrics: CcipReadMetrics,
+}
+
+impl CcipReadClient {
+    pub fn new(conf: CcipReadConf, metrics: CcipReadMetrics) -> eyre::Result<Self> {
+        let mut builder = Client::builder()
+            .timeout(conf.timeout)
+            // Redirects could lead to hosts that aren't allowed
+            .redirect(reqwest::redirect::Policy::none());
+        if conf.allowed_hosts.is_empty() {
+            // Filtering when connecting rather than when checking the URL,
+            // so a host can't resolve to another address in between
+            builder = builder.dns_resolver(Arc::new(PublicAddressResolver));
+        }
+        Ok(Self {
+            client: builder.build()?,
+            conf,
+            metrics,
+        })
+    }
+
+    /// How many of the URLs of a lookup are tried
+    pub fn max_urls(&self) -> usize {
+        self.conf.max_urls
+    }
+
+    /// How long fetching the data of a lookup may take in total, across
+    /// all of its URLs and nested lookups
+    pub fn total_timeout(&self) -> Duration {
+        self.conf.total_timeout
+    }
+
+    /// Fetches the data of `lookup` from the gateway at `url`, which is one
+    /// of the lookup's URL templates. Transient failures are retried with
+    /// exponential backoff.
+    pub async fn fetch(&self, url: &str, lookup: &OffchainLookup) -> Result<Vec<u8>, GatewayError> {
+        // Need to explicitly convert the sender H160 the hex because the `ToString` implementation
+        // for `H160` truncates the output. (e.g. `0xc66a…7b6f` instead of returning
+        // the full address)
+        let sender = bytes_to_hex(lookup.sender.as_bytes());
+        let data = bytes_to_hex(&lookup.call_data);
+        let interpolated_url = url.replace("{sender}", &sender).replace("{data}", &data);
+        let gateway = self.gateway_label(&interpolated_url);
+        let parsed_url = match self.check_url(&interpolated_url) {
+            Ok(url) => url,
+            Err(err) => {
+                self.record(&gateway, Err(&err));
+                return Err(err);
+            }
+        };
+        // Per EIP-3668, URLs without `{data}` take the data in a POST body
+        let body = (!url.contains("{data}")).then(|| json!({ "sender": sender, "data": data }));
+
+        let mut backoff = self.conf.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            let start = Instant::now();
+            let result = self.request(parsed_url.clone(), body.as_ref()).await;
+            self.metrics
+                .latency
+                .with_label_values(&[&gateway])
+                .observe(start.elapsed().as_secs_f64());
+            self.record(&gateway, result.as_ref().map(|_| ()));
+            match result {
+                Err(err) if err.is_transient() && attempt < self.conf.max_retries => {
+                    debug!(%gateway, attempt, error = %err, "Retrying CCIP read gateway request");
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Records that the ISM rejected the data returned by a gateway
+    pub fn record_rejected(&self, url: &str) {
+        self.metrics
+            .requests
+            .with_label_values(&[&self.gateway_label(url), "rejected"])
+            .inc();
+    }
+
+    fn record(&self, gateway: &str, result: Result<(), &GatewayError>) {
+        let status = match result {
+            Ok(()) => "success",
+            Err(err) => {
+                warn!(%gateway, error = %err, "CCIP read gateway request failed");
+                err.status_label()
+            }
+        };
+        self.metrics
+            .requests
+            .with_label_values(&[gateway, status])
+            .inc();
+    }
+
+    fn check_url(&self, url: &str) -> Result<Url, GatewayError> {
+        let parsed = Url::parse(url).map_err(|_| GatewayError::NotAllowed(url.to_owned()))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(GatewayError::NotAllowed(url.to_owned()));
+        }
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| GatewayError::NotAllowed(url.to_owned()))?
+            .to_lowercase();
+        if host_matches(&host, &self.conf.denied_hosts) {
+            return Err(GatewayError::NotAllowed(url.to_owned()));
+        }
+        if self.conf.allowed_hosts.is_empty() {
+            // Hosts that are names are checked when resolved
+            let ip = host.trim_start_matches('[').trim_end_matches(']');
+            if ip.parse::<IpAddr>().is_ok_and(|ip| !is_public(ip)) {
+                return Err(GatewayError::NotAllowed(url.to_owned()));
+            }
+        } else if !host_matches(&host, &self.conf.allowed_hosts) {
+            return Err(GatewayError::NotAllowed(url.to_owned()));
+        }
+        Ok(parsed)
+    }
+
+    /// The host of a gateway URL, used to label metrics. Hosts that aren't
+    /// allowed explicitly are all labelled `other`, since any host can be
+    /// chosen by an ISM deployer.
+    fn gateway_label(&self, url: &str) -> String {
+        Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .filter(|host| host_matches(host, &self.conf.allowed_hosts))
+            .unwrap_or_else(|| "other".to_owned())
+    }
+
+    async fn request(
+        &self,
+        url: Url,
+        body: Option<&serde_json::Value>,
+    ) -> Result<Vec<u8>, GatewayError> {
+        let request = match body {
+            Some(body) => self.client.post(url).json(body),
+            None => self.client.get(url),
+        };
+        let mut response = request.send().await.map_err(timeout_or_request)?;
+        if !response.status().is_success() {
+            return Err(GatewayError::Status(response.status()));
+        }
+
+        // Read the body in chunks, so an oversized response is dropped
+        // without buffering all of it
+        let max = self.conf.max_response_bytes;
+        if response.content_length().unwrap_or_default() > max as u64 {
+            return Err(GatewayError::ResponseTooLarge(max));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(timeout_or_request)? {
+            if bytes.len() + chunk.len() > max {
+                return Err(GatewayError::ResponseTooLarge(max));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let response: OffchainResponse = serde_json::from_slice(&bytes)
+            .map_err(|err| GatewayError::InvalidResponse(err.to_string()))?;
+        let data = response.data.strip_prefix("0x").unwrap_or(&response.data);
+        ethers::utils::hex::decode(data)
+            .map_err(|err| GatewayError::InvalidResponse(err.to_string()))
+    }
+}
+
+/// Whether `host` is one of `hosts` or a subdomain of one of them
+fn host_matches(host: &str, hosts: &[String]) -> bool {
+    hosts
+        .iter()
+        .any(|allowed| host == allowed || host.ends_with(&format!(".{allowed}")))
+}
+
+/// Whether an address is reachable on the public internet, as opposed to
+/// e.g. the relayer's own host or private network
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 100.64.0.0/10 is shared by carrier-grade NATs
+    let shared = a == 100 && (b & 0xc0) == 64;
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || shared)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    // fc00::/7 is unique local, fe80::/10 is link-local
+    let unique_local = (first & 0xfe00) == 0xfc00;
+    let link_local = (first & 0xffc0) == 0xfe80;
+    !(ip.is_loopback() || ip.is_unspecified() || unique_local || link_local)
+}
+
+/// Resolves gateway hosts to their public addresses only
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public(name))
+    }
+}
+
+async fn resolve_public(name: Name) -> Result<Addrs, Box<dyn Error + Send + Sync>> {
+    let addrs = tokio::net::lookup_host((name.as_str(), 0))
+        .await?
+        .filter(|addr| is_public(addr.ip()))
+        .collect::<Vec<_>>();
+    if addrs.is_empty() {
+        return Err(format!("{} has no public address", name.as_str()).into());
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+fn timeout_or_request(err: reqwest::Error) -> GatewayError {
+    if err.is_timeout() {
+        GatewayError::Timeout
+    } else {
+        GatewayError::Request(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+    };
+
+    use axum::{extract::State, routing, Router};
+    use ccip_gateway::{CcipReadRequest, Gateway, Resolver, ResolverError};
+    use hyperlane_core::H160;
+    use prometheus::Registry;
+
+    use super::*;
+
+    /// Responds with the call data prefixed by the sender
+    #[derive(Debug)]
+    struct EchoResolver;
+
+    #[async_trait::async_trait]
+    impl Resolver for EchoResolver {
+        async fn resolve(&self, request: &CcipReadRequest) -> Result<Vec<u8>, ResolverError> {
+            Ok([request.sender.as_bytes(), &request.call_data].concat())
+        }
+    }
+
+    fn serve(router: Router) -> SocketAddr {
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(router.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn build_client(conf: CcipReadConf) -> CcipReadClient {
+        let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        CcipReadClient::new(conf, CcipReadMetrics::new(&core_metrics)).unwrap()
+    }
+
+    /// Allows the gateways served by the tests
+    fn local_conf() -> CcipReadConf {
+        CcipReadConf {
+            allowed_hosts: vec!["127.0.0.1".to_owned()],
+            ..Default::default()
+        }
+    }
+
+    fn lookup(sender: H160) -> OffchainLookup {
+        OffchainLookup {
+            sender,
+            urls: vec![],
+            call_data: vec![1, 2, 3],
+            callback_function: [0; 4],
+            extra_data: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_from_gateway() {
+        let sender = H160::repeat_byte(1);
+        let addr = serve(
+            Gateway::new()
+                .with_resolver(sender, Arc::new(EchoResolver))
+                .router(),
+        );
+        let client = build_client(local_conf());
+        let expected = [sender.as_bytes(), &[1, 2, 3]].concat();
+
+        // GET
+        let url = format!("http://{addr}/{{sender}}/{{data}}.json");
+        assert_eq!(client.fetch(&url, &lookup(sender)).await.unwrap(), expected);
+
+        // POST
+        let url = format!("http://{addr}/");
+        assert_eq!(client.fetch(&url, &lookup(sender)).await.unwrap(), expected);
+
+        // The gateway doesn't resolve the sender
+        assert!(matches!(
+            client.fetch(&url, &lookup(H160::zero())).await,
+            Err(GatewayError::Status(StatusCode::NOT_FOUND))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_allowed_and_denied_hosts() {
+        let client = build_client(CcipReadConf {
+            allowed_hosts: vec!["gateway.xyz".to_owned(), "127.0.0.1".to_owned()],
+            denied_hosts: vec!["bad.gateway.xyz".to_owned()],
+            ..Default::default()
+        });
+        assert!(client.check_url("https://gateway.xyz/{sender}").is_ok());
+        assert!(client.check_url("https://api.gateway.xyz/").is_ok());
+        assert!(client.check_url("https://bad.gateway.xyz/").is_err());
+        assert!(client.check_url("https://api.bad.gateway.xyz/").is_err());
+        assert!(client.check_url("https://evilgateway.xyz/").is_err());
+        assert!(client.check_url("file:///etc/passwd").is_err());
+
+        let sender = H160::repeat_byte(1);
+        assert!(matches!(
+            client
+                .fetch("https://other.xyz/{sender}/{data}", &lookup(sender))
+                .await,
+            Err(GatewayError::NotAllowed(_))
+        ));
+
+        // Only explicitly allowed hosts get their own metrics label
+        assert_eq!(
+            client.gateway_label("https://api.gateway.xyz/"),
+            "api.gateway.xyz"
+        );
+        assert_eq!(client.gateway_label("https://other.xyz/"), "other");
+    }
+
+    #[tokio::test]
+    async fn test_private_addresses_without_allowed_hosts() {
+        let client = build_client(CcipReadConf {
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        });
+        assert!(client.check_url("https://gateway.xyz/{sender}").is_ok());
+        assert!(client.check_url("https://8.8.8.8/").is_ok());
+        assert!(client.check_url("https://[2001:4860::8888]/").is_ok());
+        for url in [
+            "http://127.0.0.1/",
+            "http://10.0.0.1/",
+            "http://192.168.1.1/",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/",
+            "http://0.0.0.0/",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[fe80::1]/",
+            "http://[::ffff:127.0.0.1]/",
+        ] {
+            assert!(client.check_url(url).is_err(), "{url} is allowed");
+        }
+        assert_eq!(client.gateway_label("https://gateway.xyz/"), "other");
+
+        // Names are checked once resolved
+        let addr = serve(Router::new().route("/", routing::post(|| async { "" })));
+        let url = format!("http://localhost:{}/", addr.port());
+        assert!(matches!(
+            client.fetch(&url, &lookup(H160::repeat_byte(1))).await,
+            Err(GatewayError::Request(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retries_and_limits() {
+        // Fails once with a server error, then responds with too much data
+        let calls = Arc::new(AtomicU32::new(0));
+        let router = Router::new()
+            .route(
+                "/",
+                routing::post(|State(calls): State<Arc<AtomicU32>>| async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        Err(StatusCode::SERVICE_UNAVAILABLE)
+                    } else {
+                        Ok(axum::Json(json!({ "data": bytes_to_hex(&[0xab; 100]) })))
+                    }
+                }),
+            )
+            .with_state(calls.clone());
+        let url = format!("http://{}/", serve(router));
+        let lookup = lookup(H160::repeat_byte(1));
+
+        let client = build_client(CcipReadConf {
+            retry_backoff: Duration::from_millis(1),
+            ..local_conf()
+        });
+        assert_eq!(client.fetch(&url, &lookup).await.unwrap(), vec![0xab; 100]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let client = build_client(CcipReadConf {
+            max_response_bytes: 64,
+            ..local_conf()
+        });
+        assert!(matches!(
+            client.fetch(&url, &lookup).await,
+            Err(GatewayError::ResponseTooLarge(64))
+        ));
+        // Oversized responses aren't retried
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
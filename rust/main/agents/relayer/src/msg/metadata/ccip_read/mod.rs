@@ -0,0 +1,121 @@
+#![allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+
+use async_trait::async_trait;
+use derive_more::Deref;
+use derive_new::new;
+use eyre::Context;
+use hyperlane_core::{
+    CcipReadIsm, HyperlaneMessage, OffchainLookup, OffchainVerification, RawHyperlaneMessage, H256,
+};
+use tracing::{debug, info, instrument, warn};
+
+use super::{base::MessageMetadataBuilder, Metadata, MetadataBuilder};
+
+pub(crate) use client::{CcipReadClient, CcipReadMetrics};
+
+mod client;
+
+/// How many times an ISM may answer the data of a gateway with another
+/// `OffchainLookup`, as EIP-3668 allows callbacks to do
+const MAX_NESTED_LOOKUPS: usize = 4;
+
+#[derive(Clone, Debug, new, Deref)]
+pub struct CcipReadIsmMetadataBuilder {
+    base: MessageMetadataBuilder,
+}
+
+#[async_trait]
+impl MetadataBuilder for CcipReadIsmMetadataBuilder {
+    #[instrument(err, skip(self, message))]
+    async fn build(&self, ism_address: H256, message: &HyperlaneMessage) -> eyre::Result<Metadata> {
+        const CTX: &str = "When fetching CcipRead metadata";
+        let ism = self.build_ccip_read_ism(ism_address).await.context(CTX)?;
+
+        let Some(lookup) = ism
+            .get_offchain_verify_info(RawHyperlaneMessage::from(message).to_vec())
+            .await
+            .context(CTX)?
+        else {
+            info!("incorrectly configured getOffchainVerifyInfo, expected OffchainLookup revert");
+            return Ok(Metadata::CouldNotFetch);
+        };
+
+        let total_timeout = self.ccip_read_client().total_timeout();
+        match tokio::time::timeout(
+            total_timeout,
+            self.follow_lookup(ism.as_ref(), ism_address, lookup, message),
+        )
+        .await
+        {
+            Ok(metadata) => Ok(metadata),
+            Err(_) => {
+                warn!(?total_timeout, "Timed out fetching CCIP read metadata");
+                Ok(Metadata::CouldNotFetch)
+            }
+        }
+    }
+}
+
+impl CcipReadIsmMetadataBuilder {
+    /// Queries the gateways of `lookup` until the ISM verifies the message
+    /// with the data one of them returns, following the lookups the ISM
+    /// answers that data with
+    async fn follow_lookup(
+        &self,
+        ism: &dyn CcipReadIsm,
+        ism_address: H256,
+        mut lookup: OffchainLookup,
+        message: &HyperlaneMessage,
+    ) -> Metadata {
+        let client = self.ccip_read_client();
+        for depth in 0..=MAX_NESTED_LOOKUPS {
+            // EIP-3668 requires clients to reject lookups for other contracts
+            if H256::from(lookup.sender) != ism_address {
+                info!(sender = ?lookup.sender, "OffchainLookup sender is not the ISM");
+                return Metadata::CouldNotFetch;
+            }
+
+            let mut nested = None;
+            for url in lookup.urls.iter().take(client.max_urls()) {
+                let Ok(metadata) = client.fetch(url, &lookup).await else {
+                    // try the next URL
+                    continue;
+                };
+                // The callback of CCIP read ISMs is the mailbox's `process`, so
+                // the response is only useful if the ISM verifies the message with it
+                match ism.verify(&metadata, message).await {
+                    Ok(OffchainVerification::Verified) => return Metadata::Found(metadata),
+                    Ok(OffchainVerification::Rejected) => {
+                        warn!(
+                            url,
+                            "ISM rejected the data returned by the CCIP read gateway"
+                        );
+                        client.record_rejected(url);
+                    }
+                    Ok(OffchainVerification::Lookup(next)) => {
+                        debug!(url, depth, "ISM requested another offchain lookup");
+                        nested = Some(next);
+                        break;
+                    }
+                    Err(err) => {
+                        // The data may still be good, so the next URL is tried
+                        // rather than failing the whole lookup
+                        warn!(url, error = ?err, "Failed to verify CCIP read gateway data");
+                    }
+                }
+            }
+
+            match nested {
+                Some(next) => lookup = next,
+                // No metadata endpoints or endpoints down
+                None => return Metadata::CouldNotFetch,
+            }
+        }
+
+        info!(
+            max = MAX_NESTED_LOOKUPS,
+            "ISM requested too many nested offchain lookups"
+        );
+        Metadata::CouldNotFetch
+    }
+}
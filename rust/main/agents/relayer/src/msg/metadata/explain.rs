@@ -0,0 +1,254 @@
+use eyre::{Context, Result};
+use futures_util::future::{join_all, BoxFuture, FutureExt};
+use hyperlane_core::{HyperlaneMessage, ModuleType, H160, H256};
+use serde::{Deserialize, Serialize};
+
+use super::{AggregationIsmMetadataBuilder, MessageMetadataBuilder, Metadata, MetadataBuilder};
+
+/// A node of the ISM tree resolved for a message
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IsmExplanation {
+    pub address: H256,
+    /// `None` if the module type couldn't be fetched
+    pub module_type: Option<ModuleType>,
+    /// The validators of multisig ISMs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validators: Vec<ValidatorExplanation>,
+    /// The signature threshold of multisig ISMs, the threshold weight of
+    /// weighted multisig ISMs, or the number of sub-modules that need to
+    /// verify the message for aggregation ISMs
+    pub threshold: Option<u128>,
+    /// The outcome of building this ISM's metadata, if it was built
+    pub metadata: Option<MetadataOutcome>,
+    /// Whether the parent ISM's metadata uses this ISM, if the parent is a
+    /// routing or aggregation ISM
+    pub chosen: Option<bool>,
+    /// Why resolving this ISM failed, if it did
+    pub error: Option<String>,
+    /// The route of routing ISMs, or the sub-modules of aggregation ISMs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<IsmExplanation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorExplanation {
+    pub address: H256,
+    /// Only set for weighted multisig ISMs
+    pub weight: Option<u128>,
+    /// `None` if the validator's checkpoint syncer couldn't be reached or
+    /// has no checkpoint yet
+    pub latest_checkpoint_index: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum MetadataOutcome {
+    Found,
+    CouldNotFetch,
+    Refused(String),
+    Blocked(String),
+    Error(String),
+}
+
+impl From<&Metadata> for MetadataOutcome {
+    fn from(metadata: &Metadata) -> Self {
+        match metadata {
+            Metadata::Found(_) => Self::Found,
+            Metadata::CouldNotFetch => Self::CouldNotFetch,
+            Metadata::Refused(reason) => Self::Refused(reason.clone()),
+            Metadata::Blocked(reason) => Self::Blocked(reason.to_string()),
+        }
+    }
+}
+
+impl MessageMetadataBuilder {
+    /// Resolves the ISM tree rooted at `ism_address` for `message`.
+    /// If `build_metadata` is set, the metadata of the root ISM is built too,
+    /// which only makes sense for dispatched messages. It is built once and
+    /// without side effects; the other ISMs of the tree are given the part of
+    /// it they are verified with, so only the ISMs the metadata uses have one.
+    pub async fn explain(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+        build_metadata: bool,
+    ) -> IsmExplanation {
+        let builder = self.as_read_only();
+        let metadata = if build_metadata {
+            Some(
+                builder
+                    .build(ism_address, message)
+                    .await
+                    .map_err(|err| format!("{err:#}")),
+            )
+        } else {
+            None
+        };
+        builder.explain_node(ism_address, message, metadata).await
+    }
+
+    fn explain_node<'a>(
+        &'a self,
+        ism_address: H256,
+        message: &'a HyperlaneMessage,
+        metadata: Option<Result<Metadata, String>>,
+    ) -> BoxFuture<'a, IsmExplanation> {
+        async move {
+            let mut node = IsmExplanation {
+                address: ism_address,
+                metadata: metadata.as_ref().map(|metadata| match metadata {
+                    Ok(metadata) => metadata.into(),
+                    Err(err) => MetadataOutcome::Error(err.clone()),
+                }),
+                ..Default::default()
+            };
+            let found = match metadata {
+                Some(Ok(Metadata::Found(metadata))) => Some(metadata),
+                _ => None,
+            };
+            if let Err(err) = self.explain_into(&mut node, message, found).await {
+                node.error = Some(format!("{err:#}"));
+            }
+            node
+        }
+        .boxed()
+    }
+
+    /// Fills in `node`, given the metadata it is verified with if it's
+    /// known
+    async fn explain_into(
+        &self,
+        node: &mut IsmExplanation,
+        message: &HyperlaneMessage,
+        metadata: Option<Vec<u8>>,
+    ) -> Result<()> {
+        let module_type = self
+            .module_type(node.address)
+            .await
+            .context("When fetching module type")?;
+        node.module_type = Some(module_type);
+
+        let cloned = self.clone_with_incremented_depth()?;
+        match module_type {
+            ModuleType::Routing => {
+                let route = self
+                    .route(node.address, message)
+                    .await
+                    .context("When fetching route")?;
+                // The route is verified with the routing ISM's metadata
+                let mut child = cloned
+                    .explain_node(route, message, metadata.map(|m| Ok(Metadata::Found(m))))
+                    .await;
+                child.chosen = Some(true);
+                node.children.push(child);
+            }
+            ModuleType::Aggregation => {
+                let (modules, threshold) = self
+                    .modules_and_threshold(node.address, message)
+                    .await
+                    .context("When fetching modules and threshold")?;
+                node.threshold = Some(threshold.into());
+                let sub_module_metadata = match &metadata {
+                    Some(metadata) => Some(AggregationIsmMetadataBuilder::sub_module_metadata(
+                        metadata,
+                        modules.len(),
+                    )),
+                    None => None,
+                };
+                node.children = join_all(modules.iter().enumerate().map(|(index, module)| {
+                    let metadata = sub_module_metadata
+                        .as_ref()
+                        .and_then(|sub_module_metadata| sub_module_metadata[index].clone())
+                        .map(|m| Ok(Metadata::Found(m)));
+                    cloned.explain_node(*module, message, metadata)
+                }))
+                .await;
+                if let Some(sub_module_metadata) = sub_module_metadata {
+                    for (child, metadata) in node.children.iter_mut().zip(sub_module_metadata) {
+                        child.chosen = Some(metadata.is_some());
+                    }
+                }
+            }
+            ModuleType::MerkleRootMultisig | ModuleType::MessageIdMultisig => {
+                let (validators, threshold) = self
+                    .build_multisig_ism(node.address)
+                    .await?
+                    .validators_and_threshold(message)
+                    .await
+                    .context("When fetching validators and threshold")?;
+                node.threshold = Some(threshold.into());
+                let weights = vec![None; validators.len()];
+                node.validators = self
+                    .explain_validators(message, &validators, weights)
+                    .await?;
+            }
+            ModuleType::WeightedMerkleRootMultisig | ModuleType::WeightedMessageIdMultisig => {
+                let (validators, threshold_weight) = self
+                    .build_weighted_multisig_ism(node.address)
+                    .await?
+                    .validators_and_threshold_weight(message)
+                    .await
+                    .context("When fetching validators and threshold weight")?;
+                node.threshold = Some(threshold_weight);
+                let (validators, weights): (Vec<_>, Vec<_>) = validators
+                    .into_iter()
+                    .map(|(validator, weight)| (validator, Some(weight)))
+                    .unzip();
+                node.validators = self
+                    .explain_validators(message, &validators, weights)
+                    .await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn explain_validators(
+        &self,
+        message: &HyperlaneMessage,
+        validators: &[H256],
+        weights: Vec<Option<u128>>,
+    ) -> Result<Vec<ValidatorExplanation>> {
+        let checkpoint_syncer = self
+            .build_checkpoint_syncer(message, validators, self.app_context.clone())
+            .await
+            .context("When building checkpoint syncer")?;
+        let latest_indices = checkpoint_syncer
+            .get_latest_indices_and_update_metrics(
+                validators,
+                self.origin_domain(),
+                self.destination_domain(),
+            )
+            .await;
+        Ok(validators
+            .iter()
+            .zip(weights)
+            .map(|(validator, weight)| ValidatorExplanation {
+                address: *validator,
+                weight,
+                latest_checkpoint_index: latest_indices
+                    .get(&H160::from(*validator))
+                    .copied()
+                    .flatten(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metadata_outcome_serialization() {
+        assert_eq!(
+            serde_json::to_value(MetadataOutcome::CouldNotFetch).unwrap(),
+            serde_json::json!({ "status": "could_not_fetch" })
+        );
+        assert_eq!(
+            serde_json::to_value(MetadataOutcome::Blocked("Recipient ISM is paused".into()))
+                .unwrap(),
+            serde_json::json!({ "status": "blocked", "reason": "Recipient ISM is paused" })
+        );
+    }
+}
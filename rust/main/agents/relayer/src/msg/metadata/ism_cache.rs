@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use ethers::utils::keccak256;
+use eyre::Result;
+use hyperlane_core::{HyperlaneMessage, ModuleType, H256};
+use tokio::sync::RwLock;
+
+/// Longest time null ISM probes are cached for. Pausing is meant to take
+/// effect quickly, so they expire sooner than the rest of the cache.
+const NULL_ISM_PROBE_TTL: Duration = Duration::from_secs(60);
+
+/// Identifies a route of a routing ISM for a message. The key holds every
+/// part of the message an ISM can route on, including the recipient and
+/// body, so messages only share routes when no ISM can tell them apart. The
+/// nonce and version are left out, since they identify the message rather
+/// than describe it. The destination is the same for all messages of a
+/// cache.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct IsmCacheKey {
+    ism: H256,
+    origin: u32,
+    sender: H256,
+    recipient: H256,
+    body_hash: H256,
+}
+
+impl IsmCacheKey {
+    pub fn new(ism: H256, message: &HyperlaneMessage) -> Self {
+        Self {
+            ism,
+            origin: message.origin,
+            sender: message.sender,
+            recipient: message.recipient,
+            body_hash: keccak256(&message.body).into(),
+        }
+    }
+}
+
+/// Cached values that expire a fixed time after they were fetched
+#[derive(Debug)]
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: RwLock<HashMap<K, (V, Instant)>>,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the cached value for `key`, calling `fetch` if there is no
+    /// fresh one. Errors aren't cached.
+    pub async fn get_or_fetch<F, Fut>(&self, key: K, fetch: F) -> Result<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V>>,
+    {
+        // In its own block to avoid holding the lock while fetching
+        {
+            let entries = self.entries.read().await;
+            if let Some((value, fetched_at)) = entries.get(&key) {
+                if fetched_at.elapsed() < self.ttl {
+                    return Ok(value.clone());
+                }
+            }
+        }
+
+        let value = fetch().await?;
+        if !self.ttl.is_zero() {
+            let mut entries = self.entries.write().await;
+            // Evict expired entries, so that keys that are never fetched
+            // again don't pile up
+            entries.retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.ttl);
+            entries.insert(key, (value.clone(), Instant::now()));
+        }
+        Ok(value)
+    }
+
+    async fn remove(&self, key: &K) -> Option<V> {
+        self.entries
+            .write()
+            .await
+            .remove(key)
+            .map(|(value, _)| value)
+    }
+
+    async fn clear(&self) -> usize {
+        let mut entries = self.entries.write().await;
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+}
+
+/// Caches the results of the ISM calls made while building metadata for
+/// messages to a destination chain. Shared by all origins of the
+/// destination, since ISMs rarely change.
+///
+/// Only routes are cached per message. Aggregation and multisig ISMs are
+/// assumed to return the same sub-modules and validators for every message,
+/// as the static ones do, so their results are cached per ISM. Should one
+/// not, the message fails to verify and the entries are invalidated.
+#[derive(Debug)]
+pub struct IsmCache {
+    pub module_types: TtlCache<H256, ModuleType>,
+    pub routes: TtlCache<IsmCacheKey, H256>,
+    pub modules_and_thresholds: TtlCache<H256, (Vec<H256>, u8)>,
+    pub validators_and_thresholds: TtlCache<H256, (Vec<H256>, u8)>,
+    pub validators_and_threshold_weights: TtlCache<H256, (Vec<(H256, u128)>, u128)>,
+    /// Whether a null ISM is paused, and the relayer it trusts if any
+    pub null_ism_probes: TtlCache<H256, (bool, Option<H256>)>,
+}
+
+impl IsmCache {
+    /// Creates a cache whose entries expire after `ttl`, or sooner for null
+    /// ISM probes. A zero `ttl` disables caching.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            module_types: TtlCache::new(ttl),
+            routes: TtlCache::new(ttl),
+            modules_and_thresholds: TtlCache::new(ttl),
+            validators_and_thresholds: TtlCache::new(ttl),
+            validators_and_threshold_weights: TtlCache::new(ttl),
+            null_ism_probes: TtlCache::new(ttl.min(NULL_ISM_PROBE_TTL)),
+        }
+    }
+
+    /// Removes the cached entries of the ISM tree rooted at `ism` for
+    /// `message`, e.g. because the message failed to verify with metadata
+    /// built from them. Entries that aren't cached per message are removed
+    /// for all messages. Returns how many entries were removed.
+    pub async fn invalidate(&self, ism: H256, message: &HyperlaneMessage) -> usize {
+        let mut removed = 0;
+        // Removing entries as the tree is walked guards against cycles
+        let mut isms = vec![ism];
+        while let Some(ism) = isms.pop() {
+            if self.module_types.remove(&ism).await.is_some() {
+                removed += 1;
+            }
+            if self.null_ism_probes.remove(&ism).await.is_some() {
+                removed += 1;
+            }
+            if let Some(route) = self.routes.remove(&IsmCacheKey::new(ism, message)).await {
+                isms.push(route);
+                removed += 1;
+            }
+            if let Some((modules, _)) = self.modules_and_thresholds.remove(&ism).await {
+                isms.extend(modules);
+                removed += 1;
+            }
+            if self.validators_and_thresholds.remove(&ism).await.is_some() {
+                removed += 1;
+            }
+            if self
+                .validators_and_threshold_weights
+                .remove(&ism)
+                .await
+                .is_some()
+            {
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Removes every cached entry. Returns how many entries were removed.
+    pub async fn invalidate_all(&self) -> usize {
+        self.module_types.clear().await
+            + self.routes.clear().await
+            + self.modules_and_thresholds.clear().await
+            + self.validators_and_thresholds.clear().await
+            + self.validators_and_threshold_weights.clear().await
+            + self.null_ism_probes.clear().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn message(sender: H256) -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: 1,
+            sender,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_caches_per_message_content() {
+        let cache = IsmCache::new(TTL);
+        let fetches = AtomicUsize::new(0);
+        let route = |message: HyperlaneMessage| {
+            let key = IsmCacheKey::new(H256::repeat_byte(1), &message);
+            let fetches = &fetches;
+            let cache = &cache;
+            async move {
+                cache
+                    .routes
+                    .get_or_fetch(key, || async {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        Ok(H256::repeat_byte(2))
+                    })
+                    .await
+                    .unwrap()
+            }
+        };
+
+        route(message(H256::repeat_byte(3))).await;
+        route(message(H256::repeat_byte(3))).await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        route(message(H256::repeat_byte(4))).await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // ISMs may route on the recipient or the body
+        route(HyperlaneMessage {
+            recipient: H256::repeat_byte(5),
+            ..message(H256::repeat_byte(3))
+        })
+        .await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+        route(HyperlaneMessage {
+            body: vec![1],
+            ..message(H256::repeat_byte(3))
+        })
+        .await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 4);
+
+        // The nonce only identifies the message
+        route(HyperlaneMessage {
+            nonce: 7,
+            ..message(H256::repeat_byte(3))
+        })
+        .await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_static_ism_results_shared_by_messages() {
+        let cache = IsmCache::new(TTL);
+        let fetches = AtomicUsize::new(0);
+        let ism = H256::repeat_byte(1);
+        for message in [
+            message(H256::repeat_byte(3)),
+            HyperlaneMessage {
+                recipient: H256::repeat_byte(5),
+                body: vec![1],
+                ..message(H256::repeat_byte(4))
+            },
+        ] {
+            let (validators, _) = cache
+                .validators_and_thresholds
+                .get_or_fetch(ism, || async {
+                    fetches.fetch_add(1, Ordering::SeqCst);
+                    Ok((vec![message.sender], 1))
+                })
+                .await
+                .unwrap();
+            // The validators fetched for the first message are reused
+            assert_eq!(validators, vec![H256::repeat_byte(3)]);
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_evicted_on_insert() {
+        let cache = TtlCache::new(Duration::from_millis(10));
+        cache.get_or_fetch(1, || async { Ok(()) }).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cache.get_or_fetch(2, || async { Ok(()) }).await.unwrap();
+        assert_eq!(cache.clear().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_zero_ttl_disables_caching() {
+        let cache = IsmCache::new(Duration::ZERO);
+        for _ in 0..2 {
+            cache
+                .module_types
+                .get_or_fetch(H256::zero(), || async { Ok(ModuleType::Null) })
+                .await
+                .unwrap();
+        }
+        assert_eq!(cache.invalidate_all().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_null_ism_probes_expire_sooner() {
+        let cache = IsmCache::new(TTL);
+        assert_eq!(cache.module_types.ttl, TTL);
+        assert_eq!(cache.null_ism_probes.ttl, NULL_ISM_PROBE_TTL);
+
+        let cache = IsmCache::new(Duration::from_secs(5));
+        assert_eq!(cache.null_ism_probes.ttl, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_removes_ism_tree() {
+        let cache = IsmCache::new(TTL);
+        let message = message(H256::repeat_byte(3));
+        let (routing, aggregation, multisig) = (
+            H256::repeat_byte(10),
+            H256::repeat_byte(11),
+            H256::repeat_byte(12),
+        );
+        let unrelated = H256::repeat_byte(13);
+        for (ism, module_type) in [
+            (routing, ModuleType::Routing),
+            (aggregation, ModuleType::Aggregation),
+            (multisig, ModuleType::MessageIdMultisig),
+            (unrelated, ModuleType::MessageIdMultisig),
+        ] {
+            cache
+                .module_types
+                .get_or_fetch(ism, || async move { Ok(module_type) })
+                .await
+                .unwrap();
+        }
+        cache
+            .routes
+            .get_or_fetch(IsmCacheKey::new(routing, &message), || async {
+                Ok(aggregation)
+            })
+            .await
+            .unwrap();
+        cache
+            .modules_and_thresholds
+            .get_or_fetch(aggregation, || async { Ok((vec![multisig], 1)) })
+            .await
+            .unwrap();
+        cache
+            .validators_and_thresholds
+            .get_or_fetch(multisig, || async { Ok((vec![H256::zero()], 1)) })
+            .await
+            .unwrap();
+
+        // 3 module types, the route, the modules and the validators
+        assert_eq!(cache.invalidate(routing, &message).await, 6);
+        // Only the unrelated ISM's module type is left
+        assert_eq!(cache.invalidate_all().await, 1);
+    }
+}
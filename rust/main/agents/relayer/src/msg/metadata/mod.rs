@@ -1,15 +1,25 @@
 mod aggregation;
+mod arb_l2_to_l1;
 mod base;
 mod ccip_read;
+mod explain;
+mod ism_cache;
 mod multisig;
 mod null_metadata;
+mod op_l2_to_l1;
 mod routing;
 
+pub(crate) use aggregation::AggregationCostTracker;
 use aggregation::AggregationIsmMetadataBuilder;
+use arb_l2_to_l1::ArbL2ToL1MetadataBuilder;
 pub(crate) use base::{
     AppContextClassifier, BaseMetadataBuilder, IsmAwareAppContextClassifier,
     MessageMetadataBuilder, Metadata, MetadataBuilder,
 };
 use ccip_read::CcipReadIsmMetadataBuilder;
+pub(crate) use ccip_read::{CcipReadClient, CcipReadMetrics};
+pub(crate) use explain::IsmExplanation;
+pub(crate) use ism_cache::IsmCache;
 use null_metadata::NullMetadataBuilder;
+use op_l2_to_l1::OpL2ToL1MetadataBuilder;
 use routing::RoutingIsmMetadataBuilder;
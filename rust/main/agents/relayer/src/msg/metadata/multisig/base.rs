@@ -7,7 +7,7 @@ use ethers::abi::Token;
 
 use eyre::{Context, Result};
 use hyperlane_base::settings::CheckpointSyncerBuildError;
-use hyperlane_base::MultisigCheckpointSyncer;
+use hyperlane_base::{MultisigCheckpointSyncer, ValidatorQuorum};
 use hyperlane_core::accumulator::merkle::Proof;
 use hyperlane_core::{HyperlaneMessage, MultisigSignedCheckpoint, H256};
 use strum::Display;
@@ -39,10 +39,24 @@ pub enum MetadataToken {
 
 #[async_trait]
 pub trait MultisigIsmMetadataBuilder: AsRef<MessageMetadataBuilder> + Send + Sync {
+    /// The validator set of the ISM for `message` and the quorum its
+    /// signatures need to reach.
+    async fn validators_and_quorum(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<H256>, ValidatorQuorum)> {
+        let (validators, threshold) = self
+            .as_ref()
+            .validators_and_threshold(ism_address, message)
+            .await?;
+        Ok((validators, ValidatorQuorum::Threshold(threshold as usize)))
+    }
+
     async fn fetch_metadata(
         &self,
         validators: &[H256],
-        threshold: u8,
+        quorum: &ValidatorQuorum,
         message: &HyperlaneMessage,
         checkpoint_syncer: &MultisigCheckpointSyncer,
     ) -> Result<Option<MultisigMetadata>>;
@@ -96,14 +110,8 @@ pub trait MultisigIsmMetadataBuilder: AsRef<MessageMetadataBuilder> + Send + Syn
 impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
     async fn build(&self, ism_address: H256, message: &HyperlaneMessage) -> Result<Metadata> {
         const CTX: &str = "When fetching MultisigIsm metadata";
-        let multisig_ism = self
-            .as_ref()
-            .build_multisig_ism(ism_address)
-            .await
-            .context(CTX)?;
-
-        let (validators, threshold) = multisig_ism
-            .validators_and_threshold(message)
+        let (validators, quorum) = self
+            .validators_and_quorum(ism_address, message)
             .await
             .context(CTX)?;
 
@@ -112,7 +120,7 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
             return Ok(Metadata::CouldNotFetch);
         }
 
-        info!(hyp_message=?message, ?validators, threshold, "List of validators and threshold for message");
+        info!(hyp_message=?message, ?validators, ?quorum, "List of validators and quorum for message");
 
         let checkpoint_syncer = match self
             .as_ref()
@@ -132,7 +140,7 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
         };
 
         if let Some(metadata) = self
-            .fetch_metadata(&validators, threshold, message, &checkpoint_syncer)
+            .fetch_metadata(&validators, &quorum, message, &checkpoint_syncer)
             .await
             .context(CTX)?
         {
@@ -140,7 +148,7 @@ impl<T: MultisigIsmMetadataBuilder> MetadataBuilder for T {
             Ok(Metadata::Found(self.format_metadata(metadata)?))
         } else {
             info!(
-                hyp_message=?message, ?validators, threshold, ism=%multisig_ism.address(),
+                hyp_message=?message, ?validators, ?quorum, ism=?ism_address,
                 "Could not fetch metadata: Unable to reach quorum"
             );
             Ok(Metadata::CouldNotFetch)
@@ -5,7 +5,7 @@ use derive_more::{AsRef, Deref};
 use derive_new::new;
 
 use eyre::{Context, Result};
-use hyperlane_base::MultisigCheckpointSyncer;
+use hyperlane_base::{MultisigCheckpointSyncer, ValidatorQuorum};
 use hyperlane_core::{unwrap_or_none_result, HyperlaneMessage, H256};
 use tracing::debug;
 
@@ -31,7 +31,7 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
     async fn fetch_metadata(
         &self,
         validators: &[H256],
-        threshold: u8,
+        quorum: &ValidatorQuorum,
         message: &HyperlaneMessage,
         checkpoint_syncer: &MultisigCheckpointSyncer,
     ) -> Result<Option<MultisigMetadata>> {
@@ -53,7 +53,7 @@ impl MultisigIsmMetadataBuilder for MerkleRootMultisigMetadataBuilder {
             checkpoint_syncer
                 .fetch_checkpoint_in_range(
                     validators,
-                    threshold as usize,
+                    quorum,
                     leaf_index,
                     highest_leaf_index,
                     self.origin_domain(),
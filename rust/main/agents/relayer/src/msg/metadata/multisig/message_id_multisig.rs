@@ -5,7 +5,7 @@ use derive_more::{AsRef, Deref};
 use derive_new::new;
 
 use eyre::{Context, Result};
-use hyperlane_base::MultisigCheckpointSyncer;
+use hyperlane_base::{MultisigCheckpointSyncer, ValidatorQuorum};
 use hyperlane_core::{unwrap_or_none_result, HyperlaneMessage, H256};
 use tracing::{debug, warn};
 
@@ -30,7 +30,7 @@ impl MultisigIsmMetadataBuilder for MessageIdMultisigMetadataBuilder {
     async fn fetch_metadata(
         &self,
         validators: &[H256],
-        threshold: u8,
+        quorum: &ValidatorQuorum,
         message: &HyperlaneMessage,
         checkpoint_syncer: &MultisigCheckpointSyncer,
     ) -> Result<Option<MultisigMetadata>> {
@@ -58,7 +58,7 @@ impl MultisigIsmMetadataBuilder for MessageIdMultisigMetadataBuilder {
 
         let quorum_checkpoint = unwrap_or_none_result!(
             checkpoint_syncer
-                .fetch_checkpoint(validators, threshold as usize, leaf_index)
+                .fetch_checkpoint(validators, quorum, leaf_index)
                 .await
                 .context(CTX)?,
             debug!("No quorum checkpoint found")
@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use derive_more::{AsRef, Deref};
+use derive_new::new;
+
+use eyre::{Context, Result};
+use hyperlane_base::{MultisigCheckpointSyncer, ValidatorQuorum};
+use hyperlane_core::{HyperlaneMessage, H256};
+
+use crate::msg::metadata::MessageMetadataBuilder;
+
+use super::base::{MetadataToken, MultisigIsmMetadataBuilder, MultisigMetadata};
+use super::{MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder};
+
+/// Fetches the stake-weighted validator set of a weighted multisig ISM.
+async fn weighted_validators_and_quorum(
+    builder: &MessageMetadataBuilder,
+    ism_address: H256,
+    message: &HyperlaneMessage,
+) -> Result<(Vec<H256>, ValidatorQuorum)> {
+    const CTX: &str = "When fetching WeightedMultisigIsm validators";
+    let (validators, threshold_weight) = builder
+        .validators_and_threshold_weight(ism_address, message)
+        .await
+        .context(CTX)?;
+    let (validators, weights) = validators.into_iter().unzip();
+    Ok((
+        validators,
+        ValidatorQuorum::Weighted {
+            weights,
+            threshold_weight,
+        },
+    ))
+}
+
+/// Weighted multisig ISMs share the metadata layout of their unweighted
+/// counterparts. They only look for signers among the first
+/// `min(validators, signatures)` validators though, which
+/// `MultisigCheckpointSyncer` accounts for by padding the signatures of
+/// weighted quorums.
+#[derive(Debug, Clone, Deref, new, AsRef)]
+pub struct WeightedMerkleRootMultisigMetadataBuilder(MessageMetadataBuilder);
+
+#[async_trait]
+impl MultisigIsmMetadataBuilder for WeightedMerkleRootMultisigMetadataBuilder {
+    async fn validators_and_quorum(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<H256>, ValidatorQuorum)> {
+        weighted_validators_and_quorum(&self.0, ism_address, message).await
+    }
+
+    fn token_layout(&self) -> Vec<MetadataToken> {
+        MerkleRootMultisigMetadataBuilder::new(self.0.clone()).token_layout()
+    }
+
+    async fn fetch_metadata(
+        &self,
+        validators: &[H256],
+        quorum: &ValidatorQuorum,
+        message: &HyperlaneMessage,
+        checkpoint_syncer: &MultisigCheckpointSyncer,
+    ) -> Result<Option<MultisigMetadata>> {
+        MerkleRootMultisigMetadataBuilder::new(self.0.clone())
+            .fetch_metadata(validators, quorum, message, checkpoint_syncer)
+            .await
+    }
+}
+
+#[derive(Debug, Clone, Deref, new, AsRef)]
+pub struct WeightedMessageIdMultisigMetadataBuilder(MessageMetadataBuilder);
+
+#[async_trait]
+impl MultisigIsmMetadataBuilder for WeightedMessageIdMultisigMetadataBuilder {
+    async fn validators_and_quorum(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<(Vec<H256>, ValidatorQuorum)> {
+        weighted_validators_and_quorum(&self.0, ism_address, message).await
+    }
+
+    fn token_layout(&self) -> Vec<MetadataToken> {
+        MessageIdMultisigMetadataBuilder::new(self.0.clone()).token_layout()
+    }
+
+    async fn fetch_metadata(
+        &self,
+        validators: &[H256],
+        quorum: &ValidatorQuorum,
+        message: &HyperlaneMessage,
+        checkpoint_syncer: &MultisigCheckpointSyncer,
+    ) -> Result<Option<MultisigMetadata>> {
+        MessageIdMultisigMetadataBuilder::new(self.0.clone())
+            .fetch_metadata(validators, quorum, message, checkpoint_syncer)
+            .await
+    }
+}
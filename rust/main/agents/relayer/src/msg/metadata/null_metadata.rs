@@ -1,22 +1,57 @@
-use super::{Metadata, MetadataBuilder};
+use super::{MessageMetadataBuilder, Metadata, MetadataBuilder};
 use async_trait::async_trait;
+use derive_more::Deref;
 use derive_new::new;
-use tracing::instrument;
+use eyre::Context;
+use tracing::{info, instrument};
 
-use hyperlane_core::{HyperlaneMessage, H256};
+use hyperlane_core::{HyperlaneDomainProtocol, HyperlaneMessage, ReprepareReason, H256};
 
-#[derive(Clone, Debug, new)]
-pub struct NullMetadataBuilder {}
+/// Builds the (empty) metadata of ISMs that don't need any, after checking
+/// that the ISM doesn't reject every message, like a paused PausableIsm or
+/// a TrustedRelayerIsm trusting another relayer. The checks are cached
+/// briefly, since they are made for every message.
+#[derive(Clone, Debug, new, Deref)]
+pub struct NullMetadataBuilder {
+    base: MessageMetadataBuilder,
+}
 
 #[async_trait]
 impl MetadataBuilder for NullMetadataBuilder {
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
-    #[instrument(err, skip(self))]
+    #[instrument(err, skip(self, _message))]
     async fn build(
         &self,
-        _ism_address: H256,
+        ism_address: H256,
         _message: &HyperlaneMessage,
     ) -> eyre::Result<Metadata> {
+        const CTX: &str = "When checking null ISM";
+        // Pausable and trusted relayer ISMs only exist on EVM chains
+        if self.destination_domain().domain_protocol() != HyperlaneDomainProtocol::Ethereum {
+            return Ok(Metadata::Found(vec![]));
+        }
+
+        let (paused, trusted_relayer) = self.null_ism_probe(ism_address).await.context(CTX)?;
+        if paused {
+            info!(?ism_address, "ISM is paused");
+            return Ok(Metadata::Blocked(ReprepareReason::IsmPaused));
+        }
+        if let Some(trusted_relayer) = trusted_relayer {
+            let relayer = self
+                .build_null_ism(ism_address)
+                .await
+                .context(CTX)?
+                .relayer();
+            if relayer != Some(trusted_relayer) {
+                info!(
+                    ?ism_address,
+                    ?trusted_relayer,
+                    ?relayer,
+                    "ISM only accepts messages from another relayer"
+                );
+                return Ok(Metadata::Blocked(ReprepareReason::RelayerNotTrustedByIsm));
+            }
+        }
         Ok(Metadata::Found(vec![]))
     }
 }
@@ -0,0 +1,101 @@
+#![allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use derive_more::Deref;
+use derive_new::new;
+use eyre::Context;
+use hyperlane_core::{HyperlaneMessage, OpL2ToL1Ism, OpWithdrawalProver, H256};
+use tracing::{info, instrument};
+
+use super::{base::MessageMetadataBuilder, Metadata, MetadataBuilder};
+use crate::msg::op_withdrawal::OpWithdrawalStep;
+
+/// Builds metadata for ISMs that verify messages sent from an OP Stack chain
+/// to L1 through the withdrawal of the message's `preVerifyMessage` call.
+///
+/// The portal only lets whoever proved a withdrawal finalize it, so the
+/// relayer proves and finalizes the withdrawal itself. Building metadata only
+/// reads the state of the withdrawal, and schedules the transaction it needs
+/// next on the destination's submitter. Once finalized, the ISM verifies the
+/// message without metadata.
+#[derive(Clone, Debug, new, Deref)]
+pub struct OpL2ToL1MetadataBuilder {
+    base: MessageMetadataBuilder,
+}
+
+#[async_trait]
+impl MetadataBuilder for OpL2ToL1MetadataBuilder {
+    #[instrument(err, skip(self, message))]
+    async fn build(&self, ism_address: H256, message: &HyperlaneMessage) -> eyre::Result<Metadata> {
+        const CTX: &str = "When fetching OPL2ToL1 metadata";
+        let ism: Arc<dyn OpL2ToL1Ism> = self
+            .build_op_l2_to_l1_ism(ism_address)
+            .await
+            .context(CTX)?
+            .into();
+
+        if ism.is_verified(message).await.context(CTX)? {
+            return Ok(Metadata::Found(vec![]));
+        }
+
+        let Some(block_number) = self.dispatched_block_number(message).context(CTX)? else {
+            info!(hyp_message=?message, "Could not fetch metadata: dispatch block of message is unknown");
+            return Ok(Metadata::CouldNotFetch);
+        };
+        let prover: Arc<dyn OpWithdrawalProver> =
+            self.build_op_withdrawal_prover().await.context(CTX)?.into();
+        let Some(withdrawal) = prover
+            .withdrawal(block_number, message.id())
+            .await
+            .context(CTX)?
+        else {
+            info!(hyp_message=?message, block_number, "Could not fetch metadata: no withdrawal found for message");
+            return Ok(Metadata::CouldNotFetch);
+        };
+        let withdrawal_hash = withdrawal.withdrawal_hash;
+
+        // Prove phase: needs an output root proposed for a block at or after
+        // the withdrawal
+        let step = if !ism.is_proven(withdrawal_hash).await.context(CTX)? {
+            match ism.latest_dispute_game().await.context(CTX)? {
+                Some(game) if game.l2_block_number >= block_number => {}
+                game => {
+                    info!(
+                        hyp_message=?message, block_number, latest_dispute_game=?game,
+                        "Could not fetch metadata: no output root proposed for the withdrawal yet"
+                    );
+                    return Ok(Metadata::CouldNotFetch);
+                }
+            }
+            OpWithdrawalStep::Prove { block_number }
+        } else {
+            // Finalize phase: the dispute game must be resolved and the
+            // finalization period must have passed
+            if !ism.is_finalizable(withdrawal_hash).await.context(CTX)? {
+                info!(hyp_message=?message, ?withdrawal_hash, "Could not fetch metadata: withdrawal can't be finalized yet");
+                return Ok(Metadata::CouldNotFetch);
+            }
+            OpWithdrawalStep::Finalize
+        };
+
+        if self.read_only {
+            info!(hyp_message=?message, ?withdrawal_hash, ?step, "Could not fetch metadata: withdrawal needs a transaction");
+            return Ok(Metadata::CouldNotFetch);
+        }
+        let scheduled = self.op_withdrawals().schedule(
+            message,
+            self.destination_domain(),
+            ism,
+            prover,
+            withdrawal,
+            step,
+        );
+        info!(
+            hyp_message=?message, ?withdrawal_hash, ?step, scheduled,
+            "Could not fetch metadata: withdrawal needs a transaction, which is sent by the submitter"
+        );
+        Ok(Metadata::CouldNotFetch)
+    }
+}
@@ -32,6 +32,7 @@ pub(crate) mod gas_payment;
 pub(crate) mod metadata;
 pub(crate) mod op_queue;
 pub(crate) mod op_submitter;
+pub(crate) mod op_withdrawal;
 pub(crate) mod processor;
 
 pub mod pending_message;
@@ -27,8 +27,8 @@ use tracing::{info, warn};
 
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::{
-    ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneDomainProtocol,
-    PendingOperationResult, QueueOperation, TxOutcome,
+    ChainCommunicationError, ChainResult, HyperlaneDomain, PendingOperationResult, QueueOperation,
+    TxOutcome,
 };
 
 use crate::msg::pending_message::CONFIRM_DELAY;
@@ -383,23 +383,12 @@ async fn confirm_op(
     confirm_queue: &mut OpQueue,
     metrics: &SerialSubmitterMetrics,
 ) {
-    let destination = op.destination_domain().clone();
     debug!(?op, "Operation submitted");
     op.set_next_attempt_after(CONFIRM_DELAY);
     confirm_queue
         .push(op, Some(PendingOperationStatus::Confirm(SubmittedBySelf)))
         .await;
     metrics.ops_submitted.inc();
-
-    if matches!(
-        destination.domain_protocol(),
-        HyperlaneDomainProtocol::Cosmos
-    ) {
-        // On cosmos chains, sleep for 1 sec (the finality period).
-        // Otherwise we get `account sequence mismatch` errors, which have caused us
-        // to lose liveness.
-        sleep(Duration::from_secs(1)).await;
-    }
 }
 
 #[instrument(skip_all, fields(%domain))]
@@ -0,0 +1,550 @@
+#![allow(clippy::clone_on_ref_ptr)] // TODO: `rustc` 1.80.1 clippy issue
+
+//! Proving and finalizing OP Stack withdrawals, which OPL2ToL1 ISMs verify
+//! messages through.
+//!
+//! Metadata builders only read the state of a withdrawal. When it needs a
+//! transaction to make progress, they schedule an [`OpWithdrawalOperation`]
+//! that goes through the same prepare, submit and confirm queues as the
+//! messages of its destination.
+
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ConfirmReason, HyperlaneDomain, HyperlaneMessage, OpDisputeGame, OpL2ToL1Ism,
+    OpWithdrawal, OpWithdrawalProof, OpWithdrawalProver, PendingOperation, PendingOperationResult,
+    PendingOperationStatus, QueueOperation, ReprepareReason, TryBatchAs, TxOutcome, H256, U256,
+};
+use prometheus::IntGauge;
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{info, instrument, warn};
+
+use super::pending_message::PendingMessage;
+
+/// The transaction a withdrawal needs next
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum OpWithdrawalStep {
+    /// Prove the withdrawal against an output root proposed at or after
+    /// the L2 block the withdrawal was sent in
+    Prove {
+        /// The L2 block the withdrawal was sent in
+        block_number: u64,
+    },
+    /// Finalize the withdrawal proven by the signer
+    Finalize,
+}
+
+/// Hands withdrawal operations to the submitter of a destination, at most
+/// one per withdrawal at a time.
+pub struct OpWithdrawalScheduler {
+    send_channel: UnboundedSender<QueueOperation>,
+    /// Hashes of the withdrawals that have an operation in the queues
+    in_flight: Arc<Mutex<HashSet<H256>>>,
+}
+
+impl Debug for OpWithdrawalScheduler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "OpWithdrawalScheduler {{ in_flight: {:?} }}",
+            self.in_flight
+        )
+    }
+}
+
+impl OpWithdrawalScheduler {
+    pub fn new(send_channel: UnboundedSender<QueueOperation>) -> Self {
+        Self {
+            send_channel,
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Schedules `step` of the withdrawal of `message`, unless an operation
+    /// for the withdrawal is already queued. Returns whether it was scheduled.
+    pub fn schedule(
+        &self,
+        message: &HyperlaneMessage,
+        destination: &HyperlaneDomain,
+        ism: Arc<dyn OpL2ToL1Ism>,
+        prover: Arc<dyn OpWithdrawalProver>,
+        withdrawal: OpWithdrawal,
+        step: OpWithdrawalStep,
+    ) -> bool {
+        let withdrawal_hash = withdrawal.withdrawal_hash;
+        if !self.in_flight.lock().unwrap().insert(withdrawal_hash) {
+            return false;
+        }
+        let operation = OpWithdrawalOperation::new(
+            message.clone(),
+            destination.clone(),
+            ism,
+            prover,
+            withdrawal,
+            step,
+            self.in_flight.clone(),
+        );
+        if self.send_channel.send(Box::new(operation)).is_err() {
+            // The operation was dropped along with the channel, which
+            // released the withdrawal
+            warn!(
+                ?withdrawal_hash,
+                "Submitter is gone, could not schedule withdrawal operation"
+            );
+            return false;
+        }
+        true
+    }
+}
+
+/// Sends the transaction a withdrawal needs next from the signer of the
+/// destination chain.
+#[derive(Serialize)]
+pub struct OpWithdrawalOperation {
+    message: HyperlaneMessage,
+    withdrawal_hash: H256,
+    step: OpWithdrawalStep,
+    status: PendingOperationStatus,
+    num_retries: u32,
+    #[serde(skip_serializing)]
+    destination: HyperlaneDomain,
+    #[serde(skip_serializing)]
+    ism: Arc<dyn OpL2ToL1Ism>,
+    #[serde(skip_serializing)]
+    prover: Arc<dyn OpWithdrawalProver>,
+    #[serde(skip_serializing)]
+    withdrawal: OpWithdrawal,
+    /// The dispute game and proof to prove the withdrawal with, once prepared
+    #[serde(skip_serializing)]
+    proof: Option<(OpDisputeGame, OpWithdrawalProof)>,
+    #[serde(skip_serializing)]
+    next_attempt_after: Option<Instant>,
+    #[serde(skip_serializing)]
+    submission_outcome: Option<TxOutcome>,
+    #[serde(skip_serializing)]
+    metric: Option<Arc<IntGauge>>,
+    #[serde(skip_serializing)]
+    in_flight: Arc<Mutex<HashSet<H256>>>,
+}
+
+impl Debug for OpWithdrawalOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "OpWithdrawalOperation {{ withdrawal_hash: {:?}, step: {:?}, num_retries: {}, message_id: {:?}, status: {:?} }}",
+            self.withdrawal_hash,
+            self.step,
+            self.num_retries,
+            self.message.id(),
+            self.status
+        )
+    }
+}
+
+impl Drop for OpWithdrawalOperation {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&self.withdrawal_hash);
+        }
+    }
+}
+
+impl TryBatchAs<HyperlaneMessage> for OpWithdrawalOperation {}
+
+impl OpWithdrawalOperation {
+    fn new(
+        message: HyperlaneMessage,
+        destination: HyperlaneDomain,
+        ism: Arc<dyn OpL2ToL1Ism>,
+        prover: Arc<dyn OpWithdrawalProver>,
+        withdrawal: OpWithdrawal,
+        step: OpWithdrawalStep,
+        in_flight: Arc<Mutex<HashSet<H256>>>,
+    ) -> Self {
+        Self {
+            message,
+            withdrawal_hash: withdrawal.withdrawal_hash,
+            step,
+            status: PendingOperationStatus::FirstPrepareAttempt,
+            num_retries: 0,
+            destination,
+            ism,
+            prover,
+            withdrawal,
+            proof: None,
+            next_attempt_after: None,
+            submission_outcome: None,
+            metric: None,
+            in_flight,
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.next_attempt_after
+            .map(|a| Instant::now() >= a)
+            .unwrap_or(true)
+    }
+
+    /// Backs off like messages do, since a withdrawal waits on its message
+    fn inc_attempts(&mut self) {
+        self.num_retries += 1;
+        self.next_attempt_after =
+            PendingMessage::calculate_msg_backoff(self.num_retries).map(|dur| Instant::now() + dur);
+    }
+
+    fn not_ready(&mut self, reason: &str) -> PendingOperationResult {
+        self.inc_attempts();
+        info!(withdrawal_hash=?self.withdrawal_hash, step=?self.step, "Withdrawal operation not ready: {reason}");
+        PendingOperationResult::NotReady
+    }
+
+    fn on_error<E: Debug>(&mut self, err: E, reason: &str) -> PendingOperationResult {
+        self.inc_attempts();
+        warn!(error=?err, withdrawal_hash=?self.withdrawal_hash, step=?self.step, "{reason}");
+        PendingOperationResult::NotReady
+    }
+
+    /// Whether the step was carried out, by this relayer or a previous run
+    async fn is_done(&self) -> ChainResult<bool> {
+        match self.step {
+            OpWithdrawalStep::Prove { .. } => self.ism.is_proven(self.withdrawal_hash).await,
+            OpWithdrawalStep::Finalize => self.ism.is_verified(&self.message).await,
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serialize]
+impl PendingOperation for OpWithdrawalOperation {
+    fn id(&self) -> H256 {
+        self.withdrawal_hash
+    }
+
+    fn status(&self) -> PendingOperationStatus {
+        self.status.clone()
+    }
+
+    fn set_status(&mut self, status: PendingOperationStatus) {
+        self.status = status;
+    }
+
+    fn priority(&self) -> u32 {
+        self.message.nonce
+    }
+
+    fn origin_domain_id(&self) -> u32 {
+        self.message.origin
+    }
+
+    fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus> {
+        None
+    }
+
+    fn destination_domain(&self) -> &HyperlaneDomain {
+        &self.destination
+    }
+
+    fn sender_address(&self) -> &H256 {
+        &self.message.sender
+    }
+
+    fn recipient_address(&self) -> &H256 {
+        &self.message.recipient
+    }
+
+    fn app_context(&self) -> Option<String> {
+        None
+    }
+
+    fn get_metric(&self) -> Option<Arc<IntGauge>> {
+        self.metric.clone()
+    }
+
+    fn set_metric(&mut self, metric: Arc<IntGauge>) {
+        self.metric = Some(metric);
+    }
+
+    #[instrument(skip(self), fields(id=?self.id()), level = "debug")]
+    async fn prepare(&mut self) -> PendingOperationResult {
+        if !self.is_ready() {
+            return PendingOperationResult::NotReady;
+        }
+        match self.is_done().await {
+            Ok(true) => {
+                info!(withdrawal_hash=?self.withdrawal_hash, step=?self.step, "Withdrawal step already carried out");
+                return PendingOperationResult::Drop;
+            }
+            Ok(false) => {}
+            Err(err) => return self.on_error(err, "Error checking withdrawal state"),
+        }
+
+        match self.step {
+            OpWithdrawalStep::Prove { block_number } => {
+                let dispute_game = match self.ism.latest_dispute_game().await {
+                    Ok(Some(game)) if game.l2_block_number >= block_number => game,
+                    Ok(_) => {
+                        return self.not_ready("no output root proposed for the withdrawal yet")
+                    }
+                    Err(err) => return self.on_error(err, "Error fetching latest dispute game"),
+                };
+                let proof = match self
+                    .prover
+                    .withdrawal_proof(self.withdrawal_hash, dispute_game.l2_block_number)
+                    .await
+                {
+                    Ok(proof) => proof,
+                    Err(err) => return self.on_error(err, "Error proving withdrawal on L2"),
+                };
+                self.proof = Some((dispute_game, proof));
+            }
+            OpWithdrawalStep::Finalize => match self.ism.is_finalizable(self.withdrawal_hash).await
+            {
+                Ok(true) => {}
+                Ok(false) => return self.not_ready("withdrawal can't be finalized yet"),
+                Err(err) => {
+                    return self.on_error(err, "Error checking if withdrawal is finalizable")
+                }
+            },
+        }
+        PendingOperationResult::Success
+    }
+
+    #[instrument(skip(self), fields(id=?self.id(), domain=%self.destination_domain()))]
+    async fn submit(&mut self) -> PendingOperationResult {
+        let outcome = match self.step {
+            OpWithdrawalStep::Prove { .. } => {
+                let (dispute_game, proof) = self
+                    .proof
+                    .as_ref()
+                    .expect("Withdrawal operation must be prepared before it can be submitted");
+                self.ism
+                    .prove_withdrawal(&self.withdrawal, dispute_game, proof)
+                    .await
+            }
+            OpWithdrawalStep::Finalize => self.ism.finalize_withdrawal(&self.withdrawal).await,
+        };
+        match outcome {
+            Ok(outcome) => {
+                info!(withdrawal_hash=?self.withdrawal_hash, step=?self.step, ?outcome, "Submitted withdrawal transaction");
+                self.set_submission_outcome(outcome);
+                PendingOperationResult::Confirm(ConfirmReason::SubmittedBySelf)
+            }
+            Err(err) => {
+                warn!(error=?err, withdrawal_hash=?self.withdrawal_hash, step=?self.step, "Error submitting withdrawal transaction");
+                self.inc_attempts();
+                PendingOperationResult::Reprepare(ReprepareReason::ErrorSubmitting)
+            }
+        }
+    }
+
+    fn set_submission_outcome(&mut self, outcome: TxOutcome) {
+        self.submission_outcome = Some(outcome);
+    }
+
+    fn get_tx_cost_estimate(&self) -> Option<U256> {
+        None
+    }
+
+    async fn confirm(&mut self) -> PendingOperationResult {
+        if !self.is_ready() {
+            return PendingOperationResult::NotReady;
+        }
+        match self.is_done().await {
+            Ok(true) => {
+                info!(withdrawal_hash=?self.withdrawal_hash, step=?self.step, submission=?self.submission_outcome, "Withdrawal step confirmed");
+                PendingOperationResult::Success
+            }
+            Ok(false) => {
+                warn!(withdrawal_hash=?self.withdrawal_hash, step=?self.step, submission=?self.submission_outcome, "Withdrawal transaction reverted or was reorged");
+                self.inc_attempts();
+                PendingOperationResult::Reprepare(ReprepareReason::RevertedOrReorged)
+            }
+            Err(err) => self.on_error(err, "Error confirming withdrawal step"),
+        }
+    }
+
+    fn set_operation_outcome(
+        &mut self,
+        submission_outcome: TxOutcome,
+        _submission_estimated_cost: U256,
+    ) {
+        self.set_submission_outcome(submission_outcome);
+    }
+
+    fn next_attempt_after(&self) -> Option<Instant> {
+        self.next_attempt_after
+    }
+
+    fn set_next_attempt_after(&mut self, delay: Duration) {
+        self.next_attempt_after = Some(Instant::now() + delay);
+    }
+
+    fn reset_attempts(&mut self) {
+        self.next_attempt_after = None;
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn set_retries(&mut self, retries: u32) {
+        self.num_retries = retries;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{
+        HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, KnownHyperlaneDomain,
+    };
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockOpL2ToL1Ism {
+        domain: HyperlaneDomain,
+        latest_dispute_game: Option<OpDisputeGame>,
+    }
+
+    impl HyperlaneChain for MockOpL2ToL1Ism {
+        fn domain(&self) -> &HyperlaneDomain {
+            &self.domain
+        }
+
+        fn provider(&self) -> Box<dyn HyperlaneProvider> {
+            unimplemented!()
+        }
+    }
+
+    impl HyperlaneContract for MockOpL2ToL1Ism {
+        fn address(&self) -> H256 {
+            H256::zero()
+        }
+    }
+
+    #[async_trait]
+    impl OpL2ToL1Ism for MockOpL2ToL1Ism {
+        async fn is_verified(&self, _message: &HyperlaneMessage) -> ChainResult<bool> {
+            Ok(false)
+        }
+
+        async fn latest_dispute_game(&self) -> ChainResult<Option<OpDisputeGame>> {
+            Ok(self.latest_dispute_game.clone())
+        }
+
+        async fn is_proven(&self, _withdrawal_hash: H256) -> ChainResult<bool> {
+            Ok(false)
+        }
+
+        async fn is_finalizable(&self, _withdrawal_hash: H256) -> ChainResult<bool> {
+            Ok(false)
+        }
+
+        async fn prove_withdrawal(
+            &self,
+            _withdrawal: &OpWithdrawal,
+            _dispute_game: &OpDisputeGame,
+            _proof: &OpWithdrawalProof,
+        ) -> ChainResult<TxOutcome> {
+            unimplemented!()
+        }
+
+        async fn finalize_withdrawal(&self, _withdrawal: &OpWithdrawal) -> ChainResult<TxOutcome> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockOpWithdrawalProver;
+
+    #[async_trait]
+    impl OpWithdrawalProver for MockOpWithdrawalProver {
+        async fn withdrawal(
+            &self,
+            _block_number: u64,
+            _message_id: H256,
+        ) -> ChainResult<Option<OpWithdrawal>> {
+            unimplemented!()
+        }
+
+        async fn withdrawal_proof(
+            &self,
+            _withdrawal_hash: H256,
+            _l2_block_number: u64,
+        ) -> ChainResult<OpWithdrawalProof> {
+            Ok(OpWithdrawalProof::default())
+        }
+    }
+
+    fn schedule_prove(
+        scheduler: &OpWithdrawalScheduler,
+        latest_dispute_game: Option<OpDisputeGame>,
+    ) -> bool {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum);
+        let ism = MockOpL2ToL1Ism {
+            domain: domain.clone(),
+            latest_dispute_game,
+        };
+        scheduler.schedule(
+            &HyperlaneMessage::default(),
+            &domain,
+            Arc::new(ism),
+            Arc::new(MockOpWithdrawalProver),
+            OpWithdrawal {
+                withdrawal_hash: H256::repeat_byte(1),
+                ..Default::default()
+            },
+            OpWithdrawalStep::Prove { block_number: 100 },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_schedules_one_operation_per_withdrawal() {
+        let (send_channel, mut receive_channel) = mpsc::unbounded_channel();
+        let scheduler = OpWithdrawalScheduler::new(send_channel);
+
+        assert!(schedule_prove(&scheduler, None));
+        assert!(!schedule_prove(&scheduler, None));
+        let operation = receive_channel.try_recv().unwrap();
+        assert!(receive_channel.try_recv().is_err());
+
+        // Once the operation leaves the queues, the withdrawal can be
+        // scheduled again
+        drop(operation);
+        assert!(schedule_prove(&scheduler, None));
+    }
+
+    #[tokio::test]
+    async fn test_prove_waits_for_output_root() {
+        let (send_channel, mut receive_channel) = mpsc::unbounded_channel();
+        let scheduler = OpWithdrawalScheduler::new(send_channel);
+        let old_game = OpDisputeGame {
+            index: U256::from(3),
+            l2_block_number: 99,
+        };
+        schedule_prove(&scheduler, Some(old_game));
+        let mut operation = receive_channel.try_recv().unwrap();
+        assert!(matches!(
+            operation.prepare().await,
+            PendingOperationResult::NotReady
+        ));
+        assert!(operation.next_attempt_after().is_some());
+        drop(operation);
+
+        let game = OpDisputeGame {
+            index: U256::from(4),
+            l2_block_number: 100,
+        };
+        schedule_prove(&scheduler, Some(game));
+        let mut operation = receive_channel.try_recv().unwrap();
+        assert!(matches!(
+            operation.prepare().await,
+            PendingOperationResult::Success
+        ));
+    }
+}
@@ -54,6 +54,9 @@ pub struct MessageContext {
     /// Hard limit on transaction gas when submitting a transaction to the
     /// destination.
     pub transaction_gas_limit: Option<U256>,
+    /// How long to wait before retrying a message whose ISM rejects every
+    /// message, e.g. because it is paused.
+    pub blocked_ism_retry_delay: Duration,
     pub metrics: MessageSubmissionMetrics,
 }
 
@@ -287,6 +290,15 @@ impl PendingOperation for PendingMessage {
                 warn!(?reason, "Metadata building refused");
                 return self.on_reprepare::<String>(None, ReprepareReason::MessageMetadataRefused);
             }
+            // Retrying won't help until the ISM is e.g. unpaused, so back off for longer
+            // than the retry count alone would
+            Metadata::Blocked(reason) => {
+                let result = self.on_reprepare::<String>(None, reason);
+                self.next_attempt_after = self
+                    .next_attempt_after
+                    .max(Some(Instant::now() + self.ctx.blocked_ism_retry_delay));
+                return result;
+            }
         };
 
         // Estimate transaction costs for the process call. If there are issues, it's
@@ -301,6 +313,13 @@ impl PendingOperation for PendingMessage {
         {
             Ok(tx_cost_estimate) => tx_cost_estimate,
             Err(err) => {
+                // The message may have failed to verify because of stale cached ISM
+                // configuration, so refetch it next time
+                self.ctx
+                    .metadata_builder
+                    .ism_cache()
+                    .invalidate(ism_address, &self.message)
+                    .await;
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorEstimatingGas);
             }
         };
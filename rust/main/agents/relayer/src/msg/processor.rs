@@ -389,9 +389,14 @@ mod test {
         merkle_tree::builder::MerkleTreeBuilder,
         msg::{
             gas_payment::GasPaymentEnforcer,
-            metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+            metadata::{
+                AggregationCostTracker, BaseMetadataBuilder, CcipReadClient, CcipReadMetrics,
+                IsmAwareAppContextClassifier, IsmCache,
+            },
+            op_withdrawal::OpWithdrawalScheduler,
         },
         processor::Processor,
+        settings::CcipReadConf,
     };
 
     use super::*;
@@ -450,6 +455,7 @@ mod test {
                 },
                 transaction_overrides: Default::default(),
                 operation_batch: Default::default(),
+                arb_send_root_lookback_blocks: None,
             }),
             metrics_conf: Default::default(),
             index: Default::default(),
@@ -470,10 +476,14 @@ mod test {
             destination_domain.name().to_owned(),
             dummy_chain_conf(destination_domain),
         );
+        let origin_chain_conf = settings.chain_setup(origin_domain).unwrap();
         let destination_chain_conf = settings.chain_setup(destination_domain).unwrap();
         let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        let ccip_read_client =
+            CcipReadClient::new(CcipReadConf::default(), CcipReadMetrics::new(&core_metrics))
+                .unwrap();
         BaseMetadataBuilder::new(
-            origin_domain.clone(),
+            origin_chain_conf.clone(),
             destination_chain_conf.clone(),
             Arc::new(RwLock::new(MerkleTreeBuilder::new())),
             Arc::new(MockValidatorAnnounceContract::default()),
@@ -481,6 +491,10 @@ mod test {
             Arc::new(core_metrics),
             db.clone(),
             IsmAwareAppContextClassifier::new(Arc::new(MockMailboxContract::default()), vec![]),
+            Arc::new(IsmCache::new(Duration::ZERO)),
+            Arc::new(ccip_read_client),
+            Arc::new(AggregationCostTracker::new(Duration::ZERO)),
+            Arc::new(OpWithdrawalScheduler::new(mpsc::unbounded_channel().0)),
         )
     }
 
@@ -496,6 +510,7 @@ mod test {
             metadata_builder: Arc::new(base_metadata_builder),
             origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
             transaction_gas_limit: Default::default(),
+            blocked_ism_retry_delay: Duration::from_secs(60 * 60),
             metrics: dummy_submission_metrics(),
         });
 
@@ -24,7 +24,7 @@ use hyperlane_core::{
 use tokio::{
     sync::{
         broadcast::Sender as BroadcastSender,
-        mpsc::{self, Receiver as MpscReceiver, UnboundedSender},
+        mpsc::{self, Receiver as MpscReceiver, UnboundedReceiver, UnboundedSender},
         RwLock,
     },
     task::JoinHandle,
@@ -37,8 +37,12 @@ use crate::{
     msg::{
         blacklist::AddressBlacklist,
         gas_payment::GasPaymentEnforcer,
-        metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+        metadata::{
+            AggregationCostTracker, BaseMetadataBuilder, CcipReadClient, CcipReadMetrics,
+            IsmAwareAppContextClassifier, IsmCache,
+        },
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
+        op_withdrawal::OpWithdrawalScheduler,
         pending_message::{MessageContext, MessageSubmissionMetrics},
         processor::{MessageProcessor, MessageProcessorMetrics},
     },
@@ -73,6 +77,13 @@ pub struct Relayer {
     /// Context data for each (origin, destination) chain pair a message can be
     /// sent between
     msg_ctxs: HashMap<ContextKey, Arc<MessageContext>>,
+    /// ISM introspection caches, keyed by destination domain id
+    ism_caches: HashMap<u32, Arc<IsmCache>>,
+    /// Channels to the submitter of each destination, keyed by domain id.
+    /// Metadata builders also send operations to them.
+    send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
+    /// Taken by the submitters when the relayer runs
+    receive_channels: HashMap<u32, UnboundedReceiver<QueueOperation>>,
     prover_syncs: HashMap<HyperlaneDomain, Arc<RwLock<MerkleTreeBuilder>>>,
     merkle_tree_hook_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<MerkleTreeInsertion>>>,
     dbs: HashMap<HyperlaneDomain, HyperlaneRocksDB>,
@@ -234,11 +245,30 @@ impl BaseAgent for Relayer {
 
         let mut msg_ctxs = HashMap::new();
         let mut destination_chains = HashMap::new();
+        let mut ism_caches = HashMap::new();
+        let mut send_channels = HashMap::with_capacity(mailboxes.len());
+        let mut receive_channels = HashMap::with_capacity(mailboxes.len());
+        let ccip_read_client = Arc::new(CcipReadClient::new(
+            settings.ccip_read.clone(),
+            CcipReadMetrics::new(&core_metrics),
+        )?);
 
         // only iterate through destination chains that were successfully instantiated
         for (destination, dest_mailbox) in mailboxes.iter() {
             let destination_chain_setup = core.settings.chain_setup(destination).unwrap().clone();
             destination_chains.insert(destination.clone(), destination_chain_setup.clone());
+            // ISMs are shared by all origins of a destination, and so is their cache
+            let ism_cache = Arc::new(IsmCache::new(settings.ism_cache_ttl));
+            ism_caches.insert(destination.id(), ism_cache.clone());
+            let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
+            // Withdrawals of OP Stack origins are proven and finalized by the
+            // destination's submitter
+            let op_withdrawals = Arc::new(OpWithdrawalScheduler::new(send_channel.clone()));
+            send_channels.insert(destination.id(), send_channel);
+            receive_channels.insert(destination.id(), receive_channel);
+            // Verification costs depend on the destination only
+            let aggregation_costs =
+                Arc::new(AggregationCostTracker::new(settings.aggregation_max_wait));
             let transaction_gas_limit: Option<U256> =
                 if skip_transaction_gas_limit_for.contains(&destination.id()) {
                     None
@@ -250,7 +280,7 @@ impl BaseAgent for Relayer {
             for (origin, validator_announce) in validator_announces.iter() {
                 let db = dbs.get(origin).unwrap().clone();
                 let metadata_builder = BaseMetadataBuilder::new(
-                    origin.clone(),
+                    core.settings.chain_setup(origin).unwrap().clone(),
                     destination_chain_setup.clone(),
                     prover_syncs[origin].clone(),
                     validator_announce.clone(),
@@ -261,6 +291,10 @@ impl BaseAgent for Relayer {
                         dest_mailbox.clone(),
                         settings.metric_app_contexts.clone(),
                     ),
+                    ism_cache.clone(),
+                    ccip_read_client.clone(),
+                    aggregation_costs.clone(),
+                    op_withdrawals.clone(),
                 );
 
                 msg_ctxs.insert(
@@ -274,6 +308,7 @@ impl BaseAgent for Relayer {
                         metadata_builder: Arc::new(metadata_builder),
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
                         transaction_gas_limit,
+                        blocked_ism_retry_delay: settings.blocked_ism_retry_delay,
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
                     }),
                 );
@@ -285,6 +320,9 @@ impl BaseAgent for Relayer {
             origin_chains: settings.origin_chains,
             destination_chains,
             msg_ctxs,
+            ism_caches,
+            send_channels,
+            receive_channels,
             core,
             message_syncs,
             interchain_gas_payment_syncs,
@@ -320,12 +358,12 @@ impl BaseAgent for Relayer {
             tasks.push(console_server.instrument(info_span!("Tokio console server")));
         }
         let sender = BroadcastSender::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
-        // send channels by destination chain
-        let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
+        let mut receive_channels = std::mem::take(&mut self.receive_channels);
         let mut prep_queues = HashMap::with_capacity(self.destination_chains.len());
         for (dest_domain, dest_conf) in &self.destination_chains {
-            let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
-            send_channels.insert(dest_domain.id(), send_channel);
+            let receive_channel = receive_channels
+                .remove(&dest_domain.id())
+                .expect("Missing receive channel for destination");
             let serial_submitter = SerialSubmitter::new(
                 dest_domain.clone(),
                 receive_channel,
@@ -389,6 +427,13 @@ impl BaseAgent for Relayer {
         let custom_routes = relayer_server::Server::new(self.destination_chains.len())
             .with_op_retry(sender.clone())
             .with_message_queue(prep_queues)
+            .with_message_contexts(
+                self.msg_ctxs
+                    .iter()
+                    .map(|(key, ctx)| ((key.origin, key.destination), ctx.clone()))
+                    .collect(),
+            )
+            .with_ism_caches(self.ism_caches.clone())
             .routes();
 
         let server = self
@@ -405,7 +450,7 @@ impl BaseAgent for Relayer {
         for origin in &self.origin_chains {
             tasks.push(self.run_message_processor(
                 origin,
-                send_channels.clone(),
+                self.send_channels.clone(),
                 task_monitor.clone(),
             ));
             tasks.push(self.run_merkle_tree_processor(origin, task_monitor.clone()));
@@ -750,6 +795,7 @@ mod test {
                         batch_contract_address: None,
                         max_batch_size: 1,
                     },
+                    arb_send_root_lookback_blocks: None,
                 }),
                 metrics_conf: PrometheusMiddlewareConf {
                     contracts: HashMap::new(),
@@ -759,6 +805,8 @@ mod test {
                     from: 0,
                     chunk_size: 1,
                     mode: IndexMode::Block,
+                    archive: None,
+                    chunk_size_bounds: None,
                 },
             },
         )];
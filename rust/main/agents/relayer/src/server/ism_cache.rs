@@ -0,0 +1,116 @@
+use axum::{
+    extract::{Query, State},
+    routing, Json, Router,
+};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::msg::metadata::IsmCache;
+
+const ISM_CACHE_INVALIDATE_API_BASE: &str = "/ism_cache/invalidate";
+
+/// ISM caches keyed by destination domain id
+pub type IsmCaches = HashMap<u32, Arc<IsmCache>>;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct IsmCacheInvalidateRequest {
+    /// Only invalidate the cache of this destination, instead of all of them
+    destination_domain: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IsmCacheInvalidateResponse {
+    /// How many cached entries were removed
+    pub invalidated: usize,
+}
+
+#[derive(new, Clone)]
+pub struct IsmCacheApi {
+    ism_caches: IsmCaches,
+}
+
+async fn invalidate_ism_cache(
+    State(ism_caches): State<IsmCaches>,
+    Query(request): Query<IsmCacheInvalidateRequest>,
+) -> Result<Json<IsmCacheInvalidateResponse>, String> {
+    let caches: Vec<_> = match request.destination_domain {
+        Some(domain) => vec![ism_caches
+            .get(&domain)
+            .ok_or_else(|| format!("No ISM cache found for domain {domain}"))?],
+        None => ism_caches.values().collect(),
+    };
+    let mut invalidated = 0;
+    for cache in caches {
+        invalidated += cache.invalidate_all().await;
+    }
+    tracing::info!(destination_domain = ?request.destination_domain, invalidated, "Invalidated ISM cache");
+    Ok(Json(IsmCacheInvalidateResponse { invalidated }))
+}
+
+impl IsmCacheApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::post(invalidate_ism_cache))
+            .with_state(self.ism_caches.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (ISM_CACHE_INVALIDATE_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use hyperlane_core::{ModuleType, H256};
+    use std::{net::SocketAddr, time::Duration};
+
+    fn setup_test_server() -> (SocketAddr, Arc<IsmCache>) {
+        let ism_cache = Arc::new(IsmCache::new(Duration::from_secs(60)));
+        let ism_caches = HashMap::from([(1, ism_cache.clone())]);
+        let (path, router) = IsmCacheApi::new(ism_caches).get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, ism_cache)
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_ism_cache() {
+        let (addr, ism_cache) = setup_test_server();
+        ism_cache
+            .module_types
+            .get_or_fetch(H256::zero(), || async { Ok(ModuleType::Null) })
+            .await
+            .unwrap();
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(format!(
+                "http://{addr}{ISM_CACHE_INVALIDATE_API_BASE}?destination_domain=1"
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response: IsmCacheInvalidateResponse = response.json().await.unwrap();
+        assert_eq!(response.invalidated, 1);
+
+        // Unknown destinations are rejected
+        let response = client
+            .post(format!(
+                "http://{addr}{ISM_CACHE_INVALIDATE_API_BASE}?destination_domain=2"
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert!(!response.status().is_success());
+    }
+}
@@ -0,0 +1,156 @@
+use axum::{
+    extract::{Query, State},
+    routing, Json, Router,
+};
+use derive_new::new;
+use hyperlane_base::db::HyperlaneDb;
+use hyperlane_core::{HyperlaneMessage, H256};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::msg::{
+    metadata::{IsmExplanation, MessageMetadataBuilder},
+    pending_message::MessageContext,
+};
+
+const ISM_EXPLAIN_API_BASE: &str = "/ism_explain";
+
+/// Message contexts keyed by (origin, destination) domain ids
+pub type MessageContexts = HashMap<(u32, u32), Arc<MessageContext>>;
+
+/// Either a dispatched message, or an (origin, recipient) pair to resolve the
+/// recipient's ISM for. Routing ISMs are resolved using the sender, if
+/// given, and an empty body.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct IsmExplainRequest {
+    origin_domain: u32,
+    message_id: Option<H256>,
+    destination_domain: Option<u32>,
+    recipient: Option<H256>,
+    sender: Option<H256>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IsmExplainResponse {
+    /// `None` when explaining an (origin, recipient) pair
+    message_id: Option<H256>,
+    origin_domain: u32,
+    destination_domain: u32,
+    recipient: H256,
+    ism: IsmExplanation,
+}
+
+#[derive(new, Clone)]
+pub struct IsmExplainApi {
+    msg_ctxs: MessageContexts,
+}
+
+impl IsmExplainRequest {
+    /// The message to resolve the ISM tree for, and whether it was dispatched
+    fn message(&self, msg_ctxs: &MessageContexts) -> Result<(HyperlaneMessage, bool), String> {
+        if let Some(message_id) = self.message_id {
+            let ctx = msg_ctxs
+                .iter()
+                .find(|((origin, _), _)| *origin == self.origin_domain)
+                .map(|(_, ctx)| ctx)
+                .ok_or_else(|| format!("Origin domain {} isn't relayed", self.origin_domain))?;
+            let message = ctx
+                .origin_db
+                .retrieve_message_by_id(&message_id)
+                .map_err(|err| format!("Failed to read message from the db: {err}"))?
+                .ok_or_else(|| format!("Message {message_id:?} wasn't indexed"))?;
+            return Ok((message, true));
+        }
+        match (self.destination_domain, self.recipient) {
+            (Some(destination), Some(recipient)) => Ok((
+                HyperlaneMessage {
+                    origin: self.origin_domain,
+                    sender: self.sender.unwrap_or_default(),
+                    destination,
+                    recipient,
+                    ..Default::default()
+                },
+                false,
+            )),
+            _ => Err(
+                "Expected either a `message_id`, or a `destination_domain` and `recipient`"
+                    .to_owned(),
+            ),
+        }
+    }
+}
+
+async fn explain_ism(
+    State(msg_ctxs): State<MessageContexts>,
+    Query(request): Query<IsmExplainRequest>,
+) -> Result<Json<IsmExplainResponse>, String> {
+    let (message, dispatched) = request.message(&msg_ctxs)?;
+    let ctx = msg_ctxs
+        .get(&(message.origin, message.destination))
+        .ok_or_else(|| {
+            format!(
+                "Messages from {} to {} aren't relayed",
+                message.origin, message.destination
+            )
+        })?;
+    // Technically it's bad practice to print the error message to the user, but
+    // this endpoint is for debugging purposes only.
+    let ism_address = ctx
+        .destination_mailbox
+        .recipient_ism(message.recipient)
+        .await
+        .map_err(|err| format!("Failed to fetch the recipient's ISM: {err}"))?;
+    let builder = MessageMetadataBuilder::new(ism_address, &message, ctx.metadata_builder.clone())
+        .await
+        .map_err(|err| format!("Failed to create a metadata builder: {err}"))?;
+    let ism = builder.explain(ism_address, &message, dispatched).await;
+
+    Ok(Json(IsmExplainResponse {
+        message_id: dispatched.then(|| message.id()),
+        origin_domain: message.origin,
+        destination_domain: message.destination,
+        recipient: message.recipient,
+        ism,
+    }))
+}
+
+impl IsmExplainApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(explain_ism))
+            .with_state(self.msg_ctxs.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (ISM_EXPLAIN_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(query: &str) -> IsmExplainRequest {
+        let uri = format!("{ISM_EXPLAIN_API_BASE}?{query}").parse().unwrap();
+        Query::try_from_uri(&uri).unwrap().0
+    }
+
+    #[test]
+    fn test_pair_request_builds_message() {
+        let recipient = H256::repeat_byte(1);
+        let request = request(&format!(
+            "origin_domain=1&destination_domain=2&recipient={recipient:?}"
+        ));
+        let (message, dispatched) = request.message(&HashMap::new()).unwrap();
+        assert!(!dispatched);
+        assert_eq!(message.origin, 1);
+        assert_eq!(message.destination, 2);
+        assert_eq!(message.recipient, recipient);
+    }
+
+    #[test]
+    fn test_incomplete_request_is_rejected() {
+        let request = request("origin_domain=1&destination_domain=2");
+        assert!(request.message(&HashMap::new()).is_err());
+    }
+}
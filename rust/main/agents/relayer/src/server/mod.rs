@@ -7,9 +7,13 @@ use crate::msg::op_queue::OperationPriorityQueue;
 
 pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 100;
 
+pub use ism_cache::*;
+pub use ism_explain::*;
 pub use list_messages::*;
 pub use message_retry::*;
 
+mod ism_cache;
+mod ism_explain;
 mod list_messages;
 mod message_retry;
 
@@ -20,6 +24,10 @@ pub struct Server {
     retry_transmitter: Option<Sender<MessageRetryRequest>>,
     #[new(default)]
     op_queues: Option<HashMap<u32, OperationPriorityQueue>>,
+    #[new(default)]
+    msg_ctxs: Option<MessageContexts>,
+    #[new(default)]
+    ism_caches: Option<IsmCaches>,
 }
 
 impl Server {
@@ -33,6 +41,16 @@ impl Server {
         self
     }
 
+    pub fn with_message_contexts(mut self, msg_ctxs: MessageContexts) -> Self {
+        self.msg_ctxs = Some(msg_ctxs);
+        self
+    }
+
+    pub fn with_ism_caches(mut self, ism_caches: IsmCaches) -> Self {
+        self.ism_caches = Some(ism_caches);
+        self
+    }
+
     /// Returns a vector of agent-specific endpoint routes to be served.
     /// Can be extended with additional routes and feature flags to enable/disable individually.
     pub fn routes(self) -> Vec<(&'static str, Router)> {
@@ -43,6 +61,12 @@ impl Server {
         if let Some(op_queues) = self.op_queues {
             routes.push(ListOperationsApi::new(op_queues).get_route());
         }
+        if let Some(msg_ctxs) = self.msg_ctxs {
+            routes.push(IsmExplainApi::new(msg_ctxs).get_route());
+        }
+        if let Some(ism_caches) = self.ism_caches {
+            routes.push(IsmCacheApi::new(ism_caches).get_route());
+        }
 
         routes
     }
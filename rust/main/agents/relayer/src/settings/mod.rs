@@ -4,7 +4,7 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{collections::HashSet, path::PathBuf, time::Duration};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
@@ -26,6 +26,12 @@ use crate::settings::matching_list::MatchingList;
 
 pub mod matching_list;
 
+/// How long ISM introspection results are cached for by default
+const DEFAULT_ISM_CACHE_TTL: Duration = Duration::from_secs(60 * 10);
+
+/// How long messages to an ISM that rejects every message wait by default
+const DEFAULT_BLOCKED_ISM_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
 /// Settings for `Relayer`
 #[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
 pub struct RelayerSettings {
@@ -61,6 +67,57 @@ pub struct RelayerSettings {
     pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
+    /// How long ISM introspection results are cached for. Zero disables
+    /// the cache.
+    pub ism_cache_ttl: Duration,
+    /// How CCIP read gateways are queried
+    pub ccip_read: CcipReadConf,
+    /// How long aggregation ISM metadata may wait for cheaper sub-modules
+    /// whose metadata isn't available yet. Zero disables waiting.
+    pub aggregation_max_wait: Duration,
+    /// How long to wait before retrying a message whose ISM rejects every
+    /// message, e.g. because it is paused
+    pub blocked_ism_retry_delay: Duration,
+}
+
+/// Config for querying the offchain gateways of CCIP read ISMs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CcipReadConf {
+    /// How long a single gateway request may take
+    pub timeout: Duration,
+    /// Responses larger than this are discarded
+    pub max_response_bytes: usize,
+    /// How many times a request to a gateway is retried after a transient
+    /// failure, before moving on to the next gateway
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled for every following one
+    pub retry_backoff: Duration,
+    /// How many of the URLs of a lookup are tried
+    pub max_urls: usize,
+    /// How long fetching the data of a lookup may take in total, across all
+    /// of its URLs and nested lookups
+    pub total_timeout: Duration,
+    /// If not empty, only gateways on these hosts or their subdomains are
+    /// queried. Otherwise gateways on private, loopback and link-local
+    /// addresses are never queried.
+    pub allowed_hosts: Vec<String>,
+    /// Gateways on these hosts or their subdomains are never queried
+    pub denied_hosts: Vec<String>,
+}
+
+impl Default for CcipReadConf {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_response_bytes: 256 * 1024,
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(500),
+            max_urls: 5,
+            total_timeout: Duration::from_secs(60),
+            allowed_hosts: vec![],
+            denied_hosts: vec![],
+        }
+    }
 }
 
 /// Config for gas payment enforcement
@@ -257,6 +314,29 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_bool()
             .unwrap_or(false);
 
+        let ism_cache_ttl = p
+            .chain(&mut err)
+            .get_opt_key("ismCacheTtlSeconds")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ISM_CACHE_TTL);
+
+        let ccip_read = parse_ccip_read_conf(&p, &mut err);
+
+        let aggregation_max_wait = p
+            .chain(&mut err)
+            .get_opt_key("aggregationMaxWaitSeconds")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::ZERO);
+
+        let blocked_ism_retry_delay = p
+            .chain(&mut err)
+            .get_opt_key("blockedIsmRetryDelaySeconds")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_BLOCKED_ISM_RETRY_DELAY);
+
         cfg_unwrap_all!(cwp, err: [base]);
 
         let skip_transaction_gas_limit_for = skip_transaction_gas_limit_for_names
@@ -320,10 +400,83 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers,
             metric_app_contexts,
+            ism_cache_ttl,
+            ccip_read,
+            aggregation_max_wait,
+            blocked_ism_retry_delay,
         })
     }
 }
 
+fn parse_ccip_read_conf(p: &ValueParser, err: &mut ConfigParsingError) -> CcipReadConf {
+    let default = CcipReadConf::default();
+    let parse_hosts = |hosts: &str| {
+        hosts
+            .split(',')
+            .map(|host| host.trim().to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect_vec()
+    };
+
+    CcipReadConf {
+        timeout: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("timeoutSeconds")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(default.timeout),
+        max_response_bytes: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("maxResponseBytes")
+            .parse_u64()
+            .map(|bytes| bytes as usize)
+            .unwrap_or(default.max_response_bytes),
+        max_retries: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("maxRetries")
+            .parse_u32()
+            .unwrap_or(default.max_retries),
+        retry_backoff: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("retryBackoffMs")
+            .parse_u64()
+            .map(Duration::from_millis)
+            .unwrap_or(default.retry_backoff),
+        max_urls: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("maxUrls")
+            .parse_u64()
+            .map(|urls| urls as usize)
+            .unwrap_or(default.max_urls),
+        total_timeout: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("totalTimeoutSeconds")
+            .parse_u64()
+            .map(Duration::from_secs)
+            .unwrap_or(default.total_timeout),
+        allowed_hosts: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("allowedHosts")
+            .parse_string()
+            .map(parse_hosts)
+            .unwrap_or_default(),
+        denied_hosts: p
+            .chain(err)
+            .get_opt_key("ccipRead")
+            .get_opt_key("deniedHosts")
+            .parse_string()
+            .map(parse_hosts)
+            .unwrap_or_default(),
+    }
+}
+
 fn parse_json_array(p: ValueParser) -> Option<(ConfigPath, Value)> {
     let mut err = ConfigParsingError::default();
 
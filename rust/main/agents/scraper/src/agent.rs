@@ -3,14 +3,21 @@ use std::{collections::HashMap, sync::Arc};
 use async_trait::async_trait;
 use derive_more::AsRef;
 use futures::future::try_join_all;
-use hyperlane_core::{Delivery, HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, H512};
+use hyperlane_core::{
+    Delivery, HyperlaneDomain, HyperlaneMessage, HyperlaneSequenceAwareIndexerStoreReader,
+    HyperlaneWatermarkedLogStore, InterchainGasPayment, H512,
+};
 use tokio::{sync::mpsc::Receiver as MpscReceiver, task::JoinHandle};
 use tracing::{info, info_span, instrument::Instrumented, trace, Instrument};
 
 use hyperlane_base::{
-    broadcast::BroadcastMpscSender, metrics::AgentMetrics, settings::IndexSettings, AgentMetadata,
-    BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics, HyperlaneAgentCore,
-    MetricsUpdater, SyncOptions,
+    broadcast::BroadcastMpscSender,
+    db::DB,
+    metrics::AgentMetrics,
+    settings::IndexSettings,
+    sink::{EventSink, SinkOutbox, SinkPublisher, SinkedLogStore},
+    AgentMetadata, BaseAgent, ChainMetrics, ContractSyncMetrics, ContractSyncer, CoreMetrics,
+    HyperlaneAgentCore, MetricsUpdater, SyncOptions,
 };
 
 use crate::{db::ScraperDb, settings::ScraperSettings, store::HyperlaneDbStore};
@@ -23,6 +30,7 @@ pub struct Scraper {
     core: HyperlaneAgentCore,
     contract_sync_metrics: Arc<ContractSyncMetrics>,
     scrapers: HashMap<u32, ChainScraper>,
+    sink: Option<Arc<dyn EventSink>>,
     settings: ScraperSettings,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
@@ -34,6 +42,32 @@ struct ChainScraper {
     index_settings: IndexSettings,
     store: HyperlaneDbStore,
     domain: HyperlaneDomain,
+    sink_outbox: Option<Arc<SinkOutbox>>,
+}
+
+/// The stores indexed events can be written to
+trait ScraperStore:
+    HyperlaneSequenceAwareIndexerStoreReader<HyperlaneMessage>
+    + HyperlaneWatermarkedLogStore<HyperlaneMessage>
+    + HyperlaneSequenceAwareIndexerStoreReader<Delivery>
+    + HyperlaneWatermarkedLogStore<Delivery>
+    + HyperlaneSequenceAwareIndexerStoreReader<InterchainGasPayment>
+    + HyperlaneWatermarkedLogStore<InterchainGasPayment>
+    + Clone
+    + 'static
+{
+}
+
+impl<S> ScraperStore for S where
+    S: HyperlaneSequenceAwareIndexerStoreReader<HyperlaneMessage>
+        + HyperlaneWatermarkedLogStore<HyperlaneMessage>
+        + HyperlaneSequenceAwareIndexerStoreReader<Delivery>
+        + HyperlaneWatermarkedLogStore<Delivery>
+        + HyperlaneSequenceAwareIndexerStoreReader<InterchainGasPayment>
+        + HyperlaneWatermarkedLogStore<InterchainGasPayment>
+        + Clone
+        + 'static
+{
 }
 
 #[async_trait]
@@ -57,9 +91,22 @@ impl BaseAgent for Scraper {
 
         let contract_sync_metrics = Arc::new(ContractSyncMetrics::new(&metrics));
 
-        let scrapers =
-            Self::build_chain_scrapers(&settings, metrics.clone(), &chain_metrics, db.clone())
-                .await;
+        let (sink, sink_db) = match &settings.sink {
+            Some(sink_settings) => (
+                Some(sink_settings.conf.build()?),
+                Some(DB::from_path(&sink_settings.db)?),
+            ),
+            None => (None, None),
+        };
+
+        let scrapers = Self::build_chain_scrapers(
+            &settings,
+            metrics.clone(),
+            &chain_metrics,
+            db.clone(),
+            sink_db.as_ref(),
+        )
+        .await;
 
         trace!(domain_count = scrapers.len(), "Created scrapers");
 
@@ -67,6 +114,7 @@ impl BaseAgent for Scraper {
             core,
             contract_sync_metrics,
             scrapers,
+            sink,
             settings,
             core_metrics: metrics,
             agent_metrics,
@@ -128,6 +176,17 @@ impl BaseAgent for Scraper {
                 }
             }
             tasks.push(metrics_updater.spawn());
+
+            if let (Some(sink), Some(outbox), Some(sink_settings)) =
+                (&self.sink, &scraper.sink_outbox, &self.settings.sink)
+            {
+                let publisher =
+                    SinkPublisher::new(outbox.clone(), sink.clone(), sink_settings.batch_size);
+                tasks.push(
+                    tokio::spawn(publisher.run())
+                        .instrument(info_span!("SinkPublisher", chain=%scraper.domain.name())),
+                );
+            }
         }
         if let Err(err) = try_join_all(tasks).await {
             tracing::error!(error = ?err, "Scraper task panicked");
@@ -139,7 +198,24 @@ impl Scraper {
     /// Sync contract data and other blockchain with the current chain state.
     /// This will spawn long-running contract sync tasks
     async fn scrape(&self, scraper: &ChainScraper) -> eyre::Result<Instrumented<JoinHandle<()>>> {
-        let store = scraper.store.clone();
+        match &scraper.sink_outbox {
+            Some(outbox) => {
+                let store = SinkedLogStore::new(
+                    scraper.store.clone(),
+                    scraper.domain.clone(),
+                    outbox.clone(),
+                );
+                self.scrape_into(scraper, store).await
+            }
+            None => self.scrape_into(scraper, scraper.store.clone()).await,
+        }
+    }
+
+    async fn scrape_into<S: ScraperStore>(
+        &self,
+        scraper: &ChainScraper,
+        store: S,
+    ) -> eyre::Result<Instrumented<JoinHandle<()>>> {
         let index_settings = scraper.index_settings.clone();
         let domain = scraper.domain.clone();
 
@@ -190,6 +266,7 @@ impl Scraper {
         settings: &ScraperSettings,
         metrics: Arc<CoreMetrics>,
         scraper_db: ScraperDb,
+        sink_db: Option<&DB>,
     ) -> eyre::Result<ChainScraper> {
         info!(domain = domain.name(), "create chain scraper for domain");
         let chain_setup = settings.chain_setup(domain)?;
@@ -208,10 +285,14 @@ impl Scraper {
             &chain_setup.index.clone(),
         )
         .await?;
+        let sink_outbox = sink_db
+            .map(|db| SinkOutbox::new(domain, db.clone()).map(Arc::new))
+            .transpose()?;
         Ok(ChainScraper {
             domain: domain.clone(),
             store,
             index_settings: chain_setup.index.clone(),
+            sink_outbox,
         })
     }
 
@@ -220,12 +301,19 @@ impl Scraper {
         metrics: Arc<CoreMetrics>,
         chain_metrics: &ChainMetrics,
         scraper_db: ScraperDb,
+        sink_db: Option<&DB>,
     ) -> HashMap<u32, ChainScraper> {
         let mut scrapers: HashMap<u32, ChainScraper> = HashMap::new();
 
         for domain in settings.chains_to_scrape.iter() {
-            match Self::build_chain_scraper(domain, settings, metrics.clone(), scraper_db.clone())
-                .await
+            match Self::build_chain_scraper(
+                domain,
+                settings,
+                metrics.clone(),
+                scraper_db.clone(),
+                sink_db,
+            )
+            .await
             {
                 Ok(scraper) => {
                     info!(domain = domain.name(), "insert chain scraper");
@@ -244,12 +332,12 @@ impl Scraper {
         scrapers
     }
 
-    async fn build_message_indexer(
+    async fn build_message_indexer<S: ScraperStore>(
         &self,
         domain: HyperlaneDomain,
         metrics: Arc<CoreMetrics>,
         contract_sync_metrics: Arc<ContractSyncMetrics>,
-        store: HyperlaneDbStore,
+        store: S,
         index_settings: IndexSettings,
     ) -> eyre::Result<(
         Instrumented<JoinHandle<()>>,
@@ -262,7 +350,7 @@ impl Scraper {
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
-                store.into(),
+                Arc::new(store),
                 true,
             )
             .await
@@ -282,12 +370,12 @@ impl Scraper {
         Ok((task, maybe_broadcaser))
     }
 
-    async fn build_delivery_indexer(
+    async fn build_delivery_indexer<S: ScraperStore>(
         &self,
         domain: HyperlaneDomain,
         metrics: Arc<CoreMetrics>,
         contract_sync_metrics: Arc<ContractSyncMetrics>,
-        store: HyperlaneDbStore,
+        store: S,
         index_settings: IndexSettings,
     ) -> eyre::Result<Instrumented<JoinHandle<()>>> {
         let sync = self
@@ -297,7 +385,7 @@ impl Scraper {
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
-                Arc::new(store.clone()) as _,
+                Arc::new(store),
                 true,
             )
             .await
@@ -319,12 +407,12 @@ impl Scraper {
         .instrument(info_span!("ChainContractSync", chain=%domain.name(), event=label)))
     }
 
-    async fn build_interchain_gas_payment_indexer(
+    async fn build_interchain_gas_payment_indexer<S: ScraperStore>(
         &self,
         domain: HyperlaneDomain,
         metrics: Arc<CoreMetrics>,
         contract_sync_metrics: Arc<ContractSyncMetrics>,
-        store: HyperlaneDbStore,
+        store: S,
         index_settings: IndexSettings,
         tx_id_receiver: Option<MpscReceiver<H512>>,
     ) -> eyre::Result<Instrumented<JoinHandle<()>>> {
@@ -335,7 +423,7 @@ impl Scraper {
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
-                Arc::new(store.clone()) as _,
+                Arc::new(store),
                 true,
             )
             .await
@@ -431,6 +519,7 @@ mod test {
                         batch_contract_address: None,
                         max_batch_size: 1,
                     },
+                    arb_send_root_lookback_blocks: None,
                 }),
                 metrics_conf: PrometheusMiddlewareConf {
                     contracts: HashMap::new(),
@@ -440,6 +529,8 @@ mod test {
                     from: 0,
                     chunk_size: 1,
                     mode: IndexMode::Block,
+                    archive: None,
+                    chunk_size_bounds: None,
                 },
             },
         )];
@@ -452,6 +543,7 @@ mod test {
             },
             db: String::new(),
             chains_to_scrape: vec![],
+            sink: None,
         }
     }
 
@@ -496,6 +588,7 @@ mod test {
             Arc::new(core_metrics),
             &chain_metrics,
             scraper_db,
+            None,
         )
         .await;
 
@@ -4,16 +4,17 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, default::Default};
+use std::{collections::HashSet, default::Default, path::PathBuf};
 
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
-use eyre::Context;
+use eyre::{eyre, Context};
 use hyperlane_base::{
     impl_loadable_from_settings,
     settings::{
         parser::{RawAgentConf, ValueParser},
         Settings,
     },
+    sink::{SinkConf, SinkSettings},
 };
 use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain};
 use serde::Deserialize;
@@ -30,8 +31,13 @@ pub struct ScraperSettings {
 
     pub db: String,
     pub chains_to_scrape: Vec<HyperlaneDomain>,
+    /// Where to additionally publish indexed events, if anywhere
+    pub sink: Option<SinkSettings>,
 }
 
+/// Default number of events published to a sink at once
+const DEFAULT_SINK_BATCH_SIZE: u32 = 100;
+
 #[derive(Debug, Deserialize)]
 #[serde(transparent)]
 struct RawScraperSettings(Value);
@@ -83,12 +89,66 @@ impl FromRawConf<RawScraperSettings> for ScraperSettings {
             Default::default()
         };
 
+        let sink = parse_sink_settings(&p, &mut err);
+
         cfg_unwrap_all!(&p.cwp, err: [base, db]);
 
         err.into_result(Self {
             base,
             db,
             chains_to_scrape,
+            sink,
         })
     }
 }
+
+/// Expects an optional `sink` object with the `type` of sink, its connection
+/// details and the `db` path its outbox is persisted at.
+fn parse_sink_settings(p: &ValueParser, err: &mut ConfigParsingError) -> Option<SinkSettings> {
+    let sink = p.chain(err).get_opt_key("sink").end()?;
+
+    let db = sink
+        .chain(err)
+        .get_key("db")
+        .parse_string()
+        .end()
+        .map(PathBuf::from);
+    let batch_size = sink
+        .chain(err)
+        .get_opt_key("batchSize")
+        .parse_u32()
+        .unwrap_or(DEFAULT_SINK_BATCH_SIZE);
+    let url = sink
+        .chain(err)
+        .get_key("url")
+        .parse_from_str("Invalid sink url")
+        .end();
+
+    let conf = match sink.chain(err).get_key("type").parse_string().end()? {
+        "webhook" => SinkConf::Webhook { url: url? },
+        "kafka" => {
+            let topic = sink.chain(err).get_key("topic").parse_string().end()?;
+            SinkConf::Kafka {
+                url: url?,
+                topic: topic.to_owned(),
+            }
+        }
+        "nats" => {
+            let subject = sink.chain(err).get_key("subject").parse_string().end()?;
+            SinkConf::Nats {
+                url: url?,
+                subject: subject.to_owned(),
+            }
+        }
+        _ => {
+            err.push(&sink.cwp + "type", eyre!("Unknown sink type"));
+            return None;
+        }
+    };
+
+    Some(SinkSettings {
+        db: db?,
+        batch_size: batch_size as usize,
+        conf,
+    })
+}
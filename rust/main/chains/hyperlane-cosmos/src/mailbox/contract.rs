@@ -3,12 +3,13 @@ use std::str::FromStr;
 
 use async_trait::async_trait;
 use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmrs::Any;
 use tracing::instrument;
 
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Mailbox, RawHyperlaneMessage,
-    ReorgPeriod, TxCostEstimate, TxOutcome, H256, U256,
+    utils::bytes_to_hex, BatchItem, BatchResult, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Mailbox,
+    QueueOperation, RawHyperlaneMessage, ReorgPeriod, TxCostEstimate, TxOutcome, H256, U256,
 };
 
 use crate::grpc::WasmProvider;
@@ -17,7 +18,7 @@ use crate::payloads::mailbox::{
     GeneralMailboxQuery, ProcessMessageRequest, ProcessMessageRequestInner,
 };
 use crate::types::tx_response_to_outcome;
-use crate::utils::get_block_height_for_reorg_period;
+use crate::utils::{exclude_indexes, get_block_height_for_reorg_period};
 use crate::{payloads, ConnectionConf, CosmosAddress, CosmosProvider, Signer};
 
 #[derive(Clone, Debug)]
@@ -161,12 +162,7 @@ impl Mailbox for CosmosMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let response: TxResponse = self
             .provider
@@ -177,6 +173,47 @@ impl Mailbox for CosmosMailbox {
         Ok(tx_response_to_outcome(response)?)
     }
 
+    #[instrument(skip(self, messages), fields(size=%messages.len()))]
+    async fn process_batch(
+        &self,
+        messages: &[BatchItem<HyperlaneMessage>],
+    ) -> ChainResult<BatchResult> {
+        let grpc = self.provider.grpc();
+        let msgs = messages
+            .iter()
+            .map(|item| {
+                grpc.execute_contract_msg(&process_message_request(
+                    &item.data,
+                    &item.submission_data.metadata,
+                ))
+            })
+            .collect::<ChainResult<Vec<Any>>>()?;
+
+        let (gas_limit, failed_indexes) = grpc.estimate_batch_gas(&msgs).await;
+        let Some(gas_limit) = gas_limit else {
+            return Ok(BatchResult::failed(msgs.len()));
+        };
+        let msgs = exclude_indexes(msgs, &failed_indexes);
+
+        let response = grpc.send_msgs(msgs, Some(gas_limit.into())).await?;
+        Ok(BatchResult::new(
+            Some(tx_response_to_outcome(response)?),
+            failed_indexes,
+        ))
+    }
+
+    #[instrument(skip(self, ops), fields(size=%ops.len()))]
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let messages = ops
+            .iter()
+            .map(|op| op.try_batch())
+            .collect::<ChainResult<Vec<BatchItem<HyperlaneMessage>>>>()?;
+        self.process_batch(&messages).await
+    }
+
     #[instrument(err, ret, skip(self), fields(hyp_message=%message, metadata=%bytes_to_hex(metadata)))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn process_estimate_costs(
@@ -184,12 +221,7 @@ impl Mailbox for CosmosMailbox {
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let gas_limit = self
             .provider
@@ -199,7 +231,7 @@ impl Mailbox for CosmosMailbox {
 
         let result = TxCostEstimate {
             gas_limit: gas_limit.into(),
-            gas_price: self.provider.grpc().gas_price(),
+            gas_price: self.provider.grpc().current_gas_price().await?,
             l2_gas_limit: None,
         };
 
@@ -211,6 +243,15 @@ impl Mailbox for CosmosMailbox {
     }
 }
 
+fn process_message_request(message: &HyperlaneMessage, metadata: &[u8]) -> ProcessMessageRequest {
+    ProcessMessageRequest {
+        process: ProcessMessageRequestInner {
+            message: hex::encode(RawHyperlaneMessage::from(message)),
+            metadata: hex::encode(metadata),
+        },
+    }
+}
+
 impl CosmosMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     pub(crate) async fn nonce_at_block(&self, block_height: Option<u64>) -> ChainResult<u32> {
@@ -0,0 +1,198 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use tendermint::abci::EventAttribute;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, Indexed, Indexer, InterchainGasPaymaster, InterchainGasPayment, LogMeta,
+    SequenceAwareIndexer, H256, H512, U256,
+};
+
+use crate::native::{parse_native_id, TypedEventAttributes};
+use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
+use crate::utils::{execute_and_parse_log_futures, parse_logs_in_range, parse_logs_in_tx};
+use crate::{ConnectionConf, CosmosProvider, Signer};
+
+/// A reference to an interchain gas paymaster of the native Hyperlane module
+/// on some Cosmos chain
+#[derive(Debug)]
+pub struct CosmosNativeInterchainGasPaymaster {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: CosmosProvider,
+}
+
+impl HyperlaneContract for CosmosNativeInterchainGasPaymaster {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for CosmosNativeInterchainGasPaymaster {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+impl InterchainGasPaymaster for CosmosNativeInterchainGasPaymaster {}
+
+impl CosmosNativeInterchainGasPaymaster {
+    /// Create a reference to the IGP with the given id on some chain
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = CosmosProvider::new(
+            locator.domain.clone(),
+            conf.clone(),
+            locator.clone(),
+            signer,
+        )?;
+
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider,
+        })
+    }
+}
+
+// ------------------ Indexer ------------------
+
+/// The gas payment event of the native IGP.
+const EVENT_GAS_PAYMENT_TYPE: &str = "hyperlane.core.post_dispatch.v1.EventGasPayment";
+
+const IGP_ID_ATTRIBUTE_KEY: &str = "igp_id";
+const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
+const DESTINATION_ATTRIBUTE_KEY: &str = "destination";
+const GAS_AMOUNT_ATTRIBUTE_KEY: &str = "gas_amount";
+const PAYMENT_ATTRIBUTE_KEY: &str = "payment";
+
+/// Struct that retrieves gas payment events of a native IGP
+#[derive(Debug, Clone)]
+pub struct CosmosNativeInterchainGasPaymasterIndexer {
+    provider: Box<CosmosWasmRpcProvider>,
+}
+
+impl CosmosNativeInterchainGasPaymasterIndexer {
+    /// Create a gas payment indexer for the IGP with the given id
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let provider = CosmosWasmRpcProvider::new_native(
+            conf,
+            locator,
+            EVENT_GAS_PAYMENT_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            provider: Box::new(provider),
+        })
+    }
+
+    #[instrument(err)]
+    fn interchain_gas_payment_parser(
+        attrs: &Vec<EventAttribute>,
+    ) -> ChainResult<ParsedEvent<InterchainGasPayment>> {
+        let attrs = TypedEventAttributes::parse(attrs)?;
+        let igp_id = attrs.get_id(IGP_ID_ATTRIBUTE_KEY)?;
+        let payment = InterchainGasPayment {
+            message_id: parse_native_id(attrs.get(MESSAGE_ID_ATTRIBUTE_KEY)?)?,
+            destination: attrs.get(DESTINATION_ATTRIBUTE_KEY)?.parse()?,
+            payment: U256::from_dec_str(attrs.get(PAYMENT_ATTRIBUTE_KEY)?)?,
+            gas_amount: U256::from_dec_str(attrs.get(GAS_AMOUNT_ATTRIBUTE_KEY)?)?,
+        };
+
+        Ok(ParsedEvent::new(igp_id, payment))
+    }
+}
+
+#[async_trait]
+impl Indexer<InterchainGasPayment> for CosmosNativeInterchainGasPaymasterIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
+        let logs_futures = parse_logs_in_range(
+            range,
+            self.provider.clone(),
+            Self::interchain_gas_payment_parser,
+            "InterchainGasPaymentCursor",
+        );
+
+        execute_and_parse_log_futures(logs_futures).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
+        parse_logs_in_tx(
+            &tx_hash.into(),
+            self.provider.clone(),
+            Self::interchain_gas_payment_parser,
+            "InterchainGasPaymentReceiver",
+        )
+        .await
+        .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<InterchainGasPayment> for CosmosNativeInterchainGasPaymasterIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // The native IGP does not keep a payment counter
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::native::native_id;
+    use crate::utils::event_attributes_from_str;
+
+    use super::*;
+
+    #[test]
+    fn test_interchain_gas_payment_parser() {
+        let igp_id = H256::from_low_u64_be(3);
+        let message_id = H256::from_low_u64_be(42);
+        let attrs = event_attributes_from_str(&format!(
+            r#"[{{"key":"destination","value":"169","index":true}},{{"key":"gas_amount","value":"\"200000\"","index":true}},{{"key":"igp_id","value":"\"{}\"","index":true}},{{"key":"message_id","value":"\"{}\"","index":true}},{{"key":"payment","value":"\"2000\"","index":true}}]"#,
+            native_id(igp_id),
+            native_id(message_id),
+        ));
+
+        let parsed_event =
+            CosmosNativeInterchainGasPaymasterIndexer::interchain_gas_payment_parser(&attrs)
+                .unwrap();
+
+        assert_eq!(
+            parsed_event,
+            ParsedEvent::new(
+                native_id(igp_id),
+                InterchainGasPayment {
+                    message_id,
+                    destination: 169,
+                    payment: U256::from(2000),
+                    gas_amount: U256::from(200000),
+                }
+            )
+        );
+    }
+}
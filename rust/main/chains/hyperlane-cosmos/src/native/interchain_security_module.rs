@@ -0,0 +1,214 @@
+use async_trait::async_trait;
+use cosmrs::Any;
+use prost::Message;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType,
+    MultisigIsm, RawHyperlaneMessage, RoutingIsm, H256, U256,
+};
+
+use crate::native::proto::{
+    self, QueryIsmRequest, QueryIsmResponse, QueryVerifyDryRunRequest, QueryVerifyDryRunResponse,
+};
+use crate::native::{native_id, parse_eth_address, parse_native_id};
+use crate::{ConnectionConf, CosmosProvider, HyperlaneCosmosError, Signer};
+
+/// A reference to an ISM of the native Hyperlane module on some Cosmos chain.
+///
+/// The module stores all ISM types behind a single query, so this type
+/// implements the generic ISM interface as well as the multisig and routing
+/// ones, decoding whichever the ISM with the given id turns out to be.
+#[derive(Debug, Clone)]
+pub struct CosmosNativeIsm {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: CosmosProvider,
+}
+
+impl CosmosNativeIsm {
+    /// Create a reference to the ISM with the given id on some chain
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = CosmosProvider::new(
+            locator.domain.clone(),
+            conf.clone(),
+            locator.clone(),
+            signer,
+        )?;
+
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider,
+        })
+    }
+
+    async fn query_ism(&self) -> ChainResult<Any> {
+        let response: QueryIsmResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_ISM_PATH,
+                QueryIsmRequest {
+                    id: native_id(self.address),
+                },
+                None,
+            )
+            .await?;
+        response
+            .ism
+            .ok_or_else(|| ChainCommunicationError::from_other_str("ISM not found"))
+    }
+}
+
+/// Maps the type url of an ISM stored by the module to its module type.
+fn module_type_from_type_url(type_url: &str) -> ChainResult<ModuleType> {
+    match type_url {
+        proto::MESSAGE_ID_MULTISIG_ISM_TYPE_URL => Ok(ModuleType::MessageIdMultisig),
+        proto::MERKLE_ROOT_MULTISIG_ISM_TYPE_URL => Ok(ModuleType::MerkleRootMultisig),
+        proto::ROUTING_ISM_TYPE_URL => Ok(ModuleType::Routing),
+        proto::NOOP_ISM_TYPE_URL => Ok(ModuleType::Null),
+        other => Err(ChainCommunicationError::from_other_str(&format!(
+            "unknown ISM type `{other}`"
+        ))),
+    }
+}
+
+impl HyperlaneContract for CosmosNativeIsm {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for CosmosNativeIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for CosmosNativeIsm {
+    /// Returns the module type of the ISM compliant with the corresponding
+    /// metadata offchain fetching and onchain formatting standard.
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        let ism = self.query_ism().await?;
+        module_type_from_type_url(&ism.type_url)
+    }
+
+    /// Dry runs the ISM verification through the mailbox query service and
+    /// returns `Some(gas_estimate)` if it succeeds.
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        let response: QueryVerifyDryRunResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_VERIFY_DRY_RUN_PATH,
+                QueryVerifyDryRunRequest {
+                    ism_id: native_id(self.address),
+                    message: hex::encode(RawHyperlaneMessage::from(message)),
+                    metadata: hex::encode(metadata),
+                },
+                None,
+            )
+            .await?;
+        // As with CosmWasm ISMs, the verification is a query rather than a
+        // simulated tx, so there is no gas usage to report.
+        let dummy_gas_value = U256::one();
+        Ok(response.verified.then_some(dummy_gas_value))
+    }
+}
+
+#[async_trait]
+impl MultisigIsm for CosmosNativeIsm {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn validators_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let ism = self.query_ism().await?;
+        match ism.type_url.as_str() {
+            proto::MESSAGE_ID_MULTISIG_ISM_TYPE_URL | proto::MERKLE_ROOT_MULTISIG_ISM_TYPE_URL => {}
+            other => {
+                return Err(ChainCommunicationError::from_other_str(&format!(
+                    "ISM of type `{other}` is not a multisig ISM"
+                )))
+            }
+        }
+        let multisig = proto::MultisigIsm::decode(ism.value.as_slice())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+
+        let validators = multisig
+            .validators
+            .iter()
+            .map(|v| parse_eth_address(v))
+            .collect::<ChainResult<Vec<_>>>()?;
+        let threshold = u8::try_from(multisig.threshold).map_err(|_| {
+            ChainCommunicationError::from_other_str(&format!(
+                "threshold {} exceeds u8",
+                multisig.threshold
+            ))
+        })?;
+        Ok((validators, threshold))
+    }
+}
+
+#[async_trait]
+impl RoutingIsm for CosmosNativeIsm {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let ism = self.query_ism().await?;
+        if ism.type_url != proto::ROUTING_ISM_TYPE_URL {
+            return Err(ChainCommunicationError::from_other_str(&format!(
+                "ISM of type `{}` is not a routing ISM",
+                ism.type_url
+            )));
+        }
+        let routing = proto::RoutingIsm::decode(ism.value.as_slice())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+
+        let route = routing
+            .routes
+            .iter()
+            .find(|route| route.domain == message.origin)
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(&format!(
+                    "no route for origin domain {}",
+                    message.origin
+                ))
+            })?;
+        parse_native_id(&route.ism)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_type_from_type_url() {
+        assert_eq!(
+            module_type_from_type_url(proto::MESSAGE_ID_MULTISIG_ISM_TYPE_URL).unwrap(),
+            ModuleType::MessageIdMultisig
+        );
+        assert_eq!(
+            module_type_from_type_url(proto::ROUTING_ISM_TYPE_URL).unwrap(),
+            ModuleType::Routing
+        );
+        assert!(module_type_from_type_url("/cosmos.bank.v1beta1.MsgSend").is_err());
+    }
+}
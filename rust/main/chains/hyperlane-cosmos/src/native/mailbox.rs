@@ -0,0 +1,481 @@
+use std::io::Cursor;
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use prost::Message;
+use tendermint::abci::EventAttribute;
+use tracing::instrument;
+
+use hyperlane_core::{
+    utils::bytes_to_hex, BatchItem, BatchResult, ChainCommunicationError, ChainResult,
+    ContractLocator, Decode, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox, QueueOperation, RawHyperlaneMessage,
+    ReorgPeriod, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, H512, U256,
+};
+
+use crate::native::proto::{
+    self, MsgProcessMessage, QueryDeliveredRequest, QueryDeliveredResponse, QueryMailboxRequest,
+    QueryMailboxResponse, QueryRecipientIsmRequest, QueryRecipientIsmResponse,
+};
+use crate::native::{native_id, parse_native_id, TypedEventAttributes};
+use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
+use crate::types::tx_response_to_outcome;
+use crate::utils::{
+    exclude_indexes, execute_and_parse_log_futures, get_block_height_for_reorg_period,
+    parse_logs_in_range, parse_logs_in_tx,
+};
+use crate::{ConnectionConf, CosmosProvider, Signer};
+
+#[derive(Clone, Debug)]
+/// A reference to a mailbox of the native Hyperlane module on some Cosmos
+/// chain
+pub struct CosmosNativeMailbox {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: CosmosProvider,
+}
+
+impl CosmosNativeMailbox {
+    /// Create a reference to the mailbox with the given id on some chain
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = CosmosProvider::new(
+            locator.domain.clone(),
+            conf.clone(),
+            locator.clone(),
+            signer,
+        )?;
+
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider,
+        })
+    }
+
+    /// Builds the `MsgProcessMessage` for delivering `message`, relayed by
+    /// the configured signer if there is one.
+    fn process_msg(&self, message: &HyperlaneMessage, metadata: &[u8]) -> MsgProcessMessage {
+        let relayer = self
+            .provider
+            .grpc()
+            .get_signer()
+            .map(|signer| signer.address.clone())
+            .unwrap_or_default();
+        MsgProcessMessage {
+            mailbox_id: native_id(self.address),
+            relayer,
+            metadata: hex::encode(metadata),
+            message: hex::encode(RawHyperlaneMessage::from(message)),
+        }
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn mailbox_at_block(&self, block_height: Option<u64>) -> ChainResult<proto::Mailbox> {
+        let response: QueryMailboxResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_MAILBOX_PATH,
+                QueryMailboxRequest {
+                    id: native_id(self.address),
+                },
+                block_height,
+            )
+            .await?;
+        response
+            .mailbox
+            .ok_or_else(|| ChainCommunicationError::from_other_str("mailbox not found"))
+    }
+
+    pub(crate) async fn nonce_at_block(&self, block_height: Option<u64>) -> ChainResult<u32> {
+        Ok(self.mailbox_at_block(block_height).await?.message_sent)
+    }
+}
+
+impl HyperlaneContract for CosmosNativeMailbox {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for CosmosNativeMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl Mailbox for CosmosNativeMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        let block_height =
+            get_block_height_for_reorg_period(self.provider.grpc(), reorg_period).await?;
+        self.nonce_at_block(block_height).await
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        let response: QueryDeliveredResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_DELIVERED_PATH,
+                QueryDeliveredRequest {
+                    id: native_id(self.address),
+                    message_id: native_id(id),
+                },
+                None,
+            )
+            .await?;
+        Ok(response.delivered)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn default_ism(&self) -> ChainResult<H256> {
+        let mailbox = self.mailbox_at_block(None).await?;
+        parse_native_id(&mailbox.default_ism)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        let response: QueryRecipientIsmResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_RECIPIENT_ISM_PATH,
+                QueryRecipientIsmRequest {
+                    recipient: native_id(recipient),
+                },
+                None,
+            )
+            .await?;
+        parse_native_id(&response.ism_id)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let msg = proto::to_any(
+            proto::MSG_PROCESS_MESSAGE_TYPE_URL,
+            &self.process_msg(message, metadata),
+        );
+        let response = self
+            .provider
+            .grpc()
+            .send_msgs(vec![msg], tx_gas_limit)
+            .await?;
+
+        tx_response_to_outcome(response)
+    }
+
+    #[instrument(skip(self, messages), fields(size=%messages.len()))]
+    async fn process_batch(
+        &self,
+        messages: &[BatchItem<HyperlaneMessage>],
+    ) -> ChainResult<BatchResult> {
+        let grpc = self.provider.grpc();
+        let msgs = messages
+            .iter()
+            .map(|item| {
+                proto::to_any(
+                    proto::MSG_PROCESS_MESSAGE_TYPE_URL,
+                    &self.process_msg(&item.data, &item.submission_data.metadata),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (gas_limit, failed_indexes) = grpc.estimate_batch_gas(&msgs).await;
+        let Some(gas_limit) = gas_limit else {
+            return Ok(BatchResult::failed(msgs.len()));
+        };
+        let msgs = exclude_indexes(msgs, &failed_indexes);
+
+        let response = grpc.send_msgs(msgs, Some(gas_limit.into())).await?;
+        Ok(BatchResult::new(
+            Some(tx_response_to_outcome(response)?),
+            failed_indexes,
+        ))
+    }
+
+    #[instrument(skip(self, ops), fields(size=%ops.len()))]
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let messages = ops
+            .iter()
+            .map(|op| op.try_batch())
+            .collect::<ChainResult<Vec<BatchItem<HyperlaneMessage>>>>()?;
+        self.process_batch(&messages).await
+    }
+
+    #[instrument(err, ret, skip(self), fields(hyp_message=%message, metadata=%bytes_to_hex(metadata)))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        let msg = proto::to_any(
+            proto::MSG_PROCESS_MESSAGE_TYPE_URL,
+            &self.process_msg(message, metadata),
+        );
+        let gas_limit = self.provider.grpc().estimate_gas(vec![msg]).await?;
+
+        Ok(TxCostEstimate {
+            gas_limit: gas_limit.into(),
+            gas_price: self.provider.grpc().current_gas_price().await?,
+            l2_gas_limit: None,
+        })
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        self.process_msg(message, metadata).encode_to_vec()
+    }
+}
+
+// ------------------ Indexers ------------------
+
+/// The dispatch event of the native mailbox.
+const EVENT_DISPATCH_TYPE: &str = "hyperlane.core.v1.EventDispatch";
+/// The process event of the native mailbox.
+const EVENT_PROCESS_TYPE: &str = "hyperlane.core.v1.EventProcess";
+
+const ORIGIN_MAILBOX_ID_ATTRIBUTE_KEY: &str = "origin_mailbox_id";
+const MESSAGE_ATTRIBUTE_KEY: &str = "message";
+const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
+
+/// Struct that retrieves dispatch events of a native Cosmos mailbox
+#[derive(Debug, Clone)]
+pub struct CosmosNativeMailboxDispatchIndexer {
+    mailbox: CosmosNativeMailbox,
+    provider: Box<CosmosWasmRpcProvider>,
+}
+
+impl CosmosNativeMailboxDispatchIndexer {
+    /// Create a dispatch indexer for the mailbox with the given id
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let mailbox = CosmosNativeMailbox::new(conf.clone(), locator.clone(), signer)?;
+        let provider = CosmosWasmRpcProvider::new_native(
+            conf,
+            locator,
+            EVENT_DISPATCH_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            mailbox,
+            provider: Box::new(provider),
+        })
+    }
+
+    #[instrument(err)]
+    fn hyperlane_message_parser(
+        attrs: &Vec<EventAttribute>,
+    ) -> ChainResult<ParsedEvent<HyperlaneMessage>> {
+        let attrs = TypedEventAttributes::parse(attrs)?;
+        let mailbox_id = attrs.get_id(ORIGIN_MAILBOX_ID_ATTRIBUTE_KEY)?;
+        // Intentionally using read_from to get a Result::Err if there's
+        // an issue with the message.
+        let mut reader = Cursor::new(hex::decode(
+            attrs.get(MESSAGE_ATTRIBUTE_KEY)?.trim_start_matches("0x"),
+        )?);
+        let message = HyperlaneMessage::read_from(&mut reader)?;
+
+        Ok(ParsedEvent::new(mailbox_id, message))
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for CosmosNativeMailboxDispatchIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let logs_futures = parse_logs_in_range(
+            range,
+            self.provider.clone(),
+            Self::hyperlane_message_parser,
+            "HyperlaneMessageCursor",
+        );
+
+        execute_and_parse_log_futures(logs_futures).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        parse_logs_in_tx(
+            &tx_hash.into(),
+            self.provider.clone(),
+            Self::hyperlane_message_parser,
+            "HyperlaneMessageReceiver",
+        )
+        .await
+        .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for CosmosNativeMailboxDispatchIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(&self).await?;
+
+        let sequence = self.mailbox.nonce_at_block(Some(tip.into())).await?;
+
+        Ok((Some(sequence), tip))
+    }
+}
+
+/// Struct that retrieves delivery events of a native Cosmos mailbox
+#[derive(Debug, Clone)]
+pub struct CosmosNativeMailboxDeliveryIndexer {
+    provider: Box<CosmosWasmRpcProvider>,
+}
+
+impl CosmosNativeMailboxDeliveryIndexer {
+    /// Create a delivery indexer for the mailbox with the given id
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let provider = CosmosWasmRpcProvider::new_native(
+            conf,
+            locator,
+            EVENT_PROCESS_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            provider: Box::new(provider),
+        })
+    }
+
+    #[instrument(err)]
+    fn hyperlane_delivery_parser(attrs: &Vec<EventAttribute>) -> ChainResult<ParsedEvent<H256>> {
+        let attrs = TypedEventAttributes::parse(attrs)?;
+        let mailbox_id = attrs.get_id(ORIGIN_MAILBOX_ID_ATTRIBUTE_KEY)?;
+        let message_id = parse_native_id(attrs.get(MESSAGE_ID_ATTRIBUTE_KEY)?)?;
+
+        Ok(ParsedEvent::new(mailbox_id, message_id))
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for CosmosNativeMailboxDeliveryIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let logs_futures = parse_logs_in_range(
+            range,
+            self.provider.clone(),
+            Self::hyperlane_delivery_parser,
+            "DeliveryCursor",
+        );
+
+        execute_and_parse_log_futures(logs_futures).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        parse_logs_in_tx(
+            &tx_hash.into(),
+            self.provider.clone(),
+            Self::hyperlane_delivery_parser,
+            "DeliveryReceiver",
+        )
+        .await
+        .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for CosmosNativeMailboxDeliveryIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(&self).await?;
+
+        // No sequence for message deliveries.
+        Ok((None, tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::providers::rpc::ParsedEvent;
+    use crate::utils::event_attributes_from_str;
+
+    use super::*;
+
+    const MAILBOX_ID: &str = "0x68797065726c616e650000000000000000000000000000000000000000000000";
+    const MESSAGE: &str = "03000000006e74726e0000000000000000000000006ba6343a09a60ac048d0e99f50b76fd99eff1063000000a9000000000000000000000000281973b53c9aacec128ac964a6f750fea40912aa48656c6c6f";
+
+    #[test]
+    fn test_hyperlane_message_parser() {
+        let attrs = event_attributes_from_str(&format!(
+            r#"[{{"key":"destination","value":"169","index":true}},{{"key":"message","value":"\"0x{MESSAGE}\"","index":true}},{{"key":"origin_mailbox_id","value":"\"{MAILBOX_ID}\"","index":true}},{{"key":"recipient","value":"\"0x000000000000000000000000281973b53c9aacec128ac964a6f750fea40912aa\"","index":true}},{{"key":"sender","value":"\"0x0000000000000000000000006ba6343a09a60ac048d0e99f50b76fd99eff1063\"","index":true}}]"#,
+        ));
+
+        let parsed_event =
+            CosmosNativeMailboxDispatchIndexer::hyperlane_message_parser(&attrs).unwrap();
+
+        assert_eq!(
+            parsed_event,
+            ParsedEvent::new(
+                MAILBOX_ID.into(),
+                HyperlaneMessage::from(hex::decode(MESSAGE).unwrap()),
+            )
+        );
+    }
+
+    #[test]
+    fn test_hyperlane_delivery_parser() {
+        let message_id = H256::from_low_u64_be(42);
+        let attrs = event_attributes_from_str(&format!(
+            r#"[{{"key":"message_id","value":"\"{}\"","index":true}},{{"key":"origin","value":"169","index":true}},{{"key":"origin_mailbox_id","value":"\"{MAILBOX_ID}\"","index":true}}]"#,
+            native_id(message_id),
+        ));
+
+        let parsed_event =
+            CosmosNativeMailboxDeliveryIndexer::hyperlane_delivery_parser(&attrs).unwrap();
+
+        assert_eq!(
+            parsed_event,
+            ParsedEvent::new(MAILBOX_ID.into(), message_id)
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use tendermint::abci::EventAttribute;
+use tracing::instrument;
+
+use hyperlane_core::{
+    accumulator::{incremental::IncrementalMerkle, TREE_DEPTH},
+    ChainCommunicationError, ChainResult, Checkpoint, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    MerkleTreeHook, MerkleTreeInsertion, ReorgPeriod, SequenceAwareIndexer, H256, H512,
+};
+
+use crate::native::proto::{self, QueryMerkleTreeHookRequest, QueryMerkleTreeHookResponse};
+use crate::native::{native_id, parse_native_id, TypedEventAttributes};
+use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
+use crate::utils::{
+    execute_and_parse_log_futures, get_block_height_for_reorg_period, parse_logs_in_range,
+    parse_logs_in_tx,
+};
+use crate::{ConnectionConf, CosmosProvider, Signer};
+
+#[derive(Debug, Clone)]
+/// A reference to a merkle tree hook of the native Hyperlane module on some
+/// Cosmos chain
+pub struct CosmosNativeMerkleTreeHook {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: CosmosProvider,
+}
+
+impl CosmosNativeMerkleTreeHook {
+    /// Create a reference to the merkle tree hook with the given id on some
+    /// chain
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = CosmosProvider::new(
+            locator.domain.clone(),
+            conf.clone(),
+            locator.clone(),
+            signer,
+        )?;
+
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider,
+        })
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    async fn tree_at_block(&self, block_height: Option<u64>) -> ChainResult<IncrementalMerkle> {
+        let response: QueryMerkleTreeHookResponse = self
+            .provider
+            .grpc()
+            .module_query(
+                proto::QUERY_MERKLE_TREE_HOOK_PATH,
+                QueryMerkleTreeHookRequest {
+                    id: native_id(self.address),
+                },
+                block_height,
+            )
+            .await?;
+        let tree = response
+            .merkle_tree_hook
+            .and_then(|hook| hook.merkle_tree)
+            .ok_or_else(|| ChainCommunicationError::from_other_str("merkle tree hook not found"))?;
+
+        if tree.leafs.len() > TREE_DEPTH {
+            return Err(ChainCommunicationError::from_other_str(&format!(
+                "merkle tree branch has {} nodes, expected at most {TREE_DEPTH}",
+                tree.leafs.len()
+            )));
+        }
+        // The branch may omit trailing nodes that have never been written
+        let mut branch = [H256::zero(); TREE_DEPTH];
+        for (node, leaf) in branch.iter_mut().zip(tree.leafs.iter()) {
+            if leaf.len() != H256::len_bytes() {
+                return Err(ChainCommunicationError::from_other_str(
+                    "merkle tree branch node is not 32 bytes",
+                ));
+            }
+            *node = H256::from_slice(leaf);
+        }
+
+        Ok(IncrementalMerkle::new(branch, tree.count as usize))
+    }
+
+    pub(crate) async fn count_at_block(&self, block_height: Option<u64>) -> ChainResult<u32> {
+        Ok(self.tree_at_block(block_height).await?.count() as u32)
+    }
+}
+
+impl HyperlaneContract for CosmosNativeMerkleTreeHook {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for CosmosNativeMerkleTreeHook {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl MerkleTreeHook for CosmosNativeMerkleTreeHook {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn tree(&self, reorg_period: &ReorgPeriod) -> ChainResult<IncrementalMerkle> {
+        let block_height =
+            get_block_height_for_reorg_period(self.provider.grpc(), reorg_period).await?;
+        self.tree_at_block(block_height).await
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        let block_height =
+            get_block_height_for_reorg_period(self.provider.grpc(), reorg_period).await?;
+        self.count_at_block(block_height).await
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn latest_checkpoint(&self, reorg_period: &ReorgPeriod) -> ChainResult<Checkpoint> {
+        let block_height =
+            get_block_height_for_reorg_period(self.provider.grpc(), reorg_period).await?;
+        let tree = self.tree_at_block(block_height).await?;
+        if tree.count() == 0 {
+            return Err(ChainCommunicationError::from_other_str(
+                "no checkpoint, merkle tree is empty",
+            ));
+        }
+
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address,
+            mailbox_domain: self.domain.id(),
+            root: tree.root(),
+            index: tree.index(),
+        })
+    }
+}
+
+// ------------------ Indexer ------------------
+
+/// The insertion event of the native merkle tree hook.
+const EVENT_INSERTED_INTO_TREE_TYPE: &str = "hyperlane.core.post_dispatch.v1.EventInsertedIntoTree";
+
+const MERKLE_TREE_HOOK_ID_ATTRIBUTE_KEY: &str = "merkle_tree_hook_id";
+const MESSAGE_ID_ATTRIBUTE_KEY: &str = "message_id";
+const INDEX_ATTRIBUTE_KEY: &str = "index";
+
+#[derive(Debug, Clone)]
+/// Struct that retrieves insertion events of a native merkle tree hook
+pub struct CosmosNativeMerkleTreeHookIndexer {
+    merkle_tree_hook: CosmosNativeMerkleTreeHook,
+    provider: Box<CosmosWasmRpcProvider>,
+}
+
+impl CosmosNativeMerkleTreeHookIndexer {
+    /// Create an insertion indexer for the merkle tree hook with the given id
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let provider = CosmosWasmRpcProvider::new_native(
+            conf.clone(),
+            locator.clone(),
+            EVENT_INSERTED_INTO_TREE_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            merkle_tree_hook: CosmosNativeMerkleTreeHook::new(conf, locator, signer)?,
+            provider: Box::new(provider),
+        })
+    }
+
+    #[instrument(err)]
+    fn merkle_tree_insertion_parser(
+        attrs: &Vec<EventAttribute>,
+    ) -> ChainResult<ParsedEvent<MerkleTreeInsertion>> {
+        let attrs = TypedEventAttributes::parse(attrs)?;
+        let hook_id = attrs.get_id(MERKLE_TREE_HOOK_ID_ATTRIBUTE_KEY)?;
+        let message_id = parse_native_id(attrs.get(MESSAGE_ID_ATTRIBUTE_KEY)?)?;
+        let leaf_index = attrs.get(INDEX_ATTRIBUTE_KEY)?.parse::<u32>()?;
+
+        Ok(ParsedEvent::new(
+            hook_id,
+            MerkleTreeInsertion::new(leaf_index, message_id),
+        ))
+    }
+}
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for CosmosNativeMerkleTreeHookIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let logs_futures = parse_logs_in_range(
+            range,
+            self.provider.clone(),
+            Self::merkle_tree_insertion_parser,
+            "MerkleTreeInsertionCursor",
+        );
+
+        execute_and_parse_log_futures(logs_futures).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        parse_logs_in_tx(
+            &tx_hash.into(),
+            self.provider.clone(),
+            Self::merkle_tree_insertion_parser,
+            "MerkleTreeInsertionReceiver",
+        )
+        .await
+        .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for CosmosNativeMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+        let sequence = self
+            .merkle_tree_hook
+            .count_at_block(Some(tip.into()))
+            .await?;
+
+        Ok((Some(sequence), tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::event_attributes_from_str;
+
+    use super::*;
+
+    #[test]
+    fn test_merkle_tree_insertion_parser() {
+        let hook_id = H256::from_low_u64_be(7);
+        let message_id = H256::from_low_u64_be(42);
+        let attrs = event_attributes_from_str(&format!(
+            r#"[{{"key":"index","value":"3","index":true}},{{"key":"merkle_tree_hook_id","value":"\"{}\"","index":true}},{{"key":"message_id","value":"\"{}\"","index":true}}]"#,
+            native_id(hook_id),
+            native_id(message_id),
+        ));
+
+        let parsed_event =
+            CosmosNativeMerkleTreeHookIndexer::merkle_tree_insertion_parser(&attrs).unwrap();
+
+        assert_eq!(
+            parsed_event,
+            ParsedEvent::new(native_id(hook_id), MerkleTreeInsertion::new(3, message_id))
+        );
+    }
+}
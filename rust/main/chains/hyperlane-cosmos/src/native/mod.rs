@@ -0,0 +1,110 @@
+//! Support for chains that embed Hyperlane as native Cosmos SDK modules
+//! (`x/mailbox`, `x/ism`, `x/warp`) rather than as CosmWasm contracts.
+//!
+//! Objects of the modules (mailboxes, ISMs, hooks) are identified by 32 byte
+//! ids, which are used as the "contract address" of the respective agent
+//! types. Queries go through the module gRPC query services and txs are
+//! sent as module messages, both reusing the signer and fallback gRPC
+//! plumbing of [`crate::grpc::WasmGrpcProvider`].
+
+use std::collections::HashMap;
+
+use tendermint::abci::EventAttribute;
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, H160, H256};
+
+pub use self::{
+    interchain_gas::*, interchain_security_module::*, mailbox::*, merkle_tree_hook::*,
+    validator_announce::*,
+};
+
+mod interchain_gas;
+mod interchain_security_module;
+mod mailbox;
+mod merkle_tree_hook;
+pub(crate) mod proto;
+mod validator_announce;
+
+/// Formats the id of a native module object the way the module expects it.
+pub(crate) fn native_id(id: H256) -> String {
+    format!("0x{}", hex::encode(id))
+}
+
+/// Parses the hex id of a native module object.
+pub(crate) fn parse_native_id(id: &str) -> ChainResult<H256> {
+    let bytes = hex::decode(id.trim_start_matches("0x"))?;
+    if bytes.len() != H256::len_bytes() {
+        return Err(ChainCommunicationError::from_other_str(&format!(
+            "invalid native module id `{id}`"
+        )));
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+/// Parses a hex encoded Ethereum address, e.g. of a validator, into an H256.
+pub(crate) fn parse_eth_address(address: &str) -> ChainResult<H256> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))?;
+    if bytes.len() != H160::len_bytes() {
+        return Err(ChainCommunicationError::from_other_str(&format!(
+            "invalid ethereum address `{address}`"
+        )));
+    }
+    Ok(H160::from_slice(&bytes).into())
+}
+
+/// The attributes of a typed event emitted by a native module.
+///
+/// Typed events JSON encode their attribute values, so strings are quoted,
+/// which is undone here.
+pub(crate) struct TypedEventAttributes(HashMap<String, String>);
+
+impl TypedEventAttributes {
+    pub(crate) fn parse(attrs: &[EventAttribute]) -> ChainResult<Self> {
+        let mut values = HashMap::new();
+        for attr in attrs {
+            let EventAttribute::V037(a) = attr else {
+                return Err(ChainCommunicationError::from_other_str(
+                    "unsupported event attribute version",
+                ));
+            };
+            values.insert(a.key.clone(), a.value.trim_matches('"').to_owned());
+        }
+        Ok(Self(values))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> ChainResult<&str> {
+        self.0
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| ChainCommunicationError::from_other_str(&format!("missing {key}")))
+    }
+
+    /// Gets a native module id attribute, normalized so that it can be
+    /// compared against the id of the indexed object.
+    pub(crate) fn get_id(&self, key: &str) -> ChainResult<String> {
+        Ok(native_id(parse_native_id(self.get(key)?)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_id_roundtrip() {
+        let id = H256::from_low_u64_be(0x1234);
+        let formatted = native_id(id);
+        assert_eq!(
+            formatted,
+            "0x0000000000000000000000000000000000000000000000000000000000001234"
+        );
+        assert_eq!(parse_native_id(&formatted).unwrap(), id);
+        assert!(parse_native_id("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_parse_eth_address() {
+        let address = parse_eth_address("0x0000000000000000000000000000000000001234").unwrap();
+        assert_eq!(address, H256::from_low_u64_be(0x1234));
+    }
+}
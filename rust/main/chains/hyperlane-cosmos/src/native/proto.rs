@@ -0,0 +1,253 @@
+//! Protobuf messages of the native Hyperlane Cosmos SDK modules.
+//!
+//! These mirror the `hyperlane.core.*` proto definitions of the module and
+//! only include the fields the agents need. Field numbers must be kept in
+//! sync with the module's `.proto` files.
+
+use cosmrs::Any;
+
+/// `Msg/ProcessMessage` of the mailbox
+pub(crate) const MSG_PROCESS_MESSAGE_TYPE_URL: &str = "/hyperlane.core.v1.MsgProcessMessage";
+/// `Msg/AnnounceValidator` of the ISM module
+pub(crate) const MSG_ANNOUNCE_VALIDATOR_TYPE_URL: &str =
+    "/hyperlane.core.interchain_security.v1.MsgAnnounceValidator";
+
+/// `Query/Mailbox` of the mailbox
+pub(crate) const QUERY_MAILBOX_PATH: &str = "/hyperlane.core.v1.Query/Mailbox";
+/// `Query/Delivered` of the mailbox
+pub(crate) const QUERY_DELIVERED_PATH: &str = "/hyperlane.core.v1.Query/Delivered";
+/// `Query/RecipientIsm` of the mailbox
+pub(crate) const QUERY_RECIPIENT_ISM_PATH: &str = "/hyperlane.core.v1.Query/RecipientIsm";
+/// `Query/VerifyDryRun` of the mailbox
+pub(crate) const QUERY_VERIFY_DRY_RUN_PATH: &str = "/hyperlane.core.v1.Query/VerifyDryRun";
+/// `Query/Ism` of the ISM module
+pub(crate) const QUERY_ISM_PATH: &str = "/hyperlane.core.interchain_security.v1.Query/Ism";
+/// `Query/AnnouncedStorageLocations` of the ISM module
+pub(crate) const QUERY_ANNOUNCED_STORAGE_LOCATIONS_PATH: &str =
+    "/hyperlane.core.interchain_security.v1.Query/AnnouncedStorageLocations";
+/// `Query/MerkleTreeHook` of the post dispatch module
+pub(crate) const QUERY_MERKLE_TREE_HOOK_PATH: &str =
+    "/hyperlane.core.post_dispatch.v1.Query/MerkleTreeHook";
+
+/// Type url of a message id multisig ISM
+pub(crate) const MESSAGE_ID_MULTISIG_ISM_TYPE_URL: &str =
+    "/hyperlane.core.interchain_security.v1.MessageIdMultisigISM";
+/// Type url of a merkle root multisig ISM
+pub(crate) const MERKLE_ROOT_MULTISIG_ISM_TYPE_URL: &str =
+    "/hyperlane.core.interchain_security.v1.MerkleRootMultisigISM";
+/// Type url of a routing ISM
+pub(crate) const ROUTING_ISM_TYPE_URL: &str = "/hyperlane.core.interchain_security.v1.RoutingISM";
+/// Type url of a noop ISM
+pub(crate) const NOOP_ISM_TYPE_URL: &str = "/hyperlane.core.interchain_security.v1.NoopISM";
+
+/// Encodes a message into an `Any` with the given type url.
+pub(crate) fn to_any<M: prost::Message>(type_url: &str, msg: &M) -> Any {
+    Any {
+        type_url: type_url.to_owned(),
+        value: msg.encode_to_vec(),
+    }
+}
+
+// ------------------ hyperlane.core.v1 ------------------
+
+/// Processes a message on the destination mailbox.
+/// Both `metadata` and `message` are hex encoded.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct MsgProcessMessage {
+    #[prost(string, tag = "1")]
+    pub mailbox_id: String,
+    #[prost(string, tag = "2")]
+    pub relayer: String,
+    #[prost(string, tag = "3")]
+    pub metadata: String,
+    #[prost(string, tag = "4")]
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Mailbox {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub owner: String,
+    #[prost(uint32, tag = "3")]
+    pub message_sent: u32,
+    #[prost(uint32, tag = "4")]
+    pub message_received: u32,
+    #[prost(string, tag = "5")]
+    pub default_ism: String,
+    #[prost(string, tag = "6")]
+    pub default_hook: String,
+    #[prost(string, tag = "7")]
+    pub required_hook: String,
+    #[prost(uint32, tag = "8")]
+    pub local_domain: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryMailboxRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryMailboxResponse {
+    #[prost(message, optional, tag = "1")]
+    pub mailbox: Option<Mailbox>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryDeliveredRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub message_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryDeliveredResponse {
+    #[prost(bool, tag = "1")]
+    pub delivered: bool,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryRecipientIsmRequest {
+    #[prost(string, tag = "1")]
+    pub recipient: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryRecipientIsmResponse {
+    #[prost(string, tag = "1")]
+    pub ism_id: String,
+}
+
+/// Both `message` and `metadata` are hex encoded.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryVerifyDryRunRequest {
+    #[prost(string, tag = "1")]
+    pub ism_id: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(string, tag = "3")]
+    pub metadata: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryVerifyDryRunResponse {
+    #[prost(bool, tag = "1")]
+    pub verified: bool,
+}
+
+// ------------------ hyperlane.core.interchain_security.v1 ------------------
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryIsmRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryIsmResponse {
+    #[prost(message, optional, tag = "1")]
+    pub ism: Option<Any>,
+}
+
+/// Shared layout of `MessageIdMultisigISM` and `MerkleRootMultisigISM`.
+/// Validators are hex encoded Ethereum addresses.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct MultisigIsm {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub owner: String,
+    #[prost(string, repeated, tag = "3")]
+    pub validators: Vec<String>,
+    #[prost(uint32, tag = "4")]
+    pub threshold: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Route {
+    #[prost(uint32, tag = "1")]
+    pub domain: u32,
+    #[prost(string, tag = "2")]
+    pub ism: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct RoutingIsm {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub owner: String,
+    #[prost(message, repeated, tag = "3")]
+    pub routes: Vec<Route>,
+}
+
+/// Announces a storage location for a validator. `validator` is a hex encoded
+/// Ethereum address and `signature` is hex encoded.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct MsgAnnounceValidator {
+    #[prost(string, tag = "1")]
+    pub validator: String,
+    #[prost(string, tag = "2")]
+    pub storage_location: String,
+    #[prost(string, tag = "3")]
+    pub signature: String,
+    #[prost(string, tag = "4")]
+    pub mailbox_id: String,
+    #[prost(string, tag = "5")]
+    pub creator: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryAnnouncedStorageLocationsRequest {
+    #[prost(string, tag = "1")]
+    pub mailbox_id: String,
+    #[prost(string, tag = "2")]
+    pub validator_address: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryAnnouncedStorageLocationsResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub storage_locations: Vec<String>,
+}
+
+// ------------------ hyperlane.core.post_dispatch.v1 ------------------
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryMerkleTreeHookRequest {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct QueryMerkleTreeHookResponse {
+    #[prost(message, optional, tag = "1")]
+    pub merkle_tree_hook: Option<WrappedMerkleTreeHook>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct WrappedMerkleTreeHook {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub owner: String,
+    #[prost(string, tag = "3")]
+    pub mailbox_id: String,
+    #[prost(message, optional, tag = "4")]
+    pub merkle_tree: Option<Tree>,
+}
+
+/// The incremental merkle tree, where `leafs` are the branch nodes.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Tree {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub leafs: Vec<Vec<u8>>,
+    #[prost(uint32, tag = "2")]
+    pub count: u32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub root: Vec<u8>,
+}
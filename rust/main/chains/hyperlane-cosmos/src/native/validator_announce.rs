@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use futures::future::try_join_all;
+
+use hyperlane_core::{
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H160, H256, U256,
+};
+
+use crate::native::native_id;
+use crate::native::proto::{
+    self, MsgAnnounceValidator, QueryAnnouncedStorageLocationsRequest,
+    QueryAnnouncedStorageLocationsResponse,
+};
+use crate::types::tx_response_to_outcome;
+use crate::{ConnectionConf, CosmosProvider, Signer};
+
+/// A reference to the validator announcements of the native Hyperlane module
+/// on some Cosmos chain.
+///
+/// The module keeps announcements per mailbox, so the configured validator
+/// announce address is the id of the mailbox.
+#[derive(Debug)]
+pub struct CosmosNativeValidatorAnnounce {
+    domain: HyperlaneDomain,
+    address: H256,
+    provider: CosmosProvider,
+}
+
+impl CosmosNativeValidatorAnnounce {
+    /// Create a reference to the validator announcements of the mailbox with
+    /// the given id
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let provider = CosmosProvider::new(
+            locator.domain.clone(),
+            conf.clone(),
+            locator.clone(),
+            signer,
+        )?;
+
+        Ok(Self {
+            domain: locator.domain.clone(),
+            address: locator.address,
+            provider,
+        })
+    }
+}
+
+impl HyperlaneContract for CosmosNativeValidatorAnnounce {
+    fn address(&self) -> H256 {
+        self.address
+    }
+}
+
+impl HyperlaneChain for CosmosNativeValidatorAnnounce {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl ValidatorAnnounce for CosmosNativeValidatorAnnounce {
+    async fn get_announced_storage_locations(
+        &self,
+        validators: &[H256],
+    ) -> ChainResult<Vec<Vec<String>>> {
+        let queries = validators.iter().map(|validator| {
+            let request = QueryAnnouncedStorageLocationsRequest {
+                mailbox_id: native_id(self.address),
+                validator_address: format!("0x{}", hex::encode(H160::from(*validator))),
+            };
+            async move {
+                let response: QueryAnnouncedStorageLocationsResponse = self
+                    .provider
+                    .grpc()
+                    .module_query(proto::QUERY_ANNOUNCED_STORAGE_LOCATIONS_PATH, request, None)
+                    .await?;
+                Ok::<_, ChainCommunicationError>(response.storage_locations)
+            }
+        });
+
+        try_join_all(queries).await
+    }
+
+    async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
+        let creator = self.provider.grpc().get_signer()?.address.clone();
+        let msg = MsgAnnounceValidator {
+            validator: format!("0x{}", hex::encode(announcement.value.validator)),
+            storage_location: announcement.value.storage_location,
+            signature: hex::encode(announcement.signature.to_vec()),
+            mailbox_id: native_id(self.address),
+            creator,
+        };
+
+        let response = self
+            .provider
+            .grpc()
+            // TODO: consider transaction overrides for Cosmos.
+            .send_msgs(
+                vec![proto::to_any(proto::MSG_ANNOUNCE_VALIDATOR_TYPE_URL, &msg)],
+                None,
+            )
+            .await?;
+
+        tx_response_to_outcome(response)
+    }
+
+    async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
+        // TODO: check user balance. For now, just try announcing and
+        // allow the announce attempt to fail if there are not enough tokens.
+        Some(0u64.into())
+    }
+}
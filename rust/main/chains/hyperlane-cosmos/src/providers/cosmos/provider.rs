@@ -9,6 +9,7 @@ use cosmrs::{proto, AccountId, Any, Coin, Tx};
 use hyperlane_core::rpc_clients::FallbackProvider;
 use itertools::{any, cloned, Itertools};
 use once_cell::sync::Lazy;
+use prometheus::Gauge;
 use serde::{Deserialize, Serialize};
 use tendermint::hash::Algorithm;
 use tendermint::Hash;
@@ -25,6 +26,8 @@ use hyperlane_core::{
 };
 
 use crate::grpc::{WasmGrpcProvider, WasmProvider};
+use crate::native::parse_native_id;
+use crate::native::proto::{MsgProcessMessage, MSG_PROCESS_MESSAGE_TYPE_URL};
 use crate::providers::cosmos::provider::parse::PacketData;
 use crate::providers::rpc::CosmosRpcClient;
 use crate::rpc_clients::CosmosFallbackProvider;
@@ -80,6 +83,12 @@ impl CosmosProvider {
         })
     }
 
+    /// Reports the gas price used for txs to `gauge`
+    pub fn with_gas_price_metric(mut self, gauge: Gauge) -> Self {
+        self.grpc_provider = self.grpc_provider.with_gas_price_metric(gauge);
+        self
+    }
+
     /// Get a grpc client
     pub fn grpc(&self) -> &WasmGrpcProvider {
         &self.grpc_provider
@@ -211,21 +220,23 @@ impl CosmosProvider {
 
     /// Extract contract address from transaction.
     fn contract(tx: &Tx, tx_hash: &H256) -> ChainResult<H256> {
-        // We merge two error messages together so that both of them are reported
-        match Self::contract_address_from_msg_execute_contract(tx) {
-            Ok(contract) => Ok(contract),
-            Err(msg_execute_contract_error) => {
-                match Self::contract_address_from_msg_recv_packet(tx) {
-                    Ok(contract) => Ok(contract),
-                    Err(msg_recv_packet_error) => {
-                        let errors = vec![msg_execute_contract_error, msg_recv_packet_error];
-                        let error = HyperlaneCosmosError::ParsingAttemptsFailed(errors);
-                        warn!(?tx_hash, ?error);
-                        Err(ChainCommunicationError::from_other(error))?
-                    }
-                }
+        let parsers: [fn(&Tx) -> Result<H256, HyperlaneCosmosError>; 3] = [
+            Self::contract_address_from_msg_execute_contract,
+            Self::contract_address_from_msg_recv_packet,
+            Self::mailbox_id_from_msg_process_message,
+        ];
+
+        // We collect all error messages so that all of them are reported
+        let mut errors = vec![];
+        for parser in parsers {
+            match parser(tx) {
+                Ok(contract) => return Ok(contract),
+                Err(err) => errors.push(err),
             }
         }
+        let error = HyperlaneCosmosError::ParsingAttemptsFailed(errors);
+        warn!(?tx_hash, ?error);
+        Err(ChainCommunicationError::from_other(error))
     }
 
     /// Assumes that there is only one `MsgExecuteContract` message in the transaction
@@ -278,6 +289,24 @@ impl CosmosProvider {
         Ok(address)
     }
 
+    /// Native Hyperlane modules have no contracts, so the recipient of a
+    /// `MsgProcessMessage` is the mailbox it is processed by.
+    fn mailbox_id_from_msg_process_message(tx: &Tx) -> Result<H256, HyperlaneCosmosError> {
+        let any = tx
+            .body
+            .messages
+            .iter()
+            .find(|a| a.type_url == MSG_PROCESS_MESSAGE_TYPE_URL)
+            .ok_or_else(|| {
+                let msg = "could not find process message of a native mailbox";
+                HyperlaneCosmosError::ParsingFailed(msg.to_owned())
+            })?;
+        let msg = MsgProcessMessage::decode(any.value.as_slice())?;
+
+        parse_native_id(&msg.mailbox_id)
+            .map_err(|err| HyperlaneCosmosError::ParsingFailed(err.to_string()))
+    }
+
     /// Reports if transaction contains fees expressed in unsupported denominations
     /// The only denomination we support at the moment is the one we express gas minimum price
     /// in the configuration of a chain. If fees contain an entry in a different denomination,
@@ -465,9 +494,18 @@ impl HyperlaneProvider for CosmosProvider {
     async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
         let height = self.grpc_provider.latest_block_height().await?;
         let latest_block = self.block_info_by_height(height).await?;
+        // Gas prices are fractional on many Cosmos chains, so this is rounded up
+        // to the lowest denomination. The unrounded price is reported by
+        // `current_gas_price` if a gas price metric is set.
+        let gas_price: U256 = self
+            .grpc_provider
+            .current_gas_price()
+            .await?
+            .ceil_to_integer()
+            .try_into()?;
         let chain_info = ChainInfo {
             latest_block,
-            min_gas_price: None,
+            min_gas_price: Some(gas_price),
         };
         Ok(Some(chain_info))
     }
@@ -1,4 +1,6 @@
 use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use cosmrs::{
@@ -27,13 +29,15 @@ use cosmrs::{
     Any, Coin,
 };
 use derive_new::new;
+use futures::future::join_all;
+use prometheus::Gauge;
 use protobuf::Message as _;
 use serde::Serialize;
 use tonic::{
     transport::{Channel, Endpoint},
     GrpcMethod, IntoRequest,
 };
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 use url::Url;
 
 use hyperlane_core::{
@@ -41,6 +45,12 @@ use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, FixedPointNumber, HyperlaneDomain, U256,
 };
 
+use crate::utils::exclude_indexes;
+
+use self::sequence::{send_with_sequence, AccountSequence, SequenceManager};
+
+mod gas_price;
+mod sequence;
 use crate::{rpc_clients::CosmosFallbackProvider, HyperlaneCosmosError};
 use crate::{signers::Signer, ConnectionConf};
 use crate::{CosmosAddress, CosmosAmount};
@@ -132,6 +142,11 @@ pub struct WasmGrpcProvider {
     /// See `<https://docs.rs/tonic/latest/tonic/transport/struct.Channel.html#multiplexing-requests>`
     provider: CosmosFallbackProvider<CosmosChannel>,
     gas_price: CosmosAmount,
+    /// Reports the gas price used for txs, if set.
+    gas_price_metric: Option<Gauge>,
+    /// Sequence of the signer, shared with other providers using the same
+    /// signer on this chain.
+    sequence_manager: Option<Arc<SequenceManager>>,
 }
 
 impl WasmGrpcProvider {
@@ -164,6 +179,10 @@ impl WasmGrpcProvider {
             conf.get_contract_address_bytes(),
         )?;
 
+        let sequence_manager = signer
+            .as_ref()
+            .map(|signer| SequenceManager::for_signer(&conf.get_chain_id(), &signer.address));
+
         Ok(Self {
             domain,
             conf,
@@ -171,17 +190,25 @@ impl WasmGrpcProvider {
             signer,
             provider,
             gas_price,
+            gas_price_metric: None,
+            sequence_manager,
         })
     }
 
+    /// Reports the gas price used for txs to `gauge`
+    pub fn with_gas_price_metric(mut self, gauge: Gauge) -> Self {
+        self.gas_price_metric = Some(gauge);
+        self
+    }
+
     /// Gets a signer, or returns an error if one is not available.
-    fn get_signer(&self) -> ChainResult<&Signer> {
+    pub(crate) fn get_signer(&self) -> ChainResult<&Signer> {
         self.signer
             .as_ref()
             .ok_or(ChainCommunicationError::SignerUnavailable)
     }
 
-    /// Get the gas price
+    /// Get the configured gas price, i.e. the minimum accepted by validators
     pub fn gas_price(&self) -> FixedPointNumber {
         self.gas_price.amount.clone()
     }
@@ -192,11 +219,12 @@ impl WasmGrpcProvider {
         &self,
         msgs: Vec<cosmrs::Any>,
         gas_limit: u64,
+        gas_price: &FixedPointNumber,
+        account: AccountSequence,
     ) -> ChainResult<(SignDoc, Coin)> {
         // As this function is only used for estimating gas or sending transactions,
         // we can reasonably expect to have a signer.
         let signer = self.get_signer()?;
-        let account_info = self.account_query(signer.address.clone()).await?;
         let current_height = self.latest_block_height().await?;
         let timeout_height = current_height + TIMEOUT_BLOCKS;
 
@@ -206,17 +234,9 @@ impl WasmGrpcProvider {
             TryInto::<u32>::try_into(timeout_height)
                 .map_err(ChainCommunicationError::from_other)?,
         );
-        let signer_info = SignerInfo::single_direct(Some(signer.public_key), account_info.sequence);
+        let signer_info = SignerInfo::single_direct(Some(signer.public_key), account.sequence);
 
-        let amount: u128 = (FixedPointNumber::from(gas_limit) * self.gas_price())
-            .ceil_to_integer()
-            .try_into()?;
-        let fee_coin = Coin::new(
-            // The fee to pay is the gas limit * the gas price
-            amount,
-            self.conf.get_canonical_asset().as_str(),
-        )
-        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let fee_coin = self.fee_coin(gas_limit, gas_price)?;
         let auth_info =
             signer_info.auth_info(Fee::from_amount_and_gas(fee_coin.clone(), gas_limit));
 
@@ -227,45 +247,93 @@ impl WasmGrpcProvider {
             .map_err(Into::<HyperlaneCosmosError>::into)?;
 
         Ok((
-            SignDoc::new(&tx_body, &auth_info, &chain_id, account_info.account_number)
+            SignDoc::new(&tx_body, &auth_info, &chain_id, account.account_number)
                 .map_err(Into::<HyperlaneCosmosError>::into)?,
             fee_coin,
         ))
     }
 
-    /// Generates a raw signed transaction including `msgs`, estimating gas if a limit is not provided,
-    /// and the Coin amount required to pay for tx fees.
-    async fn generate_raw_signed_tx_and_fee(
+    /// The Coin amount required to pay for a tx with `gas_limit` at `gas_price`.
+    fn fee_coin(&self, gas_limit: u64, gas_price: &FixedPointNumber) -> ChainResult<Coin> {
+        let amount: u128 = (FixedPointNumber::from(gas_limit) * gas_price.clone())
+            .ceil_to_integer()
+            .try_into()?;
+        let fee_coin = Coin::new(
+            // The fee to pay is the gas limit * the gas price
+            amount,
+            self.conf.get_canonical_asset().as_str(),
+        )
+        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(fee_coin)
+    }
+
+    /// Generates a raw signed transaction including `msgs`, using the given
+    /// gas price and account sequence.
+    async fn generate_raw_signed_tx(
         &self,
         msgs: Vec<cosmrs::Any>,
-        gas_limit: Option<u64>,
-    ) -> ChainResult<(Vec<u8>, Coin)> {
-        let gas_limit = if let Some(l) = gas_limit {
-            l
-        } else {
-            self.estimate_gas(msgs.clone()).await?
-        };
-
-        let (sign_doc, fee) = self
-            .generate_unsigned_sign_doc_and_fee(msgs, gas_limit)
+        gas_limit: u64,
+        gas_price: &FixedPointNumber,
+        account: AccountSequence,
+    ) -> ChainResult<Vec<u8>> {
+        let (sign_doc, _) = self
+            .generate_unsigned_sign_doc_and_fee(msgs, gas_limit, gas_price, account)
             .await?;
 
         let signer = self.get_signer()?;
         let tx_signed = sign_doc
             .sign(&signer.signing_key()?)
             .map_err(Into::<HyperlaneCosmosError>::into)?;
-        Ok((
-            tx_signed
-                .to_bytes()
-                .map_err(Into::<HyperlaneCosmosError>::into)?,
-            fee,
-        ))
+        let tx_bytes = tx_signed
+            .to_bytes()
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(tx_bytes)
+    }
+
+    /// Gets the sequence manager of the signer, or returns an error if a
+    /// signer is not available.
+    fn sequence_manager(&self) -> ChainResult<&SequenceManager> {
+        self.sequence_manager
+            .as_deref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+
+    /// Returns the locally tracked account sequence of the signer, querying
+    /// it from the chain if it is not known.
+    async fn account_sequence(
+        &self,
+        state: &mut Option<AccountSequence>,
+    ) -> ChainResult<AccountSequence> {
+        if let Some(account) = *state {
+            return Ok(account);
+        }
+        let account = self.query_account_sequence().await?;
+        *state = Some(account);
+        Ok(account)
+    }
+
+    /// Queries the account sequence of the signer from the chain.
+    async fn query_account_sequence(&self) -> ChainResult<AccountSequence> {
+        let signer = self.get_signer()?;
+        let account_info = self.account_query(signer.address.clone()).await?;
+        Ok(AccountSequence {
+            account_number: account_info.account_number,
+            sequence: account_info.sequence,
+        })
     }
 
     /// Estimates gas for a transaction containing `msgs`.
-    async fn estimate_gas(&self, msgs: Vec<cosmrs::Any>) -> ChainResult<u64> {
+    pub(crate) async fn estimate_gas(&self, msgs: Vec<cosmrs::Any>) -> ChainResult<u64> {
+        // Simulate with the local sequence, so that txs still pending in the
+        // mempool don't cause a sequence mismatch.
+        let account = {
+            let mut state = self.sequence_manager()?.lock().await;
+            self.account_sequence(&mut state).await?
+        };
         // Get a sign doc with 0 gas, because we plan to simulate
-        let (sign_doc, _) = self.generate_unsigned_sign_doc_and_fee(msgs, 0).await?;
+        let (sign_doc, _) = self
+            .generate_unsigned_sign_doc_and_fee(msgs, 0, &self.gas_price(), account)
+            .await?;
 
         let raw_tx = TxRaw {
             body_bytes: sign_doc.body_bytes,
@@ -311,6 +379,139 @@ impl WasmGrpcProvider {
         Ok(gas_estimate)
     }
 
+    /// Estimates gas for a batch of `msgs` to be sent in a single tx.
+    ///
+    /// Cosmos txs are atomic, so if the batch fails to simulate as a whole,
+    /// each message is simulated on its own and the failing ones are excluded.
+    /// Returns the gas estimate for the remaining messages, if there are any,
+    /// along with the indexes of the excluded ones.
+    pub(crate) async fn estimate_batch_gas(&self, msgs: &[Any]) -> (Option<u64>, Vec<usize>) {
+        estimate_batch_gas_with(msgs, |msgs| self.estimate_gas(msgs)).await
+    }
+
+    /// Signs and broadcasts a transaction containing `msgs`, estimating gas
+    /// if a limit is not provided.
+    pub(crate) async fn send_msgs(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxResponse> {
+        let signer = self.get_signer()?;
+        // We often use U256s to represent gas limits, but Cosmos expects u64s. Try to convert,
+        // and if it fails, just fallback to None which will result in gas estimation.
+        let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
+            Ok(limit) => Some(limit),
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    "failed to convert gas_limit to u64, falling back to estimation"
+                );
+                None
+            }
+        });
+        let gas_limit = match gas_limit {
+            Some(limit) => limit,
+            None => self.estimate_gas(msgs.clone()).await?,
+        };
+
+        // Check if the signer has enough funds to pay for the fee so we can get
+        // a more informative error.
+        let gas_price = self.current_gas_price().await?;
+        let fee = self.fee_coin(gas_limit, &gas_price)?;
+        let signer_balance = self
+            .get_balance(signer.address.clone(), fee.denom.to_string())
+            .await?;
+        let fee_amount: U256 = fee.amount.into();
+        if signer_balance < fee_amount {
+            return Err(ChainCommunicationError::InsufficientFunds {
+                required: fee_amount,
+                available: signer_balance,
+            });
+        }
+
+        // Hold the sequence lock until the tx is accepted into the mempool, so
+        // concurrent sends get consecutive sequences.
+        let mut state = self.sequence_manager()?.lock().await;
+        let (msgs, gas_price) = (&msgs, &gas_price);
+        send_with_sequence(
+            &mut state,
+            || self.query_account_sequence(),
+            |account| async move {
+                let tx_bytes = self
+                    .generate_raw_signed_tx(msgs.clone(), gas_limit, gas_price, account)
+                    .await?;
+                self.broadcast_tx(tx_bytes).await
+            },
+        )
+        .await
+    }
+
+    /// Broadcasts a signed tx, returning once it has been checked by the node.
+    async fn broadcast_tx(&self, tx_bytes: Vec<u8>) -> ChainResult<TxResponse> {
+        self.provider
+            .call(move |provider| {
+                let tx_bytes = tx_bytes.clone();
+                let future = async move {
+                    let mut client = TxServiceClient::new(provider.channel.clone());
+                    let tx_req = BroadcastTxRequest {
+                        tx_bytes,
+                        mode: BroadcastMode::Sync as i32,
+                    };
+                    client
+                        .broadcast_tx(tx_req)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?
+                        .into_inner()
+                        .tx_response
+                        .ok_or_else(|| ChainCommunicationError::from_other_str("Empty tx_response"))
+                };
+                Box::pin(future)
+            })
+            .await
+    }
+
+    /// Queries a native Cosmos SDK module over gRPC, where `path` is the full
+    /// method path, e.g. `/hyperlane.core.v1.Query/Mailbox`.
+    pub(crate) async fn module_query<Req, Res>(
+        &self,
+        path: &'static str,
+        request: Req,
+        block_height: Option<u64>,
+    ) -> ChainResult<Res>
+    where
+        Req: prost::Message + Clone + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        let response = self
+            .provider
+            .call(move |provider| {
+                let request = request.clone();
+                let future = async move {
+                    let mut grpc_client = tonic::client::Grpc::new(provider.channel.clone());
+                    grpc_client
+                        .ready()
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?;
+
+                    let mut req = tonic::Request::new(request);
+                    if let Some(block_height) = block_height {
+                        req.metadata_mut()
+                            .insert("x-cosmos-block-height", block_height.into());
+                    }
+                    let codec = tonic::codec::ProstCodec::<Req, Res>::default();
+                    let response: tonic::Response<Res> = grpc_client
+                        .unary(req, http::uri::PathAndQuery::from_static(path), codec)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?;
+                    Ok(response.into_inner())
+                };
+                Box::pin(future)
+            })
+            .await?;
+
+        Ok(response)
+    }
+
     /// Fetches balance for a given `address` and `denom`
     pub async fn get_balance(&self, address: String, denom: String) -> ChainResult<U256> {
         let response = self
@@ -444,6 +645,19 @@ impl WasmGrpcProvider {
         })
     }
 
+    /// Builds a `MsgExecuteContract` from the signer to the stored contract
+    /// address, executing `payload`.
+    pub(crate) fn execute_contract_msg<T: Serialize>(&self, payload: &T) -> ChainResult<Any> {
+        let signer = self.get_signer()?;
+        let msg = MsgExecuteContract {
+            sender: signer.address.clone(),
+            contract: self.get_contract_address().address(),
+            msg: serde_json::to_string(payload)?.as_bytes().to_vec(),
+            funds: vec![],
+        };
+        Any::from_msg(&msg).map_err(ChainCommunicationError::from_other)
+    }
+
     fn get_contract_address(&self) -> &CosmosAddress {
         &self.contract_address
     }
@@ -551,63 +765,8 @@ impl WasmProvider for WasmGrpcProvider {
     where
         T: Serialize + Send + Sync + Clone + Debug,
     {
-        let signer = self.get_signer()?;
-        let contract_address = self.get_contract_address();
-        let msg = MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        };
-        let msgs = vec![Any::from_msg(&msg).map_err(ChainCommunicationError::from_other)?];
-        let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
-            Ok(limit) => Some(limit),
-            Err(err) => {
-                tracing::warn!(
-                    ?err,
-                    "failed to convert gas_limit to u64, falling back to estimation"
-                );
-                None
-            }
-        });
-        let (tx_bytes, fee) = self.generate_raw_signed_tx_and_fee(msgs, gas_limit).await?;
-
-        // Check if the signer has enough funds to pay for the fee so we can get
-        // a more informative error.
-        let signer_balance = self
-            .get_balance(signer.address.clone(), fee.denom.to_string())
-            .await?;
-        let fee_amount: U256 = fee.amount.into();
-        if signer_balance < fee_amount {
-            return Err(ChainCommunicationError::InsufficientFunds {
-                required: fee_amount,
-                available: signer_balance,
-            });
-        }
-
-        let tx_res = self
-            .provider
-            .call(move |provider| {
-                let tx_bytes = tx_bytes.clone();
-                let future = async move {
-                    let mut client = TxServiceClient::new(provider.channel.clone());
-                    // We often use U256s to represent gas limits, but Cosmos expects u64s. Try to convert,
-                    // and if it fails, just fallback to None which will result in gas estimation.
-                    let tx_req = BroadcastTxRequest {
-                        tx_bytes,
-                        mode: BroadcastMode::Sync as i32,
-                    };
-                    client
-                        .broadcast_tx(tx_req)
-                        .await
-                        .map_err(Into::<HyperlaneCosmosError>::into)?
-                        .into_inner()
-                        .tx_response
-                        .ok_or_else(|| ChainCommunicationError::from_other_str("Empty tx_response"))
-                };
-                Box::pin(future)
-            })
-            .await?;
+        let msgs = vec![self.execute_contract_msg(&payload)?];
+        let tx_res = self.send_msgs(msgs, gas_limit).await?;
         debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
         Ok(tx_res)
     }
@@ -618,25 +777,58 @@ impl WasmProvider for WasmGrpcProvider {
     {
         // Estimating gas requires a signer, which we can reasonably expect to have
         // since we need one to send a tx with the estimated gas anyways.
-        let signer = self.get_signer()?;
-        let contract_address = self.get_contract_address();
-        let msg = MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        };
-
         let response = self
-            .estimate_gas(vec![
-                Any::from_msg(&msg).map_err(ChainCommunicationError::from_other)?
-            ])
+            .estimate_gas(vec![self.execute_contract_msg(&payload)?])
             .await?;
 
         Ok(response)
     }
 }
 
+/// The batching logic of `WasmGrpcProvider::estimate_batch_gas`, with
+/// `estimate_gas` simulating a tx containing the messages it is given.
+async fn estimate_batch_gas_with<F, Fut>(msgs: &[Any], estimate_gas: F) -> (Option<u64>, Vec<usize>)
+where
+    F: Fn(Vec<Any>) -> Fut,
+    Fut: Future<Output = ChainResult<u64>>,
+{
+    match estimate_gas(msgs.to_vec()).await {
+        Ok(gas) => return (Some(gas), vec![]),
+        Err(err) => {
+            debug!(
+                ?err,
+                batch_size = msgs.len(),
+                "Failed to simulate batch, simulating messages individually"
+            )
+        }
+    }
+
+    let estimates = join_all(msgs.iter().map(|msg| estimate_gas(vec![msg.clone()]))).await;
+    let failed_indexes = estimates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, estimate)| estimate.is_err().then_some(i))
+        .collect::<Vec<_>>();
+    if failed_indexes.len() == msgs.len() {
+        return (None, failed_indexes);
+    }
+
+    // Messages that succeed on their own can still conflict with each other,
+    // so the remaining batch is simulated again.
+    let remaining = exclude_indexes(msgs.to_vec(), &failed_indexes);
+    match estimate_gas(remaining).await {
+        Ok(gas) => (Some(gas), failed_indexes),
+        Err(err) => {
+            warn!(
+                ?err,
+                ?failed_indexes,
+                "Failed to simulate batch after excluding failing messages"
+            );
+            (None, (0..msgs.len()).collect())
+        }
+    }
+}
+
 #[async_trait]
 impl BlockNumberGetter for WasmGrpcProvider {
     async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
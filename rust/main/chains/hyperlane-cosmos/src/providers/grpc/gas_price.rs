@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use tracing::{debug, warn};
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, FixedPointNumber};
+
+use crate::CosmosGasPriceOracleConfig;
+
+use super::WasmGrpcProvider;
+
+/// `Query/GasPrice` of the Skip `feemarket` module
+const FEEMARKET_GAS_PRICE_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+/// `Query/GetEipBaseFee` of the Osmosis `txfees` module
+const OSMOSIS_EIP_BASE_FEE_PATH: &str = "/osmosis.txfees.v1beta1.Query/GetEipBaseFee";
+
+/// The number of decimals of a Cosmos SDK `LegacyDec`.
+const LEGACY_DEC_DECIMALS: u32 = 18;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<DecCoin>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct DecCoin {
+    #[prost(string, tag = "1")]
+    denom: String,
+    #[prost(string, tag = "2")]
+    amount: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryEipBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryEipBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    base_fee: String,
+}
+
+impl WasmGrpcProvider {
+    /// The gas price to pay for txs sent now, as determined by the configured
+    /// gas price oracle.
+    ///
+    /// Falls back to the configured gas price if the oracle can't be queried,
+    /// and never goes below it.
+    pub(crate) async fn current_gas_price(&self) -> ChainResult<FixedPointNumber> {
+        let minimum = self.gas_price();
+        let queried = match self.conf.get_gas_price_oracle() {
+            CosmosGasPriceOracleConfig::Static => return Ok(minimum),
+            CosmosGasPriceOracleConfig::FeeMarket { multiplier } => self
+                .feemarket_gas_price()
+                .await
+                .map(|price| price * multiplier.clone()),
+            CosmosGasPriceOracleConfig::OsmosisTxFees { multiplier } => self
+                .osmosis_eip_base_fee()
+                .await
+                .map(|price| price * multiplier.clone()),
+        };
+
+        let gas_price = match queried {
+            Ok(price) => price.max(minimum),
+            Err(err) => {
+                warn!(?err, domain=?self.domain, "Failed to query gas price, using the configured one");
+                minimum
+            }
+        };
+        debug!(%gas_price, domain=?self.domain, "Using gas price");
+        if let Some(gauge) = &self.gas_price_metric {
+            gauge.set(gas_price.to_f64_lossy());
+        }
+        Ok(gas_price)
+    }
+
+    async fn feemarket_gas_price(&self) -> ChainResult<FixedPointNumber> {
+        let denom = self.conf.get_canonical_asset();
+        let response: GasPriceResponse = self
+            .module_query(
+                FEEMARKET_GAS_PRICE_PATH,
+                GasPriceRequest {
+                    denom: denom.clone(),
+                },
+                None,
+            )
+            .await?;
+        let price = response
+            .price
+            .ok_or_else(|| ChainCommunicationError::from_other_str("gas price not present"))?;
+        if price.denom != denom {
+            return Err(ChainCommunicationError::from_other_str(&format!(
+                "gas price is in `{}`, expected `{denom}`",
+                price.denom
+            )));
+        }
+        parse_legacy_dec(&price.amount)
+    }
+
+    async fn osmosis_eip_base_fee(&self) -> ChainResult<FixedPointNumber> {
+        let response: QueryEipBaseFeeResponse = self
+            .module_query(OSMOSIS_EIP_BASE_FEE_PATH, QueryEipBaseFeeRequest {}, None)
+            .await?;
+        parse_legacy_dec(&response.base_fee)
+    }
+}
+
+/// Parses a `LegacyDec` returned by a gRPC query. In protobuf encoding these
+/// are the integer value scaled by 10^18, but some chains return the decimal
+/// representation instead, so both are accepted.
+fn parse_legacy_dec(value: &str) -> ChainResult<FixedPointNumber> {
+    let number = FixedPointNumber::from_str(value)?;
+    if value.contains('.') {
+        return Ok(number);
+    }
+    Ok(number / FixedPointNumber::from(10u64.pow(LEGACY_DEC_DECIMALS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legacy_dec() {
+        assert_eq!(
+            parse_legacy_dec("25000000000000000").unwrap(),
+            FixedPointNumber::from_str("0.025").unwrap()
+        );
+        assert_eq!(
+            parse_legacy_dec("0.025000000000000000").unwrap(),
+            FixedPointNumber::from_str("0.025").unwrap()
+        );
+        assert!(parse_legacy_dec("not a number").is_err());
+    }
+}
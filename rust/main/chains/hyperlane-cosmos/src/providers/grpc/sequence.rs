@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use hyperlane_core::ChainResult;
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// The codespace of errors returned by the Cosmos SDK itself.
+const SDK_CODESPACE: &str = "sdk";
+/// `ErrWrongSequence` in the Cosmos SDK error registry.
+const WRONG_SEQUENCE_CODE: u32 = 32;
+/// The number of times a tx is re-signed with the sequence expected by the
+/// chain after a sequence mismatch.
+const MAX_SEQUENCE_MISMATCH_RETRIES: usize = 1;
+
+/// Sequence managers shared by all providers of the same signer on the same
+/// chain, keyed by chain id and signer address.
+static SEQUENCE_MANAGERS: Lazy<StdMutex<HashMap<(String, String), Arc<SequenceManager>>>> =
+    Lazy::new(Default::default);
+
+/// The account number and the sequence to use for the next tx of a signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AccountSequence {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+/// Tracks the sequence of a signer locally, so that txs can be sent before
+/// the previous ones are included in a block.
+///
+/// The sequence is only queried from the chain when it is unknown, i.e. on
+/// startup and after a failed broadcast, and is incremented locally for every
+/// tx accepted into the mempool.
+#[derive(Debug, Default)]
+pub(crate) struct SequenceManager {
+    state: Mutex<Option<AccountSequence>>,
+}
+
+impl SequenceManager {
+    /// Returns the sequence manager of `address` on the chain with `chain_id`,
+    /// creating one if needed.
+    pub(crate) fn for_signer(chain_id: &str, address: &str) -> Arc<Self> {
+        let mut managers = SEQUENCE_MANAGERS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        managers
+            .entry((chain_id.to_owned(), address.to_owned()))
+            .or_default()
+            .clone()
+    }
+
+    /// Locks the sequence state. The lock should be held from reading the
+    /// sequence until the tx using it has been broadcast.
+    pub(crate) async fn lock(&self) -> tokio::sync::MutexGuard<'_, Option<AccountSequence>> {
+        self.state.lock().await
+    }
+}
+
+/// Sends a tx signed with the sequence tracked in `state`, which is queried
+/// from the chain with `query` if it is not known, and keeps `state` up to
+/// date with the outcome.
+///
+/// If the tx is rejected because the local sequence is stale, e.g. because a
+/// pending tx was dropped from the mempool or the signer was used elsewhere,
+/// it is signed again with the sequence expected by the chain.
+pub(crate) async fn send_with_sequence<Q, QF, S, SF>(
+    state: &mut Option<AccountSequence>,
+    query: Q,
+    send: S,
+) -> ChainResult<TxResponse>
+where
+    Q: Fn() -> QF,
+    QF: Future<Output = ChainResult<AccountSequence>>,
+    S: Fn(AccountSequence) -> SF,
+    SF: Future<Output = ChainResult<TxResponse>>,
+{
+    let mut attempt = 0;
+    loop {
+        let account = match *state {
+            Some(account) => account,
+            None => {
+                let account = query().await?;
+                *state = Some(account);
+                account
+            }
+        };
+        let tx_res = match send(account).await {
+            Ok(tx_res) => tx_res,
+            Err(err) => {
+                // The tx may or may not have reached the mempool, so the
+                // sequence is queried again for the next tx.
+                *state = None;
+                return Err(err);
+            }
+        };
+
+        if !is_sequence_mismatch(&tx_res.codespace, tx_res.code) {
+            if tx_res.code == 0 {
+                *state = Some(AccountSequence {
+                    sequence: account.sequence + 1,
+                    ..account
+                });
+            }
+            return Ok(tx_res);
+        }
+
+        *state = parse_expected_sequence(&tx_res.raw_log).map(|sequence| AccountSequence {
+            sequence,
+            ..account
+        });
+        warn!(
+            used_sequence = account.sequence,
+            expected_sequence = ?state.as_ref().map(|account| account.sequence),
+            raw_log = %tx_res.raw_log,
+            "Account sequence mismatch"
+        );
+        attempt += 1;
+        if attempt > MAX_SEQUENCE_MISMATCH_RETRIES {
+            return Ok(tx_res);
+        }
+    }
+}
+
+/// Returns whether a tx was rejected because of an unexpected sequence.
+pub(crate) fn is_sequence_mismatch(codespace: &str, code: u32) -> bool {
+    codespace == SDK_CODESPACE && code == WRONG_SEQUENCE_CODE
+}
+
+/// Parses the sequence expected by the chain out of a sequence mismatch
+/// error, e.g. `account sequence mismatch, expected 5, got 4: incorrect
+/// account sequence`.
+pub(crate) fn parse_expected_sequence(log: &str) -> Option<u64> {
+    let (_, rest) = log.split_once("account sequence mismatch, expected ")?;
+    let digits = rest
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_core::ChainCommunicationError;
+
+    use super::*;
+
+    const ACCOUNT_NUMBER: u64 = 3;
+
+    fn account(sequence: u64) -> AccountSequence {
+        AccountSequence {
+            account_number: ACCOUNT_NUMBER,
+            sequence,
+        }
+    }
+
+    /// The response of a chain expecting `expected` to a tx signed with
+    /// `used`, with the expected sequence in the log if `log_expected`
+    fn response(used: u64, expected: u64, log_expected: bool) -> TxResponse {
+        if used == expected {
+            return TxResponse::default();
+        }
+        let raw_log = if log_expected {
+            format!("account sequence mismatch, expected {expected}, got {used}: incorrect account sequence")
+        } else {
+            "incorrect account sequence".to_owned()
+        };
+        TxResponse {
+            codespace: SDK_CODESPACE.to_owned(),
+            code: WRONG_SEQUENCE_CODE,
+            raw_log,
+            ..Default::default()
+        }
+    }
+
+    /// Sends through `send_with_sequence` to a chain expecting `expected`,
+    /// returning the outcome and the sequences txs were signed with
+    async fn send(
+        state: &mut Option<AccountSequence>,
+        expected: u64,
+        log_expected: bool,
+    ) -> (ChainResult<TxResponse>, Vec<u64>) {
+        let sent = StdMutex::new(vec![]);
+        let result = send_with_sequence(
+            state,
+            || async move { Ok(account(expected)) },
+            |account| {
+                sent.lock().unwrap().push(account.sequence);
+                async move { Ok(response(account.sequence, expected, log_expected)) }
+            },
+        )
+        .await;
+        (result, sent.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_send_with_known_sequence() {
+        let mut state = Some(account(4));
+        let (result, sent) = send(&mut state, 4, true).await;
+        assert_eq!(result.unwrap().code, 0);
+        assert_eq!(sent, vec![4]);
+        assert_eq!(state, Some(account(5)));
+
+        // Unknown sequences are queried
+        let mut state = None;
+        let (result, sent) = send(&mut state, 7, true).await;
+        assert_eq!(result.unwrap().code, 0);
+        assert_eq!(sent, vec![7]);
+        assert_eq!(state, Some(account(8)));
+    }
+
+    #[tokio::test]
+    async fn test_send_recovers_from_sequence_mismatch() {
+        // The tx is signed again with the sequence in the error
+        let mut state = Some(account(4));
+        let (result, sent) = send(&mut state, 6, true).await;
+        assert_eq!(result.unwrap().code, 0);
+        assert_eq!(sent, vec![4, 6]);
+        assert_eq!(state, Some(account(7)));
+
+        // Or with the sequence queried from the chain, if the error doesn't
+        // include it
+        let mut state = Some(account(4));
+        let (result, sent) = send(&mut state, 6, false).await;
+        assert_eq!(result.unwrap().code, 0);
+        assert_eq!(sent, vec![4, 6]);
+        assert_eq!(state, Some(account(7)));
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up_on_repeated_sequence_mismatch() {
+        // The chain's sequence keeps moving, e.g. because the signer is used
+        // elsewhere
+        let mut state = Some(account(4));
+        let sent = StdMutex::new(vec![]);
+        let result = send_with_sequence(
+            &mut state,
+            || async { Ok(account(0)) },
+            |account| {
+                sent.lock().unwrap().push(account.sequence);
+                let expected = account.sequence + 1;
+                async move { Ok(response(account.sequence, expected, true)) }
+            },
+        )
+        .await;
+
+        let tx_res = result.unwrap();
+        assert!(is_sequence_mismatch(&tx_res.codespace, tx_res.code));
+        assert_eq!(sent.into_inner().unwrap(), vec![4, 5]);
+        // The next tx uses the last expected sequence
+        assert_eq!(state, Some(account(6)));
+    }
+
+    #[tokio::test]
+    async fn test_send_forgets_sequence_after_broadcast_error() {
+        let mut state = Some(account(4));
+        let result = send_with_sequence(
+            &mut state,
+            || async { Ok(account(0)) },
+            |_| async { Err(ChainCommunicationError::from_other_str("broadcast failed")) },
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(state, None);
+    }
+
+    #[test]
+    fn test_parse_expected_sequence() {
+        assert_eq!(
+            parse_expected_sequence(
+                "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+            ),
+            Some(5)
+        );
+        assert_eq!(
+            parse_expected_sequence(
+                "rpc error: code = Unknown desc = account sequence mismatch, expected 120, got 118"
+            ),
+            Some(120)
+        );
+        assert_eq!(parse_expected_sequence("out of gas"), None);
+    }
+
+    #[test]
+    fn test_sequence_manager_shared_per_signer() {
+        let a = SequenceManager::for_signer("neutron-1", "neutron1abc");
+        let b = SequenceManager::for_signer("neutron-1", "neutron1abc");
+        let c = SequenceManager::for_signer("osmosis-1", "neutron1abc");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+    }
+}
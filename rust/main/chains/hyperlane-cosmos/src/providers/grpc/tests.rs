@@ -1,12 +1,22 @@
 use std::str::FromStr;
+use std::sync::Mutex;
 
+use cosmrs::Any;
+use futures::future::ready;
 use url::Url;
 
 use hyperlane_core::config::OperationBatchConfig;
-use hyperlane_core::{ContractLocator, HyperlaneDomain, KnownHyperlaneDomain, NativeToken};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneDomain, KnownHyperlaneDomain,
+    NativeToken,
+};
 
+use super::estimate_batch_gas_with;
 use crate::grpc::{WasmGrpcProvider, WasmProvider};
-use crate::{ConnectionConf, CosmosAddress, CosmosAmount, RawCosmosAmount};
+use crate::{
+    ConnectionConf, CosmosAddress, CosmosAmount, CosmosConnectionMode, CosmosGasPriceOracleConfig,
+    RawCosmosAmount,
+};
 
 #[ignore]
 #[tokio::test]
@@ -45,6 +55,89 @@ async fn test_wasm_contract_info_no_contract() {
     assert!(result.is_err());
 }
 
+/// A message whose simulation uses `gas`, or fails if `gas` is zero.
+/// Messages of the same `group` fail when simulated together.
+fn msg(group: &str, gas: u8) -> Any {
+    Any {
+        type_url: group.to_owned(),
+        value: vec![gas],
+    }
+}
+
+/// Simulates like a chain would for the messages built by `msg`, recording
+/// the size of every simulated batch
+fn simulate(msgs: &[Any], batches: &Mutex<Vec<usize>>) -> ChainResult<u64> {
+    batches.lock().unwrap().push(msgs.len());
+    let failing = msgs.iter().any(|msg| msg.value[0] == 0);
+    let conflicting = msgs.iter().enumerate().any(|(i, msg)| {
+        !msg.type_url.is_empty() && msgs[..i].iter().any(|m| m.type_url == msg.type_url)
+    });
+    if failing || conflicting {
+        return Err(ChainCommunicationError::from_other_str("simulation failed"));
+    }
+    Ok(msgs.iter().map(|msg| msg.value[0] as u64).sum())
+}
+
+#[tokio::test]
+async fn test_estimate_batch_gas_of_good_batch() {
+    // given
+    let msgs = vec![msg("", 1), msg("", 2)];
+    let batches = Mutex::new(vec![]);
+
+    // when
+    let result = estimate_batch_gas_with(&msgs, |msgs| ready(simulate(&msgs, &batches))).await;
+
+    // then
+    assert_eq!(result, (Some(3), vec![]));
+    assert_eq!(*batches.lock().unwrap(), vec![2]);
+}
+
+#[tokio::test]
+async fn test_estimate_batch_gas_excludes_failing_messages() {
+    // given
+    let msgs = vec![msg("", 1), msg("", 0), msg("", 4), msg("", 0)];
+    let batches = Mutex::new(vec![]);
+
+    // when
+    let result = estimate_batch_gas_with(&msgs, |msgs| ready(simulate(&msgs, &batches))).await;
+
+    // then
+    // The good messages are estimated again without the failing ones
+    assert_eq!(result, (Some(5), vec![1, 3]));
+    assert_eq!(*batches.lock().unwrap(), vec![4, 1, 1, 1, 1, 2]);
+}
+
+#[tokio::test]
+async fn test_estimate_batch_gas_of_failing_batch() {
+    // given
+    let msgs = vec![msg("", 0), msg("", 0)];
+    let batches = Mutex::new(vec![]);
+
+    // when
+    let result = estimate_batch_gas_with(&msgs, |msgs| ready(simulate(&msgs, &batches))).await;
+
+    // then
+    // Nothing is left to estimate again
+    assert_eq!(result, (None, vec![0, 1]));
+    assert_eq!(*batches.lock().unwrap(), vec![2, 1, 1]);
+}
+
+#[tokio::test]
+async fn test_estimate_batch_gas_when_resimulation_fails() {
+    // given
+    // The first two messages succeed on their own but conflict together
+    let msgs = vec![msg("a", 1), msg("a", 2), msg("", 0)];
+    let batches = Mutex::new(vec![]);
+
+    // when
+    let result = estimate_batch_gas_with(&msgs, |msgs| ready(simulate(&msgs, &batches))).await;
+
+    // then
+    // Every message is excluded, since it's unknown which ones conflict
+    assert_eq!(result, (None, vec![0, 1, 2]));
+    assert_eq!(*batches.lock().unwrap(), vec![3, 1, 1, 1, 2]);
+}
+
 fn provider(address: &str) -> WasmGrpcProvider {
     let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Neutron);
     let address = CosmosAddress::from_str(address).unwrap();
@@ -68,6 +161,8 @@ fn provider(address: &str) -> WasmGrpcProvider {
                 decimals: 6,
                 denom: "untrn".to_owned(),
             },
+            CosmosConnectionMode::CosmWasm,
+            CosmosGasPriceOracleConfig::Static,
         ),
         CosmosAmount {
             denom: "untrn".to_owned(),
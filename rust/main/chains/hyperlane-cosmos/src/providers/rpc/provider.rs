@@ -21,6 +21,7 @@ use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneDomain, LogMeta, H256, U256,
 };
 
+use crate::native::native_id;
 use crate::rpc::CosmosRpcClient;
 use crate::rpc_clients::CosmosFallbackProvider;
 use crate::{ConnectionConf, CosmosAddress, CosmosProvider, HyperlaneCosmosError};
@@ -79,6 +80,9 @@ impl<T: PartialEq> ParsedEvent<T> {
 pub struct CosmosWasmRpcProvider {
     domain: HyperlaneDomain,
     contract_address: CosmosAddress,
+    /// The emitter target events must have, i.e. the bech32 contract address
+    /// for CosmWasm or the hex object id for native modules.
+    emitter: String,
     target_event_kind: String,
     reorg_period: u32,
     rpc_client: CosmosFallbackProvider<CosmosRpcClient>,
@@ -93,6 +97,55 @@ impl CosmosWasmRpcProvider {
         locator: ContractLocator,
         event_type: String,
         reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let contract_address = CosmosAddress::from_h256(
+            locator.address,
+            conf.get_bech32_prefix().as_str(),
+            conf.get_contract_address_bytes(),
+        )?;
+        let emitter = contract_address.address();
+        Self::with_target_event(
+            conf,
+            locator,
+            contract_address,
+            emitter,
+            format!("{}-{}", Self::WASM_TYPE, event_type),
+            reorg_period,
+        )
+    }
+
+    /// Create a new RPC provider indexing the typed events of a native
+    /// Hyperlane module, where `event_type` is the full protobuf name of the
+    /// event, e.g. `hyperlane.core.v1.EventDispatch`.
+    pub fn new_native(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        event_type: String,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let contract_address = CosmosAddress::from_h256(
+            locator.address,
+            conf.get_bech32_prefix().as_str(),
+            conf.get_contract_address_bytes(),
+        )?;
+        let emitter = native_id(locator.address);
+        Self::with_target_event(
+            conf,
+            locator,
+            contract_address,
+            emitter,
+            event_type,
+            reorg_period,
+        )
+    }
+
+    fn with_target_event(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        contract_address: CosmosAddress,
+        emitter: String,
+        target_event_kind: String,
+        reorg_period: u32,
     ) -> ChainResult<Self> {
         let providers = conf
             .get_rpc_urls()
@@ -106,12 +159,9 @@ impl CosmosWasmRpcProvider {
 
         Ok(Self {
             domain: locator.domain.clone(),
-            contract_address: CosmosAddress::from_h256(
-                locator.address,
-                conf.get_bech32_prefix().as_str(),
-                conf.get_contract_address_bytes(),
-            )?,
-            target_event_kind: format!("{}-{}", Self::WASM_TYPE, event_type),
+            contract_address,
+            emitter,
+            target_event_kind,
             reorg_period,
             rpc_client: provider,
         })
@@ -215,7 +265,7 @@ impl CosmosWasmRpcProvider {
                     // in the event matches the contract address we are indexing.
                     // Otherwise, we might index events from other contracts that happen
                     // to have the same target event name.
-                    if parsed_event.contract_address != self.contract_address.address() {
+                    if parsed_event.contract_address != self.emitter {
                         trace!(tx_hash=?tx_hash, log_idx, ?event, "Event contract address does not match indexer contract address");
                         return None;
                     }
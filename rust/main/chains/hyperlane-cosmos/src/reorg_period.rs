@@ -0,0 +1,48 @@
+use hyperlane_core::{ChainCommunicationError, ReorgPeriod};
+
+/// Cosmos reorg period, as a number of blocks behind the tip.
+///
+/// CometBFT blocks are final as soon as they are committed, so every finality
+/// tag resolves to the latest committed block.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CosmosReorgPeriod(u32);
+
+impl CosmosReorgPeriod {
+    /// The number of blocks behind the tip that state should be read at
+    pub fn blocks(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<&ReorgPeriod> for CosmosReorgPeriod {
+    type Error = ChainCommunicationError;
+
+    fn try_from(value: &ReorgPeriod) -> Result<Self, Self::Error> {
+        match value {
+            ReorgPeriod::None => Ok(Self(0)),
+            ReorgPeriod::Blocks(blocks) => Ok(Self(blocks.get())),
+            ReorgPeriod::Tag(tag) => match tag.as_str() {
+                "finalized" | "safe" | "latest" => Ok(Self(0)),
+                _ => Err(ChainCommunicationError::InvalidReorgPeriod(value.clone())),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorg_period_to_blocks() {
+        let blocks = |reorg_period: ReorgPeriod| {
+            CosmosReorgPeriod::try_from(&reorg_period).map(|p| p.blocks())
+        };
+
+        assert_eq!(blocks(ReorgPeriod::None).unwrap(), 0);
+        assert_eq!(blocks(ReorgPeriod::from_blocks(5)).unwrap(), 5);
+        assert_eq!(blocks(ReorgPeriod::Tag("finalized".into())).unwrap(), 0);
+        assert_eq!(blocks(ReorgPeriod::Tag("safe".into())).unwrap(), 0);
+        assert!(blocks(ReorgPeriod::Tag("pending".into())).is_err());
+    }
+}
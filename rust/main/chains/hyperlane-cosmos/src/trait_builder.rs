@@ -32,6 +32,46 @@ pub struct ConnectionConf {
     pub operation_batch: OperationBatchConfig,
     /// Native Token
     native_token: NativeToken,
+    /// Whether Hyperlane is deployed as CosmWasm contracts or as native
+    /// Cosmos SDK modules on this chain
+    connection_mode: CosmosConnectionMode,
+    /// How the gas price of txs is determined
+    gas_price_oracle: CosmosGasPriceOracleConfig,
+}
+
+/// How Hyperlane is deployed on a Cosmos chain, which determines whether the
+/// agents talk to it through CosmWasm contract calls or through the gRPC
+/// query and message services of native Cosmos SDK modules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CosmosConnectionMode {
+    /// Hyperlane contracts are CosmWasm contracts
+    #[default]
+    CosmWasm,
+    /// Hyperlane is embedded in the chain as native modules, e.g. `x/mailbox`
+    NativeModule,
+}
+
+/// Configuration of how the gas price of txs is determined.
+///
+/// Dynamic prices never go below the configured `gas_price`, which is the
+/// minimum accepted by validators.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CosmosGasPriceOracleConfig {
+    /// Always use the configured gas price
+    #[default]
+    Static,
+    /// Query the current gas price from the Skip `feemarket` module, scaled
+    /// by `multiplier`
+    FeeMarket {
+        /// Multiplier applied to the queried gas price
+        multiplier: FixedPointNumber,
+    },
+    /// Query the EIP-1559 base fee from the Osmosis `txfees` module, scaled
+    /// by `multiplier`
+    OsmosisTxFees {
+        /// Multiplier applied to the queried base fee
+        multiplier: FixedPointNumber,
+    },
 }
 
 /// Untyped cosmos amount
@@ -123,6 +163,16 @@ impl ConnectionConf {
         self.contract_address_bytes
     }
 
+    /// Get the connection mode
+    pub fn get_connection_mode(&self) -> CosmosConnectionMode {
+        self.connection_mode
+    }
+
+    /// Get the gas price oracle configuration
+    pub fn get_gas_price_oracle(&self) -> &CosmosGasPriceOracleConfig {
+        &self.gas_price_oracle
+    }
+
     /// Create a new connection configuration
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -135,6 +185,8 @@ impl ConnectionConf {
         contract_address_bytes: usize,
         operation_batch: OperationBatchConfig,
         native_token: NativeToken,
+        connection_mode: CosmosConnectionMode,
+        gas_price_oracle: CosmosGasPriceOracleConfig,
     ) -> Self {
         Self {
             grpc_urls,
@@ -146,6 +198,8 @@ impl ConnectionConf {
             contract_address_bytes,
             operation_batch,
             native_token,
+            connection_mode,
+            gas_price_oracle,
         }
     }
 }
@@ -15,6 +15,7 @@ use hyperlane_core::{ChainCommunicationError, ChainResult, Indexed, LogMeta, Reo
 
 use crate::grpc::{WasmGrpcProvider, WasmProvider};
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
+use crate::CosmosReorgPeriod;
 
 type FutureChainResults<T> = Vec<JoinHandle<(ChainResult<Vec<(T, LogMeta)>>, u32)>>;
 
@@ -25,24 +26,19 @@ pub(crate) static CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64: Lazy<String> =
     Lazy::new(|| BASE64.encode(CONTRACT_ADDRESS_ATTRIBUTE_KEY));
 
 /// Given a `reorg_period`, returns the block height at the moment.
-/// If the `reorg_period` is None, a block height of None is given,
+/// If the `reorg_period` resolves to the tip, a block height of None is given,
 /// indicating that the tip directly can be used.
 pub(crate) async fn get_block_height_for_reorg_period(
     provider: &WasmGrpcProvider,
     reorg_period: &ReorgPeriod,
 ) -> ChainResult<Option<u64>> {
-    let block_height = match reorg_period {
-        ReorgPeriod::Blocks(blocks) => {
+    let block_height = match CosmosReorgPeriod::try_from(reorg_period)?.blocks() {
+        0 => None,
+        blocks => {
             let tip = provider.latest_block_height().await?;
-            let block_height = tip - blocks.get() as u64;
+            let block_height = tip - blocks as u64;
             Some(block_height)
         }
-        ReorgPeriod::None => None,
-        ReorgPeriod::Tag(_) => {
-            return Err(ChainCommunicationError::InvalidReorgPeriod(
-                reorg_period.clone(),
-            ))
-        }
     };
 
     Ok(block_height)
@@ -107,6 +103,16 @@ pub(crate) async fn execute_and_parse_log_futures<T: Into<Indexed<T>>>(
     Ok(result)
 }
 
+/// Drops the items at `indexes` from `items`, keeping the order of the rest.
+pub(crate) fn exclude_indexes<T>(items: Vec<T>, indexes: &[usize]) -> Vec<T> {
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !indexes.contains(i))
+        .map(|(_, item)| item)
+        .collect()
+}
+
 #[cfg(test)]
 /// Helper function to create a Vec<EventAttribute> from a JSON string -
 /// crate::payloads::general::EventAttribute has a Deserialize impl while
@@ -120,3 +126,15 @@ pub(crate) fn event_attributes_from_str(
         .map(|attr| attr.into())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclude_indexes() {
+        assert_eq!(exclude_indexes(vec![0, 1, 2, 3], &[1, 3]), vec![0, 2]);
+        assert_eq!(exclude_indexes(vec![0, 1], &[]), vec![0, 1]);
+        assert!(exclude_indexes(vec![0, 1], &[0, 1]).is_empty());
+    }
+}
@@ -39,6 +39,9 @@ pub struct ConnectionConf {
     pub transaction_overrides: TransactionOverrides,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// How many blocks to search back for the latest send root confirmed in
+    /// the Arbitrum outbox, when relaying to ArbL2ToL1 ISMs on this chain
+    pub arb_send_root_lookback_blocks: Option<u64>,
 }
 
 /// Ethereum transaction overrides.
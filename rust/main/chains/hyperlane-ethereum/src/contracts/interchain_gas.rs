@@ -15,7 +15,7 @@ use hyperlane_core::{
 };
 use tracing::instrument;
 
-use super::utils::{fetch_raw_logs_and_meta, get_finalized_block_number};
+use super::utils::{decode_log, fetch_raw_logs_and_meta, get_finalized_block_number};
 use crate::interfaces::i_interchain_gas_paymaster::{
     GasPaymentFilter, IInterchainGasPaymaster as EthereumInterchainGasPaymasterInternal,
     IINTERCHAINGASPAYMASTER_ABI,
@@ -87,6 +87,19 @@ where
     }
 }
 
+/// Decodes a `GasPayment` log of the interchain gas paymaster, e.g. from an
+/// `eth_getLogs` export
+pub fn decode_gas_payment_log(log: &ethers::types::Log) -> Option<Indexed<InterchainGasPayment>> {
+    decode_log::<GasPaymentFilter>(log).map(|event| {
+        Indexed::new(InterchainGasPayment {
+            message_id: H256::from(event.message_id),
+            destination: event.destination_domain,
+            payment: event.payment.into(),
+            gas_amount: event.gas_amount.into(),
+        })
+    })
+}
+
 #[async_trait]
 impl<M> Indexer<InterchainGasPayment> for EthereumInterchainGasPaymasterIndexer<M>
 where
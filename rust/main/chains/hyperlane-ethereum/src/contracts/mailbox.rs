@@ -30,7 +30,7 @@ use crate::interfaces::arbitrum_node_interface::ArbitrumNodeInterface;
 use crate::interfaces::i_mailbox::{
     IMailbox as EthereumMailboxInternal, ProcessCall, IMAILBOX_ABI,
 };
-use crate::interfaces::mailbox::DispatchFilter;
+use crate::interfaces::mailbox::{DispatchFilter, ProcessIdFilter};
 use crate::tx::{call_with_reorg_period, fill_tx_gas_params, report_tx};
 use crate::{
     BuildableWithProvider, ConnectionConf, EthereumProvider, EthereumReorgPeriod,
@@ -38,7 +38,7 @@ use crate::{
 };
 
 use super::multicall::{self, build_multicall};
-use super::utils::{fetch_raw_logs_and_meta, get_finalized_block_number};
+use super::utils::{decode_log, fetch_raw_logs_and_meta, get_finalized_block_number};
 
 impl<M> std::fmt::Display for EthereumMailboxInternal<M>
 where
@@ -133,6 +133,17 @@ where
     }
 }
 
+/// Decodes a `Dispatch` log of the mailbox, e.g. from an `eth_getLogs` export
+pub fn decode_dispatch_log(log: &ethers::types::Log) -> Option<Indexed<HyperlaneMessage>> {
+    decode_log::<DispatchFilter>(log)
+        .map(|event| HyperlaneMessage::from(event.message.to_vec()).into())
+}
+
+/// Decodes a `ProcessId` log of the mailbox, e.g. from an `eth_getLogs` export
+pub fn decode_process_id_log(log: &ethers::types::Log) -> Option<Indexed<H256>> {
+    decode_log::<ProcessIdFilter>(log).map(|event| Indexed::new(H256::from(event.message_id)))
+}
+
 #[async_trait]
 impl<M> Indexer<HyperlaneMessage> for EthereumMailboxIndexer<M>
 where
@@ -643,6 +654,7 @@ mod test {
             },
             transaction_overrides: Default::default(),
             operation_batch: Default::default(),
+            arb_send_root_lookback_blocks: None,
         };
 
         let mailbox = EthereumMailbox::new(
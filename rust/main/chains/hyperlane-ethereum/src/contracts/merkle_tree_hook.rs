@@ -20,7 +20,7 @@ use crate::interfaces::merkle_tree_hook::{
 use crate::tx::call_with_reorg_period;
 use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, EthereumReorgPeriod};
 
-use super::utils::{fetch_raw_logs_and_meta, get_finalized_block_number};
+use super::utils::{decode_log, fetch_raw_logs_and_meta, get_finalized_block_number};
 
 // We don't need the reverse of this impl, so it's ok to disable the clippy lint
 #[allow(clippy::from_over_into)]
@@ -111,6 +111,15 @@ where
     }
 }
 
+/// Decodes an `InsertedIntoTree` log of the merkle tree hook, e.g. from an
+/// `eth_getLogs` export
+pub fn decode_inserted_into_tree_log(
+    log: &ethers::types::Log,
+) -> Option<Indexed<MerkleTreeInsertion>> {
+    decode_log::<InsertedIntoTreeFilter>(log)
+        .map(|event| MerkleTreeInsertion::new(event.index, H256::from(event.message_id)).into())
+}
+
 #[async_trait]
 impl<M> Indexer<MerkleTreeInsertion> for EthereumMerkleTreeHookIndexer<M>
 where
@@ -1,6 +1,7 @@
 pub use {interchain_gas::*, mailbox::*, merkle_tree_hook::*, validator_announce::*};
 
 pub(crate) use utils::get_finalized_block_number;
+pub use utils::mined_log_meta;
 
 mod interchain_gas;
 mod mailbox;
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use ethers::{
     abi::RawLog,
     providers::Middleware,
-    types::{H160 as EthersH160, H256 as EthersH256},
+    types::{Log, H160 as EthersH160, H256 as EthersH256},
 };
 use ethers_contract::{ContractError, EthEvent, LogMeta as EthersLogMeta};
 use hyperlane_core::{ChainCommunicationError, ChainResult, LogMeta, H512};
@@ -36,18 +36,34 @@ where
             if log.address != contract_address {
                 return None;
             }
-            let raw_log = RawLog {
-                topics: log.topics.clone(),
-                data: log.data.to_vec(),
-            };
             let log_meta: EthersLogMeta = (&log).into();
-            let event_filter = T::decode_log(&raw_log).ok();
-            event_filter.map(|log| (log, log_meta.into()))
+            decode_log::<T>(&log).map(|event| (event, log_meta.into()))
         })
         .collect();
     Ok(logs)
 }
 
+/// Decodes a log into the event `T`, if the log's topics match the event's signature
+pub fn decode_log<T: EthEvent>(log: &Log) -> Option<T> {
+    let raw_log = RawLog {
+        topics: log.topics.clone(),
+        data: log.data.to_vec(),
+    };
+    T::decode_log(&raw_log).ok()
+}
+
+/// Metadata of a log returned by `eth_getLogs`, or `None` if the log is pending
+pub fn mined_log_meta(log: &Log) -> Option<LogMeta> {
+    Some(LogMeta {
+        address: log.address.into(),
+        block_number: log.block_number?.as_u64(),
+        block_hash: log.block_hash?.into(),
+        transaction_id: log.transaction_hash?.into(),
+        transaction_index: log.transaction_index?.as_u64(),
+        log_index: log.log_index?.into(),
+    })
+}
+
 #[instrument(level = "trace", err, ret, skip(provider))]
 pub async fn get_finalized_block_number<M>(
     provider: &M,
@@ -0,0 +1,292 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{BlockId, Filter, H160 as EthersH160};
+use ethers_contract::EthEvent;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ArbL2ToL1Ism, ArbL2ToL1Tx, ArbitrumOutboxProver, ChainCommunicationError, ChainResult,
+    ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, RawHyperlaneMessage, H256, U256,
+};
+
+use crate::interfaces::arb_l2_to_l1_ism::ArbL2ToL1Ism as EthereumArbL2ToL1IsmInternal;
+use crate::interfaces::arb_sys::{ArbSys, L2ToL1TxFilter};
+use crate::interfaces::arbitrum_node_interface::ArbitrumNodeInterface;
+use crate::interfaces::arbitrum_outbox::{ArbitrumOutbox, SendRootUpdatedFilter};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+
+/// Address of the ArbSys precompile on Arbitrum Nitro chains
+const ARB_SYS_ADDRESS: u64 = 0x64;
+/// Address of the NodeInterface precompile on Arbitrum Nitro chains
+const NODE_INTERFACE_ADDRESS: u64 = 0xC8;
+/// How many L1 blocks to search back for the latest confirmed send root by
+/// default. Arbitrum One confirms assertions roughly every hour, so a day's
+/// worth of Ethereum blocks leaves plenty of margin.
+const DEFAULT_SEND_ROOT_LOOKBACK_BLOCKS: u64 = 7_200;
+
+pub struct ArbL2ToL1IsmBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for ArbL2ToL1IsmBuilder {
+    type Output = Box<dyn ArbL2ToL1Ism>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumArbL2ToL1Ism::new(
+            Arc::new(provider),
+            locator,
+            conn.arb_send_root_lookback_blocks
+                .unwrap_or(DEFAULT_SEND_ROOT_LOOKBACK_BLOCKS),
+        ))
+    }
+}
+
+/// A reference to an ArbL2ToL1Ism contract on some Ethereum chain
+#[derive(Debug)]
+pub struct EthereumArbL2ToL1Ism<M>
+where
+    M: Middleware,
+{
+    contract: Arc<EthereumArbL2ToL1IsmInternal<M>>,
+    domain: HyperlaneDomain,
+    send_root_lookback_blocks: u64,
+}
+
+impl<M> EthereumArbL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a reference to an ArbL2ToL1Ism at a specific Ethereum address
+    /// on some chain, which searches the latest `send_root_lookback_blocks`
+    /// blocks for confirmed send roots
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        send_root_lookback_blocks: u64,
+    ) -> Self {
+        Self {
+            contract: Arc::new(EthereumArbL2ToL1IsmInternal::new(locator.address, provider)),
+            domain: locator.domain.clone(),
+            send_root_lookback_blocks,
+        }
+    }
+
+    async fn outbox(&self) -> ChainResult<ArbitrumOutbox<M>> {
+        let address = self.contract.arb_outbox().call().await?;
+        Ok(ArbitrumOutbox::new(address, self.contract.client()))
+    }
+}
+
+impl<M> HyperlaneChain for EthereumArbL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.contract.client(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+impl<M> HyperlaneContract for EthereumArbL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    fn address(&self) -> H256 {
+        self.contract.address().into()
+    }
+}
+
+#[async_trait]
+impl<M> ArbL2ToL1Ism for EthereumArbL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self, message))]
+    async fn is_verified(&self, message: &HyperlaneMessage) -> ChainResult<bool> {
+        let verified = self
+            .contract
+            .is_verified(RawHyperlaneMessage::from(message).to_vec().into())
+            .call()
+            .await?;
+        Ok(verified)
+    }
+
+    #[instrument(err, skip(self))]
+    async fn authorized_hook(&self) -> ChainResult<H256> {
+        let hook = self.contract.authorized_hook().call().await?;
+        Ok(hook.into())
+    }
+
+    #[instrument(err, skip(self))]
+    async fn is_spent(&self, position: U256) -> ChainResult<bool> {
+        let spent = self
+            .outbox()
+            .await?
+            .is_spent(position.into())
+            .call()
+            .await?;
+        Ok(spent)
+    }
+
+    #[instrument(err, skip(self))]
+    async fn latest_confirmed_block(&self) -> ChainResult<Option<H256>> {
+        let outbox = self.outbox().await?;
+        let client = self.contract.client();
+        let tip = client
+            .get_block_number()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .as_u64();
+        let filter = Filter::new()
+            .address(outbox.address())
+            .topic0(SendRootUpdatedFilter::signature())
+            .from_block(tip.saturating_sub(self.send_root_lookback_blocks))
+            .to_block(tip);
+        let logs = client
+            .get_logs(&filter)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        // The L2 block hash is the second indexed topic of `SendRootUpdated`
+        Ok(logs
+            .last()
+            .and_then(|log| log.topics.get(2))
+            .map(|block_hash| (*block_hash).into()))
+    }
+}
+
+pub struct ArbitrumOutboxProverBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for ArbitrumOutboxProverBuilder {
+    type Output = Box<dyn ArbitrumOutboxProver>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        _locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumArbitrumOutboxProver::new(Arc::new(provider)))
+    }
+}
+
+/// Proves L2 to L1 transactions of an Arbitrum Nitro chain using its
+/// precompiles
+#[derive(Debug)]
+pub struct EthereumArbitrumOutboxProver<M>
+where
+    M: Middleware,
+{
+    arb_sys: ArbSys<M>,
+    node_interface: ArbitrumNodeInterface<M>,
+}
+
+impl<M> EthereumArbitrumOutboxProver<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a prover using the precompiles of the chain `provider` is
+    /// connected to
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            arb_sys: ArbSys::new(
+                EthersH160::from_low_u64_be(ARB_SYS_ADDRESS),
+                provider.clone(),
+            ),
+            node_interface: ArbitrumNodeInterface::new(
+                EthersH160::from_low_u64_be(NODE_INTERFACE_ADDRESS),
+                provider,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl<M> ArbitrumOutboxProver for EthereumArbitrumOutboxProver<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self))]
+    async fn l2_to_l1_tx(
+        &self,
+        block_number: u64,
+        caller: H256,
+        message_id: H256,
+    ) -> ChainResult<Option<ArbL2ToL1Tx>> {
+        let events: Vec<L2ToL1TxFilter> = self
+            .arb_sys
+            .event::<L2ToL1TxFilter>()
+            .from_block(block_number)
+            .to_block(block_number)
+            .query()
+            .await?;
+        // The hook sends `preVerifyMessage(bytes32 messageId)` calldata. Anyone
+        // can send an L2 to L1 transaction carrying the id, so only the
+        // authorized hook's are considered.
+        let tx = events.into_iter().find(|event| {
+            H256::from(event.caller) == caller
+                && event
+                    .data
+                    .windows(32)
+                    .any(|window| window == message_id.as_bytes())
+        });
+        Ok(tx.map(|event| ArbL2ToL1Tx {
+            caller: event.caller.into(),
+            destination: event.destination.into(),
+            position: event.position.into(),
+            arb_block_num: event.arb_block_num.into(),
+            eth_block_num: event.eth_block_num.into(),
+            timestamp: event.timestamp.into(),
+            callvalue: event.callvalue.into(),
+            data: event.data.to_vec(),
+        }))
+    }
+
+    #[instrument(err, skip(self))]
+    async fn send_count(&self, block_hash: H256) -> ChainResult<u64> {
+        let block = self
+            .arb_sys
+            .client()
+            .get_block(BlockId::Hash(block_hash.into()))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Confirmed L2 block not found")
+            })?;
+        // Nitro blocks store the send count in the first 8 bytes of the mix hash
+        let mix_hash = block.mix_hash.ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Confirmed L2 block has no mix hash")
+        })?;
+        let mut send_count = [0u8; 8];
+        send_count.copy_from_slice(&mix_hash.as_bytes()[..8]);
+        Ok(u64::from_be_bytes(send_count))
+    }
+
+    #[instrument(err, skip(self))]
+    async fn outbox_proof(&self, size: u64, leaf: u64) -> ChainResult<Vec<H256>> {
+        let (_send, _root, proof) = self
+            .node_interface
+            .construct_outbox_proof(size, leaf)
+            .call()
+            .await?;
+        Ok(proof.into_iter().map(H256::from).collect())
+    }
+}
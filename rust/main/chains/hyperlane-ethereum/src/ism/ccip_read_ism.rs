@@ -10,7 +10,8 @@ use tracing::instrument;
 
 use hyperlane_core::{
     CcipReadIsm, ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneProvider, H256,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, OffchainLookup as CoreOffchainLookup,
+    OffchainVerification, RawHyperlaneMessage, H256,
 };
 
 pub use crate::interfaces::i_ccip_read_ism::{
@@ -91,12 +92,59 @@ where
 {
     #[instrument(err)]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
-    async fn get_offchain_verify_info(&self, message: Vec<u8>) -> ChainResult<()> {
-        self.contract
+    async fn get_offchain_verify_info(
+        &self,
+        message: Vec<u8>,
+    ) -> ChainResult<Option<CoreOffchainLookup>> {
+        let result = self
+            .contract
             .get_offchain_verify_info(message.into())
             .call()
-            .await?;
-        Ok(())
+            .await;
+        match result {
+            Ok(()) => Ok(None),
+            Err(err) if err.is_revert() => Ok(err
+                .decode_revert::<OffchainLookup>()
+                .map(offchain_lookup_to_core)),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[instrument(err, skip(self, metadata, message))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn verify(
+        &self,
+        metadata: &[u8],
+        message: &HyperlaneMessage,
+    ) -> ChainResult<OffchainVerification> {
+        let result = self
+            .contract
+            .verify(
+                metadata.to_owned().into(),
+                RawHyperlaneMessage::from(message).to_vec().into(),
+            )
+            .call()
+            .await;
+        match result {
+            Ok(true) => Ok(OffchainVerification::Verified),
+            Ok(false) => Ok(OffchainVerification::Rejected),
+            // ISMs usually revert rather than return false on bad metadata
+            Err(err) if err.is_revert() => Ok(err
+                .decode_revert::<OffchainLookup>()
+                .map(offchain_lookup_to_core)
+                .map_or(OffchainVerification::Rejected, OffchainVerification::Lookup)),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+fn offchain_lookup_to_core(lookup: OffchainLookup) -> CoreOffchainLookup {
+    CoreOffchainLookup {
+        sender: lookup.sender.into(),
+        urls: lookup.urls,
+        call_data: lookup.call_data.to_vec(),
+        callback_function: lookup.callback_function,
+        extra_data: lookup.extra_data.to_vec(),
     }
 }
 
@@ -1,10 +1,14 @@
 pub use {
-    aggregation_ism::*, ccip_read_ism::*, interchain_security_module::*, multisig_ism::*,
-    routing_ism::*,
+    aggregation_ism::*, arb_l2_to_l1_ism::*, ccip_read_ism::*, interchain_security_module::*,
+    multisig_ism::*, null_ism::*, op_l2_to_l1_ism::*, routing_ism::*, weighted_multisig_ism::*,
 };
 
 mod aggregation_ism;
+mod arb_l2_to_l1_ism;
 mod ccip_read_ism;
 mod interchain_security_module;
 mod multisig_ism;
+mod null_ism;
+mod op_l2_to_l1_ism;
 mod routing_ism;
+mod weighted_multisig_ism;
@@ -0,0 +1,125 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers_contract::ContractError;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, NullIsm, H256,
+};
+
+use crate::interfaces::pausable_ism::PausableIsm;
+use crate::interfaces::trusted_relayer_ism::TrustedRelayerIsm;
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+
+pub struct NullIsmBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for NullIsmBuilder {
+    type Output = Box<dyn NullIsm>;
+    const NEEDS_SIGNER: bool = true;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumNullIsm::new(Arc::new(provider), locator))
+    }
+}
+
+/// A reference to an ISM of `ModuleType::Null` on some Ethereum chain,
+/// probed for the PausableIsm and TrustedRelayerIsm interfaces
+#[derive(Debug)]
+pub struct EthereumNullIsm<M>
+where
+    M: Middleware,
+{
+    pausable: PausableIsm<M>,
+    trusted_relayer: TrustedRelayerIsm<M>,
+    domain: HyperlaneDomain,
+}
+
+impl<M> EthereumNullIsm<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a reference to a null ISM at a specific Ethereum address on
+    /// some chain
+    pub fn new(provider: Arc<M>, locator: &ContractLocator) -> Self {
+        Self {
+            pausable: PausableIsm::new(locator.address, provider.clone()),
+            trusted_relayer: TrustedRelayerIsm::new(locator.address, provider),
+            domain: locator.domain.clone(),
+        }
+    }
+}
+
+/// Whether a call failed because the contract doesn't implement the
+/// function, rather than because of the RPC
+fn is_not_implemented<M: Middleware>(err: &ContractError<M>) -> bool {
+    err.is_revert()
+        || matches!(
+            err,
+            ContractError::DecodingError(_) | ContractError::AbiError(_)
+        )
+}
+
+impl<M> HyperlaneChain for EthereumNullIsm<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.pausable.client(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+impl<M> HyperlaneContract for EthereumNullIsm<M>
+where
+    M: Middleware + 'static,
+{
+    fn address(&self) -> H256 {
+        self.pausable.address().into()
+    }
+}
+
+#[async_trait]
+impl<M> NullIsm for EthereumNullIsm<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self))]
+    async fn paused(&self) -> ChainResult<bool> {
+        match self.pausable.paused().call().await {
+            Ok(paused) => Ok(paused),
+            Err(err) if is_not_implemented(&err) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[instrument(err, skip(self))]
+    async fn trusted_relayer(&self) -> ChainResult<Option<H256>> {
+        match self.trusted_relayer.trusted_relayer().call().await {
+            Ok(relayer) => Ok(Some(relayer.into())),
+            Err(err) if is_not_implemented(&err) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn relayer(&self) -> Option<H256> {
+        self.pausable.client().default_sender().map(Into::into)
+    }
+}
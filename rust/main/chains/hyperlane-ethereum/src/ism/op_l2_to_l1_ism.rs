@@ -0,0 +1,414 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use ethers::types::{Address, BlockId, H160 as EthersH160, H256 as EthersH256};
+use ethers::utils::keccak256;
+use tokio::sync::OnceCell;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, OpDisputeGame, OpL2ToL1Ism, OpWithdrawal,
+    OpWithdrawalProof, OpWithdrawalProver, RawHyperlaneMessage, TxOutcome, H256, U256,
+};
+
+use crate::interfaces::dispute_game_factory::DisputeGameFactory;
+use crate::interfaces::i_dispute_game::IDisputeGame;
+use crate::interfaces::l2_to_l1_message_passer::{L2ToL1MessagePasser, MessagePassedFilter};
+use crate::interfaces::op_l2_to_l1_ism::OpL2ToL1Ism as EthereumOpL2ToL1IsmInternal;
+use crate::interfaces::optimism_portal2::{
+    OptimismPortal2, OutputRootProof, WithdrawalTransaction,
+};
+use crate::tx::{fill_tx_gas_params, report_tx};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+
+/// Address of the L2ToL1MessagePasser predeploy on OP Stack chains
+const MESSAGE_PASSER_ADDRESS: &str = "0x4200000000000000000000000000000000000016";
+/// How many of the latest dispute games to search for one of the type
+/// respected by the portal
+const DISPUTE_GAME_LOOKBACK: u64 = 50;
+
+pub struct OpL2ToL1IsmBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for OpL2ToL1IsmBuilder {
+    type Output = Box<dyn OpL2ToL1Ism>;
+    const NEEDS_SIGNER: bool = true;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumOpL2ToL1Ism::new(Arc::new(provider), conn, locator))
+    }
+}
+
+/// A reference to an OPL2ToL1Ism contract on some Ethereum chain
+#[derive(Debug)]
+pub struct EthereumOpL2ToL1Ism<M>
+where
+    M: Middleware,
+{
+    contract: Arc<EthereumOpL2ToL1IsmInternal<M>>,
+    domain: HyperlaneDomain,
+    provider: Arc<M>,
+    conn: ConnectionConf,
+    portal: OnceCell<OptimismPortal2<M>>,
+}
+
+impl<M> EthereumOpL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a reference to an OPL2ToL1Ism at a specific Ethereum address on
+    /// some chain
+    pub fn new(provider: Arc<M>, conn: &ConnectionConf, locator: &ContractLocator) -> Self {
+        Self {
+            contract: Arc::new(EthereumOpL2ToL1IsmInternal::new(
+                locator.address,
+                provider.clone(),
+            )),
+            domain: locator.domain.clone(),
+            provider,
+            conn: conn.clone(),
+            portal: OnceCell::new(),
+        }
+    }
+
+    /// The portal of the ISM. Only fault proof portals are supported: portals
+    /// that prove withdrawals against an `L2OutputOracle` are rejected, since
+    /// they have no dispute game factory.
+    async fn portal(&self) -> ChainResult<&OptimismPortal2<M>> {
+        self.portal
+            .get_or_try_init(|| async {
+                let address = self.contract.portal().call().await?;
+                let portal = OptimismPortal2::new(address, self.provider.clone());
+                match portal.dispute_game_factory().call().await {
+                    Ok(_) => Ok(portal),
+                    Err(err) if err.is_revert() => Err(ChainCommunicationError::from_other_str(
+                        &format!("OptimismPortal {address:?} has no dispute game factory, portals using an L2OutputOracle are not supported"),
+                    )),
+                    Err(err) => Err(err.into()),
+                }
+            })
+            .await
+    }
+
+    /// The address proofs are submitted from
+    fn proof_submitter(&self) -> ChainResult<Address> {
+        self.provider
+            .default_sender()
+            .ok_or(ChainCommunicationError::SignerUnavailable)
+    }
+}
+
+impl<M> HyperlaneChain for EthereumOpL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.provider.clone(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+impl<M> HyperlaneContract for EthereumOpL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    fn address(&self) -> H256 {
+        self.contract.address().into()
+    }
+}
+
+#[async_trait]
+impl<M> OpL2ToL1Ism for EthereumOpL2ToL1Ism<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self, message))]
+    async fn is_verified(&self, message: &HyperlaneMessage) -> ChainResult<bool> {
+        let verified = self
+            .contract
+            .is_verified(RawHyperlaneMessage::from(message).to_vec().into())
+            .call()
+            .await?;
+        Ok(verified)
+    }
+
+    #[instrument(err, skip(self))]
+    async fn latest_dispute_game(&self) -> ChainResult<Option<OpDisputeGame>> {
+        let portal = self.portal().await?;
+        let respected_game_type = portal.respected_game_type().call().await?;
+        let factory = DisputeGameFactory::new(
+            portal.dispute_game_factory().call().await?,
+            self.provider.clone(),
+        );
+        let game_count = factory.game_count().call().await?.as_u64();
+        for index in (game_count.saturating_sub(DISPUTE_GAME_LOOKBACK)..game_count).rev() {
+            let (game_type, _timestamp, proxy) = factory.game_at_index(index.into()).call().await?;
+            if game_type != respected_game_type {
+                continue;
+            }
+            let l2_block_number = IDisputeGame::new(proxy, self.provider.clone())
+                .l2_block_number()
+                .call()
+                .await?;
+            return Ok(Some(OpDisputeGame {
+                index: index.into(),
+                l2_block_number: l2_block_number.as_u64(),
+            }));
+        }
+        Ok(None)
+    }
+
+    #[instrument(err, skip(self))]
+    async fn is_proven(&self, withdrawal_hash: H256) -> ChainResult<bool> {
+        let (_dispute_game, timestamp) = self
+            .portal()
+            .await?
+            .proven_withdrawals(withdrawal_hash.into(), self.proof_submitter()?)
+            .call()
+            .await?;
+        Ok(timestamp != 0)
+    }
+
+    #[instrument(err, skip(self))]
+    async fn is_finalizable(&self, withdrawal_hash: H256) -> ChainResult<bool> {
+        // `checkWithdrawal` reverts with the reason the withdrawal can't be
+        // finalized yet
+        match self
+            .portal()
+            .await?
+            .check_withdrawal(withdrawal_hash.into(), self.proof_submitter()?)
+            .call()
+            .await
+        {
+            Ok(()) => Ok(true),
+            Err(err) if err.is_revert() => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    #[instrument(err, skip(self, proof))]
+    async fn prove_withdrawal(
+        &self,
+        withdrawal: &OpWithdrawal,
+        dispute_game: &OpDisputeGame,
+        proof: &OpWithdrawalProof,
+    ) -> ChainResult<TxOutcome> {
+        let output_root_proof = OutputRootProof {
+            version: proof.version.into(),
+            state_root: proof.state_root.into(),
+            message_passer_storage_root: proof.message_passer_storage_root.into(),
+            latest_blockhash: proof.latest_blockhash.into(),
+        };
+        let tx = self.portal().await?.prove_withdrawal_transaction(
+            withdrawal_transaction(withdrawal),
+            dispute_game.index.into(),
+            output_root_proof,
+            proof
+                .withdrawal_proof
+                .iter()
+                .map(|node| node.clone().into())
+                .collect(),
+        );
+        let tx = fill_tx_gas_params(
+            tx,
+            self.provider.clone(),
+            &self.conn.transaction_overrides,
+            &self.domain,
+        )
+        .await?;
+        Ok(report_tx(tx).await?.into())
+    }
+
+    #[instrument(err, skip(self))]
+    async fn finalize_withdrawal(&self, withdrawal: &OpWithdrawal) -> ChainResult<TxOutcome> {
+        let tx = self
+            .portal()
+            .await?
+            .finalize_withdrawal_transaction(withdrawal_transaction(withdrawal));
+        let tx = fill_tx_gas_params(
+            tx,
+            self.provider.clone(),
+            &self.conn.transaction_overrides,
+            &self.domain,
+        )
+        .await?;
+        Ok(report_tx(tx).await?.into())
+    }
+}
+
+fn withdrawal_transaction(withdrawal: &OpWithdrawal) -> WithdrawalTransaction {
+    WithdrawalTransaction {
+        nonce: withdrawal.nonce.into(),
+        sender: withdrawal.sender.into(),
+        target: withdrawal.target.into(),
+        value: withdrawal.value.into(),
+        gas_limit: withdrawal.gas_limit.into(),
+        data: withdrawal.data.clone().into(),
+    }
+}
+
+pub struct OpWithdrawalProverBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for OpWithdrawalProverBuilder {
+    type Output = Box<dyn OpWithdrawalProver>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        _locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumOpWithdrawalProver::new(Arc::new(provider)))
+    }
+}
+
+/// Proves withdrawals of an OP Stack chain using its predeploys
+#[derive(Debug)]
+pub struct EthereumOpWithdrawalProver<M>
+where
+    M: Middleware,
+{
+    message_passer: L2ToL1MessagePasser<M>,
+}
+
+impl<M> EthereumOpWithdrawalProver<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a prover using the predeploys of the chain `provider` is
+    /// connected to
+    pub fn new(provider: Arc<M>) -> Self {
+        let address: EthersH160 = MESSAGE_PASSER_ADDRESS
+            .parse()
+            .expect("valid message passer address");
+        Self {
+            message_passer: L2ToL1MessagePasser::new(address, provider),
+        }
+    }
+}
+
+#[async_trait]
+impl<M> OpWithdrawalProver for EthereumOpWithdrawalProver<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self))]
+    async fn withdrawal(
+        &self,
+        block_number: u64,
+        message_id: H256,
+    ) -> ChainResult<Option<OpWithdrawal>> {
+        let events: Vec<MessagePassedFilter> = self
+            .message_passer
+            .event::<MessagePassedFilter>()
+            .from_block(block_number)
+            .to_block(block_number)
+            .query()
+            .await?;
+        // The withdrawal relays the hook's `preVerifyMessage(bytes32 messageId)`
+        // call through the cross domain messengers
+        let withdrawal = events.into_iter().find(|event| {
+            event
+                .data
+                .windows(32)
+                .any(|window| window == message_id.as_bytes())
+        });
+        Ok(withdrawal.map(|event| OpWithdrawal {
+            nonce: event.nonce.into(),
+            sender: event.sender.into(),
+            target: event.target.into(),
+            value: event.value.into(),
+            gas_limit: event.gas_limit.into(),
+            data: event.data.to_vec(),
+            withdrawal_hash: event.withdrawal_hash.into(),
+        }))
+    }
+
+    #[instrument(err, skip(self))]
+    async fn withdrawal_proof(
+        &self,
+        withdrawal_hash: H256,
+        l2_block_number: u64,
+    ) -> ChainResult<OpWithdrawalProof> {
+        let client = self.message_passer.client();
+        let block_id = BlockId::from(l2_block_number);
+        let block = client
+            .get_block(block_id)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .ok_or_else(|| ChainCommunicationError::from_other_str("L2 block not found"))?;
+        let block_hash = block
+            .hash
+            .ok_or_else(|| ChainCommunicationError::from_other_str("L2 block has no hash"))?;
+
+        // Withdrawals are stored in the `sentMessages` mapping at slot 0
+        let mut slot_preimage = withdrawal_hash.to_fixed_bytes().to_vec();
+        slot_preimage.extend([0u8; 32]);
+        let slot = EthersH256::from(keccak256(slot_preimage));
+        let account_proof = client
+            .get_proof(self.message_passer.address(), vec![slot], Some(block_id))
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let storage_proof = account_proof
+            .storage_proof
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("No storage proof returned for withdrawal")
+            })?;
+
+        Ok(OpWithdrawalProof {
+            version: H256::zero(),
+            state_root: block.state_root.into(),
+            message_passer_storage_root: account_proof.storage_hash.into(),
+            latest_blockhash: block_hash.into(),
+            withdrawal_proof: storage_proof
+                .proof
+                .into_iter()
+                .map(|node| node.to_vec())
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_withdrawal_transaction_conversion() {
+        let withdrawal = OpWithdrawal {
+            nonce: U256::from(7),
+            sender: H256::from_low_u64_be(1),
+            target: H256::from_low_u64_be(2),
+            value: U256::zero(),
+            gas_limit: U256::from(200_000),
+            data: vec![1, 2, 3],
+            withdrawal_hash: H256::repeat_byte(9),
+        };
+        let tx = withdrawal_transaction(&withdrawal);
+        assert_eq!(tx.nonce, 7.into());
+        assert_eq!(tx.sender, Address::from_low_u64_be(1));
+        assert_eq!(tx.target, Address::from_low_u64_be(2));
+        assert_eq!(tx.gas_limit, 200_000.into());
+        assert_eq!(tx.data.to_vec(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,135 @@
+#![allow(clippy::enum_variant_names)]
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ethers::providers::Middleware;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, RawHyperlaneMessage, WeightedMultisigIsm, H256,
+};
+
+use crate::interfaces::i_static_weighted_multisig_ism::{
+    IStaticWeightedMultisigIsm as EthereumWeightedMultisigIsmInternal,
+    ISTATICWEIGHTEDMULTISIGISM_ABI,
+};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+
+impl<M> std::fmt::Display for EthereumWeightedMultisigIsmInternal<M>
+where
+    M: Middleware,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub struct WeightedMultisigIsmBuilder {}
+
+#[async_trait]
+impl BuildableWithProvider for WeightedMultisigIsmBuilder {
+    type Output = Box<dyn WeightedMultisigIsm>;
+    const NEEDS_SIGNER: bool = false;
+
+    async fn build_with_provider<M: Middleware + 'static>(
+        &self,
+        provider: M,
+        _conn: &ConnectionConf,
+        locator: &ContractLocator,
+    ) -> Self::Output {
+        Box::new(EthereumWeightedMultisigIsm::new(
+            Arc::new(provider),
+            locator,
+        ))
+    }
+}
+
+/// A reference to a WeightedMultisigIsm contract on some Ethereum chain
+#[derive(Debug)]
+pub struct EthereumWeightedMultisigIsm<M>
+where
+    M: Middleware,
+{
+    contract: Arc<EthereumWeightedMultisigIsmInternal<M>>,
+    domain: HyperlaneDomain,
+}
+
+impl<M> EthereumWeightedMultisigIsm<M>
+where
+    M: Middleware + 'static,
+{
+    /// Create a reference to a weighted multisig ISM at a specific Ethereum address on some
+    /// chain
+    pub fn new(provider: Arc<M>, locator: &ContractLocator) -> Self {
+        Self {
+            contract: Arc::new(EthereumWeightedMultisigIsmInternal::new(
+                locator.address,
+                provider,
+            )),
+            domain: locator.domain.clone(),
+        }
+    }
+}
+
+impl<M> HyperlaneChain for EthereumWeightedMultisigIsm<M>
+where
+    M: Middleware + 'static,
+{
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(EthereumProvider::new(
+            self.contract.client(),
+            self.domain.clone(),
+        ))
+    }
+}
+
+impl<M> HyperlaneContract for EthereumWeightedMultisigIsm<M>
+where
+    M: Middleware + 'static,
+{
+    fn address(&self) -> H256 {
+        self.contract.address().into()
+    }
+}
+
+#[async_trait]
+impl<M> WeightedMultisigIsm for EthereumWeightedMultisigIsm<M>
+where
+    M: Middleware + 'static,
+{
+    #[instrument(err, skip(self, message))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn validators_and_threshold_weight(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<(H256, u128)>, u128)> {
+        let (validator_infos, threshold_weight) = self
+            .contract
+            .validators_and_threshold_weight(RawHyperlaneMessage::from(message).to_vec().into())
+            .call()
+            .await?;
+        let validators = validator_infos
+            .iter()
+            .map(|info| (H256::from(info.signing_address), info.weight))
+            .collect();
+        Ok((validators, threshold_weight))
+    }
+}
+
+pub struct EthereumWeightedMultisigIsmAbi;
+
+impl HyperlaneAbi for EthereumWeightedMultisigIsmAbi {
+    const SELECTOR_SIZE_BYTES: usize = 4;
+
+    fn fn_map() -> HashMap<Vec<u8>, &'static str> {
+        crate::extract_fn_map(&ISTATICWEIGHTEDMULTISIGISM_ABI)
+    }
+}
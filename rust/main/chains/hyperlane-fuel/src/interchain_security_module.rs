@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use fuels::{
+    prelude::{Bech32ContractId, WalletUnlocked},
+    types::Bytes,
+};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType,
+    RawHyperlaneMessage, H256, U256,
+};
+use tracing::instrument;
+
+use crate::{
+    contracts::interchain_security_module::{
+        InterchainSecurityModule as FuelInterchainSecurityModuleInner, ModuleType as FuelModuleType,
+    },
+    conversions::*,
+    ConnectionConf, FuelProvider,
+};
+
+/// A reference to an InterchainSecurityModule contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelInterchainSecurityModule {
+    contract: FuelInterchainSecurityModuleInner<WalletUnlocked>,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelInterchainSecurityModule {
+    /// Create a new fuel ISM contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        mut wallet: WalletUnlocked,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        wallet.set_provider(fuel_provider.provider().clone());
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelInterchainSecurityModule {
+            contract: FuelInterchainSecurityModuleInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelInterchainSecurityModule {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelInterchainSecurityModule {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for FuelInterchainSecurityModule {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        self.contract
+            .methods()
+            .module_type()
+            .simulate()
+            .await
+            .map(|r| IsmType(r.value).into())
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        let call_res = self
+            .contract
+            .methods()
+            .verify(
+                Bytes(metadata.to_vec()),
+                Bytes(RawHyperlaneMessage::from(message)),
+            )
+            .determine_missing_contracts(Some(3))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .simulate()
+            .await;
+
+        // A reverting `verify` is how most ISMs reject metadata, so it is not
+        // treated as an RPC error.
+        match call_res {
+            Ok(res) if res.value => Ok(Some(res.gas_used.into())),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Wrapper around the generated fuel `ModuleType` so it can be converted into
+/// the core type.
+struct IsmType(FuelModuleType);
+
+impl From<IsmType> for ModuleType {
+    fn from(value: IsmType) -> Self {
+        match value.0 {
+            FuelModuleType::UNUSED => ModuleType::Unused,
+            FuelModuleType::ROUTING => ModuleType::Routing,
+            FuelModuleType::AGGREGATION => ModuleType::Aggregation,
+            FuelModuleType::LEGACY_MULTISIG => ModuleType::LegacyMultisig,
+            FuelModuleType::MERKLE_ROOT_MULTISIG => ModuleType::MerkleRootMultisig,
+            FuelModuleType::MESSAGE_ID_MULTISIG => ModuleType::MessageIdMultisig,
+            FuelModuleType::NULL => ModuleType::Null,
+            FuelModuleType::CCIP_READ => ModuleType::CcipRead,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_type_conversion() {
+        assert_eq!(
+            ModuleType::from(IsmType(FuelModuleType::MESSAGE_ID_MULTISIG)),
+            ModuleType::MessageIdMultisig
+        );
+        assert_eq!(
+            ModuleType::from(IsmType(FuelModuleType::ROUTING)),
+            ModuleType::Routing
+        );
+        assert_eq!(
+            ModuleType::from(IsmType(FuelModuleType::UNUSED)),
+            ModuleType::Unused
+        );
+    }
+}
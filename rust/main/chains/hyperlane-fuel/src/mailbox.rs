@@ -1,20 +1,22 @@
 use crate::{
-    contracts::mailbox::Mailbox as FuelMailboxInner, conversions::*, ConnectionConf, FuelProvider,
+    contracts::mailbox::{DispatchEvent, Mailbox as FuelMailboxInner, ProcessEvent},
+    conversions::*,
+    ConnectionConf, FuelProvider,
 };
 use async_trait::async_trait;
 use fuels::{
+    core::codec::{calldata, encode_fn_selector},
     prelude::{Bech32ContractId, WalletUnlocked},
     tx::{Receipt, ScriptExecutionResult},
     types::{transaction::TxPolicies, Bytes},
 };
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneAbi,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
-    Indexed, Indexer, LogMeta, Mailbox, RawHyperlaneMessage, ReorgPeriod, SequenceAwareIndexer,
-    TxCostEstimate, TxOutcome, H256, H512, U256,
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer,
+    LogMeta, Mailbox, RawHyperlaneMessage, ReorgPeriod, SequenceAwareIndexer, TxCostEstimate,
+    TxOutcome, H256, H512, U256,
 };
 use std::{
-    collections::HashMap,
     fmt::{Debug, Formatter},
     ops::RangeInclusive,
 };
@@ -34,7 +36,7 @@ impl FuelMailbox {
         locator: ContractLocator<'_>,
         mut wallet: WalletUnlocked,
     ) -> ChainResult<Self> {
-        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await;
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
 
         wallet.set_provider(fuel_provider.provider().clone());
         let address = Bech32ContractId::from_h256(&locator.address);
@@ -69,15 +71,24 @@ impl Debug for FuelMailbox {
     }
 }
 
+/// Fuel does not support querying point-in-time, but its blocks are final as
+/// soon as they are produced, so reading at the tip satisfies any finality tag.
+pub(crate) fn ensure_tip_reorg_period(reorg_period: &ReorgPeriod) -> ChainResult<()> {
+    match reorg_period {
+        ReorgPeriod::None => Ok(()),
+        ReorgPeriod::Tag(tag) if matches!(tag.as_str(), "finalized" | "safe" | "latest") => Ok(()),
+        _ => Err(ChainCommunicationError::InvalidReorgPeriod(
+            reorg_period.clone(),
+        )),
+    }
+}
+
 #[async_trait]
 impl Mailbox for FuelMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
-        assert!(
-            reorg_period.is_none(),
-            "Fuel does not support querying point-in-time"
-        );
+        ensure_tip_reorg_period(reorg_period)?;
         self.contract
             .methods()
             .nonce()
@@ -174,7 +185,10 @@ impl Mailbox for FuelMailbox {
             })
             .any(|result| matches!(result, ScriptExecutionResult::Success));
 
-        let tx_id = H512::from(call_res.tx_id.unwrap().into_h256());
+        let tx_id = call_res
+            .tx_id
+            .map(|id| H512::from(id.into_h256()))
+            .unwrap_or_default();
         Ok(TxOutcome {
             transaction_id: tx_id,
             executed: success,
@@ -213,8 +227,12 @@ impl Mailbox for FuelMailbox {
     }
 
     fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
-        // Seems like this is not needed for Fuel, as it's only used in mocks
-        todo!()
+        let args = calldata!(
+            Bytes(metadata.to_vec()),
+            Bytes(RawHyperlaneMessage::from(message))
+        )
+        .unwrap_or_default();
+        [encode_fn_selector("process"), args].concat()
     }
 }
 
@@ -230,10 +248,11 @@ impl FuelMailboxIndexer {
     pub async fn new(
         conf: &ConnectionConf,
         locator: ContractLocator<'_>,
-        wallet: WalletUnlocked,
+        mut wallet: WalletUnlocked,
     ) -> ChainResult<Self> {
-        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await;
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
 
+        wallet.set_provider(fuel_provider.provider().clone());
         let address = Bech32ContractId::from_h256(&locator.address);
         let contract = FuelMailboxInner::new(address, wallet);
 
@@ -250,10 +269,21 @@ impl Indexer<HyperlaneMessage> for FuelMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        let mailbox_address = self.contract.contract_id().clone();
-        self.provider
-            .index_logs_in_range(range, mailbox_address)
-            .await
+        let logs = self
+            .provider
+            .index_logs_in_range::<DispatchEvent>(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+            )
+            .await?;
+        Ok(logs
+            .into_iter()
+            .map(|(event, log_meta)| {
+                let message = HyperlaneMessage::from(event.message.bytes.0);
+                (message.into(), log_meta)
+            })
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -267,7 +297,18 @@ impl Indexer<H256> for FuelMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!() // Needed for scraper
+        let logs = self
+            .provider
+            .index_logs_in_range::<ProcessEvent>(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+            )
+            .await?;
+        Ok(logs
+            .into_iter()
+            .map(|(event, log_meta)| (event.message_id.into_h256().into(), log_meta))
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -301,15 +342,3 @@ impl SequenceAwareIndexer<HyperlaneMessage> for FuelMailboxIndexer {
             .map(|sequence| (Some(sequence as u32), tip))
     }
 }
-
-#[allow(dead_code)] // TODO: Remove this once the FuelMailboxAbi is implemented
-struct FuelMailboxAbi;
-
-impl HyperlaneAbi for FuelMailboxAbi {
-    const SELECTOR_SIZE_BYTES: usize = 8;
-
-    fn fn_map() -> HashMap<Vec<u8>, &'static str> {
-        // Can't support this without Fuels exporting it in the generated code
-        todo!()
-    }
-}
@@ -0,0 +1,188 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use fuels::prelude::{Bech32ContractId, WalletUnlocked};
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, ChainCommunicationError, ChainResult, Checkpoint,
+    ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider,
+    Indexed, Indexer, LogMeta, MerkleTreeHook, MerkleTreeInsertion, ReorgPeriod,
+    SequenceAwareIndexer, H256,
+};
+use tracing::instrument;
+
+use crate::{
+    contracts::merkle_tree_hook::{
+        InsertedIntoTreeEvent, MerkleTreeHook as FuelMerkleTreeHookInner,
+    },
+    conversions::*,
+    mailbox::ensure_tip_reorg_period,
+    ConnectionConf, FuelProvider,
+};
+
+/// A reference to a MerkleTreeHook contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelMerkleTreeHook {
+    contract: FuelMerkleTreeHookInner<WalletUnlocked>,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelMerkleTreeHook {
+    /// Create a new fuel merkle tree hook
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        mut wallet: WalletUnlocked,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        wallet.set_provider(fuel_provider.provider().clone());
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelMerkleTreeHook {
+            contract: FuelMerkleTreeHookInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelMerkleTreeHook {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelMerkleTreeHook {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl MerkleTreeHook for FuelMerkleTreeHook {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn tree(&self, reorg_period: &ReorgPeriod) -> ChainResult<IncrementalMerkle> {
+        ensure_tip_reorg_period(reorg_period)?;
+        let tree = self
+            .contract
+            .methods()
+            .tree()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        let branch = tree.branch.map(|node| node.into_h256());
+        let count = usize::try_from(tree.count).map_err(ChainCommunicationError::from_other)?;
+        Ok(IncrementalMerkle::new(branch, count))
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        ensure_tip_reorg_period(reorg_period)?;
+        self.contract
+            .methods()
+            .count()
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn latest_checkpoint(&self, reorg_period: &ReorgPeriod) -> ChainResult<Checkpoint> {
+        ensure_tip_reorg_period(reorg_period)?;
+        let (root, index) = self
+            .contract
+            .methods()
+            .latest_checkpoint()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address(),
+            mailbox_domain: self.domain.id(),
+            root: root.into_h256(),
+            index,
+        })
+    }
+}
+
+/// Struct that retrieves insertion events for a Fuel MerkleTreeHook contract
+#[derive(Debug)]
+pub struct FuelMerkleTreeHookIndexer {
+    contract: FuelMerkleTreeHookInner<WalletUnlocked>,
+    provider: FuelProvider,
+}
+
+impl FuelMerkleTreeHookIndexer {
+    /// Create a new FuelMerkleTreeHookIndexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        mut wallet: WalletUnlocked,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        wallet.set_provider(fuel_provider.provider().clone());
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelMerkleTreeHookIndexer {
+            contract: FuelMerkleTreeHookInner::new(address, wallet),
+            provider: fuel_provider,
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let logs = self
+            .provider
+            .index_logs_in_range::<InsertedIntoTreeEvent>(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+            )
+            .await?;
+        Ok(logs
+            .into_iter()
+            .map(|(event, log_meta)| {
+                let insertion = MerkleTreeInsertion::new(event.index, event.message_id.into_h256());
+                (insertion.into(), log_meta)
+            })
+            .collect())
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.provider.get_finalized_block_number().await?;
+
+        self.contract
+            .methods()
+            .count()
+            .simulate()
+            .await
+            .map(|r| (Some(r.value), tip))
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
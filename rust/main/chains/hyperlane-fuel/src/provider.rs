@@ -1,8 +1,12 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{collections::HashMap, ops::RangeInclusive, str::FromStr};
 
 use async_trait::async_trait;
 use fuels::{
     client::{FuelClient, PageDirection, PaginationRequest},
+    core::{
+        codec::LogDecoder,
+        traits::{Parameterize, Tokenizable},
+    },
     prelude::Provider,
     tx::Receipt,
     types::{
@@ -10,16 +14,14 @@ use fuels::{
         block::Block,
         gas_price::LatestGasPrice,
         transaction::{Transaction, TransactionType},
-        transaction_response::TransactionResponse,
         tx_status::TxStatus,
         Address, BlockHeight, Bytes32, ContractId,
     },
 };
-use futures::future::join_all;
+use futures::future::try_join_all;
 use hyperlane_core::{
     h512_to_bytes, BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, HyperlaneProviderError, Indexed, LogMeta,
-    TxnInfo, H256, H512, U256,
+    HyperlaneDomain, HyperlaneProvider, HyperlaneProviderError, LogMeta, TxnInfo, H256, H512, U256,
 };
 
 use crate::{make_client, make_provider, prelude::FuelIntoH256, ConnectionConf};
@@ -34,15 +36,15 @@ pub struct FuelProvider {
 
 impl FuelProvider {
     /// Create a new fuel provider
-    pub async fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
-        let provider = make_provider(conf).await.unwrap();
-        let client = make_client(conf).unwrap();
+    pub async fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> ChainResult<Self> {
+        let provider = make_provider(conf).await?;
+        let client = make_client(conf)?;
 
-        Self {
+        Ok(Self {
             domain,
             provider,
             client,
-        }
+        })
     }
 
     /// Get the inner provider
@@ -61,72 +63,19 @@ impl FuelProvider {
         Ok(gas_price)
     }
 
-    /// Check if a transaction is from a contract
-    /// @note: Only works for checking script transactions
-    /// Assumes that the first input is the contract id
-    #[allow(clippy::get_first)] // TODO: `rustc` 1.80.1 clippy issue
-    fn is_transaction_from_contract(
-        res: &TransactionResponse,
-        contract: &Bech32ContractId,
-    ) -> bool {
-        if let TransactionType::Script(script_transaction) = &res.transaction {
-            if script_transaction.inputs().get(0).is_some_and(|input| {
-                input
-                    .contract_id()
-                    .is_some_and(|id| id == &ContractId::from(&contract.into()))
-            }) {
-                return true;
-            }
-        }
-        false
-    }
-
-    /// Check if a transaction is a call to the dispatch function of the Mailbox contract
-    #[allow(clippy::match_like_matches_macro)] // TODO: `rustc` 1.80.1 clippy issue
-    #[allow(clippy::into_iter_on_ref)] // TODO: `rustc` 1.80.1 clippy issue
-    fn is_dispatch_call(res: &TransactionResponse) -> bool {
-        // let selector = encode_fn_selector("dispatch");
-        // println!("selector: {:?}", selector); // XXX see if we can get the correct txn by selector
-        // Apparently we should be able to see it in the call logs
-
-        let receipts = match &res.status {
-            TxStatus::Success { receipts } => receipts,
-            _ => return false,
-        };
-        let log_data_receipts = receipts
-            .into_iter()
-            .filter(|rec| {
-                // if let Receipt::Call { param1, param2, .. } = rec {
-                //     print!(
-                //         "param1: {:?}, param2: {:?}",
-                //         param1.to_be_bytes(),
-                //         param2.to_be_bytes()
-                //     );
-                // }
-
-                match rec {
-                    Receipt::LogData { .. } => true,
-                    _ => false,
-                }
-            })
-            .collect::<Vec<_>>();
-
-        // Dispatch is the only call that has 2 log data receipts
-        match log_data_receipts.len() {
-            2 => true,
-            _ => false,
-        }
-    }
-
     #[allow(clippy::clone_on_copy)] // TODO: `rustc` 1.80.1 clippy issue
     async fn get_block_data(
         &self,
-        range: std::ops::RangeInclusive<u32>,
+        range: RangeInclusive<u32>,
     ) -> ChainResult<(Vec<Block>, HashMap<Bytes32, (Bytes32, u64)>)> {
         let result_amount = range.end() - range.start() + 1;
         let req = PaginationRequest {
-            cursor: Some(range.start().to_string()),
-            results: i32::try_from(result_amount).expect("Invalid range"),
+            // The cursor is exclusive, so start right before the first block of the range
+            cursor: range
+                .start()
+                .checked_sub(1)
+                .map(|height| height.to_string()),
+            results: i32::try_from(result_amount).map_err(ChainCommunicationError::from_other)?,
             direction: PageDirection::Forward,
         };
 
@@ -158,116 +107,70 @@ impl FuelProvider {
             .map_err(ChainCommunicationError::from_other)
     }
 
-    /// index logs in a range
-    #[allow(clippy::clone_on_copy)] // TODO: `rustc` 1.80.1 clippy issue
-    #[allow(clippy::manual_map)] // TODO: `rustc` 1.80.1 clippy issue
-    #[allow(clippy::into_iter_on_ref)] // TODO: `rustc` 1.80.1 clippy issue
-    #[allow(clippy::needless_borrow)] // TODO: `rustc` 1.80.1 clippy issue
-    pub async fn index_logs_in_range(
+    /// Fetch all logs of type `T` emitted by `contract` within `range`.
+    ///
+    /// Fuel has no log filtering by topic, so every transaction in the range
+    /// is fetched and its receipts decoded with the contract's `log_decoder`,
+    /// which only matches logs emitted by that contract.
+    pub async fn index_logs_in_range<T>(
         &self,
-        range: std::ops::RangeInclusive<u32>,
-        mailbox_contract: Bech32ContractId,
-    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        let (blocks, transaction_map) = self.get_block_data(range.clone()).await.unwrap();
+        range: RangeInclusive<u32>,
+        contract: &Bech32ContractId,
+        log_decoder: &LogDecoder,
+    ) -> ChainResult<Vec<(T, LogMeta)>>
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
+        let (blocks, transaction_map) = self.get_block_data(range).await?;
 
-        // Transaction ids from selected blocks
         let transaction_ids = blocks
             .into_iter()
-            .map(|block| block.transactions)
-            .flat_map(|txs| txs.into_iter())
+            .flat_map(|block| block.transactions)
             .collect::<Vec<_>>();
 
-        let futures = transaction_ids
-            .into_iter()
-            .map(|tx_id| {
-                let provider = self.provider.clone();
-                let tx_clone = tx_id.clone();
-                async move {
-                    let result = provider.get_transaction_by_id(&tx_id).await.unwrap();
-                    (tx_clone, result)
-                }
-            })
-            .collect::<Vec<_>>();
-
-        // Filter transactions
-        // 1. Transaction type is Script
-        // 2. Transaction status is Success
-        // 3. Transaction is from mailbox contract
-        // 4. Transaction is a dispatch call
-        // 5. Transaction data is valid
-        let transaction_data = join_all(futures)
-            .await
-            .into_iter()
-            .filter_map(|(tx_id, tx_data)| match tx_data {
-                Some(tx_data) => Some((tx_id, tx_data)),
-                _ => None,
-            })
-            .filter(|(_, tx_data)| {
-                matches!(tx_data.transaction, TransactionType::Script(_))
-                    && matches!(tx_data.status, TxStatus::Success { .. })
-                    && Self::is_transaction_from_contract(&tx_data, &mailbox_contract)
-                    && Self::is_dispatch_call(&tx_data)
-            })
-            .collect::<Vec<_>>();
-
-        // Full data needed to construct the logs
-        let full_tx_data = transaction_data
-            .into_iter()
-            .filter_map(|(tx_id, tx_data)| {
-                let receipts = match &tx_data.status {
-                    TxStatus::Success { receipts } => receipts,
-                    _ => return None,
-                };
-
-                let (log_index, mut receipt_log_data) = receipts
+        let transactions = try_join_all(transaction_ids.into_iter().map(|tx_id| async move {
+            self.provider
+                .get_transaction_by_id(&tx_id)
+                .await
+                .map(|tx| (tx_id, tx))
+                .map_err(ChainCommunicationError::from_other)
+        }))
+        .await?;
+
+        let mut logs = vec![];
+        for (tx_id, tx) in transactions {
+            let Some(tx) = tx else {
+                continue;
+            };
+            let TxStatus::Success { receipts } = &tx.status else {
+                continue;
+            };
+            let Some((block_hash, transaction_index)) = transaction_map.get(&tx_id) else {
+                continue;
+            };
+            let block_number = tx.block_height.map(|h| *h as u64).unwrap_or_default();
+
+            for (log_index, receipt) in receipts.iter().enumerate() {
+                let Some(log) = log_decoder
+                    .decode_logs_with_type::<T>(std::slice::from_ref(receipt))
+                    .map_err(ChainCommunicationError::from_other)?
                     .into_iter()
-                    .enumerate()
-                    .filter_map(|(log_index, rec)| {
-                        // We only care about LogData receipts with data length greater than 32 bytes
-                        match rec {
-                            Receipt::LogData { .. }
-                                if rec.data().is_some_and(|data| data.len() > 32) =>
-                            {
-                                let data = rec.data().map(|data| data.to_owned());
-
-                                match data {
-                                    Some(data) => Some((U256::from(log_index), data)),
-                                    _ => None,
-                                }
-                            }
-                            _ => None,
-                        }
-                    })
-                    .next()?; // Each dispatch call should have only one log data receipt
-
-                if !receipt_log_data.is_empty() {
-                    // We cut out the message id, recipient and domain which are encoded in the first 76 bytes
-                    receipt_log_data.drain(0..76);
-                    let encoded_message = HyperlaneMessage::from(receipt_log_data);
-                    Some((tx_id, tx_data, encoded_message, log_index))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<(Bytes32, TransactionResponse, HyperlaneMessage, U256)>>(); // Collect all Vec<u8> from each transaction into a Vec<Vec<u8>>
-
-        let indexed_logs: Vec<(Indexed<HyperlaneMessage>, LogMeta)> = full_tx_data
-            .into_iter()
-            .map(|(tx_id, tx, message, log_index)| {
-                let (block_hash, transaction_index) = transaction_map.get(&tx_id).unwrap();
-
+                    .next()
+                else {
+                    continue;
+                };
                 let log_meta = LogMeta {
-                    address: mailbox_contract.clone().into_h256(),
-                    block_number: *tx.block_height.unwrap().deref() as u64,
+                    address: contract.into_h256(),
+                    block_number,
                     block_hash: block_hash.into_h256(),
                     transaction_id: H512::from(tx_id.into_h256()),
-                    transaction_index: transaction_index.clone(),
-                    log_index,
+                    transaction_index: *transaction_index,
+                    log_index: U256::from(log_index),
                 };
-                (message.into(), log_meta)
-            })
-            .collect::<Vec<_>>();
-        Ok(indexed_logs)
+                logs.push((log, log_meta));
+            }
+        }
+        Ok(logs)
     }
 }
 
@@ -290,7 +193,7 @@ impl HyperlaneProvider for FuelProvider {
             .provider
             .block_by_height(BlockHeight::new(height as u32))
             .await
-            .map_err(|e| HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+            .map_err(|_| HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
 
         let block_info = match block_res {
             Some(block) => BlockInfo {
@@ -327,7 +230,12 @@ impl HyperlaneProvider for FuelProvider {
 
         match transaction_res {
             Some(transaction) => {
-                let block_number = transaction.block_height.unwrap();
+                let block_number = transaction.block_height.ok_or_else(|| {
+                    ChainCommunicationError::CustomError(format!(
+                        "Transaction {} is not included in a block",
+                        hash
+                    ))
+                })?;
 
                 let gas_price = self
                     .provider
@@ -405,10 +313,12 @@ impl HyperlaneProvider for FuelProvider {
 
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
         let base = self.provider.base_asset_id();
-        let asset = *Address::from_bytes_ref_checked(address.as_bytes()).expect("Invalid address");
+        let address = Address::from_str(&address).map_err(|e| {
+            ChainCommunicationError::CustomError(format!("Invalid address {address}: {e}"))
+        })?;
 
         self.provider
-            .get_asset_balance(&asset.into(), *base)
+            .get_asset_balance(&address.into(), *base)
             .await
             .map(|balance| Ok(U256::from(balance)))
             .map_err(|e| {
@@ -0,0 +1,176 @@
+//! Tests against a fuel-core node with the Hyperlane contracts deployed and
+//! some messages dispatched, e.g. by the fuel e2e setup. They are ignored by
+//! default, run them with `cargo test -p hyperlane-fuel -- --ignored` after
+//! setting:
+//! - `FUEL_RPC_URL`, defaulting to a local node
+//! - `FUEL_MAILBOX_ID`, `FUEL_MERKLE_TREE_HOOK_ID`, `FUEL_IGP_ID` and
+//!   `FUEL_VALIDATOR_ANNOUNCE_ID`
+//! - `FUEL_SIGNER_KEY`, a funded key, defaulting to the key fuel-core funds
+//!   in local networks
+
+use std::str::FromStr;
+
+use ethers::signers::LocalWallet;
+use fuels::{crypto::SecretKey, prelude::WalletUnlocked};
+use hyperlane_core::{
+    Announcement, ContractLocator, HyperlaneDomain, HyperlaneMessage, HyperlaneSigner,
+    HyperlaneSignerExt, Indexer, KnownHyperlaneDomain, Mailbox, MerkleTreeHook, ReorgPeriod,
+    SequenceAwareIndexer, ValidatorAnnounce, H256,
+};
+use hyperlane_ethereum::Signers;
+use url::Url;
+
+use crate::{
+    ConnectionConf, FuelInterchainGasPaymasterIndexer, FuelMailbox, FuelMailboxIndexer,
+    FuelMerkleTreeHook, FuelMerkleTreeHookIndexer, FuelValidatorAnnounce,
+};
+
+const LOCAL_RPC_URL: &str = "http://127.0.0.1:4000/v1/graphql";
+/// The key fuel-core funds when started with its default local chain config
+const LOCAL_SIGNER_KEY: &str = "0xde97d8624a438121b86a1956544bd72ed68cd69f2c99555b08b1e8c51ffd511c";
+
+fn conf() -> ConnectionConf {
+    let url = std::env::var("FUEL_RPC_URL").unwrap_or_else(|_| LOCAL_RPC_URL.to_owned());
+    ConnectionConf {
+        url: Url::parse(&url).unwrap(),
+    }
+}
+
+fn domain() -> HyperlaneDomain {
+    HyperlaneDomain::Known(KnownHyperlaneDomain::FuelTest1)
+}
+
+fn contract_id(var: &str) -> H256 {
+    let id = std::env::var(var).unwrap_or_else(|_| panic!("{var} must be set"));
+    H256::from_str(&id).unwrap()
+}
+
+fn wallet() -> WalletUnlocked {
+    let key = std::env::var("FUEL_SIGNER_KEY").unwrap_or_else(|_| LOCAL_SIGNER_KEY.to_owned());
+    WalletUnlocked::new_from_private_key(SecretKey::from_str(&key).unwrap(), None)
+}
+
+#[ignore = "Requires a fuel-core node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_mailbox_indexer_finds_every_dispatch() {
+    let domain = domain();
+    let address = contract_id("FUEL_MAILBOX_ID");
+    let locator = ContractLocator::new(&domain, address);
+    let indexer = FuelMailboxIndexer::new(&conf(), locator.clone(), wallet())
+        .await
+        .unwrap();
+    let mailbox = FuelMailbox::new(&conf(), locator, wallet()).await.unwrap();
+
+    let (count, tip) =
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&indexer)
+            .await
+            .unwrap();
+    let count = count.unwrap();
+    assert!(count > 0, "no messages were dispatched");
+
+    // Dispatches are found once each, starting from the first block
+    let dispatches = Indexer::<HyperlaneMessage>::fetch_logs_in_range(&indexer, 0..=tip)
+        .await
+        .unwrap();
+    let nonces: Vec<u32> = dispatches
+        .iter()
+        .map(|(message, _)| message.inner().nonce)
+        .collect();
+    assert_eq!(nonces, (0..count).collect::<Vec<_>>());
+    for (message, meta) in &dispatches {
+        assert_eq!(message.inner().origin, domain.id());
+        assert_eq!(meta.address, address);
+        assert!(meta.block_number <= tip as u64);
+    }
+
+    // Every indexed delivery was delivered
+    let deliveries = Indexer::<H256>::fetch_logs_in_range(&indexer, 0..=tip)
+        .await
+        .unwrap();
+    for (id, meta) in deliveries {
+        assert_eq!(meta.address, address);
+        assert!(mailbox.delivered(*id.inner()).await.unwrap());
+    }
+}
+
+#[ignore = "Requires a fuel-core node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_merkle_tree_hook_indexer_finds_every_insertion() {
+    let domain = domain();
+    let address = contract_id("FUEL_MERKLE_TREE_HOOK_ID");
+    let locator = ContractLocator::new(&domain, address);
+    let indexer = FuelMerkleTreeHookIndexer::new(&conf(), locator.clone(), wallet())
+        .await
+        .unwrap();
+    let hook = FuelMerkleTreeHook::new(&conf(), locator, wallet())
+        .await
+        .unwrap();
+
+    let (count, tip) = indexer.latest_sequence_count_and_tip().await.unwrap();
+    assert_eq!(count, Some(hook.count(&ReorgPeriod::None).await.unwrap()));
+
+    let insertions = indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    let indexes: Vec<u32> = insertions
+        .iter()
+        .map(|(insertion, _)| insertion.inner().index())
+        .collect();
+    assert_eq!(indexes, (0..count.unwrap()).collect::<Vec<_>>());
+}
+
+#[ignore = "Requires a fuel-core node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_igp_indexer_decodes_payments() {
+    let domain = domain();
+    let address = contract_id("FUEL_IGP_ID");
+    let indexer = FuelInterchainGasPaymasterIndexer::new(
+        &conf(),
+        ContractLocator::new(&domain, address),
+        wallet(),
+    )
+    .await
+    .unwrap();
+
+    let (count, tip) = indexer.latest_sequence_count_and_tip().await.unwrap();
+    // The IGP has no payment sequence
+    assert_eq!(count, None);
+
+    let payments = indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    assert!(!payments.is_empty(), "no gas payments were made");
+    for (payment, meta) in payments {
+        assert_eq!(meta.address, address);
+        assert!(!payment.inner().message_id.is_zero());
+    }
+}
+
+#[ignore = "Requires a fuel-core node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_validator_announce() {
+    let domain = domain();
+    let locator = ContractLocator::new(&domain, contract_id("FUEL_VALIDATOR_ANNOUNCE_ID"));
+    let validator_announce = FuelValidatorAnnounce::new(&conf(), locator, wallet())
+        .await
+        .unwrap();
+
+    // A new validator each run, so the announcement isn't a replay
+    let validator = Signers::Local(LocalWallet::new(&mut ethers::core::rand::thread_rng()));
+    let announcement = Announcement {
+        validator: validator.eth_address(),
+        mailbox_address: contract_id("FUEL_MAILBOX_ID"),
+        mailbox_domain: domain.id(),
+        storage_location: "file:///tmp/fuel-validator".to_owned(),
+    };
+    let signed = validator.sign(announcement.clone()).await.unwrap();
+
+    assert!(validator_announce
+        .announce_tokens_needed(signed.clone())
+        .await
+        .is_some());
+    let outcome = validator_announce.announce(signed).await.unwrap();
+    assert!(outcome.executed);
+
+    let locations = validator_announce
+        .get_announced_storage_locations(&[announcement.validator.into()])
+        .await
+        .unwrap();
+    assert_eq!(locations, vec![vec![announcement.storage_location]]);
+}
@@ -1,44 +1,151 @@
 use async_trait::async_trait;
-
+use fuels::{
+    accounts::ViewOnlyAccount,
+    prelude::{Bech32ContractId, WalletUnlocked},
+    programs::calls::{CallHandler, ContractCall},
+    tx::{Receipt, ScriptExecutionResult},
+    types::{Bits256, Bytes, EvmAddress},
+};
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H256, H512, U256,
+};
+use tracing::{instrument, trace};
+
+use crate::{
+    contracts::validator_announce::ValidatorAnnounce as FuelValidatorAnnounceInner, conversions::*,
+    ConnectionConf, FuelProvider,
 };
 
 /// A reference to a ValidatorAnnounce contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelValidatorAnnounce {}
+pub struct FuelValidatorAnnounce {
+    contract: FuelValidatorAnnounceInner<WalletUnlocked>,
+    provider: FuelProvider,
+    domain: HyperlaneDomain,
+}
+
+impl FuelValidatorAnnounce {
+    /// Create a new fuel validator announce contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        mut wallet: WalletUnlocked,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        wallet.set_provider(fuel_provider.provider().clone());
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelValidatorAnnounce {
+            contract: FuelValidatorAnnounceInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+
+    fn announce_contract_call(
+        &self,
+        announcement: SignedType<Announcement>,
+    ) -> CallHandler<WalletUnlocked, ContractCall, ()> {
+        let serialized_signature: [u8; 65] = announcement.signature.into();
+        // Fuel stores EVM addresses left-padded to 32 bytes.
+        let validator = EvmAddress::from(Bits256::from_h256(&H256::from(
+            announcement.value.validator,
+        )));
+
+        self.contract.methods().announce(
+            validator,
+            announcement.value.storage_location,
+            Bytes(serialized_signature.to_vec()),
+        )
+    }
+}
 
 impl HyperlaneContract for FuelValidatorAnnounce {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelValidatorAnnounce {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl ValidatorAnnounce for FuelValidatorAnnounce {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn get_announced_storage_locations(
         &self,
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>> {
-        todo!()
+        let validators = validators.iter().map(Bits256::from_h256).collect();
+        self.contract
+            .methods()
+            .get_announced_storage_locations(validators)
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(ChainCommunicationError::from_other)
     }
 
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
-        todo!()
+        let gas_price = self.provider.get_gas_price().await?;
+
+        let call_res = self
+            .announce_contract_call(announcement)
+            .call()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        // Extract transaction success from the receipts
+        let success = call_res
+            .receipts
+            .iter()
+            .filter_map(|r| match r {
+                Receipt::ScriptResult { result, .. } => Some(result),
+                _ => None,
+            })
+            .any(|result| matches!(result, ScriptExecutionResult::Success));
+
+        let tx_id = call_res
+            .tx_id
+            .map(|id| H512::from(id.into_h256()))
+            .unwrap_or_default();
+        Ok(TxOutcome {
+            transaction_id: tx_id,
+            executed: success,
+            gas_used: call_res.gas_used.into(),
+            gas_price: gas_price.into(),
+        })
     }
 
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
-        todo!()
+        let Ok(cost) = self
+            .announce_contract_call(announcement)
+            .estimate_transaction_cost(None, None)
+            .await
+        else {
+            trace!("Unable to estimate announce cost");
+            return None;
+        };
+
+        let base_asset = *self.provider.provider().base_asset_id();
+        let Ok(balance) = self.contract.account().get_asset_balance(&base_asset).await else {
+            trace!("Unable to query balance");
+            return None;
+        };
+
+        Some(U256::from(cost.total_fee.saturating_sub(balance)))
     }
 }
@@ -0,0 +1,297 @@
+//! Decoding of dispatched messages from exported Solana data, used to backfill
+//! message indexing from archives instead of the RPC.
+
+use std::{collections::HashSet, str::FromStr as _};
+
+use hyperlane_sealevel_mailbox::{
+    accounts::DispatchedMessageAccount, mailbox_dispatched_message_pda_seeds,
+};
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, Decode as _, HyperlaneMessage, Indexed, LogMeta, H256,
+    H512, U256,
+};
+
+use crate::log_meta_composer::{
+    filter_by_encoding, filter_by_validity, is_message_dispatch_instruction,
+};
+use crate::mailbox::SPL_NOOP;
+use crate::utils::from_base58;
+
+/// Decodes the messages dispatched by `mailbox` in a single `getTransaction`
+/// response (JSON encoding).
+///
+/// The mailbox logs the dispatched message PDA's data with a CPI into the SPL
+/// noop program, so only deployments built with the noop log are supported. A
+/// noop log is only trusted if its PDA is an account of a mailbox `OutboxDispatch`
+/// instruction in the same transaction, since anyone can call the noop program.
+pub fn decode_archived_dispatch_transaction(
+    transaction: &str,
+    mailbox: H256,
+) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+    let mailbox = Pubkey::new_from_array(mailbox.0);
+    let transaction =
+        serde_json::from_str::<EncodedConfirmedTransactionWithStatusMeta>(transaction)
+            .map_err(ChainCommunicationError::from_other)?;
+    let Some((transaction_hash, account_keys, instructions)) =
+        filter_by_encoding(transaction.transaction)
+            .and_then(|(tx, meta)| filter_by_validity(tx, meta))
+    else {
+        // failed transactions and ones without a json encoding can't carry dispatches
+        return Ok(vec![]);
+    };
+
+    let index_of = |key: &Pubkey| {
+        let key = key.to_string();
+        account_keys
+            .iter()
+            .position(|account| *account == key)
+            .map(|index| index as u8)
+    };
+    let (Some(mailbox_index), Some(noop_index)) = (
+        index_of(&mailbox),
+        index_of(&Pubkey::from_str(SPL_NOOP).expect("valid pubkey")),
+    ) else {
+        return Ok(vec![]);
+    };
+
+    // Accounts that a mailbox dispatch instruction in this transaction operated upon
+    let dispatch_accounts = instructions
+        .iter()
+        .filter(|instruction| instruction.program_id_index == mailbox_index)
+        .filter(|instruction| {
+            from_base58(&instruction.data)
+                .map(|data| is_message_dispatch_instruction(&data))
+                .unwrap_or_default()
+        })
+        .flat_map(|instruction| instruction.accounts.iter().copied())
+        .collect::<HashSet<_>>();
+
+    let mut messages = vec![];
+    for instruction in instructions
+        .iter()
+        .filter(|instruction| instruction.program_id_index == noop_index)
+    {
+        let data = from_base58(&instruction.data)?;
+        let Ok(dispatched) = DispatchedMessageAccount::fetch(&mut data.as_slice()) else {
+            continue;
+        };
+        let dispatched = dispatched.into_inner();
+        let (pda, _bump) = Pubkey::find_program_address(
+            mailbox_dispatched_message_pda_seeds!(dispatched.unique_message_pubkey),
+            &mailbox,
+        );
+        if !index_of(&pda).is_some_and(|index| dispatch_accounts.contains(&index)) {
+            continue;
+        }
+
+        let message = HyperlaneMessage::read_from(&mut dispatched.encoded_message.as_slice())?;
+        let meta = LogMeta {
+            address: mailbox.to_bytes().into(),
+            block_number: dispatched.slot,
+            block_hash: H256::zero(),
+            transaction_id: transaction_hash,
+            transaction_index: 0,
+            log_index: U256::from(dispatched.nonce),
+        };
+        messages.push((message.into(), meta));
+    }
+    Ok(messages)
+}
+
+/// Decodes a dispatched message PDA from a single `getProgramAccounts` entry
+/// (base64 encoding) of `mailbox`. Returns `None` for other mailbox accounts.
+///
+/// The dispatched message PDAs are what the mailbox indexer reads, so unlike
+/// transaction dumps this works for every deployment.
+pub fn decode_archived_dispatch_account(
+    account: &str,
+    mailbox: H256,
+) -> ChainResult<Option<(Indexed<HyperlaneMessage>, LogMeta)>> {
+    let mailbox = Pubkey::new_from_array(mailbox.0);
+    let keyed_account = serde_json::from_str::<RpcKeyedAccount>(account)
+        .map_err(ChainCommunicationError::from_other)?;
+    let pubkey =
+        Pubkey::from_str(&keyed_account.pubkey).map_err(ChainCommunicationError::from_other)?;
+    let account = keyed_account
+        .account
+        .decode::<Account>()
+        .ok_or_else(|| ChainCommunicationError::from_other_str("Unsupported account encoding"))?;
+    if account.owner != mailbox {
+        return Ok(None);
+    }
+    let Ok(dispatched) = DispatchedMessageAccount::fetch(&mut account.data.as_slice()) else {
+        return Ok(None);
+    };
+    let dispatched = dispatched.into_inner();
+    let (pda, _bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(dispatched.unique_message_pubkey),
+        &mailbox,
+    );
+    if pda != pubkey {
+        return Ok(None);
+    }
+
+    let message = HyperlaneMessage::read_from(&mut dispatched.encoded_message.as_slice())?;
+    let meta = LogMeta {
+        address: mailbox.to_bytes().into(),
+        block_number: dispatched.slot,
+        block_hash: H256::zero(),
+        transaction_id: H512::zero(),
+        transaction_index: 0,
+        log_index: U256::from(dispatched.nonce),
+    };
+    Ok(Some((message.into(), meta)))
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_sealevel_mailbox::{
+        accounts::DispatchedMessage,
+        instruction::{Instruction as MailboxInstruction, OutboxDispatch},
+    };
+    use solana_account_decoder::{UiAccount, UiAccountEncoding};
+    use solana_sdk::bs58;
+
+    use account_utils::SizedData as _;
+    use hyperlane_core::Encode as _;
+
+    use super::*;
+
+    const MAILBOX: &str = "E588QtVUvresuXq2KoNEwAmoifCzYGpRBdHByN9KQMbi";
+
+    fn dispatched_message(nonce: u32) -> (Pubkey, HyperlaneMessage, Vec<u8>) {
+        let mailbox = Pubkey::from_str(MAILBOX).unwrap();
+        let unique_message_pubkey = Pubkey::new_unique();
+        let message = HyperlaneMessage {
+            nonce,
+            body: vec![1, 2, 3],
+            ..Default::default()
+        };
+        let account = DispatchedMessageAccount::from(DispatchedMessage::new(
+            nonce,
+            100,
+            unique_message_pubkey,
+            message.to_vec(),
+        ));
+        let mut data = vec![0; account.size()];
+        account.store_in_slice(&mut data).unwrap();
+        let (pda, _) = Pubkey::find_program_address(
+            mailbox_dispatched_message_pda_seeds!(unique_message_pubkey),
+            &mailbox,
+        );
+        (pda, message, data)
+    }
+
+    fn dispatch_transaction(pda: &Pubkey, noop_data: &[u8]) -> String {
+        let dispatch = MailboxInstruction::OutboxDispatch(OutboxDispatch {
+            sender: Pubkey::new_unique(),
+            destination_domain: 1,
+            recipient: H256::zero(),
+            message_body: vec![],
+        })
+        .into_instruction_data()
+        .unwrap();
+        serde_json::json!({
+            "slot": 100,
+            "blockTime": null,
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": [],
+                "postBalances": [],
+                "innerInstructions": [{
+                    "index": 0,
+                    "instructions": [{
+                        "programIdIndex": 3,
+                        "accounts": [],
+                        "data": bs58::encode(noop_data).into_string(),
+                    }],
+                }],
+                "logMessages": [],
+            },
+            "transaction": {
+                "signatures": [bs58::encode([7u8; 64]).into_string()],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 2,
+                    },
+                    "accountKeys": [
+                        Pubkey::new_unique().to_string(),
+                        pda.to_string(),
+                        MAILBOX,
+                        SPL_NOOP,
+                    ],
+                    "recentBlockhash": Pubkey::new_unique().to_string(),
+                    "instructions": [{
+                        "programIdIndex": 2,
+                        "accounts": [0, 1, 3],
+                        "data": bs58::encode(dispatch).into_string(),
+                    }],
+                },
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_decode_dispatch_transaction() {
+        let (pda, message, data) = dispatched_message(42);
+        let transaction = dispatch_transaction(&pda, &data);
+
+        let mailbox = H256::from(Pubkey::from_str(MAILBOX).unwrap().to_bytes());
+        let messages = decode_archived_dispatch_transaction(&transaction, mailbox).unwrap();
+
+        assert_eq!(messages.len(), 1);
+        let (indexed, meta) = &messages[0];
+        assert_eq!(indexed.inner(), &message);
+        assert_eq!(indexed.sequence, Some(42));
+        assert_eq!(meta.block_number, 100);
+        assert_eq!(meta.transaction_id, H512::from([7u8; 64]));
+    }
+
+    #[test]
+    fn test_decode_dispatch_transaction_ignores_unrelated_noop_logs() {
+        // the noop log is for a PDA that the dispatch instruction didn't touch
+        let (pda, _, _) = dispatched_message(1);
+        let (_, _, forged) = dispatched_message(2);
+        let transaction = dispatch_transaction(&pda, &forged);
+
+        let mailbox = H256::from(Pubkey::from_str(MAILBOX).unwrap().to_bytes());
+        let messages = decode_archived_dispatch_transaction(&transaction, mailbox).unwrap();
+
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn test_decode_dispatch_account() {
+        let (pda, message, data) = dispatched_message(7);
+        let mailbox = Pubkey::from_str(MAILBOX).unwrap();
+        let account = Account {
+            lamports: 1,
+            data,
+            owner: mailbox,
+            executable: false,
+            rent_epoch: 0,
+        };
+        let keyed_account = RpcKeyedAccount {
+            pubkey: pda.to_string(),
+            account: UiAccount::encode(&pda, &account, UiAccountEncoding::Base64, None, None),
+        };
+        let json = serde_json::to_string(&keyed_account).unwrap();
+
+        let (indexed, meta) = decode_archived_dispatch_account(&json, mailbox.to_bytes().into())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(indexed.inner(), &message);
+        assert_eq!(indexed.sequence, Some(7));
+        assert_eq!(meta.log_index, U256::from(7));
+    }
+}
@@ -6,18 +6,23 @@ use hyperlane_sealevel_igp::{
     accounts::{GasPaymentAccount, ProgramDataAccount},
     igp_gas_payment_pda_seeds, igp_program_data_pda_seeds,
 };
-use solana_sdk::{account::Account, clock::Slot, pubkey::Pubkey};
+use solana_sdk::{
+    account::Account, clock::Slot, commitment_config::CommitmentConfig, pubkey::Pubkey,
+};
 use tracing::{info, instrument};
 
 use hyperlane_core::{
     config::StrOrIntParseError, ChainCommunicationError, ChainResult, ContractLocator,
     HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
-    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256, H512, U256,
+    InterchainGasPaymaster, InterchainGasPayment, LogMeta, ReorgPeriod, SequenceAwareIndexer, H256,
+    H512, U256,
 };
 
 use crate::account::{search_accounts_by_discriminator, search_and_validate_account};
 use crate::log_meta_composer::{is_interchain_payment_instruction, LogMetaComposer};
-use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
+use crate::{
+    ConnectionConf, SealevelProvider, SealevelReorgPeriod, SealevelRpcClient, SealevelRpcMetrics,
+};
 
 /// The offset to get the `unique_gas_payment_pubkey` field from the serialized GasPaymentData.
 /// The account data includes prefixes that are accounted for here: a 1 byte initialized flag
@@ -39,8 +44,9 @@ impl SealevelInterchainGasPaymaster {
     pub async fn new(
         conf: &ConnectionConf,
         igp_account_locator: &ContractLocator<'_>,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> ChainResult<Self> {
-        let provider = SealevelProvider::new(igp_account_locator.domain.clone(), conf);
+        let provider = SealevelProvider::new(igp_account_locator.domain.clone(), conf, rpc_metrics);
         let program_id =
             Self::determine_igp_program_id(provider.rpc(), &igp_account_locator.address).await?;
         let (data_pda_pubkey, _) =
@@ -93,6 +99,7 @@ pub struct SealevelInterchainGasPaymasterIndexer {
     igp: SealevelInterchainGasPaymaster,
     log_meta_composer: LogMetaComposer,
     advanced_log_meta: bool,
+    commitment: CommitmentConfig,
 }
 
 /// IGP payment data on Sealevel
@@ -108,12 +115,15 @@ impl SealevelInterchainGasPaymasterIndexer {
     pub async fn new(
         conf: &ConnectionConf,
         igp_account_locator: ContractLocator<'_>,
+        reorg_period: &ReorgPeriod,
         advanced_log_meta: bool,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> ChainResult<Self> {
-        // Set the `processed` commitment at rpc level
-        let rpc_client = SealevelRpcClient::new(conf.url.to_string());
+        let commitment = SealevelReorgPeriod::try_from(reorg_period)?.commitment();
 
-        let igp = SealevelInterchainGasPaymaster::new(conf, &igp_account_locator).await?;
+        let igp =
+            SealevelInterchainGasPaymaster::new(conf, &igp_account_locator, rpc_metrics).await?;
+        let rpc_client = igp.provider.rpc().clone();
 
         let log_meta_composer = LogMetaComposer::new(
             igp.program_id,
@@ -126,6 +136,7 @@ impl SealevelInterchainGasPaymasterIndexer {
             igp,
             log_meta_composer,
             advanced_log_meta,
+            commitment,
         })
     }
 
@@ -156,7 +167,7 @@ impl SealevelInterchainGasPaymasterIndexer {
         // Now that we have the valid gas payment PDA pubkey, we can get the full account data.
         let account = self
             .rpc_client
-            .get_account_with_finalized_commitment(&valid_payment_pda_pubkey)
+            .get_account_with_commitment(&valid_payment_pda_pubkey, self.commitment)
             .await?;
         let gas_payment_account = GasPaymentAccount::fetch(&mut account.data.as_ref())
             .map_err(ChainCommunicationError::from_other)?
@@ -222,7 +233,10 @@ impl SealevelInterchainGasPaymasterIndexer {
         payment_pda_pubkey: &Pubkey,
         payment_pda_slot: &Slot,
     ) -> ChainResult<LogMeta> {
-        let block = self.rpc_client.get_block(*payment_pda_slot).await?;
+        let block = self
+            .rpc_client
+            .get_block_with_commitment(*payment_pda_slot, self.commitment)
+            .await?;
 
         self.log_meta_composer
             .log_meta(block, log_index, payment_pda_pubkey, payment_pda_slot)
@@ -281,7 +295,7 @@ impl SequenceAwareIndexer<InterchainGasPayment> for SealevelInterchainGasPaymast
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
         let program_data_account = self
             .rpc_client
-            .get_account_with_finalized_commitment(&self.igp.data_pda_pubkey)
+            .get_account_with_commitment(&self.igp.data_pda_pubkey, self.commitment)
             .await?;
         let program_data = ProgramDataAccount::fetch(&mut program_data_account.data.as_ref())
             .map_err(ChainCommunicationError::from_other)?
@@ -290,7 +304,12 @@ impl SequenceAwareIndexer<InterchainGasPayment> for SealevelInterchainGasPaymast
             .payment_count
             .try_into()
             .map_err(StrOrIntParseError::from)?;
-        let tip = self.igp.provider.rpc().get_slot().await?;
+        let tip = self
+            .igp
+            .provider
+            .rpc()
+            .get_slot_with_commitment(self.commitment)
+            .await?;
         Ok((Some(payment_count), tip))
     }
 }
@@ -10,7 +10,9 @@ use hyperlane_core::{
 use hyperlane_sealevel_interchain_security_module_interface::InterchainSecurityModuleInstruction;
 use serializable_account_meta::SimulationReturnData;
 
-use crate::{ConnectionConf, SealevelKeypair, SealevelProvider, SealevelRpcClient};
+use crate::{
+    ConnectionConf, SealevelKeypair, SealevelProvider, SealevelRpcClient, SealevelRpcMetrics,
+};
 
 /// A reference to an InterchainSecurityModule contract on some Sealevel chain
 #[derive(Debug)]
@@ -26,8 +28,9 @@ impl SealevelInterchainSecurityModule {
         conf: &ConnectionConf,
         locator: ContractLocator,
         payer: Option<SealevelKeypair>,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> Self {
-        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let provider = SealevelProvider::new(locator.domain.clone(), conf, rpc_metrics);
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
         Self {
             payer,
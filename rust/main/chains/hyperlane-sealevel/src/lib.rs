@@ -5,18 +5,22 @@
 #![deny(warnings)]
 
 pub use crate::multisig_ism::*;
+pub use archive::*;
 pub use interchain_gas::*;
 pub use interchain_security_module::*;
 pub use keypair::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
 pub use provider::*;
+pub use reorg_period::*;
 pub(crate) use rpc::SealevelRpcClient;
-pub use solana_sdk::signer::keypair::Keypair;
+pub use rpc::SealevelRpcMetrics;
+pub use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair};
 pub use trait_builder::*;
 pub use validator_announce::*;
 
 mod account;
+mod archive;
 mod error;
 mod interchain_gas;
 mod interchain_security_module;
@@ -27,6 +31,7 @@ mod merkle_tree_hook;
 mod multisig_ism;
 mod priority_fee;
 mod provider;
+mod reorg_period;
 mod rpc;
 mod trait_builder;
 mod tx_submitter;
@@ -40,7 +40,7 @@ use hyperlane_core::{
     ReorgPeriod, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, H512, U256,
 };
 
-use crate::tx_submitter::TransactionSubmitter;
+use crate::tx_submitter::{SubmittedTransaction, TransactionSubmitter};
 use crate::{
     account::{search_accounts_by_discriminator, search_and_validate_account},
     priority_fee::PriorityFeeOracle,
@@ -51,10 +51,16 @@ use crate::{
     },
     SealevelKeypair,
 };
-use crate::{ConnectionConf, SealevelProvider, SealevelRpcClient};
+use crate::{
+    ConnectionConf, SealevelProvider, SealevelReorgPeriod, SealevelRpcClient, SealevelRpcMetrics,
+};
 
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
-const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
+pub(crate) const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
+
+/// How many times a process transaction is rebuilt and resubmitted, with an
+/// escalated priority fee, after its blockhash expired without it landing.
+const MAX_PROCESS_RESUBMISSIONS: u32 = 2;
 
 // Earlier versions of collateral warp routes were deployed off a version where the mint
 // was requested as a writeable account for handle instruction. This is not necessary,
@@ -85,6 +91,7 @@ pub struct SealevelMailbox {
     payer: Option<SealevelKeypair>,
     priority_fee_oracle: Box<dyn PriorityFeeOracle>,
     tx_submitter: Box<dyn TransactionSubmitter>,
+    address_lookup_tables: Vec<Pubkey>,
 }
 
 impl SealevelMailbox {
@@ -93,8 +100,9 @@ impl SealevelMailbox {
         conf: &ConnectionConf,
         locator: ContractLocator,
         payer: Option<SealevelKeypair>,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> ChainResult<Self> {
-        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let provider = SealevelProvider::new(locator.domain.clone(), conf, rpc_metrics);
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
         let domain = locator.domain.id();
         let inbox = Pubkey::find_program_address(mailbox_inbox_pda_seeds!(), &program_id);
@@ -110,10 +118,13 @@ impl SealevelMailbox {
             inbox,
             outbox,
             payer,
-            priority_fee_oracle: conf.priority_fee_oracle.create_oracle(),
-            tx_submitter: conf
-                .transaction_submitter
-                .create_submitter(provider.rpc().url()),
+            priority_fee_oracle: conf.priority_fee_oracle.create_oracle(provider.rpc()),
+            tx_submitter: conf.transaction_submitter.create_submitter(
+                provider.rpc(),
+                locator.domain,
+                rpc_metrics,
+            )?,
+            address_lookup_tables: conf.address_lookup_tables.clone(),
             provider,
         })
     }
@@ -372,9 +383,17 @@ impl SealevelMailbox {
     }
 
     async fn get_inbox(&self) -> ChainResult<Box<Inbox>> {
+        self.get_inbox_with_commitment(CommitmentConfig::finalized())
+            .await
+    }
+
+    async fn get_inbox_with_commitment(
+        &self,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<Box<Inbox>> {
         let account = self
             .rpc()
-            .get_account_with_finalized_commitment(&self.inbox.0)
+            .get_account_with_commitment(&self.inbox.0, commitment)
             .await?;
         let inbox = InboxAccount::fetch(&mut account.data.as_ref())
             .map_err(ChainCommunicationError::from_other)?
@@ -474,33 +493,65 @@ impl Mailbox for SealevelMailbox {
 
         let process_instruction = self.get_process_instruction(message, metadata).await?;
 
-        let tx = self
-            .provider
-            .rpc()
-            .build_estimated_tx_for_instruction(
-                process_instruction,
-                self.get_payer()?,
-                &*self.tx_submitter,
-                &*self.priority_fee_oracle,
-            )
-            .await?;
+        let rpc = self.tx_submitter.rpc_client().unwrap_or_else(|| self.rpc());
 
-        tracing::info!(?tx, "Created sealevel transaction to process message");
+        let mut resubmission = 0;
+        let (tx, signature, path, send_instant) = loop {
+            let tx = self
+                .provider
+                .rpc()
+                .build_estimated_tx_for_instruction(
+                    process_instruction.clone(),
+                    self.get_payer()?,
+                    &*self.tx_submitter,
+                    &*self.priority_fee_oracle,
+                    &self.address_lookup_tables,
+                    resubmission,
+                )
+                .await?;
 
-        let signature = self.tx_submitter.send_transaction(&tx, true).await?;
+            tracing::info!(
+                ?tx,
+                resubmission,
+                "Created sealevel transaction to process message"
+            );
 
-        tracing::info!(?tx, ?signature, "Sealevel transaction sent");
+            let SubmittedTransaction { signature, path } = self
+                .tx_submitter
+                .send_transaction(&tx, self.get_payer()?, true)
+                .await?;
 
-        let send_instant = std::time::Instant::now();
+            tracing::info!(?tx, ?signature, ?path, "Sealevel transaction sent");
 
-        let rpc = self.tx_submitter.rpc_client().unwrap_or_else(|| self.rpc());
+            let send_instant = std::time::Instant::now();
 
-        // Wait for the transaction to be confirmed.
-        rpc.wait_for_transaction_confirmation(&tx).await?;
+            // Wait for the transaction to be confirmed.
+            if rpc.wait_for_transaction_confirmation(&tx).await? {
+                break (tx, signature, path, send_instant);
+            }
+
+            if resubmission >= MAX_PROCESS_RESUBMISSIONS {
+                return Err(ChainCommunicationError::from_other(
+                    solana_client::rpc_request::RpcError::ForUser(
+                        "unable to confirm transaction. \
+                        This can happen in situations such as transaction expiration \
+                        and insufficient fee-payer funds"
+                            .to_string(),
+                    ),
+                ));
+            }
+
+            warn!(
+                ?signature,
+                resubmission,
+                "Sealevel transaction expired before landing, resubmitting with a higher priority fee"
+            );
+            resubmission += 1;
+        };
 
         // We expect time_to_confirm to fluctuate depending on the commitment level when submitting the
         // tx, but still use it as a proxy for tx latency to help debug.
-        tracing::info!(?tx, ?signature, time_to_confirm=?send_instant.elapsed(), "Sealevel transaction confirmed");
+        tracing::info!(?tx, ?signature, ?path, time_to_confirm=?send_instant.elapsed(), "Sealevel transaction confirmed");
 
         // TODO: not sure if this actually checks if the transaction was executed / reverted?
         // Confirm the transaction.
@@ -540,6 +591,8 @@ impl Mailbox for SealevelMailbox {
                 self.get_payer()?,
                 &*self.tx_submitter,
                 &*self.priority_fee_oracle,
+                &self.address_lookup_tables,
+                0,
             )
             .await?;
 
@@ -567,6 +620,8 @@ pub struct SealevelMailboxIndexer {
     dispatch_message_log_meta_composer: LogMetaComposer,
     delivery_message_log_meta_composer: LogMetaComposer,
     advanced_log_meta: bool,
+    reorg_period: ReorgPeriod,
+    commitment: CommitmentConfig,
 }
 
 impl SealevelMailboxIndexer {
@@ -574,10 +629,13 @@ impl SealevelMailboxIndexer {
     pub fn new(
         conf: &ConnectionConf,
         locator: ContractLocator,
+        reorg_period: &ReorgPeriod,
         advanced_log_meta: bool,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> ChainResult<Self> {
+        let commitment = SealevelReorgPeriod::try_from(reorg_period)?.commitment();
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
-        let mailbox = SealevelMailbox::new(conf, locator, None)?;
+        let mailbox = SealevelMailbox::new(conf, locator, None, rpc_metrics)?;
 
         let dispatch_message_log_meta_composer = LogMetaComposer::new(
             mailbox.program_id,
@@ -597,6 +655,8 @@ impl SealevelMailboxIndexer {
             dispatch_message_log_meta_composer,
             delivery_message_log_meta_composer,
             advanced_log_meta,
+            reorg_period: reorg_period.clone(),
+            commitment,
         })
     }
 
@@ -628,7 +688,7 @@ impl SealevelMailboxIndexer {
         // Now that we have the valid message storage PDA pubkey, we can get the full account data.
         let account = self
             .rpc()
-            .get_account_with_finalized_commitment(&valid_message_storage_pda_pubkey)
+            .get_account_with_commitment(&valid_message_storage_pda_pubkey, self.commitment)
             .await?;
         let dispatched_message_account =
             DispatchedMessageAccount::fetch(&mut account.data.as_ref())
@@ -684,7 +744,7 @@ impl SealevelMailboxIndexer {
             .mailbox
             .provider
             .rpc()
-            .get_block(*message_account_slot)
+            .get_block_with_commitment(*message_account_slot, self.commitment)
             .await?;
 
         self.dispatch_message_log_meta_composer
@@ -724,7 +784,7 @@ impl SealevelMailboxIndexer {
         // we can get the full account data.
         let account = self
             .rpc()
-            .get_account_with_finalized_commitment(&valid_message_storage_pda_pubkey)
+            .get_account_with_commitment(&valid_message_storage_pda_pubkey, self.commitment)
             .await?;
         let delivered_message_account = ProcessedMessageAccount::fetch(&mut account.data.as_ref())
             .map_err(ChainCommunicationError::from_other)?
@@ -779,7 +839,7 @@ impl SealevelMailboxIndexer {
             .mailbox
             .provider
             .rpc()
-            .get_block(*message_account_slot)
+            .get_block_with_commitment(*message_account_slot, self.commitment)
             .await?;
 
         self.delivery_message_log_meta_composer
@@ -824,9 +884,14 @@ impl Indexer<HyperlaneMessage> for SealevelMailboxIndexer {
 impl SequenceAwareIndexer<HyperlaneMessage> for SealevelMailboxIndexer {
     #[instrument(err, skip(self))]
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        let tip = self.mailbox.provider.rpc().get_slot().await?;
+        let tip = self
+            .mailbox
+            .provider
+            .rpc()
+            .get_slot_with_commitment(self.commitment)
+            .await?;
         // TODO: need to make sure the call and tip are at the same height?
-        let count = Mailbox::count(&self.mailbox, &ReorgPeriod::None).await?;
+        let count = Mailbox::count(&self.mailbox, &self.reorg_period).await?;
         Ok((Some(count), tip))
     }
 }
@@ -861,13 +926,21 @@ impl Indexer<H256> for SealevelMailboxIndexer {
 #[async_trait]
 impl SequenceAwareIndexer<H256> for SealevelMailboxIndexer {
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        let inbox = self.mailbox.get_inbox().await?;
+        let inbox = self
+            .mailbox
+            .get_inbox_with_commitment(self.commitment)
+            .await?;
         let sequence = inbox
             .processed_count
             .try_into()
             .map_err(StrOrIntParseError::from)?;
 
-        let tip = self.mailbox.provider.rpc().get_slot().await?;
+        let tip = self
+            .mailbox
+            .provider
+            .rpc()
+            .get_slot_with_commitment(self.commitment)
+            .await?;
 
         Ok((Some(sequence), tip))
     }
@@ -10,21 +10,18 @@ use hyperlane_core::{
 use hyperlane_sealevel_mailbox::accounts::OutboxAccount;
 use tracing::instrument;
 
-use crate::{SealevelMailbox, SealevelMailboxIndexer};
+use crate::{SealevelMailbox, SealevelMailboxIndexer, SealevelReorgPeriod};
 
 #[async_trait]
 impl MerkleTreeHook for SealevelMailbox {
     #[instrument(err, ret, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn tree(&self, reorg_period: &ReorgPeriod) -> ChainResult<IncrementalMerkle> {
-        assert!(
-            reorg_period.is_none(),
-            "Sealevel does not support querying point-in-time"
-        );
+        let reorg_period = SealevelReorgPeriod::try_from(reorg_period)?;
 
         let outbox_account = self
             .rpc()
-            .get_account_with_finalized_commitment(&self.outbox.0)
+            .get_account_with_commitment(&self.outbox.0, reorg_period.commitment())
             .await?;
         let outbox = OutboxAccount::fetch(&mut outbox_account.data.as_ref())
             .map_err(ChainCommunicationError::from_other)?
@@ -36,11 +33,6 @@ impl MerkleTreeHook for SealevelMailbox {
     #[instrument(err, ret, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn latest_checkpoint(&self, reorg_period: &ReorgPeriod) -> ChainResult<Checkpoint> {
-        assert!(
-            reorg_period.is_none(),
-            "Sealevel does not support querying point-in-time"
-        );
-
         let tree = self.tree(reorg_period).await?;
 
         let root = tree.root();
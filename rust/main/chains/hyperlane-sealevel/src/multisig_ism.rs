@@ -10,7 +10,9 @@ use solana_sdk::{
     pubkey::Pubkey,
 };
 
-use crate::{ConnectionConf, SealevelKeypair, SealevelProvider, SealevelRpcClient};
+use crate::{
+    ConnectionConf, SealevelKeypair, SealevelProvider, SealevelRpcClient, SealevelRpcMetrics,
+};
 
 use multisig_ism::interface::{
     MultisigIsmInstruction, VALIDATORS_AND_THRESHOLD_ACCOUNT_METAS_PDA_SEEDS,
@@ -31,8 +33,9 @@ impl SealevelMultisigIsm {
         conf: &ConnectionConf,
         locator: ContractLocator,
         payer: Option<SealevelKeypair>,
+        rpc_metrics: &SealevelRpcMetrics,
     ) -> Self {
-        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let provider = SealevelProvider::new(locator.domain.clone(), conf, rpc_metrics);
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
 
         Self {
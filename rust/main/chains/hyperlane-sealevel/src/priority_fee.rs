@@ -3,15 +3,28 @@ use derive_new::new;
 use hyperlane_core::{ChainCommunicationError, ChainResult};
 use reqwest::Client;
 use serde::Deserialize;
-use solana_sdk::{bs58, transaction::Transaction};
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount, bs58, pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
 
-use crate::{HeliusPriorityFeeLevel, HeliusPriorityFeeOracleConfig};
+use crate::{
+    HeliusPriorityFeeLevel, HeliusPriorityFeeOracleConfig, RecentFeesPriorityFeeOracleConfig,
+    SealevelRpcClient,
+};
 
 /// A trait for fetching the priority fee for a transaction.
 #[async_trait]
 pub trait PriorityFeeOracle: Send + Sync {
     /// Fetch the priority fee in microlamports for a transaction.
-    async fn get_priority_fee(&self, transaction: &Transaction) -> ChainResult<u64>;
+    async fn get_priority_fee(&self, transaction: &VersionedTransaction) -> ChainResult<u64>;
+
+    /// The priority fee to use when resubmitting a transaction whose previous
+    /// submission expired without landing. `resubmission` is 0 for the first
+    /// submission. By default the fee is not changed.
+    fn escalate_priority_fee(&self, priority_fee: u64, _resubmission: u32) -> u64 {
+        priority_fee
+    }
 }
 
 /// A priority fee oracle that returns a constant fee.
@@ -22,7 +35,7 @@ pub struct ConstantPriorityFeeOracle {
 
 #[async_trait]
 impl PriorityFeeOracle for ConstantPriorityFeeOracle {
-    async fn get_priority_fee(&self, _transaction: &Transaction) -> ChainResult<u64> {
+    async fn get_priority_fee(&self, _transaction: &VersionedTransaction) -> ChainResult<u64> {
         Ok(self.fee)
     }
 }
@@ -61,7 +74,7 @@ impl HeliusPriorityFeeOracle {
 
 #[async_trait]
 impl PriorityFeeOracle for HeliusPriorityFeeOracle {
-    async fn get_priority_fee(&self, transaction: &Transaction) -> ChainResult<u64> {
+    async fn get_priority_fee(&self, transaction: &VersionedTransaction) -> ChainResult<u64> {
         let base58_tx = bs58::encode(
             bincode::serialize(transaction).map_err(ChainCommunicationError::from_other)?,
         )
@@ -100,6 +113,159 @@ impl PriorityFeeOracle for HeliusPriorityFeeOracle {
     }
 }
 
+/// A source of the prioritization fees paid by recently landed transactions.
+#[async_trait]
+pub trait RecentPrioritizationFees: Send + Sync {
+    /// Fetch the prioritization fees, in microlamports, of recent slots for
+    /// transactions that write to all of `accounts`.
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ChainResult<Vec<u64>>;
+
+    /// Fetch the address lookup tables with the given addresses, to resolve
+    /// the accounts a transaction loads from them.
+    async fn get_address_lookup_table_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ChainResult<Vec<AddressLookupTableAccount>>;
+}
+
+#[async_trait]
+impl RecentPrioritizationFees for SealevelRpcClient {
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> ChainResult<Vec<u64>> {
+        SealevelRpcClient::get_recent_prioritization_fees(self, accounts).await
+    }
+
+    async fn get_address_lookup_table_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ChainResult<Vec<AddressLookupTableAccount>> {
+        SealevelRpcClient::get_address_lookup_table_accounts(self, addresses).await
+    }
+}
+
+/// A priority fee oracle that uses the standard `getRecentPrioritizationFees`
+/// RPC method, so it works with any RPC provider.
+///
+/// Fees are queried for the accounts locked for writing by the transaction
+/// (e.g. the mailbox inbox and the recipient's PDAs), as these are the ones the
+/// transaction competes for.
+#[derive(Debug, Clone)]
+pub struct RecentFeesPriorityFeeOracle<S> {
+    source: S,
+    config: RecentFeesPriorityFeeOracleConfig,
+}
+
+impl<S> RecentFeesPriorityFeeOracle<S> {
+    pub fn new(source: S, config: RecentFeesPriorityFeeOracleConfig) -> Self {
+        Self { source, config }
+    }
+
+    /// Applies the configured floor and cap to a fee.
+    fn clamp(&self, fee: u64) -> u64 {
+        let fee = fee.max(self.config.floor);
+        match self.config.cap {
+            Some(cap) => fee.min(cap),
+            None => fee,
+        }
+    }
+}
+
+#[async_trait]
+impl<S: RecentPrioritizationFees> PriorityFeeOracle for RecentFeesPriorityFeeOracle<S> {
+    async fn get_priority_fee(&self, transaction: &VersionedTransaction) -> ChainResult<u64> {
+        let accounts = self.writable_non_signer_accounts(transaction).await;
+        let fees = self
+            .source
+            .get_recent_prioritization_fees(&accounts)
+            .await?;
+        let fee = self.clamp(percentile(fees, self.config.percentile));
+
+        tracing::debug!(
+            fee,
+            ?accounts,
+            percentile = self.config.percentile,
+            "Fetched priority fee from recent prioritization fees"
+        );
+
+        Ok(fee)
+    }
+
+    fn escalate_priority_fee(&self, priority_fee: u64, resubmission: u32) -> u64 {
+        let mut fee = priority_fee as u128;
+        for _ in 0..resubmission {
+            if fee > u64::MAX as u128 {
+                break;
+            }
+            fee = fee * (100 + self.config.escalation_percent as u128) / 100;
+        }
+        self.clamp(fee.try_into().unwrap_or(u64::MAX))
+    }
+}
+
+impl<S: RecentPrioritizationFees> RecentFeesPriorityFeeOracle<S> {
+    /// The accounts a transaction locks for writing, excluding signers. The
+    /// fee payer is writable, but its fees are not related to the contention
+    /// the transaction faces, so it is skipped.
+    ///
+    /// Accounts loaded from address lookup tables are resolved by fetching
+    /// the tables. If that fails, only the static account keys are used.
+    async fn writable_non_signer_accounts(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Vec<Pubkey> {
+        let message = &transaction.message;
+        let mut accounts: Vec<Pubkey> = message
+            .static_account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| message.is_maybe_writable(*i) && !message.is_signer(*i))
+            .map(|(_, key)| *key)
+            .collect();
+
+        let lookups = message.address_table_lookups().unwrap_or_default();
+        if lookups.is_empty() {
+            return accounts;
+        }
+        let table_addresses: Vec<Pubkey> =
+            lookups.iter().map(|lookup| lookup.account_key).collect();
+        let tables = match self
+            .source
+            .get_address_lookup_table_accounts(&table_addresses)
+            .await
+        {
+            Ok(tables) => tables,
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    ?table_addresses,
+                    "Failed to fetch address lookup tables, using static accounts only"
+                );
+                return accounts;
+            }
+        };
+        // Accounts loaded from lookup tables can't be signers
+        for (lookup, table) in lookups.iter().zip(&tables) {
+            accounts.extend(
+                lookup
+                    .writable_indexes
+                    .iter()
+                    .filter_map(|index| table.addresses.get(*index as usize)),
+            );
+        }
+        accounts
+    }
+}
+
+/// The nearest-rank `percentile` of `fees`, or 0 if there are none.
+fn percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let percentile = percentile.min(100) as usize;
+    let rank = (percentile * fees.len()).div_ceil(100).max(1);
+    fees[rank - 1]
+}
+
 /// The result of a JSON-RPC request to the Helius API.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -120,14 +286,250 @@ struct GetPriorityFeeEstimateResult {
 
 #[cfg(test)]
 mod test {
-    use solana_sdk::{bs58, transaction::Transaction};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use hyperlane_core::ChainCommunicationError;
+    use hyperlane_core::ChainResult;
+    use solana_sdk::{
+        address_lookup_table_account::AddressLookupTableAccount,
+        bs58,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::{v0, Message, VersionedMessage},
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::{Transaction, VersionedTransaction},
+    };
 
     use crate::{
         priority_fee::{HeliusPriorityFeeOracle, PriorityFeeOracle},
-        HeliusPriorityFeeLevel, HeliusPriorityFeeOracleConfig,
+        HeliusPriorityFeeLevel, HeliusPriorityFeeOracleConfig, RecentFeesPriorityFeeOracleConfig,
+    };
+
+    use super::{
+        GetPriorityFeeEstimateResult, JsonRpcResult, RecentFeesPriorityFeeOracle,
+        RecentPrioritizationFees,
     };
 
-    use super::{GetPriorityFeeEstimateResult, JsonRpcResult};
+    /// Returns canned fees and lookup tables, and records the accounts it was
+    /// queried for.
+    #[derive(Default)]
+    struct MockRecentFees {
+        fees: Vec<u64>,
+        lookup_tables: Vec<AddressLookupTableAccount>,
+        queried_accounts: Mutex<Vec<Pubkey>>,
+    }
+
+    #[async_trait]
+    impl RecentPrioritizationFees for MockRecentFees {
+        async fn get_recent_prioritization_fees(
+            &self,
+            accounts: &[Pubkey],
+        ) -> ChainResult<Vec<u64>> {
+            *self.queried_accounts.lock().unwrap() = accounts.to_vec();
+            Ok(self.fees.clone())
+        }
+
+        async fn get_address_lookup_table_accounts(
+            &self,
+            addresses: &[Pubkey],
+        ) -> ChainResult<Vec<AddressLookupTableAccount>> {
+            addresses
+                .iter()
+                .map(|address| {
+                    self.lookup_tables
+                        .iter()
+                        .find(|table| table.key == *address)
+                        .cloned()
+                        .ok_or_else(|| {
+                            ChainCommunicationError::from_other_str("lookup table not found")
+                        })
+                })
+                .collect()
+        }
+    }
+
+    fn recent_fees_oracle(
+        fees: Vec<u64>,
+        config: RecentFeesPriorityFeeOracleConfig,
+    ) -> RecentFeesPriorityFeeOracle<MockRecentFees> {
+        RecentFeesPriorityFeeOracle::new(
+            MockRecentFees {
+                fees,
+                ..Default::default()
+            },
+            config,
+        )
+    }
+
+    fn process_like_instruction(
+        payer: Pubkey,
+        inbox: Pubkey,
+        recipient_pda: Pubkey,
+    ) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+                AccountMeta::new(inbox, false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new(recipient_pda, false),
+            ],
+        )
+    }
+
+    fn process_like_transaction(
+        payer: Pubkey,
+        inbox: Pubkey,
+        recipient_pda: Pubkey,
+    ) -> VersionedTransaction {
+        let instruction = process_like_instruction(payer, inbox, recipient_pda);
+        Transaction::new_unsigned(Message::new(&[instruction], Some(&payer))).into()
+    }
+
+    #[tokio::test]
+    async fn test_recent_fees_percentile() {
+        let fees = vec![500, 100, 0, 300, 200, 0, 400, 1000, 0, 600];
+        let tx = process_like_transaction(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        for (percentile, expected) in [(0, 0), (50, 200), (75, 500), (90, 600), (100, 1000)] {
+            let oracle = recent_fees_oracle(
+                fees.clone(),
+                RecentFeesPriorityFeeOracleConfig {
+                    percentile,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(
+                oracle.get_priority_fee(&tx).await.unwrap(),
+                expected,
+                "percentile {percentile}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recent_fees_floor_and_cap() {
+        let tx = process_like_transaction(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let config = RecentFeesPriorityFeeOracleConfig {
+            percentile: 50,
+            floor: 150,
+            cap: Some(10_000),
+            ..Default::default()
+        };
+
+        // No recent fees at all
+        let oracle = recent_fees_oracle(vec![], config.clone());
+        assert_eq!(oracle.get_priority_fee(&tx).await.unwrap(), 150);
+
+        // Below the floor
+        let oracle = recent_fees_oracle(vec![0, 10, 100], config.clone());
+        assert_eq!(oracle.get_priority_fee(&tx).await.unwrap(), 150);
+
+        // Above the cap
+        let oracle = recent_fees_oracle(vec![50_000, 80_000], config);
+        assert_eq!(oracle.get_priority_fee(&tx).await.unwrap(), 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_recent_fees_queries_writable_accounts() {
+        let payer = Pubkey::new_unique();
+        let inbox = Pubkey::new_unique();
+        let recipient_pda = Pubkey::new_unique();
+        let tx = process_like_transaction(payer, inbox, recipient_pda);
+
+        let oracle = recent_fees_oracle(vec![1], Default::default());
+        oracle.get_priority_fee(&tx).await.unwrap();
+
+        let mut queried = oracle.source.queried_accounts.lock().unwrap().clone();
+        queried.sort();
+        let mut expected = vec![inbox, recipient_pda];
+        expected.sort();
+        assert_eq!(queried, expected);
+    }
+
+    #[tokio::test]
+    async fn test_recent_fees_queries_writable_accounts_from_lookup_tables() {
+        let payer = Pubkey::new_unique();
+        let inbox = Pubkey::new_unique();
+        let recipient_pda = Pubkey::new_unique();
+        let instruction = process_like_instruction(payer, inbox, recipient_pda);
+        // Every account but the payer and the program can be loaded from the
+        // table, including readonly ones
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: instruction
+                .accounts
+                .iter()
+                .skip(1)
+                .map(|meta| meta.pubkey)
+                .collect(),
+        };
+        let message = v0::Message::try_compile(
+            &payer,
+            &[instruction],
+            &[lookup_table.clone()],
+            Hash::default(),
+        )
+        .unwrap();
+        assert!(!message.address_table_lookups.is_empty());
+        let tx = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+
+        let oracle = RecentFeesPriorityFeeOracle::new(
+            MockRecentFees {
+                fees: vec![1],
+                lookup_tables: vec![lookup_table],
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        oracle.get_priority_fee(&tx).await.unwrap();
+
+        let mut queried = oracle.source.queried_accounts.lock().unwrap().clone();
+        queried.sort();
+        let mut expected = vec![inbox, recipient_pda];
+        expected.sort();
+        assert_eq!(queried, expected);
+
+        // The static accounts are still used if the table can't be fetched
+        let oracle = recent_fees_oracle(vec![1], Default::default());
+        oracle.get_priority_fee(&tx).await.unwrap();
+        assert!(oracle.source.queried_accounts.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recent_fees_escalation() {
+        let oracle = recent_fees_oracle(
+            vec![],
+            RecentFeesPriorityFeeOracleConfig {
+                escalation_percent: 50,
+                cap: Some(2_000),
+                ..Default::default()
+            },
+        );
+        assert_eq!(oracle.escalate_priority_fee(1_000, 0), 1_000);
+        assert_eq!(oracle.escalate_priority_fee(1_000, 1), 1_500);
+        assert_eq!(oracle.escalate_priority_fee(1_000, 2), 2_000);
+        assert_eq!(oracle.escalate_priority_fee(u64::MAX, 100), 2_000);
+
+        // Other oracles don't escalate by default
+        let constant = super::ConstantPriorityFeeOracle::new(1_000);
+        assert_eq!(constant.escalate_priority_fee(1_000, 2), 1_000);
+    }
 
     #[tokio::test]
     async fn test_helius_get_priority_fee() {
@@ -149,7 +551,7 @@ mod test {
         let process_tx_bytes = bs58::decode(process_tx_base58).into_vec().unwrap();
         let transaction: Transaction = bincode::deserialize(&process_tx_bytes).unwrap();
 
-        oracle.get_priority_fee(&transaction).await.unwrap();
+        oracle.get_priority_fee(&transaction.into()).await.unwrap();
     }
 
     #[test]
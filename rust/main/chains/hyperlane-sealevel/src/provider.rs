@@ -19,7 +19,7 @@ use hyperlane_core::{
 
 use crate::error::HyperlaneSealevelError;
 use crate::utils::{decode_h256, decode_h512, decode_pubkey};
-use crate::{ConnectionConf, SealevelRpcClient};
+use crate::{ConnectionConf, SealevelRpcClient, SealevelRpcMetrics};
 
 lazy_static! {
     static ref NATIVE_PROGRAMS: HashSet<String> = HashSet::from([
@@ -44,9 +44,17 @@ pub struct SealevelProvider {
 
 impl SealevelProvider {
     /// Create a new Sealevel provider.
-    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
+    pub fn new(
+        domain: HyperlaneDomain,
+        conf: &ConnectionConf,
+        rpc_metrics: &SealevelRpcMetrics,
+    ) -> Self {
         // Set the `processed` commitment at rpc level
-        let rpc_client = Arc::new(SealevelRpcClient::new(conf.url.to_string()));
+        let rpc_client = Arc::new(SealevelRpcClient::new_with_fallback(
+            &conf.urls,
+            &domain,
+            rpc_metrics,
+        ));
         let native_token = conf.native_token.clone();
 
         Self {
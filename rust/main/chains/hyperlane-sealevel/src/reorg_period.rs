@@ -0,0 +1,73 @@
+use hyperlane_core::{ChainCommunicationError, ChainResult, ReorgPeriod};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+/// Sealevel reorg period, expressed as the commitment level that chain state
+/// is read at. Sealevel cannot read state at a point in time, so reorg
+/// periods given as a number of blocks are not supported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SealevelReorgPeriod(CommitmentConfig);
+
+impl SealevelReorgPeriod {
+    /// The commitment level state should be read at
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.0
+    }
+}
+
+impl Default for SealevelReorgPeriod {
+    fn default() -> Self {
+        Self(CommitmentConfig::finalized())
+    }
+}
+
+impl TryFrom<&ReorgPeriod> for SealevelReorgPeriod {
+    type Error = ChainCommunicationError;
+
+    fn try_from(value: &ReorgPeriod) -> Result<Self, Self::Error> {
+        let commitment = match value {
+            // Reads have always been made at the finalized commitment level
+            ReorgPeriod::None => CommitmentConfig::finalized(),
+            ReorgPeriod::Tag(tag) => match tag.as_str() {
+                "finalized" => CommitmentConfig::finalized(),
+                // `processed` is not offered, since blocks can't be fetched at that commitment
+                "confirmed" | "safe" => CommitmentConfig::confirmed(),
+                _ => return Err(ChainCommunicationError::InvalidReorgPeriod(value.clone())),
+            },
+            ReorgPeriod::Blocks(_) => {
+                return Err(ChainCommunicationError::InvalidReorgPeriod(value.clone()))
+            }
+        };
+        Ok(Self(commitment))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reorg_period_to_commitment() {
+        let commitment = |reorg_period: ReorgPeriod| {
+            SealevelReorgPeriod::try_from(&reorg_period).map(|p| p.commitment())
+        };
+
+        assert_eq!(
+            commitment(ReorgPeriod::None).unwrap(),
+            CommitmentConfig::finalized()
+        );
+        assert_eq!(
+            commitment(ReorgPeriod::Tag("finalized".into())).unwrap(),
+            CommitmentConfig::finalized()
+        );
+        assert_eq!(
+            commitment(ReorgPeriod::Tag("confirmed".into())).unwrap(),
+            CommitmentConfig::confirmed()
+        );
+        assert_eq!(
+            commitment(ReorgPeriod::Tag("safe".into())).unwrap(),
+            CommitmentConfig::confirmed()
+        );
+        assert!(commitment(ReorgPeriod::Tag("processed".into())).is_err());
+        assert!(commitment(ReorgPeriod::from_blocks(32)).is_err());
+    }
+}
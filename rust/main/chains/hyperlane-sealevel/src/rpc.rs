@@ -1,3 +1,7 @@
 pub use client::SealevelRpcClient;
+pub use metrics::SealevelRpcMetrics;
 
 mod client;
+mod fallback;
+mod lookup_tables;
+mod metrics;
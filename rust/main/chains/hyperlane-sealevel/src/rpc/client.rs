@@ -1,6 +1,7 @@
 use base64::Engine;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_address_lookup_table_program::state::AddressLookupTable;
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
     rpc_client::SerializableTransaction,
@@ -8,54 +9,104 @@ use solana_client::{
         RpcBlockConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
         RpcSimulateTransactionConfig, RpcTransactionConfig,
     },
+    rpc_request::RpcRequest,
     rpc_response::{Response, RpcSimulateTransactionResult},
 };
 use solana_program::clock::Slot;
 use solana_sdk::{
     account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
     hash::Hash,
     instruction::{AccountMeta, Instruction},
-    message::Message,
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
     signature::{Signature, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use solana_transaction_status::{
     EncodedConfirmedTransactionWithStatusMeta, TransactionStatus, UiConfirmedBlock,
     UiReturnDataEncoding, UiTransactionEncoding,
 };
 
-use hyperlane_core::{ChainCommunicationError, ChainResult, U256};
+use serde::Deserialize;
+use url::Url;
+
+use hyperlane_core::{
+    rpc_clients::FallbackProvider, ChainCommunicationError, ChainResult, HyperlaneDomain, U256,
+};
 
 use crate::{
     error::HyperlaneSealevelError, priority_fee::PriorityFeeOracle,
     tx_submitter::TransactionSubmitter, SealevelKeypair,
 };
 
+use super::{
+    fallback::SealevelRpcEndpoint, lookup_tables::AddressLookupTableCache, SealevelRpcMetrics,
+};
+
 const COMPUTE_UNIT_MULTIPLIER_NUMERATOR: u32 = 11;
 const COMPUTE_UNIT_MULTIPLIER_DENOMINATOR: u32 = 10;
 
 const PRIORITY_FEE_MULTIPLIER_NUMERATOR: u64 = 110;
 const PRIORITY_FEE_MULTIPLIER_DENOMINATOR: u64 = 100;
 
+/// An entry of the response to `getRecentPrioritizationFees`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    #[allow(dead_code)]
+    slot: Slot,
+    prioritization_fee: u64,
+}
+
 pub struct SealevelTxCostEstimate {
     compute_units: u32,
     compute_unit_price_micro_lamports: u64,
 }
 
-pub struct SealevelRpcClient(RpcClient);
+/// A Sealevel RPC client that spreads requests over one or more nodes.
+///
+/// Nodes are tried in order of priority, and nodes that stop producing new
+/// slots are moved to the back of the queue, see `FallbackProvider`.
+#[derive(Clone)]
+pub struct SealevelRpcClient {
+    fallback: FallbackProvider<SealevelRpcEndpoint, SealevelRpcEndpoint>,
+    address_lookup_tables: AddressLookupTableCache,
+}
 
 impl SealevelRpcClient {
     /// The max amount of compute units for a transaction.
     const MAX_COMPUTE_UNITS: u32 = 1_400_000;
 
+    /// Create a client for a single, uninstrumented node.
     pub fn new(rpc_endpoint: String) -> Self {
-        Self(RpcClient::new_with_commitment(
-            rpc_endpoint,
-            CommitmentConfig::processed(),
-        ))
+        Self::from_endpoints(vec![SealevelRpcEndpoint::from_client(
+            RpcClient::new_with_commitment(rpc_endpoint, CommitmentConfig::processed()),
+        )])
+    }
+
+    /// Create a client that falls back through `urls`, highest priority
+    /// first, reporting request metrics for each of them.
+    pub fn new_with_fallback(
+        urls: &[Url],
+        domain: &HyperlaneDomain,
+        metrics: &SealevelRpcMetrics,
+    ) -> Self {
+        Self::from_endpoints(
+            urls.iter()
+                .map(|url| SealevelRpcEndpoint::new(url, domain.name(), metrics.clone()))
+                .collect(),
+        )
+    }
+
+    pub(crate) fn from_endpoints(endpoints: Vec<SealevelRpcEndpoint>) -> Self {
+        Self {
+            fallback: FallbackProvider::new(endpoints),
+            address_lookup_tables: AddressLookupTableCache::default(),
+        }
     }
 
     pub async fn confirm_transaction_with_commitment(
@@ -63,12 +114,21 @@ impl SealevelRpcClient {
         signature: &Signature,
         commitment: CommitmentConfig,
     ) -> ChainResult<bool> {
-        self.0
-            .confirm_transaction_with_commitment(signature, commitment)
+        let signature = *signature;
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .confirm_transaction_with_commitment(&signature, commitment)
+                        .await
+                        .map(|ctx| ctx.value)
+                        .map_err(HyperlaneSealevelError::ClientError)
+                        .map_err(Into::into)
+                };
+                Box::pin(future)
+            })
             .await
-            .map(|ctx| ctx.value)
-            .map_err(HyperlaneSealevelError::ClientError)
-            .map_err(Into::into)
     }
 
     /// Simulates an Instruction that will return a list of AccountMetas.
@@ -100,71 +160,141 @@ impl SealevelRpcClient {
         &self,
         pubkey: &Pubkey,
     ) -> ChainResult<Account> {
-        self.get_account_option_with_finalized_commitment(pubkey)
+        self.get_account_with_commitment(pubkey, CommitmentConfig::finalized())
+            .await
+    }
+
+    pub async fn get_account_option_with_finalized_commitment(
+        &self,
+        pubkey: &Pubkey,
+    ) -> ChainResult<Option<Account>> {
+        self.get_account_option_with_commitment(pubkey, CommitmentConfig::finalized())
+            .await
+    }
+
+    pub async fn get_account_with_commitment(
+        &self,
+        pubkey: &Pubkey,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<Account> {
+        self.get_account_option_with_commitment(pubkey, commitment)
             .await?
             .ok_or_else(|| ChainCommunicationError::from_other_str("Could not find account data"))
     }
 
-    pub async fn get_account_option_with_finalized_commitment(
+    pub async fn get_account_option_with_commitment(
         &self,
         pubkey: &Pubkey,
+        commitment: CommitmentConfig,
     ) -> ChainResult<Option<Account>> {
-        let account = self
-            .0
-            .get_account_with_commitment(pubkey, CommitmentConfig::finalized())
+        let pubkey = *pubkey;
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    let account = endpoint
+                        .client()
+                        .get_account_with_commitment(&pubkey, commitment)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)?
+                        .value;
+                    Ok(account)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)?
-            .value;
-        Ok(account)
     }
 
     pub async fn get_balance(&self, pubkey: &Pubkey) -> ChainResult<U256> {
+        let pubkey = *pubkey;
         let balance = self
-            .0
-            .get_balance(pubkey)
-            .await
-            .map_err(Into::<HyperlaneSealevelError>::into)
-            .map_err(ChainCommunicationError::from)?;
+            .fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_balance(&pubkey)
+                        .await
+                        .map_err(Into::<HyperlaneSealevelError>::into)
+                        .map_err(ChainCommunicationError::from)
+                };
+                Box::pin(future)
+            })
+            .await?;
 
         Ok(balance.into())
     }
 
     pub async fn get_block(&self, slot: u64) -> ChainResult<UiConfirmedBlock> {
+        self.get_block_with_commitment(slot, CommitmentConfig::finalized())
+            .await
+    }
+
+    pub async fn get_block_with_commitment(
+        &self,
+        slot: u64,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<UiConfirmedBlock> {
         let config = RpcBlockConfig {
-            commitment: Some(CommitmentConfig::finalized()),
+            commitment: Some(commitment),
             max_supported_transaction_version: Some(0),
             ..Default::default()
         };
-        self.0
-            .get_block_with_config(slot, config)
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_block_with_config(slot, config)
+                        .await
+                        .map_err(HyperlaneSealevelError::ClientError)
+                        .map_err(Into::into)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(HyperlaneSealevelError::ClientError)
-            .map_err(Into::into)
     }
 
     pub async fn get_multiple_accounts_with_finalized_commitment(
         &self,
         pubkeys: &[Pubkey],
     ) -> ChainResult<Vec<Option<Account>>> {
-        let accounts = self
-            .0
-            .get_multiple_accounts_with_commitment(pubkeys, CommitmentConfig::finalized())
+        self.fallback
+            .call(move |endpoint| {
+                let pubkeys = pubkeys.to_vec();
+                let future = async move {
+                    let accounts = endpoint
+                        .client()
+                        .get_multiple_accounts_with_commitment(
+                            &pubkeys,
+                            CommitmentConfig::finalized(),
+                        )
+                        .await
+                        .map_err(ChainCommunicationError::from_other)?
+                        .value;
+                    Ok(accounts)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)?
-            .value;
-
-        Ok(accounts)
     }
 
     pub async fn get_latest_blockhash_with_commitment(
         &self,
         commitment: CommitmentConfig,
     ) -> ChainResult<Hash> {
-        self.0
-            .get_latest_blockhash_with_commitment(commitment)
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_latest_blockhash_with_commitment(commitment)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                        .map(|(blockhash, _)| blockhash)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
-            .map(|(blockhash, _)| blockhash)
     }
 
     pub async fn get_program_accounts_with_config(
@@ -172,25 +302,116 @@ impl SealevelRpcClient {
         pubkey: &Pubkey,
         config: RpcProgramAccountsConfig,
     ) -> ChainResult<Vec<(Pubkey, Account)>> {
-        self.0
-            .get_program_accounts_with_config(pubkey, config)
+        let pubkey = *pubkey;
+        self.fallback
+            .call(move |endpoint| {
+                let config = config.clone();
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_program_accounts_with_config(&pubkey, config)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
     }
 
     pub async fn get_signature_statuses(
         &self,
         signatures: &[Signature],
     ) -> ChainResult<Response<Vec<Option<TransactionStatus>>>> {
-        self.0
-            .get_signature_statuses(signatures)
+        self.fallback
+            .call(move |endpoint| {
+                let signatures = signatures.to_vec();
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_signature_statuses(&signatures)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
+    }
+
+    /// Gets the prioritization fees, in micro lamports, paid in recent slots
+    /// by transactions locking all of `accounts` for writing.
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        accounts: &[Pubkey],
+    ) -> ChainResult<Vec<u64>> {
+        self.fallback
+            .call(move |endpoint| {
+                let accounts = accounts.iter().map(Pubkey::to_string).collect::<Vec<_>>();
+                let future = async move {
+                    // Not exposed by the pinned Solana client, so the request is built manually
+                    let fees: Vec<RpcPrioritizationFee> = endpoint
+                        .client()
+                        .send(
+                            RpcRequest::Custom {
+                                method: "getRecentPrioritizationFees",
+                            },
+                            serde_json::json!([accounts]),
+                        )
+                        .await
+                        .map_err(ChainCommunicationError::from_other)?;
+                    Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+                };
+                Box::pin(future)
+            })
+            .await
+    }
+
+    /// Gets the address lookup tables with the given addresses. Tables are
+    /// cached for a few minutes, see `AddressLookupTableCache`.
+    pub async fn get_address_lookup_table_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ChainResult<Vec<AddressLookupTableAccount>> {
+        self.address_lookup_tables
+            .get_or_fetch(addresses, |stale| async move {
+                self.fetch_address_lookup_table_accounts(&stale).await
+            })
+            .await
+    }
+
+    async fn fetch_address_lookup_table_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> ChainResult<Vec<AddressLookupTableAccount>> {
+        let accounts = self
+            .get_multiple_accounts_with_finalized_commitment(addresses)
+            .await?;
+        addresses
+            .iter()
+            .zip(accounts)
+            .map(|(key, account)| {
+                let account = account.ok_or_else(|| {
+                    ChainCommunicationError::from_other_str(&format!(
+                        "Address lookup table {key} not found"
+                    ))
+                })?;
+                let table = AddressLookupTable::deserialize(&account.data)
+                    .map_err(ChainCommunicationError::from_other)?;
+                Ok(AddressLookupTableAccount {
+                    key: *key,
+                    addresses: table.addresses.to_vec(),
+                })
+            })
+            .collect()
     }
 
     pub async fn get_slot(&self) -> ChainResult<u32> {
+        self.get_slot_with_commitment(CommitmentConfig::finalized())
+            .await
+    }
+
+    pub async fn get_slot_with_commitment(&self, commitment: CommitmentConfig) -> ChainResult<u32> {
         let slot = self
-            .get_slot_raw()
+            .get_slot_raw_with_commitment(commitment)
             .await?
             .try_into()
             // FIXME solana block height is u64...
@@ -199,10 +420,26 @@ impl SealevelRpcClient {
     }
 
     pub async fn get_slot_raw(&self) -> ChainResult<Slot> {
-        self.0
-            .get_slot_with_commitment(CommitmentConfig::finalized())
+        self.get_slot_raw_with_commitment(CommitmentConfig::finalized())
+            .await
+    }
+
+    pub async fn get_slot_raw_with_commitment(
+        &self,
+        commitment: CommitmentConfig,
+    ) -> ChainResult<Slot> {
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_slot_with_commitment(commitment)
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
     }
 
     pub async fn get_transaction(
@@ -214,45 +451,75 @@ impl SealevelRpcClient {
             commitment: Some(CommitmentConfig::finalized()),
             ..Default::default()
         };
-        self.0
-            .get_transaction_with_config(signature, config)
+        let signature = *signature;
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .get_transaction_with_config(&signature, config)
+                        .await
+                        .map_err(HyperlaneSealevelError::ClientError)
+                        .map_err(Into::into)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(HyperlaneSealevelError::ClientError)
-            .map_err(Into::into)
     }
 
     pub async fn is_blockhash_valid(&self, hash: &Hash) -> ChainResult<bool> {
-        self.0
-            .is_blockhash_valid(hash, CommitmentConfig::processed())
+        let hash = *hash;
+        self.fallback
+            .call(move |endpoint| {
+                let future = async move {
+                    endpoint
+                        .client()
+                        .is_blockhash_valid(&hash, CommitmentConfig::processed())
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
     }
 
     pub async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
         skip_preflight: bool,
     ) -> ChainResult<Signature> {
-        self.0
-            .send_transaction_with_config(
-                transaction,
-                RpcSendTransactionConfig {
-                    skip_preflight,
-                    ..Default::default()
-                },
-            )
+        // Resubmitting the same signed transaction to another node is safe,
+        // it can only land once.
+        self.fallback
+            .call(move |endpoint| {
+                let transaction = transaction.clone();
+                let future = async move {
+                    endpoint
+                        .client()
+                        .send_transaction_with_config(
+                            &transaction,
+                            RpcSendTransactionConfig {
+                                skip_preflight,
+                                ..Default::default()
+                            },
+                        )
+                        .await
+                        .map_err(ChainCommunicationError::from_other)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)
     }
 
     /// Polls the RPC until the transaction is confirmed or the blockhash
-    /// expires.
+    /// expires. Returns `false` if the blockhash expired before the
+    /// transaction landed, in which case it can be resubmitted.
     /// Standalone logic stolen from Solana's non-blocking client,
     /// decoupled from the sending of a transaction.
     pub async fn wait_for_transaction_confirmation(
         &self,
         transaction: &impl SerializableTransaction,
-    ) -> ChainResult<()> {
+    ) -> ChainResult<bool> {
         let signature = transaction.get_signature();
 
         const GET_STATUS_RETRIES: usize = usize::MAX;
@@ -269,11 +536,11 @@ impl SealevelRpcClient {
                 self.get_signature_statuses(&[*signature]).await?;
             let signature_status = signature_statuses.value.first().cloned().flatten();
             match signature_status {
-                Some(_) => return Ok(()),
+                Some(_) => return Ok(true),
                 None => {
                     if !self.is_blockhash_valid(&recent_blockhash).await? {
                         // Block hash is not found by some reason
-                        break;
+                        return Ok(false);
                     } else if cfg!(not(test))
                         // Ignore sleep at last step.
                         && status_retry < GET_STATUS_RETRIES
@@ -286,14 +553,7 @@ impl SealevelRpcClient {
             }
         }
 
-        Err(ChainCommunicationError::from_other(
-            solana_client::rpc_request::RpcError::ForUser(
-                "unable to confirm transaction. \
-                This can happen in situations such as transaction expiration \
-                and insufficient fee-payer funds"
-                    .to_string(),
-            ),
-        ))
+        Ok(false)
     }
 
     /// Simulates an instruction, and attempts to deserialize it into a T.
@@ -314,7 +574,7 @@ impl SealevelRpcClient {
             Some(&payer.pubkey()),
             &recent_blockhash,
         ));
-        let simulation = self.simulate_transaction(&transaction).await?;
+        let simulation = self.simulate_transaction(&transaction.into()).await?;
 
         if let Some(return_data) = simulation.return_data {
             let bytes = match return_data.data.1 {
@@ -334,32 +594,45 @@ impl SealevelRpcClient {
 
     pub async fn simulate_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
     ) -> ChainResult<RpcSimulateTransactionResult> {
-        let result = self
-            .0
-            .simulate_transaction_with_config(
-                transaction,
-                RpcSimulateTransactionConfig {
-                    sig_verify: false,
-                    replace_recent_blockhash: true,
-                    ..Default::default()
-                },
-            )
+        self.fallback
+            .call(move |endpoint| {
+                let transaction = transaction.clone();
+                let future = async move {
+                    let result = endpoint
+                        .client()
+                        .simulate_transaction_with_config(
+                            &transaction,
+                            RpcSimulateTransactionConfig {
+                                sig_verify: false,
+                                replace_recent_blockhash: true,
+                                ..Default::default()
+                            },
+                        )
+                        .await
+                        .map_err(ChainCommunicationError::from_other)?
+                        .value;
+                    Ok(result)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(ChainCommunicationError::from_other)?
-            .value;
-
-        Ok(result)
     }
 
     /// Gets the estimated costs for a given instruction.
+    /// `resubmission` is the number of times the transaction was previously
+    /// submitted and expired, and is used to escalate the priority fee.
+    /// `address_lookup_tables` are used if the transaction is too large
+    /// without them, see `create_transaction_for_instruction`.
     pub async fn get_estimated_costs_for_instruction(
         &self,
         instruction: Instruction,
         payer: &SealevelKeypair,
         tx_submitter: &dyn TransactionSubmitter,
         priority_fee_oracle: &dyn PriorityFeeOracle,
+        address_lookup_tables: &[Pubkey],
+        resubmission: u32,
     ) -> ChainResult<SealevelTxCostEstimate> {
         // Build a transaction that sets the max compute units and a dummy compute unit price.
         // This is used for simulation to get the actual compute unit limit. We set dummy values
@@ -372,6 +645,7 @@ impl SealevelRpcClient {
                 instruction.clone(),
                 payer,
                 tx_submitter,
+                address_lookup_tables,
                 false,
             )
             .await?;
@@ -407,7 +681,9 @@ impl SealevelRpcClient {
                 / COMPUTE_UNIT_MULTIPLIER_DENOMINATOR,
         );
 
-        let mut priority_fee = priority_fee_oracle.get_priority_fee(&simulation_tx).await?;
+        let priority_fee = priority_fee_oracle.get_priority_fee(&simulation_tx).await?;
+        let mut priority_fee =
+            priority_fee_oracle.escalate_priority_fee(priority_fee, resubmission);
 
         if let Ok(max_priority_fee) = std::env::var("SVM_MAX_PRIORITY_FEE") {
             let max_priority_fee = max_priority_fee.parse()?;
@@ -443,7 +719,9 @@ impl SealevelRpcClient {
         payer: &SealevelKeypair,
         tx_submitter: &dyn TransactionSubmitter,
         priority_fee_oracle: &dyn PriorityFeeOracle,
-    ) -> ChainResult<Transaction> {
+        address_lookup_tables: &[Pubkey],
+        resubmission: u32,
+    ) -> ChainResult<VersionedTransaction> {
         // Get the estimated costs for the instruction.
         let SealevelTxCostEstimate {
             compute_units,
@@ -454,6 +732,8 @@ impl SealevelRpcClient {
                 payer,
                 tx_submitter,
                 priority_fee_oracle,
+                address_lookup_tables,
+                resubmission,
             )
             .await?;
 
@@ -471,6 +751,7 @@ impl SealevelRpcClient {
                 instruction,
                 payer,
                 tx_submitter,
+                address_lookup_tables,
                 true,
             )
             .await?;
@@ -480,6 +761,11 @@ impl SealevelRpcClient {
 
     /// Creates a transaction for a given instruction, compute unit limit, and compute unit price.
     /// If `sign` is true, the transaction will be signed.
+    ///
+    /// A legacy transaction is created, unless it would exceed the maximum
+    /// transaction size and `address_lookup_tables` are provided, in which case
+    /// a v0 transaction loading accounts from these tables is created instead.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_transaction_for_instruction(
         &self,
         compute_unit_limit: u32,
@@ -487,8 +773,9 @@ impl SealevelRpcClient {
         instruction: Instruction,
         payer: &SealevelKeypair,
         tx_submitter: &dyn TransactionSubmitter,
+        address_lookup_tables: &[Pubkey],
         sign: bool,
-    ) -> ChainResult<Transaction> {
+    ) -> ChainResult<VersionedTransaction> {
         let instructions = vec![
             // Set the compute unit limit.
             ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
@@ -501,36 +788,71 @@ impl SealevelRpcClient {
             instruction,
         ];
 
-        let tx = if sign {
+        let recent_blockhash = if sign {
             // Getting the finalized blockhash eliminates the chance the blockhash
             // gets reorged out, causing the tx to be invalid. The tradeoff is this
             // will cause the tx to expire in about 47 seconds (instead of the typical 60).
-            let recent_blockhash = self
-                .get_latest_blockhash_with_commitment(CommitmentConfig::finalized())
+            self.get_latest_blockhash_with_commitment(CommitmentConfig::finalized())
                 .await
-                .map_err(ChainCommunicationError::from_other)?;
+                .map_err(ChainCommunicationError::from_other)?
+        } else {
+            Hash::default()
+        };
 
-            Transaction::new_signed_with_payer(
-                &instructions,
-                Some(&payer.pubkey()),
-                &[payer.keypair()],
-                recent_blockhash,
-            )
+        let mut message = VersionedMessage::Legacy(Message::new_with_blockhash(
+            &instructions,
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        ));
+        if !address_lookup_tables.is_empty() && !fits_in_packet(&message) {
+            let address_lookup_table_accounts = self
+                .get_address_lookup_table_accounts(address_lookup_tables)
+                .await?;
+            message = VersionedMessage::V0(
+                v0::Message::try_compile(
+                    &payer.pubkey(),
+                    &instructions,
+                    &address_lookup_table_accounts,
+                    recent_blockhash,
+                )
+                .map_err(ChainCommunicationError::from_other)?,
+            );
+            tracing::debug!(
+                ?address_lookup_tables,
+                "Transaction too large for a legacy transaction, using a v0 transaction"
+            );
+        }
+
+        let tx = if sign {
+            VersionedTransaction::try_new(message, &[payer.keypair()])
+                .map_err(ChainCommunicationError::from_other)?
         } else {
-            Transaction::new_unsigned(Message::new(&instructions, Some(&payer.pubkey())))
+            unsigned_transaction(message)
         };
 
         Ok(tx)
     }
+}
 
-    pub fn url(&self) -> String {
-        self.0.url()
+/// A transaction with placeholder signatures, e.g. for simulation.
+fn unsigned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
     }
 }
 
+/// Whether a transaction with `message` fits within the maximum transaction
+/// size once signed.
+fn fits_in_packet(message: &VersionedMessage) -> bool {
+    bincode::serialized_size(&unsigned_transaction(message.clone()))
+        .map(|size| size <= PACKET_DATA_SIZE as u64)
+        .unwrap_or(false)
+}
+
 impl std::fmt::Debug for SealevelRpcClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("RpcClient { ... }")
+        std::fmt::Debug::fmt(&self.fallback, f)
     }
 }
 
@@ -1,4 +1,18 @@
-use crate::SealevelRpcClient;
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{
+        v0::{self, LoadedAddresses, LoadedMessage},
+        Message, VersionedMessage,
+    },
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+use crate::{tx_submitter::RpcTransactionSubmitter, SealevelKeypair, SealevelRpcClient};
+
+use super::fits_in_packet;
 
 //#[tokio::test]
 async fn _test_get_block() {
@@ -12,3 +26,133 @@ async fn _test_get_block() {
     // then
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_large_transaction_fits_with_lookup_table() {
+    // given
+    let payer = Pubkey::new_unique();
+    let accounts = (0..48).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let instructions = [Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[0; 64],
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false))
+            .collect(),
+    )];
+    let lookup_table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: accounts,
+    };
+
+    // when
+    let legacy = VersionedMessage::Legacy(Message::new_with_blockhash(
+        &instructions,
+        Some(&payer),
+        &Hash::default(),
+    ));
+    let v0 = VersionedMessage::V0(
+        v0::Message::try_compile(&payer, &instructions, &[lookup_table], Hash::default()).unwrap(),
+    );
+
+    // then
+    assert!(!fits_in_packet(&legacy));
+    assert!(fits_in_packet(&v0));
+}
+
+#[tokio::test]
+async fn test_create_v0_transaction_with_lookup_table() {
+    // given
+    let payer = SealevelKeypair::new(Keypair::new());
+    let instruction = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[0; 64],
+        (0..48)
+            .map(|i| {
+                if i % 2 == 0 {
+                    AccountMeta::new(Pubkey::new_unique(), false)
+                } else {
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false)
+                }
+            })
+            .collect(),
+    );
+    let lookup_table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect(),
+    };
+    // No requests are made, as the table is cached and the transactions
+    // aren't signed
+    let client = SealevelRpcClient::new("http://127.0.0.1:1".to_string());
+    client.address_lookup_tables.insert(lookup_table.clone());
+    let tx_submitter = RpcTransactionSubmitter::new(client.clone());
+
+    // when
+    let legacy_tx = client
+        .create_transaction_for_instruction(
+            SealevelRpcClient::MAX_COMPUTE_UNITS,
+            0,
+            instruction.clone(),
+            &payer,
+            &tx_submitter,
+            &[],
+            false,
+        )
+        .await
+        .unwrap();
+    let v0_tx = client
+        .create_transaction_for_instruction(
+            SealevelRpcClient::MAX_COMPUTE_UNITS,
+            0,
+            instruction.clone(),
+            &payer,
+            &tx_submitter,
+            &[lookup_table.key],
+            false,
+        )
+        .await
+        .unwrap();
+
+    // then
+    assert!(matches!(legacy_tx.message, VersionedMessage::Legacy(_)));
+    assert!(!fits_in_packet(&legacy_tx.message));
+
+    let VersionedMessage::V0(message) = &v0_tx.message else {
+        panic!("expected a v0 transaction");
+    };
+    assert!(fits_in_packet(&v0_tx.message));
+    assert_eq!(v0_tx.signatures.len(), 1);
+    assert_eq!(message.account_keys[0], payer.pubkey());
+
+    // Every account is loaded from the table, keeping its writability
+    let [lookup] = &message.address_table_lookups[..] else {
+        panic!("expected a single lookup");
+    };
+    assert_eq!(lookup.account_key, lookup_table.key);
+    let resolve = |indexes: &[u8]| {
+        indexes
+            .iter()
+            .map(|index| lookup_table.addresses[*index as usize])
+            .collect()
+    };
+    let loaded = LoadedMessage::new(
+        message.clone(),
+        LoadedAddresses {
+            writable: resolve(&lookup.writable_indexes),
+            readonly: resolve(&lookup.readonly_indexes),
+        },
+    );
+    let account_keys = loaded.account_keys();
+    for meta in &instruction.accounts {
+        let index = account_keys
+            .iter()
+            .position(|key| *key == meta.pubkey)
+            .unwrap();
+        assert!(index >= message.account_keys.len());
+        assert_eq!(loaded.is_writable(index), meta.is_writable);
+    }
+}
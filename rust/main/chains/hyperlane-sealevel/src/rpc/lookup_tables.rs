@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use solana_sdk::{address_lookup_table_account::AddressLookupTableAccount, pubkey::Pubkey};
+
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+
+/// How long a fetched address lookup table is reused for. Tables are only
+/// ever extended, so a cached table is at worst missing recently added
+/// addresses, which only makes transactions using it larger.
+const ADDRESS_LOOKUP_TABLE_TTL: Duration = Duration::from_secs(300);
+
+/// Address lookup tables fetched by a `SealevelRpcClient`, shared by its
+/// clones, so that they aren't fetched again for every transaction built.
+#[derive(Debug, Clone)]
+pub(crate) struct AddressLookupTableCache {
+    ttl: Duration,
+    tables: Arc<Mutex<HashMap<Pubkey, (Instant, AddressLookupTableAccount)>>>,
+}
+
+impl Default for AddressLookupTableCache {
+    fn default() -> Self {
+        Self::new(ADDRESS_LOOKUP_TABLE_TTL)
+    }
+}
+
+impl AddressLookupTableCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            tables: Default::default(),
+        }
+    }
+
+    /// Gets the tables with the given addresses, in order. Tables that
+    /// aren't cached, or were cached longer than the ttl ago, are fetched
+    /// with `fetch`.
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        addresses: &[Pubkey],
+        fetch: F,
+    ) -> ChainResult<Vec<AddressLookupTableAccount>>
+    where
+        F: FnOnce(Vec<Pubkey>) -> Fut,
+        Fut: Future<Output = ChainResult<Vec<AddressLookupTableAccount>>>,
+    {
+        let stale: Vec<Pubkey> = {
+            let tables = self.tables.lock().unwrap();
+            addresses
+                .iter()
+                .filter(|address| {
+                    !tables
+                        .get(address)
+                        .is_some_and(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+                })
+                .copied()
+                .collect()
+        };
+        if !stale.is_empty() {
+            let fetched = fetch(stale).await?;
+            let fetched_at = Instant::now();
+            let mut tables = self.tables.lock().unwrap();
+            for table in fetched {
+                tables.insert(table.key, (fetched_at, table));
+            }
+        }
+
+        let tables = self.tables.lock().unwrap();
+        addresses
+            .iter()
+            .map(|address| {
+                tables
+                    .get(address)
+                    .map(|(_, table)| table.clone())
+                    .ok_or_else(|| {
+                        ChainCommunicationError::from_other_str(&format!(
+                            "Address lookup table {address} not found"
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    /// Caches `table` as if it was just fetched.
+    #[cfg(test)]
+    pub fn insert(&self, table: AddressLookupTableAccount) {
+        self.tables
+            .lock()
+            .unwrap()
+            .insert(table.key, (Instant::now(), table));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::ready;
+
+    use super::*;
+
+    fn table() -> AddressLookupTableAccount {
+        AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        }
+    }
+
+    /// Gets `addresses` from `cache`, fetching from `chain`. Returns the
+    /// tables and the addresses that were fetched.
+    async fn get(
+        cache: &AddressLookupTableCache,
+        addresses: &[Pubkey],
+        chain: &[AddressLookupTableAccount],
+    ) -> (ChainResult<Vec<AddressLookupTableAccount>>, Vec<Pubkey>) {
+        let fetched = Mutex::new(vec![]);
+        let result = cache
+            .get_or_fetch(addresses, |stale| {
+                fetched.lock().unwrap().extend(stale.iter().copied());
+                ready(Ok(chain
+                    .iter()
+                    .filter(|table| stale.contains(&table.key))
+                    .cloned()
+                    .collect()))
+            })
+            .await;
+        (result, fetched.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_fetches_tables_once() {
+        let (first, second) = (table(), table());
+        let chain = [first.clone(), second.clone()];
+        let cache = AddressLookupTableCache::default();
+
+        let (tables, fetched) = get(&cache, &[first.key], &chain).await;
+        assert_eq!(tables.unwrap(), vec![first.clone()]);
+        assert_eq!(fetched, vec![first.key]);
+
+        // Only the table that isn't cached yet is fetched
+        let (tables, fetched) = get(&cache, &[second.key, first.key], &chain).await;
+        assert_eq!(tables.unwrap(), vec![second.clone(), first.clone()]);
+        assert_eq!(fetched, vec![second.key]);
+
+        let (tables, fetched) = get(&cache, &[first.key, second.key], &chain).await;
+        assert_eq!(tables.unwrap(), vec![first, second]);
+        assert!(fetched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetches_expired_tables_again() {
+        let mut table = table();
+        let cache = AddressLookupTableCache::new(Duration::ZERO);
+        let (tables, _) = get(&cache, &[table.key], &[table.clone()]).await;
+        assert_eq!(tables.unwrap(), vec![table.clone()]);
+
+        // The table was extended since
+        table.addresses.push(Pubkey::new_unique());
+        let (tables, fetched) = get(&cache, &[table.key], &[table.clone()]).await;
+        assert_eq!(tables.unwrap(), vec![table.clone()]);
+        assert_eq!(fetched, vec![table.key]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_table() {
+        let cache = AddressLookupTableCache::default();
+        let (tables, _) = get(&cache, &[Pubkey::new_unique()], &[]).await;
+        assert!(tables.is_err());
+    }
+}
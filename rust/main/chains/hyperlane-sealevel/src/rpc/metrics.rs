@@ -0,0 +1,152 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use prometheus::{CounterVec, IntCounterVec};
+use serde_json::Value;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+/// Prometheus metrics recorded for every request made to a Sealevel RPC node.
+///
+/// These are expected to be the same metric vecs the ethereum json rpc
+/// clients report into, i.e. labelled with `provider_node`, `chain`, `method`
+/// and `status`, so that RPC health can be compared across protocols.
+#[derive(Clone, Debug, Default)]
+pub struct SealevelRpcMetrics {
+    /// Total number of requests made to a node.
+    pub request_count: Option<IntCounterVec>,
+    /// Total number of seconds spent making requests to a node.
+    pub request_duration_seconds: Option<CounterVec>,
+}
+
+/// An `RpcSender` wrapper that instruments every request with prometheus
+/// metrics.
+pub(crate) struct PrometheusRpcSender<S> {
+    inner: S,
+    metrics: SealevelRpcMetrics,
+    provider_node: String,
+    chain: String,
+}
+
+impl<S> PrometheusRpcSender<S> {
+    pub fn new(
+        inner: S,
+        metrics: SealevelRpcMetrics,
+        provider_node: String,
+        chain: String,
+    ) -> Self {
+        Self {
+            inner,
+            metrics,
+            provider_node,
+            chain,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> RpcSender for PrometheusRpcSender<S>
+where
+    S: RpcSender + Send + Sync,
+{
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let start = Instant::now();
+        let res = self.inner.send(request, params).await;
+        let method = request.to_string();
+        let status = if res.is_ok() { "success" } else { "failure" };
+        let labels = [
+            self.provider_node.as_str(),
+            self.chain.as_str(),
+            method.as_str(),
+            status,
+        ];
+        if let Some(counter) = &self.metrics.request_count {
+            counter.with_label_values(&labels).inc();
+        }
+        if let Some(counter) = &self.metrics.request_duration_seconds {
+            counter
+                .with_label_values(&labels)
+                .inc_by(start.elapsed().as_secs_f64());
+        }
+        res
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{Opts, Registry};
+    use solana_client::mock_sender::MockSender;
+
+    use super::*;
+
+    const LABELS: &[&str] = &["provider_node", "chain", "method", "status"];
+
+    fn metrics(registry: &Registry) -> SealevelRpcMetrics {
+        let request_count =
+            IntCounterVec::new(Opts::new("request_count", "request count"), LABELS).unwrap();
+        let request_duration_seconds = CounterVec::new(
+            Opts::new("request_duration_seconds", "request duration"),
+            LABELS,
+        )
+        .unwrap();
+        registry.register(Box::new(request_count.clone())).unwrap();
+        registry
+            .register(Box::new(request_duration_seconds.clone()))
+            .unwrap();
+        SealevelRpcMetrics {
+            request_count: Some(request_count),
+            request_duration_seconds: Some(request_duration_seconds),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_requests_per_node() {
+        let registry = Registry::new();
+        let metrics = metrics(&registry);
+
+        let succeeds = PrometheusRpcSender::new(
+            MockSender::new("succeeds"),
+            metrics.clone(),
+            "good.node".to_owned(),
+            "solanamainnet".to_owned(),
+        );
+        let fails = PrometheusRpcSender::new(
+            MockSender::new("fails"),
+            metrics.clone(),
+            "bad.node".to_owned(),
+            "solanamainnet".to_owned(),
+        );
+
+        succeeds
+            .send(RpcRequest::GetSlot, Value::Null)
+            .await
+            .unwrap();
+        let _ = fails.send(RpcRequest::GetSlot, Value::Null).await;
+
+        let request_count = metrics.request_count.unwrap();
+        assert_eq!(
+            request_count
+                .with_label_values(&["good.node", "solanamainnet", "getSlot", "success"])
+                .get(),
+            1
+        );
+        let bad_node_requests = request_count
+            .with_label_values(&["bad.node", "solanamainnet", "getSlot", "success"])
+            .get()
+            + request_count
+                .with_label_values(&["bad.node", "solanamainnet", "getSlot", "failure"])
+                .get();
+        assert_eq!(bad_node_requests, 1);
+    }
+}
@@ -1,17 +1,28 @@
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError, NativeToken};
+use hyperlane_core::{
+    config::OperationBatchConfig, ChainCommunicationError, ChainResult, HyperlaneDomain,
+    NativeToken,
+};
 use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
 use url::Url;
 
 use crate::{
-    priority_fee::{ConstantPriorityFeeOracle, HeliusPriorityFeeOracle, PriorityFeeOracle},
-    tx_submitter::{JitoTransactionSubmitter, RpcTransactionSubmitter, TransactionSubmitter},
+    priority_fee::{
+        ConstantPriorityFeeOracle, HeliusPriorityFeeOracle, PriorityFeeOracle,
+        RecentFeesPriorityFeeOracle,
+    },
+    tx_submitter::{
+        JitoBundleTransactionSubmitter, JitoTransactionSubmitter, MultiRpcTransactionSubmitter,
+        RpcTransactionSubmitter, TransactionSubmitter,
+    },
+    SealevelRpcClient, SealevelRpcMetrics,
 };
 
 /// Sealevel connection configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionConf {
-    /// Fully qualified string to connect to
-    pub url: Url,
+    /// Fully qualified strings to connect to, in order of priority
+    pub urls: Vec<Url>,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
     /// Native token and its denomination
@@ -20,6 +31,9 @@ pub struct ConnectionConf {
     pub priority_fee_oracle: PriorityFeeOracleConfig,
     /// Transaction submitter configuration
     pub transaction_submitter: TransactionSubmitterConfig,
+    /// Address lookup tables to load accounts from when a transaction is too
+    /// large to be sent as a legacy transaction
+    pub address_lookup_tables: Vec<Pubkey>,
 }
 
 /// An error type when parsing a connection configuration.
@@ -40,6 +54,9 @@ pub enum PriorityFeeOracleConfig {
     Constant(u64),
     /// A Helius priority fee oracle
     Helius(HeliusPriorityFeeOracleConfig),
+    /// An oracle based on the fees returned by the `getRecentPrioritizationFees`
+    /// RPC method
+    RecentFees(RecentFeesPriorityFeeOracleConfig),
 }
 
 impl Default for PriorityFeeOracleConfig {
@@ -50,7 +67,7 @@ impl Default for PriorityFeeOracleConfig {
 
 impl PriorityFeeOracleConfig {
     /// Create a new priority fee oracle from the configuration
-    pub fn create_oracle(&self) -> Box<dyn PriorityFeeOracle> {
+    pub fn create_oracle(&self, rpc_client: &SealevelRpcClient) -> Box<dyn PriorityFeeOracle> {
         match self {
             PriorityFeeOracleConfig::Constant(fee) => {
                 Box::new(ConstantPriorityFeeOracle::new(*fee))
@@ -58,6 +75,9 @@ impl PriorityFeeOracleConfig {
             PriorityFeeOracleConfig::Helius(config) => {
                 Box::new(HeliusPriorityFeeOracle::new(config.clone()))
             }
+            PriorityFeeOracleConfig::RecentFees(config) => Box::new(
+                RecentFeesPriorityFeeOracle::new(rpc_client.clone(), config.clone()),
+            ),
         }
     }
 }
@@ -71,6 +91,31 @@ pub struct HeliusPriorityFeeOracleConfig {
     pub fee_level: HeliusPriorityFeeLevel,
 }
 
+/// Configuration for the recent prioritization fees oracle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentFeesPriorityFeeOracleConfig {
+    /// The percentile of the recent fees to use, from 0 to 100
+    pub percentile: u8,
+    /// The minimum fee to use, in micro lamports
+    pub floor: u64,
+    /// The maximum fee to use, in micro lamports
+    pub cap: Option<u64>,
+    /// By how many percent the fee is increased every time a transaction is
+    /// resubmitted after its blockhash expired
+    pub escalation_percent: u64,
+}
+
+impl Default for RecentFeesPriorityFeeOracleConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            floor: 0,
+            cap: None,
+            escalation_percent: 25,
+        }
+    }
+}
+
 /// The priority fee level to use
 #[derive(Debug, Clone, Serialize, Default)]
 pub enum HeliusPriorityFeeLevel {
@@ -105,6 +150,17 @@ pub enum TransactionSubmitterConfig {
         /// The URL to use. If not provided, a default Jito URL will be used
         url: Option<String>,
     },
+    /// Send transactions in Jito bundles together with a tip transaction,
+    /// falling back to the RPC if a bundle doesn't land
+    JitoBundle {
+        /// The bundles URL to use. If not provided, a default Jito URL will be used
+        url: Option<String>,
+    },
+    /// Send each transaction to several RPCs in parallel
+    MultiRpc {
+        /// The URLs to send to in addition to the default RPC
+        urls: Vec<Url>,
+    },
 }
 
 impl Default for TransactionSubmitterConfig {
@@ -114,11 +170,20 @@ impl Default for TransactionSubmitterConfig {
 }
 
 impl TransactionSubmitterConfig {
-    /// Create a new transaction submitter from the configuration
-    pub fn create_submitter(&self, default_rpc_url: String) -> Box<dyn TransactionSubmitter> {
-        match self {
+    /// Create a new transaction submitter from the configuration. Without
+    /// a configured url, the RPC submitter sends through `default_rpc_client`.
+    /// Requests to additional RPCs are reported to `rpc_metrics`.
+    pub fn create_submitter(
+        &self,
+        default_rpc_client: &SealevelRpcClient,
+        domain: &HyperlaneDomain,
+        rpc_metrics: &SealevelRpcMetrics,
+    ) -> ChainResult<Box<dyn TransactionSubmitter>> {
+        let submitter: Box<dyn TransactionSubmitter> = match self {
             TransactionSubmitterConfig::Rpc { url } => Box::new(RpcTransactionSubmitter::new(
-                url.clone().unwrap_or(default_rpc_url),
+                url.clone()
+                    .map(SealevelRpcClient::new)
+                    .unwrap_or_else(|| default_rpc_client.clone()),
             )),
             TransactionSubmitterConfig::Jito { url } => {
                 // Default to a bundle-only URL (i.e. revert protected)
@@ -129,7 +194,29 @@ impl TransactionSubmitterConfig {
                     },
                 )))
             }
-        }
+            TransactionSubmitterConfig::JitoBundle { url } => {
+                Box::new(JitoBundleTransactionSubmitter::new(
+                    url.clone().unwrap_or_else(|| {
+                        "https://mainnet.block-engine.jito.wtf/api/v1/bundles".to_string()
+                    }),
+                    default_rpc_client.clone(),
+                )?)
+            }
+            TransactionSubmitterConfig::MultiRpc { urls } => {
+                Box::new(MultiRpcTransactionSubmitter::new(
+                    std::iter::once(default_rpc_client.clone())
+                        .chain(urls.iter().map(|url| {
+                            SealevelRpcClient::new_with_fallback(
+                                std::slice::from_ref(url),
+                                domain,
+                                rpc_metrics,
+                            )
+                        }))
+                        .collect(),
+                ))
+            }
+        };
+        Ok(submitter)
     }
 }
 
@@ -1,11 +1,55 @@
+use std::time::{Duration, Instant};
+
 use async_trait::async_trait;
-use hyperlane_core::ChainResult;
+use futures::future::join_all;
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use reqwest::Client;
+use serde::Deserialize;
 use solana_sdk::{
-    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
-    signature::Signature, transaction::Transaction,
+    bs58,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer as _,
+    transaction::{Transaction, VersionedTransaction},
 };
+use tracing::{debug, warn};
+
+use crate::{SealevelKeypair, SealevelRpcClient};
+
+/// A random Jito fee account, taken from the getFeeAccount RPC response:
+/// https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#gettipaccounts
+const JITO_TIP_ACCOUNT: Pubkey =
+    solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh");
+
+/// The minimum tip to include in a transaction or bundle.
+/// From https://docs.jito.wtf/lowlatencytxnsend/#sendtransaction
+const JITO_MINIMUM_TIP_LAMPORTS: u64 = 1000;
 
-use crate::SealevelRpcClient;
+/// The path through which a transaction was submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionPath {
+    /// Sent to a single RPC
+    Rpc,
+    /// Sent to several RPCs in parallel
+    MultiRpc,
+    /// Sent as a single transaction through Jito
+    Jito,
+    /// Sent as part of a Jito bundle, which landed
+    JitoBundle,
+    /// Sent to the RPC after a Jito bundle failed to land
+    JitoBundleRpcFallback,
+}
+
+/// A transaction that was sent to the chain.
+#[derive(Debug, Clone, Copy)]
+pub struct SubmittedTransaction {
+    /// The signature of the transaction
+    pub signature: Signature,
+    /// The path it was submitted through
+    pub path: SubmissionPath,
+}
 
 /// A trait for submitting transactions to the chain.
 #[async_trait]
@@ -18,12 +62,14 @@ pub trait TransactionSubmitter: Send + Sync {
         payer: &Pubkey,
     ) -> Instruction;
 
-    /// Send a transaction to the chain.
+    /// Send a transaction to the chain. `payer` signs any transactions the
+    /// submitter sends alongside it, e.g. a bundle tip.
     async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
+        payer: &SealevelKeypair,
         skip_preflight: bool,
-    ) -> ChainResult<Signature>;
+    ) -> ChainResult<SubmittedTransaction>;
 
     fn rpc_client(&self) -> Option<&SealevelRpcClient> {
         None
@@ -37,10 +83,8 @@ pub struct RpcTransactionSubmitter {
 }
 
 impl RpcTransactionSubmitter {
-    pub fn new(url: String) -> Self {
-        Self {
-            rpc_client: SealevelRpcClient::new(url),
-        }
+    pub fn new(rpc_client: SealevelRpcClient) -> Self {
+        Self { rpc_client }
     }
 }
 
@@ -57,12 +101,18 @@ impl TransactionSubmitter for RpcTransactionSubmitter {
 
     async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
+        _payer: &SealevelKeypair,
         skip_preflight: bool,
-    ) -> ChainResult<Signature> {
-        self.rpc_client
+    ) -> ChainResult<SubmittedTransaction> {
+        let signature = self
+            .rpc_client
             .send_transaction(transaction, skip_preflight)
-            .await
+            .await?;
+        Ok(SubmittedTransaction {
+            signature,
+            path: SubmissionPath::Rpc,
+        })
     }
 
     fn rpc_client(&self) -> Option<&SealevelRpcClient> {
@@ -70,6 +120,73 @@ impl TransactionSubmitter for RpcTransactionSubmitter {
     }
 }
 
+/// A transaction submitter that sends the same signed transaction to several
+/// RPCs in parallel, to increase the chance of it landing. This is safe, as a
+/// signed transaction can only land once.
+#[derive(Debug)]
+pub struct MultiRpcTransactionSubmitter {
+    /// The first client is also used to confirm transactions
+    rpc_clients: Vec<SealevelRpcClient>,
+}
+
+impl MultiRpcTransactionSubmitter {
+    pub fn new(rpc_clients: Vec<SealevelRpcClient>) -> Self {
+        Self { rpc_clients }
+    }
+}
+
+#[async_trait]
+impl TransactionSubmitter for MultiRpcTransactionSubmitter {
+    fn get_priority_fee_instruction(
+        &self,
+        compute_unit_price_micro_lamports: u64,
+        _compute_units: u64,
+        _payer: &Pubkey,
+    ) -> Instruction {
+        ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price_micro_lamports)
+    }
+
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        _payer: &SealevelKeypair,
+        skip_preflight: bool,
+    ) -> ChainResult<SubmittedTransaction> {
+        let results = join_all(
+            self.rpc_clients
+                .iter()
+                .map(|client| client.send_transaction(transaction, skip_preflight)),
+        )
+        .await;
+
+        let mut errors = vec![];
+        let mut signature = None;
+        for (client, result) in self.rpc_clients.iter().zip(results) {
+            match result {
+                Ok(sig) => signature = Some(sig),
+                Err(err) => {
+                    debug!(?client, ?err, "Failed to send transaction to RPC");
+                    errors.push(err);
+                }
+            }
+        }
+
+        match signature {
+            Some(signature) => Ok(SubmittedTransaction {
+                signature,
+                path: SubmissionPath::MultiRpc,
+            }),
+            None => Err(errors.into_iter().next().unwrap_or_else(|| {
+                ChainCommunicationError::from_other_str("No RPCs to send the transaction to")
+            })),
+        }
+    }
+
+    fn rpc_client(&self) -> Option<&SealevelRpcClient> {
+        self.rpc_clients.first()
+    }
+}
+
 /// A transaction submitter that uses the Jito API to submit transactions.
 #[derive(Debug)]
 pub struct JitoTransactionSubmitter {
@@ -77,10 +194,6 @@ pub struct JitoTransactionSubmitter {
 }
 
 impl JitoTransactionSubmitter {
-    /// The minimum tip to include in a transaction.
-    /// From https://docs.jito.wtf/lowlatencytxnsend/#sendtransaction
-    const MINIMUM_TIP_LAMPORTS: u64 = 1000;
-
     pub fn new(url: String) -> Self {
         Self {
             rpc_client: SealevelRpcClient::new(url),
@@ -96,28 +209,335 @@ impl TransactionSubmitter for JitoTransactionSubmitter {
         compute_units: u64,
         payer: &Pubkey,
     ) -> Instruction {
-        // Divide by 1_000_000 to convert from microlamports to lamports.
-        let tip_lamports = (compute_units * compute_unit_price_micro_lamports) / 1_000_000;
-        let tip_lamports = tip_lamports.max(Self::MINIMUM_TIP_LAMPORTS);
-
         // The tip is a standalone transfer to a Jito fee account.
         // See https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#sendbundle.
         solana_sdk::system_instruction::transfer(
             payer,
-            // A random Jito fee account, taken from the getFeeAccount RPC response:
-            // https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#gettipaccounts
-            &solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
-            tip_lamports,
+            &JITO_TIP_ACCOUNT,
+            jito_tip_lamports(compute_unit_price_micro_lamports, compute_units),
         )
     }
 
     async fn send_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &VersionedTransaction,
+        _payer: &SealevelKeypair,
         skip_preflight: bool,
-    ) -> ChainResult<Signature> {
-        self.rpc_client
+    ) -> ChainResult<SubmittedTransaction> {
+        let signature = self
+            .rpc_client
             .send_transaction(transaction, skip_preflight)
+            .await?;
+        Ok(SubmittedTransaction {
+            signature,
+            path: SubmissionPath::Jito,
+        })
+    }
+}
+
+/// A transaction submitter that sends transactions in a Jito bundle together
+/// with a separate tip transaction, and falls back to the RPC if the bundle
+/// doesn't land.
+///
+/// Transactions keep a regular compute unit price, so they remain competitive
+/// when sent through the fallback.
+#[derive(Debug)]
+pub struct JitoBundleTransactionSubmitter {
+    client: Client,
+    url: String,
+    fallback_rpc_client: SealevelRpcClient,
+}
+
+impl JitoBundleTransactionSubmitter {
+    /// How often the status of a sent bundle is polled
+    const BUNDLE_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+    /// How long to wait for a bundle to land before falling back to the RPC
+    const BUNDLE_LANDING_TIMEOUT: Duration = Duration::from_secs(20);
+    /// How long a single request to the block engine may take
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+    /// How long a whole bundle attempt may take, including the requests made
+    /// while waiting for it to land, before falling back to the RPC
+    const BUNDLE_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(url: String, fallback_rpc_client: SealevelRpcClient) -> ChainResult<Self> {
+        let client = Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .build()
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(Self {
+            client,
+            url,
+            fallback_rpc_client,
+        })
+    }
+
+    async fn call<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> ChainResult<T> {
+        let request_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: JsonRpcResponse<T> = self
+            .client
+            .post(self.url.clone())
+            .json(&request_body)
+            .send()
             .await
+            .map_err(ChainCommunicationError::from_other)?
+            .json()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        match response {
+            JsonRpcResponse {
+                result: Some(result),
+                ..
+            } => Ok(result),
+            JsonRpcResponse { error, .. } => Err(ChainCommunicationError::from_other_str(
+                &format!("Jito `{method}` request failed: {error:?}"),
+            )),
+        }
+    }
+
+    /// Sends `transactions` as a bundle, returning the bundle id.
+    async fn send_bundle(&self, transactions: &[&VersionedTransaction]) -> ChainResult<String> {
+        let encoded = transactions
+            .iter()
+            .map(|tx| {
+                bincode::serialize(tx)
+                    .map(|bytes| bs58::encode(bytes).into_string())
+                    .map_err(ChainCommunicationError::from_other)
+            })
+            .collect::<ChainResult<Vec<_>>>()?;
+        self.call("sendBundle", serde_json::json!([encoded])).await
+    }
+
+    /// Polls the status of a bundle until it lands, fails, or the timeout is
+    /// reached. Returns whether it landed.
+    async fn wait_for_bundle(&self, bundle_id: &str) -> ChainResult<bool> {
+        let start = Instant::now();
+        while start.elapsed() < Self::BUNDLE_LANDING_TIMEOUT {
+            let statuses: InflightBundleStatuses = self
+                .call(
+                    "getInflightBundleStatuses",
+                    serde_json::json!([[bundle_id]]),
+                )
+                .await?;
+            match statuses.value.first().map(|status| status.status) {
+                Some(BundleStatus::Landed) => return Ok(true),
+                Some(BundleStatus::Failed) => return Ok(false),
+                // Bundles may not be known to the block engine right after
+                // they are sent, so `Invalid` is polled like `Pending`
+                Some(BundleStatus::Pending) | Some(BundleStatus::Invalid) | None => {}
+            }
+            tokio::time::sleep(Self::BUNDLE_STATUS_POLL_INTERVAL).await;
+        }
+        Ok(false)
+    }
+
+    /// Sends `transaction` in a bundle with a tip transaction, returning
+    /// whether the bundle landed.
+    async fn try_send_bundle(
+        &self,
+        transaction: &VersionedTransaction,
+        payer: &SealevelKeypair,
+    ) -> ChainResult<bool> {
+        let (compute_unit_price_micro_lamports, compute_units) =
+            compute_budget_of_transaction(transaction);
+        let tip_transaction: VersionedTransaction = Transaction::new_signed_with_payer(
+            &[solana_sdk::system_instruction::transfer(
+                &payer.pubkey(),
+                &JITO_TIP_ACCOUNT,
+                jito_tip_lamports(compute_unit_price_micro_lamports, compute_units),
+            )],
+            Some(&payer.pubkey()),
+            &[payer.keypair()],
+            *transaction.message.recent_blockhash(),
+        )
+        .into();
+
+        // The tip goes last, so it's only paid if the delivery lands
+        let bundle_id = self.send_bundle(&[transaction, &tip_transaction]).await?;
+        debug!(bundle_id, signature=?transaction.signatures.first(), "Sent Jito bundle");
+        self.wait_for_bundle(&bundle_id).await
+    }
+}
+
+#[async_trait]
+impl TransactionSubmitter for JitoBundleTransactionSubmitter {
+    fn get_priority_fee_instruction(
+        &self,
+        compute_unit_price_micro_lamports: u64,
+        _compute_units: u64,
+        _payer: &Pubkey,
+    ) -> Instruction {
+        ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price_micro_lamports)
+    }
+
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+        payer: &SealevelKeypair,
+        skip_preflight: bool,
+    ) -> ChainResult<SubmittedTransaction> {
+        let signature = *transaction
+            .signatures
+            .first()
+            .ok_or_else(|| ChainCommunicationError::from_other_str("Transaction is not signed"))?;
+
+        let attempt = tokio::time::timeout(
+            Self::BUNDLE_ATTEMPT_TIMEOUT,
+            self.try_send_bundle(transaction, payer),
+        );
+        match attempt.await {
+            Ok(Ok(true)) => {
+                return Ok(SubmittedTransaction {
+                    signature,
+                    path: SubmissionPath::JitoBundle,
+                })
+            }
+            Ok(Ok(false)) => warn!(?signature, "Jito bundle did not land, falling back to RPC"),
+            Ok(Err(err)) => warn!(
+                ?signature,
+                ?err,
+                "Failed to send Jito bundle, falling back to RPC"
+            ),
+            Err(_) => warn!(?signature, "Jito bundle timed out, falling back to RPC"),
+        }
+
+        // If the bundle did land after all, resending the same signed
+        // transaction is a no-op.
+        let signature = self
+            .fallback_rpc_client
+            .send_transaction(transaction, skip_preflight)
+            .await?;
+        Ok(SubmittedTransaction {
+            signature,
+            path: SubmissionPath::JitoBundleRpcFallback,
+        })
+    }
+
+    fn rpc_client(&self) -> Option<&SealevelRpcClient> {
+        Some(&self.fallback_rpc_client)
+    }
+}
+
+/// The Jito tip for a transaction with the given compute budget.
+fn jito_tip_lamports(compute_unit_price_micro_lamports: u64, compute_units: u64) -> u64 {
+    // Divide by 1_000_000 to convert from microlamports to lamports.
+    let tip_lamports = (compute_units * compute_unit_price_micro_lamports) / 1_000_000;
+    tip_lamports.max(JITO_MINIMUM_TIP_LAMPORTS)
+}
+
+/// Reads the compute unit price and limit set by the compute budget
+/// instructions of a transaction, defaulting to zero if they are not set.
+fn compute_budget_of_transaction(transaction: &VersionedTransaction) -> (u64, u64) {
+    /// Discriminants of `ComputeBudgetInstruction`
+    const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+    const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+    let message = &transaction.message;
+    let mut compute_unit_price = 0;
+    let mut compute_units = 0;
+    for instruction in message.instructions() {
+        // Program ids can't be loaded from lookup tables, so they are always
+        // among the static account keys
+        let program_id = message
+            .static_account_keys()
+            .get(instruction.program_id_index as usize);
+        if program_id != Some(&solana_sdk::compute_budget::id()) {
+            continue;
+        }
+        match instruction.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_LIMIT, data)) => {
+                if let Ok(bytes) = data.try_into() {
+                    compute_units = u32::from_le_bytes(bytes).into();
+                }
+            }
+            Some((&SET_COMPUTE_UNIT_PRICE, data)) => {
+                if let Ok(bytes) = data.try_into() {
+                    compute_unit_price = u64::from_le_bytes(bytes);
+                }
+            }
+            _ => {}
+        }
+    }
+    (compute_unit_price, compute_units)
+}
+
+/// A JSON-RPC response of the Jito block engine.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<serde_json::Value>,
+}
+
+/// The result of a `getInflightBundleStatuses` request.
+#[derive(Debug, Deserialize)]
+struct InflightBundleStatuses {
+    value: Vec<InflightBundleStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InflightBundleStatus {
+    status: BundleStatus,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+enum BundleStatus {
+    Invalid,
+    Pending,
+    Failed,
+    Landed,
+}
+
+#[cfg(test)]
+mod test {
+    use solana_sdk::{hash::Hash, message::Message, signature::Keypair};
+
+    use super::*;
+
+    #[test]
+    fn test_compute_budget_of_transaction() {
+        let payer = Keypair::new();
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000),
+            solana_sdk::system_instruction::transfer(&payer.pubkey(), &JITO_TIP_ACCOUNT, 1),
+        ];
+        let transaction: VersionedTransaction = Transaction::new_unsigned(
+            Message::new_with_blockhash(&instructions, Some(&payer.pubkey()), &Hash::default()),
+        )
+        .into();
+
+        assert_eq!(
+            compute_budget_of_transaction(&transaction),
+            (5_000, 200_000)
+        );
+    }
+
+    #[test]
+    fn test_jito_tip_lamports() {
+        assert_eq!(jito_tip_lamports(5_000, 200_000), JITO_MINIMUM_TIP_LAMPORTS);
+        assert_eq!(jito_tip_lamports(1_000_000, 200_000), 200_000);
+    }
+
+    #[test]
+    fn test_deserialize_inflight_bundle_statuses() {
+        let response: JsonRpcResponse<InflightBundleStatuses> = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","result":{"context":{"slot":280999028},"value":[{"bundle_id":"b31e5fae","status":"Landed","landed_slot":280999027}]},"id":1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.result.unwrap().value[0].status,
+            BundleStatus::Landed
+        );
     }
 }
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    AggregationIsm, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, H256,
+};
+use starknet::macros::selector;
+use tracing::instrument;
+
+use crate::{
+    contract::StarknetContract,
+    types::{encode_message, FeltReader},
+    ConnectionConf,
+};
+
+/// A reference to an AggregationIsm contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetAggregationIsm {
+    contract: StarknetContract,
+}
+
+impl StarknetAggregationIsm {
+    /// Create a reference to an aggregation ISM at a specific Starknet address
+    /// on some chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetAggregationIsm {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetAggregationIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl AggregationIsm for StarknetAggregationIsm {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn modules_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let result = self
+            .contract
+            .call(selector!("modules_and_threshold"), encode_message(message))
+            .await?;
+
+        // (Span<ContractAddress>, u8)
+        let mut reader = FeltReader::new(&result);
+        let modules = reader.array(|r| r.address())?;
+        let threshold = reader.u8()?;
+        Ok((modules, threshold))
+    }
+}
@@ -0,0 +1,111 @@
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneDomain, ReorgPeriod, TxCostEstimate,
+    TxOutcome, H256, U256,
+};
+use starknet::core::types::{BlockId, BlockTag, Call, Felt};
+
+use crate::{felt_to_h256, h256_to_felt, ConnectionConf, Signer, StarknetProvider};
+
+/// A deployed Starknet contract together with the provider used to call and
+/// invoke it. Shared by all the Hyperlane contract wrappers of this crate.
+#[derive(Debug, Clone)]
+pub(crate) struct StarknetContract {
+    address: Felt,
+    provider: StarknetProvider,
+}
+
+impl StarknetContract {
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            address: h256_to_felt(&locator.address)?,
+            provider: StarknetProvider::new(locator.domain.clone(), conf, signer).await?,
+        })
+    }
+
+    pub fn address(&self) -> H256 {
+        felt_to_h256(&self.address)
+    }
+
+    pub fn felt_address(&self) -> Felt {
+        self.address
+    }
+
+    pub fn domain(&self) -> &HyperlaneDomain {
+        self.provider.domain()
+    }
+
+    pub fn provider(&self) -> &StarknetProvider {
+        &self.provider
+    }
+
+    /// Call a view function at the latest block.
+    pub async fn call(&self, selector: Felt, calldata: Vec<Felt>) -> ChainResult<Vec<Felt>> {
+        self.call_at_block(selector, calldata, BlockId::Tag(BlockTag::Latest))
+            .await
+    }
+
+    /// Call a view function at the block implied by `reorg_period`.
+    pub async fn call_at(
+        &self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+        reorg_period: &ReorgPeriod,
+    ) -> ChainResult<Vec<Felt>> {
+        let block_id = self.provider.block_id(reorg_period).await?;
+        self.call_at_block(selector, calldata, block_id).await
+    }
+
+    pub async fn call_at_block(
+        &self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+        block_id: BlockId,
+    ) -> ChainResult<Vec<Felt>> {
+        self.provider
+            .call(self.address, selector, calldata, block_id)
+            .await
+    }
+
+    fn invocation(&self, selector: Felt, calldata: Vec<Felt>) -> Call {
+        Call {
+            to: self.address,
+            selector,
+            calldata,
+        }
+    }
+
+    pub async fn invoke(
+        &self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        self.provider
+            .invoke(self.invocation(selector, calldata), gas_limit)
+            .await
+    }
+
+    pub async fn estimate_invoke(
+        &self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+    ) -> ChainResult<TxCostEstimate> {
+        self.provider
+            .estimate_invoke(self.invocation(selector, calldata))
+            .await
+    }
+
+    pub async fn estimate_invoke_fee(
+        &self,
+        selector: Felt,
+        calldata: Vec<Felt>,
+    ) -> ChainResult<U256> {
+        self.provider
+            .estimate_invoke_fee(self.invocation(selector, calldata))
+            .await
+    }
+}
@@ -0,0 +1,67 @@
+use hyperlane_core::{H256, U256};
+use starknet::core::types::Felt;
+
+use crate::HyperlaneStarknetError;
+
+/// Starknet addresses and hashes are field elements smaller than
+/// `2^251 + 17 * 2^192 + 1`, so they map onto the low bits of an `H256`
+/// without loss.
+pub fn felt_to_h256(felt: &Felt) -> H256 {
+    H256::from(felt.to_bytes_be())
+}
+
+/// Convert an `H256` into a field element, failing if it is outside of the
+/// field rather than silently reducing it.
+pub fn h256_to_felt(value: &H256) -> Result<Felt, HyperlaneStarknetError> {
+    let felt = Felt::from_bytes_be(value.as_fixed_bytes());
+    if felt.to_bytes_be() != value.0 {
+        return Err(HyperlaneStarknetError::InvalidFelt(*value));
+    }
+    Ok(felt)
+}
+
+/// Convert a field element into a `U256`.
+pub fn felt_to_u256(felt: &Felt) -> U256 {
+    U256::from_big_endian(&felt.to_bytes_be())
+}
+
+/// Split a `U256` into the `(low, high)` felts of a Cairo `u256`.
+pub fn u256_to_felts(value: U256) -> [Felt; 2] {
+    let low = value.low_u128();
+    let high = (value >> 128).low_u128();
+    [Felt::from(low), Felt::from(high)]
+}
+
+/// Split an `H256` into the `(low, high)` felts of a Cairo `u256`. Hyperlane
+/// contracts on Starknet store message ids, senders and recipients as `u256`.
+pub fn h256_to_u256_felts(value: &H256) -> [Felt; 2] {
+    u256_to_felts(U256::from_big_endian(value.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_felt_h256_roundtrip() {
+        // The STRK token address
+        let address =
+            H256::from_str("04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d")
+                .unwrap();
+        let felt = h256_to_felt(&address).unwrap();
+        assert_eq!(felt_to_h256(&felt), address);
+    }
+
+    #[test]
+    fn test_h256_out_of_field_is_rejected() {
+        assert!(h256_to_felt(&H256::repeat_byte(0xff)).is_err());
+    }
+
+    #[test]
+    fn test_u256_split() {
+        let value = (U256::from(7) << 128) + U256::from(9);
+        assert_eq!(u256_to_felts(value), [Felt::from(9u64), Felt::from(7u64)]);
+    }
+}
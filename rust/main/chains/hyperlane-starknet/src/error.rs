@@ -0,0 +1,34 @@
+use hyperlane_core::ChainCommunicationError;
+use starknet::providers::ProviderError;
+
+/// Errors from the crates specific to the hyperlane-starknet
+/// implementation.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum HyperlaneStarknetError {
+    /// Starknet JSON-RPC provider error
+    #[error("{0}")]
+    ProviderError(#[from] ProviderError),
+    /// Error building, signing or sending a transaction from the account
+    #[error("{0}")]
+    AccountError(String),
+    /// A value returned by a contract or event could not be decoded
+    #[error("{0}")]
+    DecodeError(String),
+    /// A 32 byte value does not fit into a Starknet field element
+    #[error("Value {0:?} is not a valid Starknet field element")]
+    InvalidFelt(hyperlane_core::H256),
+    /// The transaction was not included within the expected time
+    #[error("Transaction {0:#x} was not included in time")]
+    TransactionTimeout(starknet::core::types::Felt),
+    /// Signer is required to submit transactions
+    #[error("A signer is required to submit transactions")]
+    SignerMissing,
+}
+
+impl From<HyperlaneStarknetError> for ChainCommunicationError {
+    fn from(value: HyperlaneStarknetError) -> Self {
+        ChainCommunicationError::from_other(value)
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, Indexed, Indexer, InterchainGasPaymaster, InterchainGasPayment, LogMeta,
+    ReorgPeriod, SequenceAwareIndexer, H256,
+};
+use starknet::macros::selector;
+
+use crate::{contract::StarknetContract, types::FeltReader, ConnectionConf};
+
+/// A reference to an IGP contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetInterchainGasPaymaster {
+    contract: StarknetContract,
+}
+
+impl StarknetInterchainGasPaymaster {
+    /// Create a reference to an IGP at a specific Starknet address on some
+    /// chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetInterchainGasPaymaster {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetInterchainGasPaymaster {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+impl InterchainGasPaymaster for StarknetInterchainGasPaymaster {}
+
+/// Struct that retrieves event data for a Starknet IGP contract
+#[derive(Debug)]
+pub struct StarknetInterchainGasPaymasterIndexer {
+    contract: StarknetContract,
+    reorg_period: ReorgPeriod,
+}
+
+impl StarknetInterchainGasPaymasterIndexer {
+    /// Create a new StarknetInterchainGasPaymasterIndexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        reorg_period: &ReorgPeriod,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+            reorg_period: reorg_period.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<InterchainGasPayment> for StarknetInterchainGasPaymasterIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
+        let logs = self
+            .contract
+            .provider()
+            .fetch_logs_in_range(range, self.contract.felt_address(), selector!("GasPayment"))
+            .await?;
+        logs.into_iter()
+            .map(|(event, log_meta)| -> ChainResult<_> {
+                // GasPayment { message_id: u256, destination_domain: u32,
+                //   gas_amount: u256, payment: u256 }
+                let mut reader = FeltReader::new(&event.data);
+                let payment = InterchainGasPayment {
+                    message_id: reader.h256()?,
+                    destination: reader.u32()?,
+                    gas_amount: reader.u256()?,
+                    payment: reader.u256()?,
+                };
+                Ok((payment.into(), log_meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.contract
+            .provider()
+            .get_finalized_block_number(&self.reorg_period)
+            .await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<InterchainGasPayment> for StarknetInterchainGasPaymasterIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+
+        // The IGP does not expose a payment sequence.
+        Ok((None, tip))
+    }
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType, H256, U256,
+};
+use starknet::macros::selector;
+use tracing::{instrument, warn};
+
+use crate::{
+    contract::StarknetContract,
+    types::{encode_bytes, encode_message, FeltReader},
+    ConnectionConf, HyperlaneStarknetError,
+};
+
+/// A reference to an InterchainSecurityModule contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetInterchainSecurityModule {
+    contract: StarknetContract,
+}
+
+impl StarknetInterchainSecurityModule {
+    /// Create a reference to an ISM at a specific Starknet address on some
+    /// chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetInterchainSecurityModule {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetInterchainSecurityModule {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for StarknetInterchainSecurityModule {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        let result = self.contract.call(selector!("module_type"), vec![]).await?;
+        // The Cairo `ModuleType` variants may carry the module address as a
+        // payload; only the variant index matters here.
+        let index = FeltReader::new(&result).u8()?;
+        Ok(module_type_from_index(index)?)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        let mut calldata = encode_bytes(metadata);
+        calldata.extend(encode_message(message));
+
+        // A reverting `verify` is how most ISMs reject metadata, so it is not
+        // treated as an RPC error.
+        let verified = match self.contract.call(selector!("verify"), calldata).await {
+            Ok(result) => FeltReader::new(&result).bool()?,
+            Err(err) => {
+                warn!(?err, "Starknet ISM verify call failed");
+                false
+            }
+        };
+        // View calls are not metered, so a dummy gas value is used like for
+        // CosmWasm ISMs.
+        let dummy_gas_value = U256::one();
+        Ok(verified.then_some(dummy_gas_value))
+    }
+}
+
+/// Map the variant index of the Cairo `ModuleType` enum onto the core type.
+fn module_type_from_index(index: u8) -> Result<ModuleType, HyperlaneStarknetError> {
+    match index {
+        0 => Ok(ModuleType::Unused),
+        1 => Ok(ModuleType::Routing),
+        2 => Ok(ModuleType::Aggregation),
+        3 => Ok(ModuleType::LegacyMultisig),
+        4 => Ok(ModuleType::MerkleRootMultisig),
+        5 => Ok(ModuleType::MessageIdMultisig),
+        6 => Ok(ModuleType::Null),
+        7 => Ok(ModuleType::CcipRead),
+        _ => Err(HyperlaneStarknetError::DecodeError(format!(
+            "Unknown ISM module type {index}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_type_from_index() {
+        assert_eq!(
+            module_type_from_index(5).unwrap(),
+            ModuleType::MessageIdMultisig
+        );
+        assert_eq!(module_type_from_index(1).unwrap(), ModuleType::Routing);
+        assert_eq!(module_type_from_index(0).unwrap(), ModuleType::Unused);
+        assert!(module_type_from_index(8).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+//! Implementation of hyperlane for Starknet.
+//!
+//! Contracts are called through their Cairo ABI: calldata and return values
+//! are Cairo-serialized felts (see `types`), and events are matched on the
+//! selector of the event name in their first key.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use self::{
+    aggregation_ism::*, conversions::*, error::*, interchain_gas::*, interchain_security_module::*,
+    mailbox::*, merkle_tree_hook::*, multisig_ism::*, provider::*, routing_ism::*, signers::*,
+    trait_builder::*, validator_announce::*,
+};
+
+mod aggregation_ism;
+mod contract;
+mod conversions;
+mod error;
+mod interchain_gas;
+mod interchain_security_module;
+mod mailbox;
+mod merkle_tree_hook;
+mod multisig_ism;
+mod provider;
+mod routing_ism;
+mod signers;
+#[cfg(test)]
+mod tests;
+mod trait_builder;
+mod types;
+mod validator_announce;
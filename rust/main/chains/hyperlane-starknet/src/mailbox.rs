@@ -0,0 +1,256 @@
+use std::{
+    fmt::{Debug, Formatter},
+    ops::RangeInclusive,
+};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
+    ReorgPeriod, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H256, U256,
+};
+use starknet::{
+    core::types::{BlockId, Felt},
+    macros::selector,
+};
+use tracing::instrument;
+
+use crate::{
+    contract::StarknetContract,
+    h256_to_u256_felts,
+    types::{encode_bytes, encode_message, FeltReader},
+    ConnectionConf, Signer, StarknetProvider,
+};
+
+/// A reference to a Mailbox contract on some Starknet chain
+pub struct StarknetMailbox {
+    contract: StarknetContract,
+}
+
+impl StarknetMailbox {
+    /// Create a reference to a mailbox at a specific Starknet address on some
+    /// chain
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, signer).await?,
+        })
+    }
+
+    fn process_args(message: &HyperlaneMessage, metadata: &[u8]) -> Vec<Felt> {
+        let mut calldata = encode_bytes(metadata);
+        calldata.extend(encode_message(message));
+        calldata
+    }
+}
+
+impl HyperlaneContract for StarknetMailbox {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+impl Debug for StarknetMailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
+    }
+}
+
+#[async_trait]
+impl Mailbox for StarknetMailbox {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        let result = self
+            .contract
+            .call_at(selector!("nonce"), vec![], reorg_period)
+            .await?;
+        Ok(FeltReader::new(&result).u32()?)
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        let result = self
+            .contract
+            .call(selector!("delivered"), h256_to_u256_felts(&id).to_vec())
+            .await?;
+        Ok(FeltReader::new(&result).bool()?)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn default_ism(&self) -> ChainResult<H256> {
+        let result = self
+            .contract
+            .call(selector!("get_default_ism"), vec![])
+            .await?;
+        Ok(FeltReader::new(&result).address()?)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        let result = self
+            .contract
+            .call(
+                selector!("recipient_ism"),
+                h256_to_u256_felts(&recipient).to_vec(),
+            )
+            .await?;
+        Ok(FeltReader::new(&result).address()?)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        self.contract
+            .invoke(
+                selector!("process"),
+                Self::process_args(message, metadata),
+                tx_gas_limit,
+            )
+            .await
+    }
+
+    #[instrument(err, ret, skip(self), fields(hyp_message=%message, metadata=%bytes_to_hex(metadata)))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        self.contract
+            .estimate_invoke(selector!("process"), Self::process_args(message, metadata))
+            .await
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        std::iter::once(selector!("process"))
+            .chain(Self::process_args(message, metadata))
+            .flat_map(|felt| felt.to_bytes_be())
+            .collect()
+    }
+}
+
+/// Struct that retrieves event data for a Starknet Mailbox contract
+#[derive(Debug)]
+pub struct StarknetMailboxIndexer {
+    contract: StarknetContract,
+    reorg_period: ReorgPeriod,
+}
+
+impl StarknetMailboxIndexer {
+    /// Create a new StarknetMailboxIndexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        reorg_period: &ReorgPeriod,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+            reorg_period: reorg_period.clone(),
+        })
+    }
+
+    fn provider(&self) -> &StarknetProvider {
+        self.contract.provider()
+    }
+}
+
+#[async_trait]
+impl Indexer<HyperlaneMessage> for StarknetMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+        let logs = self
+            .provider()
+            .fetch_logs_in_range(range, self.contract.felt_address(), selector!("Dispatch"))
+            .await?;
+        logs.into_iter()
+            .map(|(event, log_meta)| -> ChainResult<_> {
+                // Dispatch { sender: u256, destination_domain: u32,
+                //   recipient_address: u256, message: Message }
+                let mut reader = FeltReader::new(&event.data);
+                reader.u256()?;
+                reader.u32()?;
+                reader.u256()?;
+                let message = reader.message()?;
+                Ok((message.into(), log_meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider()
+            .get_finalized_block_number(&self.reorg_period)
+            .await
+    }
+}
+
+#[async_trait]
+impl Indexer<H256> for StarknetMailboxIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
+        let logs = self
+            .provider()
+            .fetch_logs_in_range(range, self.contract.felt_address(), selector!("ProcessId"))
+            .await?;
+        logs.into_iter()
+            .map(|(event, log_meta)| -> ChainResult<_> {
+                let message_id = FeltReader::new(&event.data).h256()?;
+                Ok((message_id.into(), log_meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider()
+            .get_finalized_block_number(&self.reorg_period)
+            .await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for StarknetMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+
+        // No sequence for message deliveries.
+        Ok((None, tip))
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for StarknetMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        let result = self
+            .contract
+            .call_at_block(selector!("nonce"), vec![], BlockId::Number(tip.into()))
+            .await?;
+        let sequence = FeltReader::new(&result).u32()?;
+        Ok((Some(sequence), tip))
+    }
+}
@@ -0,0 +1,183 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    accumulator::{incremental::IncrementalMerkle, TREE_DEPTH},
+    ChainResult, Checkpoint, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, Indexed, Indexer, LogMeta, MerkleTreeHook, MerkleTreeInsertion, ReorgPeriod,
+    SequenceAwareIndexer, H256, U256,
+};
+use starknet::{core::types::BlockId, macros::selector};
+use tracing::instrument;
+
+use crate::{
+    contract::StarknetContract, types::FeltReader, ConnectionConf, HyperlaneStarknetError,
+};
+
+/// A reference to a MerkleTreeHook contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetMerkleTreeHook {
+    contract: StarknetContract,
+}
+
+impl StarknetMerkleTreeHook {
+    /// Create a reference to a merkle tree hook at a specific Starknet
+    /// address on some chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetMerkleTreeHook {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetMerkleTreeHook {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl MerkleTreeHook for StarknetMerkleTreeHook {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn tree(&self, reorg_period: &ReorgPeriod) -> ChainResult<IncrementalMerkle> {
+        let result = self
+            .contract
+            .call_at(selector!("tree"), vec![], reorg_period)
+            .await?;
+
+        // Tree { branch: Array<ByteData { value: u256, size: u32 }>, count: u256 }
+        let mut reader = FeltReader::new(&result);
+        let nodes = reader.array(|r| {
+            let node = r.h256()?;
+            r.u32()?;
+            Ok(node)
+        })?;
+        let count = reader.u256()?;
+
+        // The contract only stores the branch nodes that have been written
+        if nodes.len() > TREE_DEPTH {
+            return Err(HyperlaneStarknetError::DecodeError(format!(
+                "Merkle tree branch has {} nodes, expected at most {TREE_DEPTH}",
+                nodes.len()
+            ))
+            .into());
+        }
+        let mut branch = [H256::zero(); TREE_DEPTH];
+        branch[..nodes.len()].copy_from_slice(&nodes);
+        if count > U256::from(u32::MAX) {
+            return Err(HyperlaneStarknetError::DecodeError(format!(
+                "Merkle tree count {count} exceeds u32"
+            ))
+            .into());
+        }
+        let count = count.as_usize();
+        Ok(IncrementalMerkle::new(branch, count))
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        let result = self
+            .contract
+            .call_at(selector!("count"), vec![], reorg_period)
+            .await?;
+        Ok(FeltReader::new(&result).u32()?)
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn latest_checkpoint(&self, reorg_period: &ReorgPeriod) -> ChainResult<Checkpoint> {
+        let result = self
+            .contract
+            .call_at(selector!("latest_checkpoint"), vec![], reorg_period)
+            .await?;
+        let mut reader = FeltReader::new(&result);
+
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address(),
+            mailbox_domain: self.domain().id(),
+            root: reader.h256()?,
+            index: reader.u32()?,
+        })
+    }
+}
+
+/// Struct that retrieves insertion events for a Starknet MerkleTreeHook
+/// contract
+#[derive(Debug)]
+pub struct StarknetMerkleTreeHookIndexer {
+    contract: StarknetContract,
+    reorg_period: ReorgPeriod,
+}
+
+impl StarknetMerkleTreeHookIndexer {
+    /// Create a new StarknetMerkleTreeHookIndexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        reorg_period: &ReorgPeriod,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+            reorg_period: reorg_period.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for StarknetMerkleTreeHookIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let logs = self
+            .contract
+            .provider()
+            .fetch_logs_in_range(
+                range,
+                self.contract.felt_address(),
+                selector!("InsertedIntoTree"),
+            )
+            .await?;
+        logs.into_iter()
+            .map(|(event, log_meta)| -> ChainResult<_> {
+                // InsertedIntoTree { id: u256, index: u32 }
+                let mut reader = FeltReader::new(&event.data);
+                let message_id = reader.h256()?;
+                let index = reader.u32()?;
+                Ok((MerkleTreeInsertion::new(index, message_id).into(), log_meta))
+            })
+            .collect()
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.contract
+            .provider()
+            .get_finalized_block_number(&self.reorg_period)
+            .await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for StarknetMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+        let result = self
+            .contract
+            .call_at_block(selector!("count"), vec![], BlockId::Number(tip.into()))
+            .await?;
+        let sequence = FeltReader::new(&result).u32()?;
+        Ok((Some(sequence), tip))
+    }
+}
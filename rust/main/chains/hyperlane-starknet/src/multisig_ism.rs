@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, MultisigIsm, H256,
+};
+use starknet::macros::selector;
+use tracing::instrument;
+
+use crate::{
+    contract::StarknetContract,
+    types::{encode_message, FeltReader},
+    ConnectionConf, HyperlaneStarknetError,
+};
+
+/// A reference to a MultisigIsm contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetMultisigIsm {
+    contract: StarknetContract,
+}
+
+impl StarknetMultisigIsm {
+    /// Create a reference to a multisig ISM at a specific Starknet address on
+    /// some chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetMultisigIsm {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetMultisigIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl MultisigIsm for StarknetMultisigIsm {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn validators_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let result = self
+            .contract
+            .call(
+                selector!("validators_and_threshold"),
+                encode_message(message),
+            )
+            .await?;
+
+        // (Span<EthAddress>, u32), where each EthAddress is a single felt
+        let mut reader = FeltReader::new(&result);
+        let validators = reader.array(|r| r.address())?;
+        let threshold = reader.u32()?;
+        let threshold = u8::try_from(threshold).map_err(|_| {
+            HyperlaneStarknetError::DecodeError(format!("Threshold {threshold} exceeds u8"))
+        })?;
+        Ok((validators, threshold))
+    }
+}
@@ -0,0 +1,486 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use hyperlane_core::{
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, LogMeta, ReorgPeriod, TxCostEstimate, TxOutcome,
+    TxnInfo, H256, H512, U256,
+};
+use starknet::{
+    accounts::{Account, ExecutionEncoding, SingleOwnerAccount},
+    core::types::{
+        BlockId, BlockTag, Call, EmittedEvent, EventFilter, ExecutionResult, FeeEstimate, Felt,
+        FunctionCall, InvokeTransaction, MaybePendingBlockWithTxHashes, StarknetError, Transaction,
+        TransactionReceiptWithBlockInfo,
+    },
+    macros::{felt, selector},
+    providers::{
+        jsonrpc::{HttpTransport, JsonRpcClient},
+        Provider, ProviderError,
+    },
+    signers::LocalWallet,
+};
+use tokio::time::{sleep, Instant};
+use tracing::{debug, instrument};
+
+use crate::{
+    felt_to_h256, felt_to_u256, h256_to_felt, types::FeltReader, ConnectionConf,
+    HyperlaneStarknetError, Signer,
+};
+
+type RpcClient = JsonRpcClient<HttpTransport>;
+type StarknetAccount = SingleOwnerAccount<RpcClient, LocalWallet>;
+
+/// The STRK fee token, deployed at the same address on every Starknet
+/// network, including starknet-devnet.
+const STRK_TOKEN_ADDRESS: Felt =
+    felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
+
+/// Number of events requested per `starknet_getEvents` page.
+const EVENTS_CHUNK_SIZE: u64 = 1000;
+
+/// How long to wait for a submitted transaction to be included.
+const TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(120);
+const TX_INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A wrapper around a Starknet JSON-RPC client, and optionally the account
+/// used to submit transactions.
+#[derive(Clone)]
+pub struct StarknetProvider {
+    domain: HyperlaneDomain,
+    rpc_client: Arc<RpcClient>,
+    account: Option<Arc<StarknetAccount>>,
+}
+
+impl StarknetProvider {
+    /// Create a new Starknet provider. The chain id is only queried when a
+    /// signer is given.
+    pub async fn new(
+        domain: HyperlaneDomain,
+        conf: &ConnectionConf,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        let rpc_client = Arc::new(JsonRpcClient::new(HttpTransport::new(conf.url.clone())));
+
+        let account = match signer {
+            Some(signer) => {
+                let chain_id = rpc_client
+                    .chain_id()
+                    .await
+                    .map_err(HyperlaneStarknetError::from)?;
+                let encoding = if signer.is_legacy {
+                    ExecutionEncoding::Legacy
+                } else {
+                    ExecutionEncoding::New
+                };
+                let mut account = SingleOwnerAccount::new(
+                    JsonRpcClient::new(HttpTransport::new(conf.url.clone())),
+                    signer.local_wallet(),
+                    signer.address,
+                    chain_id,
+                    encoding,
+                );
+                // Nonces and fee estimates have to account for transactions
+                // that are not yet in a block.
+                account.set_block_id(BlockId::Tag(BlockTag::Pending));
+                Some(Arc::new(account))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            domain,
+            rpc_client,
+            account,
+        })
+    }
+
+    /// Get the inner JSON-RPC client
+    pub fn rpc_client(&self) -> &JsonRpcClient<HttpTransport> {
+        &self.rpc_client
+    }
+
+    fn account(&self) -> ChainResult<&StarknetAccount> {
+        self.account
+            .as_deref()
+            .ok_or_else(|| HyperlaneStarknetError::SignerMissing.into())
+    }
+
+    /// The latest block that is at least `reorg_period` blocks behind the tip.
+    /// Starknet has no finality tags beyond the latest accepted block, so
+    /// every supported tag resolves to it.
+    pub async fn get_finalized_block_number(&self, reorg_period: &ReorgPeriod) -> ChainResult<u32> {
+        let blocks = match reorg_period {
+            ReorgPeriod::None => 0,
+            ReorgPeriod::Blocks(blocks) => blocks.get(),
+            ReorgPeriod::Tag(tag) if matches!(tag.as_str(), "finalized" | "safe" | "latest") => 0,
+            ReorgPeriod::Tag(_) => {
+                return Err(ChainCommunicationError::InvalidReorgPeriod(
+                    reorg_period.clone(),
+                ))
+            }
+        };
+        let tip = self
+            .rpc_client
+            .block_number()
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+        let finalized = tip.saturating_sub(blocks.into());
+        u32::try_from(finalized).map_err(ChainCommunicationError::from_other)
+    }
+
+    /// The block that state should be read at for the given reorg period.
+    pub(crate) async fn block_id(&self, reorg_period: &ReorgPeriod) -> ChainResult<BlockId> {
+        match reorg_period {
+            ReorgPeriod::None => Ok(BlockId::Tag(BlockTag::Latest)),
+            _ => self
+                .get_finalized_block_number(reorg_period)
+                .await
+                .map(|number| BlockId::Number(number.into())),
+        }
+    }
+
+    /// Call a view function of a contract.
+    pub(crate) async fn call(
+        &self,
+        contract_address: Felt,
+        entry_point_selector: Felt,
+        calldata: Vec<Felt>,
+        block_id: BlockId,
+    ) -> ChainResult<Vec<Felt>> {
+        let call = FunctionCall {
+            contract_address,
+            entry_point_selector,
+            calldata,
+        };
+        Ok(self
+            .rpc_client
+            .call(call, block_id)
+            .await
+            .map_err(HyperlaneStarknetError::from)?)
+    }
+
+    /// The address of the account transactions are sent from, if a signer
+    /// is configured.
+    pub fn account_address(&self) -> Option<Felt> {
+        self.account.as_ref().map(|account| account.address())
+    }
+
+    async fn fee_estimate(&self, call: Call) -> ChainResult<FeeEstimate> {
+        Ok(self
+            .account()?
+            .execute_v3(vec![call])
+            .estimate_fee()
+            .await
+            .map_err(|e| HyperlaneStarknetError::AccountError(e.to_string()))?)
+    }
+
+    /// Estimate the cost of invoking `call` from the signer's account.
+    pub(crate) async fn estimate_invoke(&self, call: Call) -> ChainResult<TxCostEstimate> {
+        let estimate = self.fee_estimate(call).await?;
+        Ok(TxCostEstimate {
+            gas_limit: felt_to_u256(&estimate.gas_consumed),
+            gas_price: felt_to_u256(&estimate.gas_price).try_into()?,
+            l2_gas_limit: None,
+        })
+    }
+
+    /// Estimate the total fee, in fri, of invoking `call` from the signer's
+    /// account.
+    pub(crate) async fn estimate_invoke_fee(&self, call: Call) -> ChainResult<U256> {
+        let estimate = self.fee_estimate(call).await?;
+        Ok(felt_to_u256(&estimate.overall_fee))
+    }
+
+    /// Invoke `call` from the signer's account and wait for it to be
+    /// included.
+    #[instrument(err, skip(self, call))]
+    pub(crate) async fn invoke(
+        &self,
+        call: Call,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let mut execution = self.account()?.execute_v3(vec![call]);
+        if let Some(gas_limit) = gas_limit {
+            execution = execution.gas(u64::try_from(gas_limit).unwrap_or(u64::MAX));
+        }
+
+        let sent = execution
+            .send()
+            .await
+            .map_err(|e| HyperlaneStarknetError::AccountError(e.to_string()))?;
+        debug!(tx_hash = ?sent.transaction_hash, "Submitted Starknet transaction");
+
+        let receipt = self.wait_for_receipt(sent.transaction_hash).await?;
+        Ok(TxOutcome {
+            transaction_id: H512::from(felt_to_h256(&sent.transaction_hash)),
+            executed: matches!(
+                receipt.receipt.execution_result(),
+                ExecutionResult::Succeeded
+            ),
+            // Fees are reported as a total rather than gas used times price
+            gas_used: felt_to_u256(&receipt.receipt.actual_fee().amount),
+            gas_price: U256::one().try_into()?,
+        })
+    }
+
+    async fn wait_for_receipt(
+        &self,
+        tx_hash: Felt,
+    ) -> ChainResult<TransactionReceiptWithBlockInfo> {
+        let start = Instant::now();
+        loop {
+            match self.rpc_client.get_transaction_receipt(tx_hash).await {
+                Ok(receipt) => return Ok(receipt),
+                Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound))
+                    if start.elapsed() < TX_INCLUSION_TIMEOUT =>
+                {
+                    sleep(TX_INCLUSION_POLL_INTERVAL).await;
+                }
+                Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {
+                    return Err(HyperlaneStarknetError::TransactionTimeout(tx_hash).into())
+                }
+                Err(e) => return Err(HyperlaneStarknetError::from(e).into()),
+            }
+        }
+    }
+
+    /// Fetch the events emitted by `address` in `range` whose first key is
+    /// `selector`, i.e. events of a single type.
+    ///
+    /// `starknet_getEvents` does not return the position of an event, so the
+    /// transaction index is looked up from the block and the log index is
+    /// the position of the event among the contract's events in its
+    /// transaction.
+    pub(crate) async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+        address: Felt,
+        selector: Felt,
+    ) -> ChainResult<Vec<(EmittedEvent, LogMeta)>> {
+        let filter = EventFilter {
+            from_block: Some(BlockId::Number((*range.start()).into())),
+            to_block: Some(BlockId::Number((*range.end()).into())),
+            address: Some(address),
+            keys: Some(vec![vec![selector]]),
+        };
+
+        let mut events = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .rpc_client
+                .get_events(filter.clone(), continuation_token, EVENTS_CHUNK_SIZE)
+                .await
+                .map_err(HyperlaneStarknetError::from)?;
+            events.extend(page.events);
+            continuation_token = page.continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        let mut block_numbers: Vec<u64> = events.iter().filter_map(|e| e.block_number).collect();
+        block_numbers.dedup();
+        let blocks = try_join_all(
+            block_numbers
+                .into_iter()
+                .map(|number| self.block_transactions(number)),
+        )
+        .await?;
+        let tx_indexes: HashMap<Felt, u64> = blocks
+            .into_iter()
+            .flat_map(|txs| txs.into_iter().zip(0u64..))
+            .collect();
+
+        let mut logs_per_tx: HashMap<Felt, u64> = HashMap::new();
+        events
+            .into_iter()
+            .map(|event| -> ChainResult<_> {
+                let (Some(block_number), Some(block_hash)) = (event.block_number, event.block_hash)
+                else {
+                    return Err(HyperlaneStarknetError::DecodeError(format!(
+                        "Event in transaction {:#x} is not in an accepted block",
+                        event.transaction_hash
+                    ))
+                    .into());
+                };
+                let log_index = logs_per_tx.entry(event.transaction_hash).or_default();
+                let meta = LogMeta {
+                    address: felt_to_h256(&event.from_address),
+                    block_number,
+                    block_hash: felt_to_h256(&block_hash),
+                    transaction_id: H512::from(felt_to_h256(&event.transaction_hash)),
+                    transaction_index: tx_indexes
+                        .get(&event.transaction_hash)
+                        .copied()
+                        .unwrap_or_default(),
+                    log_index: U256::from(*log_index),
+                };
+                *log_index += 1;
+                Ok((event, meta))
+            })
+            .collect()
+    }
+
+    async fn block_transactions(&self, number: u64) -> ChainResult<Vec<Felt>> {
+        match self
+            .rpc_client
+            .get_block_with_tx_hashes(BlockId::Number(number))
+            .await
+            .map_err(HyperlaneStarknetError::from)?
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(block.transactions),
+            MaybePendingBlockWithTxHashes::PendingBlock(block) => Ok(block.transactions),
+        }
+    }
+}
+
+impl Debug for StarknetProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StarknetProvider")
+            .field("domain", &self.domain)
+            .field(
+                "account",
+                &self.account.as_ref().map(|a| format!("{:#x}", a.address())),
+            )
+            .finish()
+    }
+}
+
+impl HyperlaneChain for StarknetProvider {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl HyperlaneProvider for StarknetProvider {
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let block = self
+            .rpc_client
+            .get_block_with_tx_hashes(BlockId::Number(height))
+            .await
+            .map_err(|_| HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+
+        match block {
+            MaybePendingBlockWithTxHashes::Block(block) => {
+                if block.block_number != height {
+                    Err(HyperlaneProviderError::IncorrectBlockByHeight(
+                        height,
+                        block.block_number,
+                    ))?;
+                }
+                Ok(BlockInfo {
+                    hash: felt_to_h256(&block.block_hash),
+                    timestamp: block.timestamp,
+                    number: block.block_number,
+                })
+            }
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+                Err(HyperlaneProviderError::CouldNotFindBlockByHeight(height).into())
+            }
+        }
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let tx_hash = h256_to_felt(&H256::from(*hash))?;
+        let transaction = self
+            .rpc_client
+            .get_transaction_by_hash(tx_hash)
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+
+        let (sender, nonce, gas_limit, gas_price) = match &transaction {
+            Transaction::Invoke(InvokeTransaction::V3(tx)) => (
+                tx.sender_address,
+                tx.nonce,
+                U256::from(tx.resource_bounds.l1_gas.max_amount),
+                Some(U256::from(tx.resource_bounds.l1_gas.max_price_per_unit)),
+            ),
+            Transaction::Invoke(InvokeTransaction::V1(tx)) => {
+                (tx.sender_address, tx.nonce, U256::zero(), None)
+            }
+            _ => {
+                return Err(HyperlaneStarknetError::DecodeError(format!(
+                    "Transaction {tx_hash:#x} is not an invoke transaction"
+                ))
+                .into())
+            }
+        };
+        let nonce = FeltReader::new(&[nonce]).u128()?;
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit,
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price,
+            nonce: u64::try_from(nonce).map_err(ChainCommunicationError::from_other)?,
+            sender: felt_to_h256(&sender),
+            // Starknet transactions are multicalls from the sender account
+            recipient: None,
+            receipt: None,
+            raw_input_data: None,
+        })
+    }
+
+    async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
+        let address = h256_to_felt(address)?;
+        match self
+            .rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), address)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => Ok(false),
+            Err(e) => Err(HyperlaneStarknetError::from(e).into()),
+        }
+    }
+
+    async fn get_balance(&self, address: String) -> ChainResult<U256> {
+        let address = Felt::from_hex(&address).map_err(|e| {
+            ChainCommunicationError::CustomError(format!("Invalid address {address}: {e}"))
+        })?;
+        let balance = self
+            .call(
+                STRK_TOKEN_ADDRESS,
+                selector!("balance_of"),
+                vec![address],
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await?;
+        Ok(FeltReader::new(&balance).u256()?)
+    }
+
+    async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
+        let block = self
+            .rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .map_err(HyperlaneStarknetError::from)?;
+
+        match block {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(Some(ChainInfo {
+                latest_block: BlockInfo {
+                    hash: felt_to_h256(&block.block_hash),
+                    timestamp: block.timestamp,
+                    number: block.block_number,
+                },
+                min_gas_price: Some(felt_to_u256(&block.l1_gas_price.price_in_fri)),
+            })),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => Ok(None),
+        }
+    }
+}
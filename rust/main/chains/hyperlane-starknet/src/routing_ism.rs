@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, RoutingIsm, H256,
+};
+use starknet::macros::selector;
+use tracing::instrument;
+
+use crate::{
+    contract::StarknetContract,
+    types::{encode_message, FeltReader},
+    ConnectionConf,
+};
+
+/// A reference to a RoutingIsm contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetRoutingIsm {
+    contract: StarknetContract,
+}
+
+impl StarknetRoutingIsm {
+    /// Create a reference to a routing ISM at a specific Starknet address on
+    /// some chain
+    pub async fn new(conf: &ConnectionConf, locator: ContractLocator<'_>) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, None).await?,
+        })
+    }
+}
+
+impl HyperlaneContract for StarknetRoutingIsm {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetRoutingIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl RoutingIsm for StarknetRoutingIsm {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let result = self
+            .contract
+            .call(selector!("route"), encode_message(message))
+            .await?;
+        Ok(FeltReader::new(&result).address()?)
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::{Debug, Formatter};
+
+use hyperlane_core::{ChainResult, H256};
+use starknet::{
+    core::types::Felt,
+    signers::{LocalWallet, SigningKey},
+};
+
+use crate::{felt_to_h256, h256_to_felt};
+
+/// Signer for Starknet chains. Starknet accounts are contracts, so the
+/// address of the account contract has to be configured alongside its key.
+#[derive(Clone)]
+pub struct Signer {
+    /// The address of the account contract
+    pub address: Felt,
+    /// Whether the account contract expects the legacy (Cairo 0) calldata
+    /// encoding for multicalls
+    pub is_legacy: bool,
+    private_key: Felt,
+}
+
+impl Signer {
+    /// create new signer
+    ///
+    /// # Arguments
+    /// * `private_key` - stark private key of the account
+    /// * `address` - address of the account contract
+    /// * `is_legacy` - whether the account uses the legacy calldata encoding
+    pub fn new(private_key: &H256, address: &H256, is_legacy: bool) -> ChainResult<Self> {
+        Ok(Self {
+            address: h256_to_felt(address)?,
+            is_legacy,
+            private_key: h256_to_felt(private_key)?,
+        })
+    }
+
+    /// The account address as an `H256`
+    pub fn address_h256(&self) -> H256 {
+        felt_to_h256(&self.address)
+    }
+
+    /// Build a wallet that signs transactions for the account
+    pub fn local_wallet(&self) -> LocalWallet {
+        LocalWallet::from(SigningKey::from_secret_scalar(self.private_key))
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("address", &format_args!("{:#x}", self.address))
+            .field("is_legacy", &self.is_legacy)
+            .finish()
+    }
+}
@@ -0,0 +1,176 @@
+//! Tests against a starknet-devnet node with the Hyperlane contracts deployed
+//! and some messages dispatched, e.g. by the starknet e2e setup. They are
+//! ignored by default, run them with
+//! `cargo test -p hyperlane-starknet -- --ignored` after setting:
+//! - `STARKNET_RPC_URL`, defaulting to a local devnet
+//! - `STARKNET_MAILBOX_ADDRESS`, `STARKNET_MERKLE_TREE_HOOK_ADDRESS`,
+//!   `STARKNET_IGP_ADDRESS` and `STARKNET_VALIDATOR_ANNOUNCE_ADDRESS`
+//! - `STARKNET_ACCOUNT_ADDRESS` and `STARKNET_ACCOUNT_KEY`, one of the
+//!   accounts the devnet funds
+
+use std::str::FromStr;
+
+use ethers::signers::LocalWallet;
+use hyperlane_core::{
+    Announcement, ContractLocator, HyperlaneDomain, HyperlaneMessage, HyperlaneSigner,
+    HyperlaneSignerExt, Indexer, KnownHyperlaneDomain, Mailbox, MerkleTreeHook, ReorgPeriod,
+    SequenceAwareIndexer, ValidatorAnnounce, H256,
+};
+use hyperlane_ethereum::Signers;
+use url::Url;
+
+use crate::{
+    ConnectionConf, Signer, StarknetInterchainGasPaymasterIndexer, StarknetMailbox,
+    StarknetMailboxIndexer, StarknetMerkleTreeHook, StarknetMerkleTreeHookIndexer,
+    StarknetValidatorAnnounce,
+};
+
+const DEVNET_RPC_URL: &str = "http://127.0.0.1:5050/rpc";
+
+fn conf() -> ConnectionConf {
+    let url = std::env::var("STARKNET_RPC_URL").unwrap_or_else(|_| DEVNET_RPC_URL.to_owned());
+    ConnectionConf {
+        url: Url::parse(&url).unwrap(),
+    }
+}
+
+fn domain() -> HyperlaneDomain {
+    HyperlaneDomain::Known(KnownHyperlaneDomain::StarknetTest23448593)
+}
+
+/// Felts are often written without their leading zeros
+fn env_h256(var: &str) -> H256 {
+    let value = std::env::var(var).unwrap_or_else(|_| panic!("{var} must be set"));
+    H256::from_str(&format!("{:0>64}", value.trim_start_matches("0x"))).unwrap()
+}
+
+fn signer() -> Signer {
+    Signer::new(
+        &env_h256("STARKNET_ACCOUNT_KEY"),
+        &env_h256("STARKNET_ACCOUNT_ADDRESS"),
+        false,
+    )
+    .unwrap()
+}
+
+#[ignore = "Requires a starknet-devnet node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_mailbox_indexer_finds_every_dispatch() {
+    let domain = domain();
+    let address = env_h256("STARKNET_MAILBOX_ADDRESS");
+    let locator = ContractLocator::new(&domain, address);
+    let indexer = StarknetMailboxIndexer::new(&conf(), locator.clone(), &ReorgPeriod::None)
+        .await
+        .unwrap();
+    let mailbox = StarknetMailbox::new(&conf(), locator, None).await.unwrap();
+
+    let (count, tip) =
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&indexer)
+            .await
+            .unwrap();
+    let count = count.unwrap();
+    assert!(count > 0, "no messages were dispatched");
+
+    // Dispatches are found once each, starting from the first block
+    let dispatches = Indexer::<HyperlaneMessage>::fetch_logs_in_range(&indexer, 0..=tip)
+        .await
+        .unwrap();
+    let nonces: Vec<u32> = dispatches
+        .iter()
+        .map(|(message, _)| message.inner().nonce)
+        .collect();
+    assert_eq!(nonces, (0..count).collect::<Vec<_>>());
+    for (message, meta) in &dispatches {
+        assert_eq!(message.inner().origin, domain.id());
+        assert_eq!(meta.address, address);
+        assert!(meta.block_number <= tip as u64);
+    }
+
+    // Every indexed delivery was delivered
+    let deliveries = Indexer::<H256>::fetch_logs_in_range(&indexer, 0..=tip)
+        .await
+        .unwrap();
+    for (id, meta) in deliveries {
+        assert_eq!(meta.address, address);
+        assert!(mailbox.delivered(*id.inner()).await.unwrap());
+    }
+}
+
+#[ignore = "Requires a starknet-devnet node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_merkle_tree_hook_indexer_finds_every_insertion() {
+    let domain = domain();
+    let locator = ContractLocator::new(&domain, env_h256("STARKNET_MERKLE_TREE_HOOK_ADDRESS"));
+    let indexer = StarknetMerkleTreeHookIndexer::new(&conf(), locator.clone(), &ReorgPeriod::None)
+        .await
+        .unwrap();
+    let hook = StarknetMerkleTreeHook::new(&conf(), locator).await.unwrap();
+
+    let (count, tip) = indexer.latest_sequence_count_and_tip().await.unwrap();
+    assert_eq!(count, Some(hook.count(&ReorgPeriod::None).await.unwrap()));
+
+    let insertions = indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    let indexes: Vec<u32> = insertions
+        .iter()
+        .map(|(insertion, _)| insertion.inner().index())
+        .collect();
+    assert_eq!(indexes, (0..count.unwrap()).collect::<Vec<_>>());
+}
+
+#[ignore = "Requires a starknet-devnet node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_igp_indexer_decodes_payments() {
+    let domain = domain();
+    let address = env_h256("STARKNET_IGP_ADDRESS");
+    let indexer = StarknetInterchainGasPaymasterIndexer::new(
+        &conf(),
+        ContractLocator::new(&domain, address),
+        &ReorgPeriod::None,
+    )
+    .await
+    .unwrap();
+
+    let (count, tip) = indexer.latest_sequence_count_and_tip().await.unwrap();
+    // The IGP has no payment sequence
+    assert_eq!(count, None);
+
+    let payments = indexer.fetch_logs_in_range(0..=tip).await.unwrap();
+    assert!(!payments.is_empty(), "no gas payments were made");
+    for (payment, meta) in payments {
+        assert_eq!(meta.address, address);
+        assert!(!payment.inner().message_id.is_zero());
+    }
+}
+
+#[ignore = "Requires a starknet-devnet node with the Hyperlane contracts deployed"]
+#[tokio::test]
+async fn test_validator_announce() {
+    let domain = domain();
+    let locator = ContractLocator::new(&domain, env_h256("STARKNET_VALIDATOR_ANNOUNCE_ADDRESS"));
+    let validator_announce = StarknetValidatorAnnounce::new(&conf(), locator, Some(signer()))
+        .await
+        .unwrap();
+
+    // A new validator each run, so the announcement isn't a replay
+    let validator = Signers::Local(LocalWallet::new(&mut ethers::core::rand::thread_rng()));
+    let announcement = Announcement {
+        validator: validator.eth_address(),
+        mailbox_address: env_h256("STARKNET_MAILBOX_ADDRESS"),
+        mailbox_domain: domain.id(),
+        storage_location: "file:///tmp/starknet-validator".to_owned(),
+    };
+    let signed = validator.sign(announcement.clone()).await.unwrap();
+
+    assert!(validator_announce
+        .announce_tokens_needed(signed.clone())
+        .await
+        .is_some());
+    let outcome = validator_announce.announce(signed).await.unwrap();
+    assert!(outcome.executed);
+
+    let locations = validator_announce
+        .get_announced_storage_locations(&[announcement.validator.into()])
+        .await
+        .unwrap();
+    assert_eq!(locations, vec![vec![announcement.storage_location]]);
+}
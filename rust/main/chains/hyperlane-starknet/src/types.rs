@@ -0,0 +1,246 @@
+//! Cairo serialization of the types taken and returned by the Hyperlane
+//! Starknet contracts.
+//!
+//! - `u256` is serialized as its `(low, high)` 128 bit halves.
+//! - `Bytes` is `{ size: u32, data: Array<u128> }`, packing the bytes into
+//!   big-endian 16 byte words with the last word right-padded with zeros.
+//! - `ByteArray` is the corelib string type: full 31 byte words, followed by
+//!   the pending word and its length.
+
+use hyperlane_core::{HyperlaneMessage, H256, U256};
+use starknet::core::types::Felt;
+
+use crate::{felt_to_h256, h256_to_u256_felts, HyperlaneStarknetError};
+
+const BYTES_WORD_SIZE: usize = 16;
+const BYTE_ARRAY_WORD_SIZE: usize = 31;
+
+/// Serialize a byte slice as a Cairo `Bytes`.
+pub(crate) fn encode_bytes(bytes: &[u8]) -> Vec<Felt> {
+    let words = bytes.chunks(BYTES_WORD_SIZE).map(|chunk| {
+        let mut word = [0u8; BYTES_WORD_SIZE];
+        word[..chunk.len()].copy_from_slice(chunk);
+        Felt::from(u128::from_be_bytes(word))
+    });
+
+    let word_count = bytes.len().div_ceil(BYTES_WORD_SIZE);
+    let mut felts = Vec::with_capacity(word_count + 2);
+    felts.push(Felt::from(bytes.len() as u64));
+    felts.push(Felt::from(word_count as u64));
+    felts.extend(words);
+    felts
+}
+
+/// Serialize a message as the Cairo `Message` struct.
+pub(crate) fn encode_message(message: &HyperlaneMessage) -> Vec<Felt> {
+    let mut felts = vec![
+        Felt::from(message.version),
+        Felt::from(message.nonce),
+        Felt::from(message.origin),
+    ];
+    felts.extend(h256_to_u256_felts(&message.sender));
+    felts.push(Felt::from(message.destination));
+    felts.extend(h256_to_u256_felts(&message.recipient));
+    felts.extend(encode_bytes(&message.body));
+    felts
+}
+
+/// Serialize a string as a Cairo `ByteArray`.
+pub(crate) fn encode_byte_array(value: &str) -> Vec<Felt> {
+    let bytes = value.as_bytes();
+    let full_words = bytes.len() / BYTE_ARRAY_WORD_SIZE;
+    let (full, pending) = bytes.split_at(full_words * BYTE_ARRAY_WORD_SIZE);
+
+    let mut felts = vec![Felt::from(full_words as u64)];
+    felts.extend(
+        full.chunks(BYTE_ARRAY_WORD_SIZE)
+            .map(Felt::from_bytes_be_slice),
+    );
+    felts.push(Felt::from_bytes_be_slice(pending));
+    felts.push(Felt::from(pending.len() as u64));
+    felts
+}
+
+/// Sequentially decodes Cairo-serialized values from a list of felts, as
+/// returned by calls or found in event data.
+pub(crate) struct FeltReader<'a> {
+    felts: std::slice::Iter<'a, Felt>,
+}
+
+impl<'a> FeltReader<'a> {
+    pub fn new(felts: &'a [Felt]) -> Self {
+        Self {
+            felts: felts.iter(),
+        }
+    }
+
+    pub fn felt(&mut self) -> Result<Felt, HyperlaneStarknetError> {
+        self.felts
+            .next()
+            .copied()
+            .ok_or_else(|| HyperlaneStarknetError::DecodeError("Unexpected end of data".into()))
+    }
+
+    pub fn bool(&mut self) -> Result<bool, HyperlaneStarknetError> {
+        Ok(self.felt()? != Felt::ZERO)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, HyperlaneStarknetError> {
+        self.uint::<1>().map(u8::from_be_bytes)
+    }
+
+    pub fn u32(&mut self) -> Result<u32, HyperlaneStarknetError> {
+        self.uint::<4>().map(u32::from_be_bytes)
+    }
+
+    pub fn u128(&mut self) -> Result<u128, HyperlaneStarknetError> {
+        self.uint::<16>().map(u128::from_be_bytes)
+    }
+
+    /// Length prefix of an `Array` or `Span`.
+    pub fn array_len(&mut self) -> Result<usize, HyperlaneStarknetError> {
+        self.u32().map(|len| len as usize)
+    }
+
+    pub fn u256(&mut self) -> Result<U256, HyperlaneStarknetError> {
+        let low = self.u128()?;
+        let high = self.u128()?;
+        Ok((U256::from(high) << 128) + U256::from(low))
+    }
+
+    /// A `u256` holding a 32 byte value such as a message id or address.
+    pub fn h256(&mut self) -> Result<H256, HyperlaneStarknetError> {
+        let mut bytes = [0u8; 32];
+        self.u256()?.to_big_endian(&mut bytes);
+        Ok(H256::from(bytes))
+    }
+
+    /// A `ContractAddress`, which is serialized as a single felt.
+    pub fn address(&mut self) -> Result<H256, HyperlaneStarknetError> {
+        self.felt().map(|felt| felt_to_h256(&felt))
+    }
+
+    pub fn array<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, HyperlaneStarknetError>,
+    ) -> Result<Vec<T>, HyperlaneStarknetError> {
+        let len = self.array_len()?;
+        (0..len).map(|_| item(self)).collect()
+    }
+
+    pub fn bytes(&mut self) -> Result<Vec<u8>, HyperlaneStarknetError> {
+        let size = self.u32()? as usize;
+        let words = self.array(|r| r.u128())?;
+        let mut bytes: Vec<u8> = words.iter().flat_map(|w| w.to_be_bytes()).collect();
+        if bytes.len() < size {
+            return Err(HyperlaneStarknetError::DecodeError(format!(
+                "Bytes of size {size} only has {} bytes of data",
+                bytes.len()
+            )));
+        }
+        bytes.truncate(size);
+        Ok(bytes)
+    }
+
+    pub fn message(&mut self) -> Result<HyperlaneMessage, HyperlaneStarknetError> {
+        Ok(HyperlaneMessage {
+            version: self.u8()?,
+            nonce: self.u32()?,
+            origin: self.u32()?,
+            sender: self.h256()?,
+            destination: self.u32()?,
+            recipient: self.h256()?,
+            body: self.bytes()?,
+        })
+    }
+
+    pub fn byte_array(&mut self) -> Result<String, HyperlaneStarknetError> {
+        let full_words = self.array(|r| r.uint::<BYTE_ARRAY_WORD_SIZE>())?;
+        let pending_word = self.felt()?.to_bytes_be();
+        let pending_len = self.u32()? as usize;
+        if pending_len >= BYTE_ARRAY_WORD_SIZE {
+            return Err(HyperlaneStarknetError::DecodeError(format!(
+                "Invalid ByteArray pending word length {pending_len}"
+            )));
+        }
+
+        let mut bytes: Vec<u8> = full_words.concat();
+        bytes.extend_from_slice(&pending_word[32 - pending_len..]);
+        String::from_utf8(bytes).map_err(|e| HyperlaneStarknetError::DecodeError(e.to_string()))
+    }
+
+    /// Read a felt that must fit into `N` bytes, returned big-endian.
+    fn uint<const N: usize>(&mut self) -> Result<[u8; N], HyperlaneStarknetError> {
+        let felt = self.felt()?;
+        let bytes = felt.to_bytes_be();
+        let (overflow, value) = bytes.split_at(32 - N);
+        if overflow.iter().any(|b| *b != 0) {
+            return Err(HyperlaneStarknetError::DecodeError(format!(
+                "Felt {felt:#x} does not fit into {N} bytes"
+            )));
+        }
+        let mut out = [0u8; N];
+        out.copy_from_slice(value);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(body: Vec<u8>) -> HyperlaneMessage {
+        HyperlaneMessage {
+            version: 3,
+            nonce: 42,
+            origin: 23448593,
+            sender: H256::from_low_u64_be(0x1234),
+            destination: 23448594,
+            recipient: H256::repeat_byte(0x07) & H256::from_low_u64_be(u64::MAX),
+            body,
+        }
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        for len in [0usize, 1, 15, 16, 17, 65] {
+            let bytes: Vec<u8> = (0..len).map(|i| i as u8 + 1).collect();
+            let felts = encode_bytes(&bytes);
+            assert_eq!(felts[0], Felt::from(len as u64));
+            assert_eq!(felts[1], Felt::from(len.div_ceil(16) as u64));
+            assert_eq!(FeltReader::new(&felts).bytes().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_bytes_are_right_padded() {
+        let felts = encode_bytes(&[0xab]);
+        assert_eq!(felts[2], Felt::from(0xab_u128 << 120));
+    }
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = message(b"hello starknet, this is a longer body".to_vec());
+        let felts = encode_message(&message);
+        let mut reader = FeltReader::new(&felts);
+        assert_eq!(reader.message().unwrap(), message);
+        assert!(reader.felt().is_err());
+    }
+
+    #[test]
+    fn test_byte_array_roundtrip() {
+        let location = "s3://hyperlane-validator-signatures-starknet/us-east-1";
+        let felts = encode_byte_array(location);
+        // 54 bytes: one full 31 byte word and 23 pending bytes
+        assert_eq!(felts.len(), 4);
+        assert_eq!(felts[0], Felt::ONE);
+        assert_eq!(felts[3], Felt::from(23u64));
+        assert_eq!(FeltReader::new(&felts).byte_array().unwrap(), location);
+    }
+
+    #[test]
+    fn test_uint_overflow_is_rejected() {
+        let felts = [Felt::from(u64::from(u32::MAX) + 1)];
+        assert!(FeltReader::new(&felts).u32().is_err());
+    }
+}
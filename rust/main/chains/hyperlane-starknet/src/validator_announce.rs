@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use hyperlane_core::{
+    Announcement, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+};
+use starknet::{core::types::Felt, macros::selector};
+use tracing::{instrument, trace};
+
+use crate::{
+    contract::StarknetContract,
+    h256_to_felt,
+    types::{encode_byte_array, encode_bytes, FeltReader},
+    ConnectionConf, Signer,
+};
+
+/// A reference to a ValidatorAnnounce contract on some Starknet chain
+#[derive(Debug)]
+pub struct StarknetValidatorAnnounce {
+    contract: StarknetContract,
+}
+
+impl StarknetValidatorAnnounce {
+    /// Create a reference to a ValidatorAnnounce contract at a specific
+    /// Starknet address on some chain
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        signer: Option<Signer>,
+    ) -> ChainResult<Self> {
+        Ok(Self {
+            contract: StarknetContract::new(conf, locator, signer).await?,
+        })
+    }
+
+    /// `announce(validator: EthAddress, storage_location: ByteArray,
+    /// signature: Bytes)`
+    fn announce_args(announcement: &SignedType<Announcement>) -> Vec<Felt> {
+        let mut calldata = vec![Felt::from_bytes_be_slice(
+            announcement.value.validator.as_bytes(),
+        )];
+        calldata.extend(encode_byte_array(&announcement.value.storage_location));
+        calldata.extend(encode_bytes(&announcement.signature.to_vec()));
+        calldata
+    }
+}
+
+impl HyperlaneContract for StarknetValidatorAnnounce {
+    fn address(&self) -> H256 {
+        self.contract.address()
+    }
+}
+
+impl HyperlaneChain for StarknetValidatorAnnounce {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.contract.domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.contract.provider().clone())
+    }
+}
+
+#[async_trait]
+impl ValidatorAnnounce for StarknetValidatorAnnounce {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn get_announced_storage_locations(
+        &self,
+        validators: &[H256],
+    ) -> ChainResult<Vec<Vec<String>>> {
+        // Validators are Ethereum addresses, left-padded to 32 bytes
+        let mut calldata = vec![Felt::from(validators.len() as u64)];
+        for validator in validators {
+            calldata.push(h256_to_felt(validator)?);
+        }
+
+        let result = self
+            .contract
+            .call(selector!("get_announced_storage_locations"), calldata)
+            .await?;
+        Ok(FeltReader::new(&result).array(|r| r.array(|r| r.byte_array()))?)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
+        self.contract
+            .invoke(
+                selector!("announce"),
+                Self::announce_args(&announcement),
+                None,
+            )
+            .await
+    }
+
+    async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
+        let provider = self.contract.provider();
+        let Some(account) = provider.account_address() else {
+            trace!("No signer configured to announce with");
+            return None;
+        };
+
+        let Ok(fee) = self
+            .contract
+            .estimate_invoke_fee(selector!("announce"), Self::announce_args(&announcement))
+            .await
+        else {
+            trace!("Unable to estimate announce cost");
+            return None;
+        };
+
+        let Ok(balance) = provider.get_balance(format!("{account:#x}")).await else {
+            trace!("Unable to query balance");
+            return None;
+        };
+
+        Some(fee.saturating_sub(balance))
+    }
+}
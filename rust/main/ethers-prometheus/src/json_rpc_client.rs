@@ -53,6 +53,21 @@ pub struct JsonRpcClientMetrics {
     request_duration_seconds: Option<CounterVec>,
 }
 
+impl JsonRpcClientMetrics {
+    /// Total number of requests made, if the metric is enabled. Useful for
+    /// non-ethers clients that report into the same metric.
+    pub fn request_count(&self) -> Option<&IntCounterVec> {
+        self.request_count.as_ref()
+    }
+
+    /// Total number of seconds spent making requests, if the metric is
+    /// enabled. Useful for non-ethers clients that report into the same
+    /// metric.
+    pub fn request_duration_seconds(&self) -> Option<&CounterVec> {
+        self.request_duration_seconds.as_ref()
+    }
+}
+
 /// Expected label names for the metric.
 pub const REQUEST_COUNT_LABELS: &[&str] = &["provider_node", "chain", "method", "status"];
 /// Help string for the metric.
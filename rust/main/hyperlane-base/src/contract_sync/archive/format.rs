@@ -0,0 +1,236 @@
+use ethers::types::Log as EthereumLog;
+use eyre::{bail, eyre, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use hyperlane_core::{
+    Decode, HyperlaneMessage, Indexed, InterchainGasPayment, LogMeta, MerkleTreeInsertion, H256,
+};
+use hyperlane_ethereum as h_eth;
+use hyperlane_sealevel as h_sealevel;
+
+use super::ArchivedLog;
+
+/// The event an archived log holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveEventKind {
+    /// A dispatched message
+    Dispatch,
+    /// The id of a delivered message
+    Delivery,
+    /// An interchain gas payment
+    GasPayment,
+    /// An insertion into the merkle tree hook
+    MerkleTreeInsertion,
+}
+
+/// The format of archive export files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveFormat {
+    /// JSONL of [`ArchivedLog`]s, which are tagged with their event kind so a
+    /// single export can hold every event type
+    #[default]
+    Hyperlane,
+    /// JSONL of `eth_getLogs` results. Each line is a log, an array of logs or a
+    /// JSON-RPC response whose result is an array of logs.
+    EthereumLogs,
+    /// Parquet export of EVM logs with one row per log, using the column names of
+    /// `eth_getLogs` in snake case (e.g. `cryo logs` exports)
+    EthereumLogsParquet,
+    /// JSONL of Sealevel `getTransaction` responses in the JSON encoding. Only
+    /// holds dispatched messages, and only for mailboxes that log them with the
+    /// SPL noop program.
+    SealevelTransactions,
+    /// JSONL of the mailbox program's `getProgramAccounts` entries in the base64
+    /// encoding. Only holds dispatched messages.
+    SealevelAccounts,
+}
+
+impl ArchiveFormat {
+    /// The extension of export files, used when loading a directory
+    pub(super) fn file_extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::EthereumLogsParquet => "parquet",
+            _ => "jsonl",
+        }
+    }
+}
+
+/// How archived logs are ordered and located. Archive files must be sorted by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchivePosition {
+    /// By the block the log was emitted in
+    Block,
+    /// By the sequence of the event, for chains whose indexers query ranges of
+    /// sequences rather than blocks (e.g. Sealevel)
+    Sequence,
+}
+
+/// An event that can be backfilled from an archive
+pub trait ArchiveEvent: Decode + Clone + Send + Sync + 'static {
+    /// The kind of event, which tags the lines of Hyperlane archives
+    const KIND: ArchiveEventKind;
+
+    /// Decodes the event from a log of the EVM contract that emits it
+    fn decode_ethereum_log(log: &EthereumLog) -> Option<Indexed<Self>>;
+
+    /// Derives the events from messages dispatched on a Sealevel chain
+    fn from_sealevel_dispatches(
+        _dispatches: Vec<(Indexed<HyperlaneMessage>, LogMeta)>,
+    ) -> Result<Vec<(Indexed<Self>, LogMeta)>> {
+        bail!(
+            "Sealevel archives only hold dispatched messages, not {:?} events",
+            Self::KIND
+        )
+    }
+}
+
+impl ArchiveEvent for HyperlaneMessage {
+    const KIND: ArchiveEventKind = ArchiveEventKind::Dispatch;
+
+    fn decode_ethereum_log(log: &EthereumLog) -> Option<Indexed<Self>> {
+        h_eth::decode_dispatch_log(log)
+    }
+
+    fn from_sealevel_dispatches(
+        dispatches: Vec<(Indexed<HyperlaneMessage>, LogMeta)>,
+    ) -> Result<Vec<(Indexed<Self>, LogMeta)>> {
+        Ok(dispatches)
+    }
+}
+
+impl ArchiveEvent for H256 {
+    const KIND: ArchiveEventKind = ArchiveEventKind::Delivery;
+
+    fn decode_ethereum_log(log: &EthereumLog) -> Option<Indexed<Self>> {
+        h_eth::decode_process_id_log(log)
+    }
+}
+
+impl ArchiveEvent for InterchainGasPayment {
+    const KIND: ArchiveEventKind = ArchiveEventKind::GasPayment;
+
+    fn decode_ethereum_log(log: &EthereumLog) -> Option<Indexed<Self>> {
+        h_eth::decode_gas_payment_log(log)
+    }
+}
+
+impl ArchiveEvent for MerkleTreeInsertion {
+    const KIND: ArchiveEventKind = ArchiveEventKind::MerkleTreeInsertion;
+
+    fn decode_ethereum_log(log: &EthereumLog) -> Option<Indexed<Self>> {
+        h_eth::decode_inserted_into_tree_log(log)
+    }
+
+    fn from_sealevel_dispatches(
+        dispatches: Vec<(Indexed<HyperlaneMessage>, LogMeta)>,
+    ) -> Result<Vec<(Indexed<Self>, LogMeta)>> {
+        // Sealevel mailboxes insert every dispatched message into their merkle tree
+        Ok(dispatches
+            .into_iter()
+            .map(|(message, meta)| {
+                let message = message.inner();
+                (
+                    MerkleTreeInsertion::new(message.nonce, message.id()).into(),
+                    meta,
+                )
+            })
+            .collect())
+    }
+}
+
+/// A line of an `eth_getLogs` JSONL export
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EthereumLogsLine {
+    Logs(Vec<EthereumLog>),
+    Response { result: Vec<EthereumLog> },
+    Log(Box<EthereumLog>),
+}
+
+/// A single record of an export file
+pub(super) enum ArchiveRecord<'a> {
+    /// A line of a JSONL export
+    Line(&'a str),
+    /// A log of an EVM export, e.g. a row of a Parquet file
+    EthereumLog(EthereumLog),
+}
+
+/// Decodes the events of one kind from records of export files
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ArchiveDecoder {
+    pub format: ArchiveFormat,
+    pub position: ArchivePosition,
+    /// The contract that emits the events
+    pub contract: H256,
+}
+
+impl ArchiveDecoder {
+    pub fn decode<T: ArchiveEvent>(
+        &self,
+        record: ArchiveRecord,
+    ) -> Result<Vec<(Indexed<T>, LogMeta)>> {
+        let line = match record {
+            ArchiveRecord::Line(line) => line,
+            ArchiveRecord::EthereumLog(log) => return self.decode_ethereum_logs(vec![log]),
+        };
+        match self.format {
+            ArchiveFormat::Hyperlane => {
+                let archived: ArchivedLog = serde_json::from_str(line)?;
+                if archived.event != T::KIND {
+                    return Ok(vec![]);
+                }
+                archived.decode().map(|log| vec![log])
+            }
+            ArchiveFormat::EthereumLogs => {
+                let logs = match serde_json::from_str(line)? {
+                    EthereumLogsLine::Logs(logs) | EthereumLogsLine::Response { result: logs } => {
+                        logs
+                    }
+                    EthereumLogsLine::Log(log) => vec![*log],
+                };
+                self.decode_ethereum_logs(logs)
+            }
+            ArchiveFormat::EthereumLogsParquet => {
+                bail!("Parquet exports don't have lines")
+            }
+            ArchiveFormat::SealevelTransactions => T::from_sealevel_dispatches(
+                h_sealevel::decode_archived_dispatch_transaction(line, self.contract)?,
+            ),
+            ArchiveFormat::SealevelAccounts => T::from_sealevel_dispatches(
+                h_sealevel::decode_archived_dispatch_account(line, self.contract)?
+                    .into_iter()
+                    .collect(),
+            ),
+        }
+    }
+
+    fn decode_ethereum_logs<T: ArchiveEvent>(
+        &self,
+        logs: Vec<EthereumLog>,
+    ) -> Result<Vec<(Indexed<T>, LogMeta)>> {
+        logs.into_iter()
+            // exports may hold every contract's logs, and logs dropped by a reorg
+            .filter(|log| log.address.as_bytes() == &self.contract.as_bytes()[12..])
+            .filter(|log| log.removed != Some(true))
+            .filter_map(|log| T::decode_ethereum_log(&log).map(|event| (event, log)))
+            .map(|(event, log)| {
+                let meta = h_eth::mined_log_meta(&log)
+                    .ok_or_else(|| eyre!("Archived log {log:?} is missing block information"))?;
+                Ok((event, meta))
+            })
+            .collect()
+    }
+
+    /// The position of a decoded log in the archive
+    pub fn position<T>(&self, (event, meta): &(Indexed<T>, LogMeta)) -> Result<u32> {
+        match self.position {
+            ArchivePosition::Block => u32::try_from(meta.block_number)
+                .with_context(|| format!("Block number {} is too large", meta.block_number)),
+            ArchivePosition::Sequence => event
+                .sequence
+                .ok_or_else(|| eyre!("Archived log {meta:?} has no sequence")),
+        }
+    }
+}
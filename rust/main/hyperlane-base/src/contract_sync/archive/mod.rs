@@ -0,0 +1,533 @@
+//! Indexing from archived log exports.
+//!
+//! Backfilling years of history by paging through `fetch_logs_in_range` over RPC is
+//! slow and rate-limited. An [`ArchiveIndexer`] serves logs from local export files
+//! instead, and an [`ArchiveBackedIndexer`] stitches it together with a live
+//! indexer, handing off to the live indexer at a configured block.
+//!
+//! Exports can be in any [`ArchiveFormat`]: JSONL of [`ArchivedLog`]s, `eth_getLogs`
+//! results as JSONL or Parquet, or Sealevel transactions or accounts. Files must be
+//! sorted by [`ArchivePosition`], and are indexed when loaded rather than held in
+//! memory.
+
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ops::RangeInclusive,
+    path::Path,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use eyre::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use hyperlane_core::{
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, Decode, Indexed, Indexer, LogMeta,
+    SequenceAwareIndexer, H256, H512,
+};
+
+pub use format::{ArchiveEvent, ArchiveEventKind, ArchiveFormat, ArchivePosition};
+
+use format::ArchiveDecoder;
+use reader::ArchiveFile;
+
+mod format;
+mod reader;
+
+/// A single archived log, stored as one JSON object per line in an export file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedLog {
+    /// The kind of event, so that exports can hold several event types
+    pub event: ArchiveEventKind,
+    /// The canonical encoding of the event, hex encoded
+    pub data: String,
+    /// The sequence of the event, if it has one
+    #[serde(default)]
+    pub sequence: Option<u32>,
+    /// Metadata of the log the event was emitted in
+    pub meta: LogMeta,
+}
+
+impl ArchivedLog {
+    /// Archive an indexed event and its log metadata
+    pub fn new<T: ArchiveEvent>(event: &Indexed<T>, meta: &LogMeta) -> Self {
+        Self {
+            event: T::KIND,
+            data: bytes_to_hex(&event.inner().to_vec()),
+            sequence: event.sequence,
+            meta: meta.clone(),
+        }
+    }
+
+    /// Decode the archived event and its log metadata
+    pub fn decode<T: Decode>(&self) -> Result<(Indexed<T>, LogMeta)> {
+        let bytes = hex::decode(self.data.trim_start_matches("0x"))
+            .context("Archived log data is not valid hex")?;
+        let inner = T::read_from(&mut bytes.as_slice())?;
+        let indexed = match self.sequence {
+            Some(sequence) => Indexed::new(inner).with_sequence(sequence),
+            None => Indexed::new(inner),
+        };
+        Ok((indexed, self.meta.clone()))
+    }
+}
+
+/// An indexer that serves the logs of one event type from archive export files.
+/// Ranges are of [`ArchivePosition`]s.
+pub struct ArchiveIndexer<T> {
+    /// Export files that hold logs of `T`, sorted by position
+    files: Arc<Vec<ArchiveFile>>,
+    decoder: ArchiveDecoder,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<T: ArchiveEvent> ArchiveIndexer<T> {
+    /// Load an archive from a single export file, or from every export file in a
+    /// directory. Only logs of `T` emitted by `contract` are served.
+    pub async fn load(
+        path: &Path,
+        format: ArchiveFormat,
+        position: ArchivePosition,
+        contract: H256,
+    ) -> Result<Self> {
+        let paths = if path.is_dir() {
+            let mut paths = vec![];
+            let mut entries = tokio::fs::read_dir(path)
+                .await
+                .with_context(|| format!("Reading archive directory {path:?}"))?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file = entry.path();
+                if file.extension().and_then(|ext| ext.to_str()) == Some(format.file_extension()) {
+                    paths.push(file);
+                }
+            }
+            paths
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        let decoder = ArchiveDecoder {
+            format,
+            position,
+            contract,
+        };
+        let mut files = tokio::task::spawn_blocking(move || {
+            paths
+                .iter()
+                .map(|path| ArchiveFile::scan::<T>(path, &decoder))
+                .collect::<Result<Vec<_>>>()
+        })
+        .await??;
+        files.retain(|file| file.range().is_some());
+        files.sort_by_key(|file| file.range().map(|range| *range.start()));
+        for pair in files.windows(2) {
+            let (previous, next) = (pair[0].range(), pair[1].range());
+            if let (Some(previous), Some(next)) = (previous, next) {
+                if previous.end() > next.start() {
+                    bail!("Archive files overlap: {previous:?} and {next:?}");
+                }
+            }
+        }
+
+        let indexer = Self {
+            files: Arc::new(files),
+            decoder,
+            _event: PhantomData,
+        };
+        info!(
+            ?path,
+            event = ?T::KIND,
+            files = indexer.files.len(),
+            last_position = ?indexer.last_position(),
+            "Loaded log archive"
+        );
+        Ok(indexer)
+    }
+
+    async fn read(&self, range: RangeInclusive<u32>) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        let files = self.files.clone();
+        let decoder = self.decoder;
+        let logs = tokio::task::spawn_blocking(move || -> Result<_> {
+            let mut logs = vec![];
+            for file in files.iter() {
+                let Some(file_range) = file.range() else {
+                    continue;
+                };
+                if file_range.end() < range.start() || file_range.start() > range.end() {
+                    continue;
+                }
+                logs.extend(file.read::<T>(&decoder, &range)?);
+            }
+            Ok(logs)
+        })
+        .await
+        .map_err(ChainCommunicationError::from_other)??;
+        Ok(logs)
+    }
+}
+
+impl<T> ArchiveIndexer<T> {
+    /// The highest position that has logs in the archive
+    pub fn last_position(&self) -> Option<u32> {
+        self.files
+            .last()
+            .and_then(|file| file.range())
+            .map(|range| *range.end())
+    }
+}
+
+impl<T> Debug for ArchiveIndexer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveIndexer")
+            .field("decoder", &self.decoder)
+            .field("files", &self.files.len())
+            .field("last_position", &self.last_position())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<T: ArchiveEvent> Indexer<T> for ArchiveIndexer<T> {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+        self.read(range).await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Ok(self.last_position().unwrap_or_default())
+    }
+
+    /// Reads the whole archive, since it isn't indexed by transaction
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        let mut logs = self.read(0..=u32::MAX).await?;
+        logs.retain(|(_, meta)| meta.transaction_id == tx_hash);
+        Ok(logs)
+    }
+}
+
+/// An indexer that serves blocks below `handoff_block` from an archive, and every
+/// other query from a live indexer. For archives positioned by sequence,
+/// `handoff_block` is a sequence, matching the ranges the live indexer is queried with.
+pub struct ArchiveBackedIndexer<T> {
+    archive: ArchiveIndexer<T>,
+    live: Arc<dyn SequenceAwareIndexer<T>>,
+    handoff_block: u32,
+}
+
+impl<T> ArchiveBackedIndexer<T> {
+    /// Create a new archive-backed indexer. Blocks strictly below `handoff_block`
+    /// are served from `archive`.
+    pub fn new(
+        archive: ArchiveIndexer<T>,
+        live: Arc<dyn SequenceAwareIndexer<T>>,
+        handoff_block: u32,
+    ) -> Self {
+        match archive.last_position() {
+            Some(last_position) if last_position.saturating_add(1) < handoff_block => {
+                warn!(
+                    last_position,
+                    handoff_block, "Archive may not cover everything before the handoff block"
+                )
+            }
+            None => warn!(handoff_block, "Archive is empty"),
+            _ => {}
+        }
+        Self {
+            archive,
+            live,
+            handoff_block,
+        }
+    }
+}
+
+impl<T> Debug for ArchiveBackedIndexer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveBackedIndexer")
+            .field("archive", &self.archive)
+            .field("live", &self.live)
+            .field("handoff_block", &self.handoff_block)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<T: ArchiveEvent> Indexer<T> for ArchiveBackedIndexer<T> {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        let (from, to) = range.into_inner();
+        let mut logs = vec![];
+        if from < self.handoff_block {
+            let archive_to = u32::min(to, self.handoff_block - 1);
+            logs.extend(self.archive.fetch_logs_in_range(from..=archive_to).await?);
+        }
+        if to >= self.handoff_block {
+            let live_from = u32::max(from, self.handoff_block);
+            logs.extend(self.live.fetch_logs_in_range(live_from..=to).await?);
+        }
+        Ok(logs)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.live.get_finalized_block_number().await
+    }
+
+    async fn fetch_logs_by_tx_hash(
+        &self,
+        tx_hash: H512,
+    ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
+        self.live.fetch_logs_by_tx_hash(tx_hash).await
+    }
+}
+
+#[async_trait]
+impl<T: ArchiveEvent> SequenceAwareIndexer<T> for ArchiveBackedIndexer<T> {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        self.live.latest_sequence_count_and_tip().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use hyperlane_core::{HyperlaneMessage, H256};
+    use mockall::predicate::eq;
+
+    use super::*;
+
+    mockall::mock! {
+        pub LiveIndexer {}
+
+        impl Debug for LiveIndexer {
+            fn fmt<'a>(&self, f: &mut std::fmt::Formatter<'a>) -> std::fmt::Result;
+        }
+
+        #[async_trait]
+        impl Indexer<H256> for LiveIndexer {
+            async fn fetch_logs_in_range(&self, range: RangeInclusive<u32>) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>>;
+            async fn get_finalized_block_number(&self) -> ChainResult<u32>;
+        }
+
+        #[async_trait]
+        impl SequenceAwareIndexer<H256> for LiveIndexer {
+            async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)>;
+        }
+    }
+
+    fn log_at(block_number: u64) -> (Indexed<H256>, LogMeta) {
+        let meta = LogMeta {
+            block_number,
+            ..Default::default()
+        };
+        (Indexed::new(H256::from_low_u64_be(block_number)), meta)
+    }
+
+    #[test]
+    fn test_archived_log_roundtrip() {
+        let message = HyperlaneMessage {
+            nonce: 7,
+            ..Default::default()
+        };
+        let event = Indexed::new(message).with_sequence(7);
+        let meta = LogMeta {
+            block_number: 100,
+            ..Default::default()
+        };
+
+        let line = serde_json::to_string(&ArchivedLog::new(&event, &meta)).unwrap();
+        let archived: ArchivedLog = serde_json::from_str(&line).unwrap();
+
+        assert_eq!(
+            archived.decode::<HyperlaneMessage>().unwrap(),
+            (event, meta)
+        );
+    }
+
+    fn write_archive(lines: impl IntoIterator<Item = String>) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(file, "{line}").unwrap();
+        }
+        file
+    }
+
+    fn archived_line<T: ArchiveEvent>((event, meta): (Indexed<T>, LogMeta)) -> String {
+        serde_json::to_string(&ArchivedLog::new(&event, &meta)).unwrap()
+    }
+
+    async fn load<T: ArchiveEvent>(
+        path: &Path,
+        format: ArchiveFormat,
+    ) -> Result<ArchiveIndexer<T>> {
+        ArchiveIndexer::load(path, format, ArchivePosition::Block, H256::zero()).await
+    }
+
+    #[tokio::test]
+    async fn test_load_archive_file() {
+        let mut lines: Vec<_> = [3, 5, 5]
+            .map(log_at)
+            .into_iter()
+            .map(archived_line)
+            .collect();
+        lines.push(String::new());
+        let file = write_archive(lines);
+
+        let archive = load::<H256>(file.path(), ArchiveFormat::Hyperlane)
+            .await
+            .unwrap();
+
+        assert_eq!(archive.last_position(), Some(5));
+        assert_eq!(archive.fetch_logs_in_range(0..=4).await.unwrap().len(), 1);
+        assert_eq!(archive.fetch_logs_in_range(5..=10).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_archive_only_serves_its_event_kind() {
+        let message = HyperlaneMessage {
+            nonce: 1,
+            ..Default::default()
+        };
+        let meta = LogMeta {
+            block_number: 2,
+            ..Default::default()
+        };
+        let file = write_archive([
+            archived_line(log_at(1)),
+            archived_line((message.clone().into(), meta.clone())),
+            archived_line(log_at(3)),
+        ]);
+
+        let deliveries = load::<H256>(file.path(), ArchiveFormat::Hyperlane)
+            .await
+            .unwrap();
+        let dispatches = load::<HyperlaneMessage>(file.path(), ArchiveFormat::Hyperlane)
+            .await
+            .unwrap();
+
+        let blocks: Vec<_> = deliveries
+            .fetch_logs_in_range(0..=10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, meta)| meta.block_number)
+            .collect();
+        assert_eq!(blocks, vec![1, 3]);
+        assert_eq!(
+            dispatches.fetch_logs_in_range(0..=10).await.unwrap(),
+            vec![(message.into(), meta)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsorted_archive_is_rejected() {
+        let file = write_archive([5, 3].map(log_at).into_iter().map(archived_line));
+
+        let result = load::<H256>(file.path(), ArchiveFormat::Hyperlane).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ranges_spanning_many_segments() {
+        let blocks = (0..3000).map(|index| index / 2);
+        let file = write_archive(blocks.map(log_at).map(archived_line));
+
+        let archive = load::<H256>(file.path(), ArchiveFormat::Hyperlane)
+            .await
+            .unwrap();
+
+        let logs = archive.fetch_logs_in_range(500..=1100).await.unwrap();
+        let blocks: Vec<_> = logs.iter().map(|(_, meta)| meta.block_number).collect();
+        let expected: Vec<_> = (500..=1100).flat_map(|block| [block, block]).collect();
+        assert_eq!(blocks, expected);
+    }
+
+    #[tokio::test]
+    async fn test_load_ethereum_logs() {
+        use ethers::{
+            abi::{encode, Token},
+            types::{Log as EthereumLog, H160 as EthersH160, H256 as EthersH256, U256, U64},
+            utils::keccak256,
+        };
+        use hyperlane_core::Encode;
+
+        let mailbox = EthersH160::repeat_byte(1);
+        let message = HyperlaneMessage {
+            nonce: 9,
+            destination: 2,
+            ..Default::default()
+        };
+        let dispatch_log = |address| EthereumLog {
+            address,
+            topics: vec![
+                keccak256("Dispatch(address,uint32,bytes32,bytes)").into(),
+                EthersH256::zero(),
+                EthersH256::from_low_u64_be(2),
+                EthersH256::zero(),
+            ],
+            data: encode(&[Token::Bytes(message.to_vec())]).into(),
+            block_hash: Some(EthersH256::zero()),
+            block_number: Some(U64::from(40)),
+            transaction_hash: Some(EthersH256::zero()),
+            transaction_index: Some(U64::zero()),
+            log_index: Some(U256::zero()),
+            ..Default::default()
+        };
+        // a JSON-RPC response holding a log of another contract too
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": [dispatch_log(mailbox), dispatch_log(EthersH160::repeat_byte(2))],
+        });
+        let file = write_archive([response.to_string()]);
+
+        let archive = ArchiveIndexer::<HyperlaneMessage>::load(
+            file.path(),
+            ArchiveFormat::EthereumLogs,
+            ArchivePosition::Block,
+            H256::from(hyperlane_core::H160::from(mailbox.0)),
+        )
+        .await
+        .unwrap();
+
+        let logs = archive.fetch_logs_in_range(0..=100).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].0, message.into());
+        assert_eq!(logs[0].1.block_number, 40);
+    }
+
+    #[tokio::test]
+    async fn test_ranges_are_split_at_handoff_block() {
+        let file = write_archive((0..20).map(log_at).map(archived_line));
+        let archive = load::<H256>(file.path(), ArchiveFormat::Hyperlane)
+            .await
+            .unwrap();
+        let mut live = MockLiveIndexer::new();
+        live.expect_fetch_logs_in_range()
+            .with(eq(10..=14))
+            .times(1)
+            .returning(|range| Ok(range.map(|block| log_at(block as u64)).collect()));
+        let indexer = ArchiveBackedIndexer::new(archive, Arc::new(live), 10);
+
+        // entirely served from the archive
+        let logs = indexer.fetch_logs_in_range(0..=9).await.unwrap();
+        assert_eq!(logs.len(), 10);
+
+        // straddles the handoff block
+        let logs = indexer.fetch_logs_in_range(5..=14).await.unwrap();
+        let blocks: Vec<_> = logs.iter().map(|(_, meta)| meta.block_number).collect();
+        assert_eq!(blocks, (5..=14).collect::<Vec<_>>());
+    }
+}
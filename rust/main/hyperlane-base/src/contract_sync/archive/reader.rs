@@ -0,0 +1,303 @@
+//! Blocking reads of export files. Files are scanned once when an archive is
+//! loaded to build a sparse index of where each position range starts, and logs
+//! are decoded again on every query instead of being held in memory.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use ethers::types::{Bytes, Log as EthereumLog, H160, H256, U256, U64};
+use eyre::{bail, ensure, eyre, Context, Result};
+use hyperlane_core::{Indexed, LogMeta};
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::{Field, Row},
+};
+
+use super::format::{ArchiveDecoder, ArchiveEvent, ArchiveFormat, ArchiveRecord};
+
+/// Number of JSONL records between two entries of the index
+const RECORDS_PER_SEGMENT: usize = 1024;
+
+/// A run of logs that can be read without reading the rest of the file: the
+/// records of a JSONL file from a byte offset on, or a row group of a Parquet file.
+#[derive(Debug, Clone)]
+struct Segment {
+    first: u32,
+    last: u32,
+    /// Byte offset of the first record, or the index of the row group
+    offset: u64,
+}
+
+/// An indexed export file
+#[derive(Debug, Clone)]
+pub(super) struct ArchiveFile {
+    path: PathBuf,
+    segments: Vec<Segment>,
+}
+
+impl ArchiveFile {
+    /// Scans an export file for the logs of `T` and indexes where they are
+    pub fn scan<T: ArchiveEvent>(path: &Path, decoder: &ArchiveDecoder) -> Result<Self> {
+        let segments = Self::scan_segments::<T>(path, decoder)
+            .with_context(|| format!("Scanning archive file {path:?}"))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            segments,
+        })
+    }
+
+    fn scan_segments<T: ArchiveEvent>(
+        path: &Path,
+        decoder: &ArchiveDecoder,
+    ) -> Result<Vec<Segment>> {
+        let mut indexer = SegmentIndexer::default();
+        if decoder.format == ArchiveFormat::EthereumLogsParquet {
+            let reader = parquet_reader(path)?;
+            for row_group in 0..reader.num_row_groups() {
+                let logs = read_row_group::<T>(&reader, row_group, decoder)?;
+                indexer.start_segment();
+                for log in &logs {
+                    indexer.push(decoder.position(log)?, row_group as u64)?;
+                }
+            }
+        } else {
+            let mut lines = JsonlReader::open(path, 0)?;
+            while let Some((offset, line)) = lines.next_line()? {
+                let logs = decode_line::<T>(decoder, &line, path, offset)?;
+                if logs.is_empty() {
+                    continue;
+                }
+                if indexer.records % RECORDS_PER_SEGMENT == 0 {
+                    indexer.start_segment();
+                }
+                indexer.records += 1;
+                for log in &logs {
+                    indexer.push(decoder.position(log)?, offset)?;
+                }
+            }
+        }
+        Ok(indexer.segments)
+    }
+
+    /// The range of positions that the file holds logs for
+    pub fn range(&self) -> Option<RangeInclusive<u32>> {
+        let first = self.segments.first()?.first;
+        let last = self.segments.last()?.last;
+        Some(first..=last)
+    }
+
+    /// Reads the logs in `range` from the file
+    pub fn read<T: ArchiveEvent>(
+        &self,
+        decoder: &ArchiveDecoder,
+        range: &RangeInclusive<u32>,
+    ) -> Result<Vec<(Indexed<T>, LogMeta)>> {
+        let mut segments = self
+            .segments
+            .iter()
+            .filter(|segment| segment.last >= *range.start() && segment.first <= *range.end())
+            .peekable();
+        let Some(start_offset) = segments.peek().map(|segment| segment.offset) else {
+            return Ok(vec![]);
+        };
+
+        let mut logs = vec![];
+        let mut in_range = |decoded: Vec<(Indexed<T>, LogMeta)>| -> Result<bool> {
+            for log in decoded {
+                let position = decoder.position(&log)?;
+                if position > *range.end() {
+                    return Ok(false);
+                }
+                if position >= *range.start() {
+                    logs.push(log);
+                }
+            }
+            Ok(true)
+        };
+
+        if decoder.format == ArchiveFormat::EthereumLogsParquet {
+            let reader = parquet_reader(&self.path)?;
+            for segment in segments {
+                let decoded = read_row_group::<T>(&reader, segment.offset as usize, decoder)?;
+                if !in_range(decoded)? {
+                    break;
+                }
+            }
+        } else {
+            // Files are sorted, so read from the first segment until the end of the range
+            let mut lines = JsonlReader::open(&self.path, start_offset)?;
+            while let Some((offset, line)) = lines.next_line()? {
+                let decoded = decode_line::<T>(decoder, &line, &self.path, offset)?;
+                if !in_range(decoded)? {
+                    break;
+                }
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// Builds the segments of a file while checking that it's sorted
+#[derive(Default)]
+struct SegmentIndexer {
+    segments: Vec<Segment>,
+    records: usize,
+    new_segment: bool,
+}
+
+impl SegmentIndexer {
+    /// Starts a new segment at the next log
+    fn start_segment(&mut self) {
+        self.new_segment = true;
+    }
+
+    fn push(&mut self, position: u32, offset: u64) -> Result<()> {
+        if let Some(last) = self.segments.last().map(|segment| segment.last) {
+            ensure!(
+                position >= last,
+                "Archive files must be sorted, but position {position} follows {last}"
+            );
+        }
+        match self.segments.last_mut() {
+            Some(segment) if !self.new_segment => segment.last = position,
+            _ => {
+                self.segments.push(Segment {
+                    first: position,
+                    last: position,
+                    offset,
+                });
+                self.new_segment = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the non-empty lines of a JSONL file along with their byte offsets
+struct JsonlReader {
+    reader: BufReader<File>,
+    offset: u64,
+}
+
+impl JsonlReader {
+    fn open(path: &Path, offset: u64) -> Result<Self> {
+        let mut file =
+            File::open(path).with_context(|| format!("Opening archive file {path:?}"))?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            offset,
+        })
+    }
+
+    fn next_line(&mut self) -> Result<Option<(u64, String)>> {
+        loop {
+            let mut line = String::new();
+            let read = self.reader.read_line(&mut line)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            let offset = self.offset;
+            self.offset += read as u64;
+            if !line.trim().is_empty() {
+                return Ok(Some((offset, line)));
+            }
+        }
+    }
+}
+
+fn decode_line<T: ArchiveEvent>(
+    decoder: &ArchiveDecoder,
+    line: &str,
+    path: &Path,
+    offset: u64,
+) -> Result<Vec<(Indexed<T>, LogMeta)>> {
+    decoder
+        .decode(ArchiveRecord::Line(line.trim()))
+        .with_context(|| format!("Parsing archive file {path:?} at byte {offset}"))
+}
+
+fn parquet_reader(path: &Path) -> Result<SerializedFileReader<File>> {
+    let file = File::open(path).with_context(|| format!("Opening archive file {path:?}"))?;
+    SerializedFileReader::new(file).with_context(|| format!("Reading Parquet file {path:?}"))
+}
+
+fn read_row_group<T: ArchiveEvent>(
+    reader: &SerializedFileReader<File>,
+    row_group: usize,
+    decoder: &ArchiveDecoder,
+) -> Result<Vec<(Indexed<T>, LogMeta)>> {
+    let mut logs = vec![];
+    for row in reader.get_row_group(row_group)?.get_row_iter(None)? {
+        let log = ethereum_log_from_row(&row?)
+            .with_context(|| format!("Parsing row group {row_group}"))?;
+        logs.extend(decoder.decode(ArchiveRecord::EthereumLog(log))?);
+    }
+    Ok(logs)
+}
+
+/// Converts a Parquet row into a log. Binary columns may be raw bytes or hex strings.
+fn ethereum_log_from_row(row: &Row) -> Result<EthereumLog> {
+    let mut log = EthereumLog::default();
+    let mut topics: [Option<H256>; 4] = Default::default();
+    for (name, field) in row.get_column_iter() {
+        match name.as_str() {
+            "address" => log.address = H160::from(fixed_bytes(field, name)?),
+            "topic0" | "topic1" | "topic2" | "topic3" => {
+                let index = (name.as_bytes()[5] - b'0') as usize;
+                topics[index] = optional_bytes(field, name)?
+                    .map(|bytes| bytes.try_into().map(H256))
+                    .transpose()
+                    .map_err(|_| eyre!("Column {name} is not 32 bytes"))?;
+            }
+            "data" => log.data = Bytes::from(optional_bytes(field, name)?.unwrap_or_default()),
+            "block_number" => log.block_number = Some(U64::from(integer(field, name)?)),
+            "block_hash" => log.block_hash = Some(H256(fixed_bytes(field, name)?)),
+            "transaction_hash" => log.transaction_hash = Some(H256(fixed_bytes(field, name)?)),
+            "transaction_index" => log.transaction_index = Some(U64::from(integer(field, name)?)),
+            "log_index" => log.log_index = Some(U256::from(integer(field, name)?)),
+            "removed" => log.removed = Some(matches!(field, Field::Bool(true))),
+            _ => {}
+        }
+    }
+    // Topics are positional, so a missing topic ends them
+    log.topics = topics.into_iter().map_while(|topic| topic).collect();
+    Ok(log)
+}
+
+fn optional_bytes(field: &Field, name: &str) -> Result<Option<Vec<u8>>> {
+    Ok(match field {
+        Field::Null => None,
+        Field::Bytes(bytes) => Some(bytes.data().to_vec()),
+        Field::Str(string) => Some(
+            hex::decode(string.trim_start_matches("0x"))
+                .with_context(|| format!("Column {name} is not valid hex"))?,
+        ),
+        _ => bail!("Column {name} is not binary"),
+    })
+}
+
+fn fixed_bytes<const N: usize>(field: &Field, name: &str) -> Result<[u8; N]> {
+    optional_bytes(field, name)?
+        .ok_or_else(|| eyre!("Column {name} is null"))?
+        .try_into()
+        .map_err(|_| eyre!("Column {name} is not {N} bytes"))
+}
+
+fn integer(field: &Field, name: &str) -> Result<u64> {
+    Ok(match *field {
+        Field::UByte(value) => value.into(),
+        Field::UShort(value) => value.into(),
+        Field::UInt(value) => value.into(),
+        Field::ULong(value) => value,
+        Field::Byte(value) => u64::try_from(value)?,
+        Field::Short(value) => u64::try_from(value)?,
+        Field::Int(value) => u64::try_from(value)?,
+        Field::Long(value) => u64::try_from(value)?,
+        _ => bail!("Column {name} is not an integer"),
+    })
+}
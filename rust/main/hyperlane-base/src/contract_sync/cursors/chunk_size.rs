@@ -0,0 +1,234 @@
+use std::ops::RangeInclusive;
+
+use hyperlane_core::ChainCommunicationError;
+
+use crate::settings::ChunkSizeBounds;
+
+/// Lowercase messages that providers return when a queried range was too large,
+/// either because it held too many results or because it took too long to answer.
+/// These are matched verbatim, so that unrelated errors don't shrink the chunk size.
+const RANGE_TOO_LARGE_ERRORS: &[&str] = &[
+    // Infura, Alchemy
+    "query returned more than 10000 results",
+    // Alchemy
+    "log response size exceeded",
+    // QuickNode
+    "eth_getlogs is limited to a",
+    // Ankr, dRPC
+    "block range is too wide",
+    // Chainstack
+    "block range limit exceeded",
+    // BNB Chain, NodeReal
+    "exceed maximum block range",
+    // BlockPI
+    "block range exceeds the limit",
+    // Geth
+    "query timeout exceeded",
+    // Erigon
+    "logs matched by query exceeds limit",
+];
+
+/// Lowercase fragments of the errors returned when a request timed out, either
+/// by the provider or by the client. A range that takes too long to answer is
+/// often too large, but so few providers say so that any timeout is treated as
+/// a hint the range may be too large.
+const TIMEOUT_ERRORS: &[&str] = &["timed out", "timeout"];
+
+/// The size of the block ranges queried by a cursor, which may adapt to how
+/// busy the chain is and how much its providers are willing to return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AdaptiveChunkSize {
+    current: u32,
+    bounds: Option<ChunkSizeBounds>,
+    /// Whether the chunk size was shrunk because of a timeout since the last
+    /// successful query. Timeouts may also be network blips unrelated to the
+    /// range, so consecutive timeouts only shrink the chunk size once.
+    shrunk_on_timeout: bool,
+}
+
+impl AdaptiveChunkSize {
+    /// A chunk size that never changes.
+    pub fn fixed(chunk_size: u32) -> Self {
+        Self {
+            current: chunk_size,
+            bounds: None,
+            shrunk_on_timeout: false,
+        }
+    }
+
+    /// A chunk size starting at `chunk_size` that adapts within `bounds`, if any.
+    pub fn new(chunk_size: u32, bounds: Option<ChunkSizeBounds>) -> Self {
+        let current = match &bounds {
+            Some(bounds) => chunk_size.clamp(bounds.min, bounds.max),
+            None => chunk_size,
+        };
+        Self {
+            current,
+            bounds,
+            shrunk_on_timeout: false,
+        }
+    }
+
+    /// The current chunk size.
+    pub fn get(&self) -> u32 {
+        self.current
+    }
+
+    /// Grows the chunk size if a full-sized range returned only a few logs.
+    /// `logs_fetched` is the number of logs the provider returned, before any
+    /// deduplication, since that's what the provider's limits apply to.
+    pub fn on_range_queried(&mut self, range: &RangeInclusive<u32>, logs_fetched: usize) {
+        let Some(bounds) = &self.bounds else {
+            return;
+        };
+        self.shrunk_on_timeout = false;
+        // Ranges truncated at the tip say little about how large ranges may be.
+        let range_span = range.end().saturating_sub(*range.start());
+        if range_span < self.current || logs_fetched >= bounds.sparse_range_log_count {
+            return;
+        }
+        let grown = self.current.saturating_add(u32::max(self.current / 2, 1));
+        self.current = u32::min(grown, bounds.max);
+    }
+
+    /// Halves the chunk size if the error indicates the queried range was too large,
+    /// or if the query timed out and no timeout shrunk it since the last successful
+    /// query. Returns true if the chunk size changed.
+    pub fn on_query_error(&mut self, err: &ChainCommunicationError) -> bool {
+        let Some(bounds) = &self.bounds else {
+            return false;
+        };
+        let err = err.to_string().to_lowercase();
+        if !matches_any(&err, RANGE_TOO_LARGE_ERRORS) {
+            if !matches_any(&err, TIMEOUT_ERRORS) || self.shrunk_on_timeout {
+                return false;
+            }
+            self.shrunk_on_timeout = true;
+        }
+        let shrunk = u32::max(self.current / 2, bounds.min);
+        let changed = shrunk != self.current;
+        self.current = shrunk;
+        changed
+    }
+}
+
+fn matches_any(err: &str, fragments: &[&str]) -> bool {
+    fragments.iter().any(|fragment| err.contains(fragment))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOUNDS: ChunkSizeBounds = ChunkSizeBounds {
+        min: 10,
+        max: 1000,
+        sparse_range_log_count: 10,
+    };
+
+    #[test]
+    fn test_fixed_chunk_size_never_changes() {
+        let mut chunk_size = AdaptiveChunkSize::fixed(100);
+        chunk_size.on_range_queried(&(0..=100), 0);
+        assert_eq!(chunk_size.get(), 100);
+
+        let err = ChainCommunicationError::from_other_str("query returned more than 10000 results");
+        assert!(!chunk_size.on_query_error(&err));
+        assert_eq!(chunk_size.get(), 100);
+    }
+
+    #[test]
+    fn test_grows_on_sparse_full_ranges_up_to_max() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, Some(BOUNDS));
+        chunk_size.on_range_queried(&(0..=100), 0);
+        assert_eq!(chunk_size.get(), 150);
+
+        // ranges truncated at the tip, or that found plenty of logs, don't grow the chunk
+        chunk_size.on_range_queried(&(100..=120), 0);
+        chunk_size.on_range_queried(&(100..=250), BOUNDS.sparse_range_log_count);
+        assert_eq!(chunk_size.get(), 150);
+
+        for _ in 0..20 {
+            let span = chunk_size.get();
+            chunk_size.on_range_queried(&(0..=span), 0);
+        }
+        assert_eq!(chunk_size.get(), BOUNDS.max);
+    }
+
+    #[test]
+    fn test_shrinks_on_range_errors_down_to_min() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, Some(BOUNDS));
+
+        // errors that merely look like range errors don't shrink the chunk
+        for unrelated in [
+            "connection refused",
+            "rate limit exceeded",
+            "too many requests",
+            "tx has more than one signer",
+        ] {
+            let unrelated = ChainCommunicationError::from_other_str(unrelated);
+            assert!(!chunk_size.on_query_error(&unrelated));
+        }
+        assert_eq!(chunk_size.get(), 100);
+
+        let too_many =
+            ChainCommunicationError::from_other_str("Query returned more than 10000 results");
+        assert!(chunk_size.on_query_error(&too_many));
+        assert_eq!(chunk_size.get(), 50);
+
+        for _ in 0..10 {
+            chunk_size.on_query_error(&too_many);
+        }
+        assert_eq!(chunk_size.get(), BOUNDS.min);
+        assert!(!chunk_size.on_query_error(&too_many));
+    }
+
+    #[test]
+    fn test_shrinks_once_per_timeout_streak() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, Some(BOUNDS));
+        let timed_out = ChainCommunicationError::from_other_str("request timed out");
+
+        assert!(chunk_size.on_query_error(&timed_out));
+        assert_eq!(chunk_size.get(), 50);
+
+        // further timeouts may be a network blip, so they don't shrink the chunk again
+        let operation_timeout = ChainCommunicationError::from_other_str("operation timeout");
+        assert!(!chunk_size.on_query_error(&timed_out));
+        assert!(!chunk_size.on_query_error(&operation_timeout));
+        assert_eq!(chunk_size.get(), 50);
+
+        // but range errors still do
+        let too_many =
+            ChainCommunicationError::from_other_str("Query returned more than 10000 results");
+        assert!(chunk_size.on_query_error(&too_many));
+        assert_eq!(chunk_size.get(), 25);
+
+        // once a query succeeds, a timeout shrinks the chunk again
+        chunk_size.on_range_queried(&(0..=25), BOUNDS.sparse_range_log_count);
+        assert_eq!(chunk_size.get(), 25);
+        assert!(chunk_size.on_query_error(&operation_timeout));
+        assert_eq!(chunk_size.get(), 12);
+    }
+
+    #[test]
+    fn test_recognizes_provider_range_errors() {
+        for message in [
+            "query returned more than 10000 results. Try with this block range [0x1, 0x2].",
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range",
+            "eth_getLogs is limited to a 10,000 range",
+            "block range is too wide",
+            "exceed maximum block range: 5000",
+            "query timeout exceeded",
+        ] {
+            let mut chunk_size = AdaptiveChunkSize::new(100, Some(BOUNDS));
+            let err = ChainCommunicationError::from_other_str(message);
+            assert!(chunk_size.on_query_error(&err), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_initial_chunk_size_is_clamped() {
+        assert_eq!(AdaptiveChunkSize::new(5000, Some(BOUNDS)).get(), BOUNDS.max);
+        assert_eq!(AdaptiveChunkSize::new(1, Some(BOUNDS)).get(), BOUNDS.min);
+    }
+}
@@ -27,6 +27,14 @@ pub struct CursorMetrics {
     /// - `event_type`: the event type the cursor is indexing. Could be anything implementing `Indexable`.
     /// - `chain`: Chain the cursor is collecting data from.
     pub cursor_max_sequence: IntGaugeVec,
+
+    /// Size of the block ranges the cursor currently queries.
+    /// Only changes over time if adaptive chunk sizing is configured.
+    /// Labels:
+    /// - `event_type`: the event type the cursor is indexing. Could be anything implementing `Indexable`.
+    /// - `chain`: Chain the cursor is collecting data from.
+    /// - `cursor_type`: The type of cursor. E.g. `forward_sequenced`, `backward_sequenced`, `forward_rate_limited`.
+    pub cursor_chunk_size: IntGaugeVec,
 }
 
 impl CursorMetrics {
@@ -56,10 +64,19 @@ impl CursorMetrics {
             )
             .expect("failed to register cursor_max_sequence metric");
 
+        let cursor_chunk_size = metrics
+            .new_int_gauge(
+                "cursor_chunk_size",
+                "Size of the block ranges queried by the cursor",
+                &["event_type", "chain", "cursor_type"],
+            )
+            .expect("failed to register cursor_chunk_size metric");
+
         CursorMetrics {
             cursor_current_block,
             cursor_current_sequence,
             cursor_max_sequence,
+            cursor_chunk_size,
         }
     }
 }
@@ -10,20 +10,23 @@ use derive_new::new;
 use eyre::Result;
 
 use hyperlane_core::{
-    ContractSyncCursor, CursorAction, HyperlaneDomain, HyperlaneWatermarkedLogStore, Indexed,
-    Indexer, LogMeta,
+    ChainCommunicationError, ContractSyncCursor, CursorAction, HyperlaneDomain,
+    HyperlaneWatermarkedLogStore, Indexed, Indexer, LogMeta,
 };
+use tracing::debug;
 
 use crate::contract_sync::eta_calculator::SyncerEtaCalculator;
 
-use super::{CursorMetrics, Indexable};
+use crate::settings::ChunkSizeBounds;
+
+use super::{AdaptiveChunkSize, CursorMetrics, Indexable};
 
 /// Time window for the moving average used in the eta calculator in seconds.
 const ETA_TIME_WINDOW: f64 = 2. * 60.;
 
 #[derive(Debug, new)]
 pub(crate) struct SyncState {
-    chunk_size: u32,
+    chunk_size: AdaptiveChunkSize,
     /// The starting block for the cursor
     start_block: u32,
     /// The next block that should be indexed.
@@ -45,13 +48,13 @@ impl SyncState {
         let (from, to) = match self.direction {
             SyncDirection::Forward => {
                 let from = self.next_block;
-                let mut to = from + self.chunk_size;
+                let mut to = from + self.chunk_size.get();
                 to = u32::min(to, tip);
                 (from, to)
             }
             SyncDirection::Backward => {
                 let to = self.next_block;
-                let from = to.saturating_sub(self.chunk_size);
+                let from = to.saturating_sub(self.chunk_size.get());
                 (from, to)
             }
         };
@@ -109,7 +112,7 @@ impl<T: Indexable + Sync + Send + Debug + 'static> RateLimitedContractSyncCursor
             last_tip_update: Instant::now(),
             eta_calculator: SyncerEtaCalculator::new(initial_height, tip, ETA_TIME_WINDOW),
             sync_state: SyncState::new(
-                chunk_size,
+                AdaptiveChunkSize::fixed(chunk_size),
                 initial_height,
                 initial_height,
                 // The rate limited cursor currently only syncs in the forward direction.
@@ -120,10 +123,17 @@ impl<T: Indexable + Sync + Send + Debug + 'static> RateLimitedContractSyncCursor
         })
     }
 
+    /// Let the size of queried block ranges adapt within `bounds`, if any.
+    pub fn with_chunk_size_bounds(mut self, bounds: Option<ChunkSizeBounds>) -> Self {
+        self.sync_state.chunk_size =
+            AdaptiveChunkSize::new(self.sync_state.chunk_size.get(), bounds);
+        self
+    }
+
     /// Wait based on how close we are to the tip and update the tip,
     /// i.e. the highest block we may scrape.
     async fn get_rate_limit(&self) -> Result<Option<Duration>> {
-        if self.sync_state.next_block + self.sync_state.chunk_size < self.tip {
+        if self.sync_state.next_block + self.sync_state.chunk_size.get() < self.tip {
             // If doing the full chunk wouldn't exceed the already known tip we do not need to rate limit.
             return Ok(None);
         }
@@ -147,7 +157,7 @@ impl<T: Indexable + Sync + Send + Debug + 'static> RateLimitedContractSyncCursor
     }
 
     fn sync_step(&self) -> u32 {
-        self.sync_state.chunk_size
+        self.sync_state.chunk_size.get()
     }
 
     async fn get_next_range(&self) -> Result<Option<RangeInclusive<u32>>> {
@@ -176,6 +186,11 @@ impl<T: Indexable + Sync + Send + Debug + 'static> RateLimitedContractSyncCursor
             .cursor_current_block
             .with_label_values(label_values)
             .set(latest_block as i64);
+
+        self.metrics
+            .cursor_chunk_size
+            .with_label_values(label_values)
+            .set(self.sync_state.chunk_size.get() as i64);
     }
 }
 
@@ -206,7 +221,7 @@ where
 
     async fn update(
         &mut self,
-        _: Vec<(Indexed<T>, LogMeta)>,
+        logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
         self.update_metrics().await;
@@ -217,7 +232,7 @@ where
                 self.sync_state.start_block,
                 self.sync_state
                     .next_block
-                    .saturating_sub(self.sync_state.chunk_size),
+                    .saturating_sub(self.sync_state.chunk_size.get()),
             ))
             .await?;
         self.sync_state.update_range(range);
@@ -237,6 +252,22 @@ where
             }
         }
     }
+
+    fn on_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        if self.sync_state.chunk_size.on_query_error(err) {
+            debug!(
+                ?range,
+                chunk_size = self.sync_state.chunk_size.get(),
+                "Shrunk chunk size after query error"
+            );
+        }
+    }
+
+    fn on_logs_fetched(&mut self, range: &RangeInclusive<u32>, logs_fetched: usize) {
+        self.sync_state
+            .chunk_size
+            .on_range_queried(range, logs_fetched);
+    }
 }
 
 impl<T: Indexable> Debug for RateLimitedContractSyncCursor<T> {
@@ -332,6 +363,13 @@ pub(crate) mod test {
                 &["event_type", "chain"],
             )
             .unwrap(),
+            cursor_chunk_size: prometheus::IntGaugeVec::new(
+                prometheus::Opts::new("cursor_chunk_size", "Chunk size of the cursor")
+                    .namespace("mock")
+                    .subsystem("cursor"),
+                &["event_type", "chain", "cursor_type"],
+            )
+            .unwrap(),
         }
     }
     async fn mock_rate_limited_cursor<T: Indexable + Debug + Send + Sync + 'static>(
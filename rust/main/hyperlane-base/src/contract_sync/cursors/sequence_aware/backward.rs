@@ -10,11 +10,15 @@ use tokio::time::sleep;
 use tracing::{debug, instrument, warn};
 
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction, HyperlaneDomain,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceIndexed,
+    indexed_to_sequence_indexed_array, ChainCommunicationError, ContractSyncCursor, CursorAction,
+    HyperlaneDomain, HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta,
+    SequenceIndexed,
 };
 
-use crate::cursors::Indexable;
+use crate::{
+    cursors::{AdaptiveChunkSize, Indexable},
+    settings::ChunkSizeBounds,
+};
 
 use super::{CursorMetrics, LastIndexedSnapshot, MetricsData, TargetSnapshot};
 
@@ -24,8 +28,9 @@ const MAX_BACKWARD_SYNC_BLOCKING_TIME: Duration = Duration::from_secs(5);
 pub(crate) struct BackwardSequenceAwareSyncCursor<T> {
     /// The max chunk size to query for logs.
     /// If in sequence mode, this is the max number of sequences to query.
-    /// If in block mode, this is the max number of blocks to query.
-    chunk_size: u32,
+    /// If in block mode, this is the max number of blocks to query, and may adapt
+    /// within the configured chunk size bounds.
+    chunk_size: AdaptiveChunkSize,
     /// A store used to check which logs have already been indexed.
     store: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
     /// A snapshot of the last log to be indexed, or if no indexing has occurred yet,
@@ -79,7 +84,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> BackwardSequenceAware
         let MetricsData { domain, metrics } = metrics_data;
 
         Self {
-            chunk_size,
+            chunk_size: AdaptiveChunkSize::fixed(chunk_size),
             store,
             current_indexing_snapshot: last_indexed_snapshot.previous_target(),
             last_indexed_snapshot,
@@ -89,6 +94,15 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> BackwardSequenceAware
         }
     }
 
+    /// Let the size of queried block ranges adapt within `bounds`, if any.
+    /// Sequence ranges are never adapted.
+    pub fn with_chunk_size_bounds(mut self, bounds: Option<ChunkSizeBounds>) -> Self {
+        if let IndexMode::Block = self.index_mode {
+            self.chunk_size = AdaptiveChunkSize::new(self.chunk_size.get(), bounds);
+        }
+        self
+    }
+
     /// Get the last indexed sequence or 0 if no logs have been indexed yet.
     pub fn last_sequence(&self) -> u32 {
         self.last_indexed_snapshot.sequence.unwrap_or(0)
@@ -126,7 +140,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> BackwardSequenceAware
         // Query the block range ending at the current_indexing_snapshot's at_block.
         current_indexing_snapshot
             .at_block
-            .saturating_sub(self.chunk_size)..=current_indexing_snapshot.at_block
+            .saturating_sub(self.chunk_size.get())..=current_indexing_snapshot.at_block
     }
 
     /// Gets the next sequence range to index.
@@ -138,7 +152,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> BackwardSequenceAware
         // Query the sequence range ending at the current_indexing_snapshot's sequence.
         current_indexing_snapshot
             .sequence
-            .saturating_sub(self.chunk_size)..=current_indexing_snapshot.sequence
+            .saturating_sub(self.chunk_size.get())..=current_indexing_snapshot.sequence
     }
 
     /// Reads the DB to check if the current indexing sequence has already been indexed,
@@ -363,6 +377,11 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> BackwardSequenceAware
             .cursor_current_sequence
             .with(&labels)
             .set(sequence as i64);
+
+        self.metrics
+            .cursor_chunk_size
+            .with(&labels)
+            .set(self.chunk_size.get() as i64);
     }
 }
 
@@ -436,6 +455,23 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ContractSyncCursor<T>
 
         Ok(())
     }
+
+    fn on_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        if self.chunk_size.on_query_error(err) {
+            debug!(
+                ?range,
+                chunk_size = self.chunk_size.get(),
+                "Shrunk chunk size after query error"
+            );
+        }
+    }
+
+    fn on_logs_fetched(&mut self, range: &RangeInclusive<u32>, logs_fetched: usize) {
+        // Sequence ranges aren't sized by the chunk size
+        if matches!(self.index_mode, IndexMode::Block) {
+            self.chunk_size.on_range_queried(range, logs_fetched);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1037,7 +1073,7 @@ mod test {
             let mut cursor = get_cursor().await;
 
             // Set the chunk size to 100 to make it easier to test.
-            cursor.chunk_size = 100;
+            cursor.chunk_size = AdaptiveChunkSize::fixed(100);
 
             // Expect the range to be:
             // (current - chunk_size, current)
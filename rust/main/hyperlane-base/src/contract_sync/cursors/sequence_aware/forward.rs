@@ -12,12 +12,15 @@ use maplit::hashmap;
 use tracing::{debug, instrument, warn};
 
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction, HyperlaneDomain,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
-    SequenceIndexed,
+    indexed_to_sequence_indexed_array, ChainCommunicationError, ContractSyncCursor, CursorAction,
+    HyperlaneDomain, HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta,
+    SequenceAwareIndexer, SequenceIndexed,
 };
 
-use crate::cursors::Indexable;
+use crate::{
+    cursors::{AdaptiveChunkSize, Indexable},
+    settings::ChunkSizeBounds,
+};
 
 use super::{CursorMetrics, LastIndexedSnapshot, MetricsData, TargetSnapshot};
 
@@ -25,8 +28,9 @@ use super::{CursorMetrics, LastIndexedSnapshot, MetricsData, TargetSnapshot};
 pub(crate) struct ForwardSequenceAwareSyncCursor<T> {
     /// The max chunk size to query for logs.
     /// If in sequence mode, this is the max number of sequences to query.
-    /// If in block mode, this is the max number of blocks to query.
-    chunk_size: u32,
+    /// If in block mode, this is the max number of blocks to query, and may adapt
+    /// within the configured chunk size bounds.
+    chunk_size: AdaptiveChunkSize,
     /// The latest sequence count querier.
     /// This is used to check if there are new logs to index and to
     /// establish targets to index towards.
@@ -86,7 +90,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ForwardSequenceAwareS
         let MetricsData { domain, metrics } = metrics_data;
 
         Self {
-            chunk_size,
+            chunk_size: AdaptiveChunkSize::fixed(chunk_size),
             latest_sequence_querier,
             store,
             last_indexed_snapshot,
@@ -101,6 +105,15 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ForwardSequenceAwareS
         }
     }
 
+    /// Let the size of queried block ranges adapt within `bounds`, if any.
+    /// Sequence ranges are never adapted.
+    pub fn with_chunk_size_bounds(mut self, bounds: Option<ChunkSizeBounds>) -> Self {
+        if let IndexMode::Block = self.index_mode {
+            self.chunk_size = AdaptiveChunkSize::new(self.chunk_size.get(), bounds);
+        }
+        self
+    }
+
     /// Get the last indexed sequence or 0 if no logs have been indexed yet.
     pub fn last_sequence(&self) -> u32 {
         self.last_indexed_snapshot.sequence.unwrap_or(0)
@@ -196,7 +209,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ForwardSequenceAwareS
         Some(
             self.current_indexing_snapshot.at_block
                 ..=u32::min(
-                    self.current_indexing_snapshot.at_block + self.chunk_size,
+                    self.current_indexing_snapshot.at_block + self.chunk_size.get(),
                     tip,
                 ),
         )
@@ -210,7 +223,7 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ForwardSequenceAwareS
         target_sequence: u32,
     ) -> RangeInclusive<u32> {
         // Query the sequence range starting from the cursor count.
-        current_sequence..=u32::min(target_sequence, current_sequence + self.chunk_size)
+        current_sequence..=u32::min(target_sequence, current_sequence + self.chunk_size.get())
     }
 
     /// Reads the DB to check if the current indexing sequence has already been indexed,
@@ -444,6 +457,11 @@ impl<T: Debug + Clone + Sync + Send + Indexable + 'static> ForwardSequenceAwareS
             .with(&labels)
             .set(sequence as i64);
 
+        self.metrics
+            .cursor_chunk_size
+            .with(&labels)
+            .set(self.chunk_size.get() as i64);
+
         labels.remove("cursor_type");
         self.metrics
             .cursor_max_sequence
@@ -513,6 +531,23 @@ impl<T: Send + Sync + Clone + Debug + Indexable + 'static> ContractSyncCursor<T>
         };
         Ok(())
     }
+
+    fn on_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        if self.chunk_size.on_query_error(err) {
+            debug!(
+                ?range,
+                chunk_size = self.chunk_size.get(),
+                "Shrunk chunk size after query error"
+            );
+        }
+    }
+
+    fn on_logs_fetched(&mut self, range: &RangeInclusive<u32>, logs_fetched: usize) {
+        // Sequence ranges aren't sized by the chunk size
+        if matches!(self.index_mode, IndexMode::Block) {
+            self.chunk_size.on_range_queried(range, logs_fetched);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -651,6 +686,13 @@ pub(crate) mod test {
                 &["event_type", "chain"],
             )
             .unwrap(),
+            cursor_chunk_size: prometheus::IntGaugeVec::new(
+                prometheus::Opts::new("cursor_chunk_size", "Chunk size of the cursor")
+                    .namespace("mock")
+                    .subsystem("cursor"),
+                &["event_type", "chain", "cursor_type"],
+            )
+            .unwrap(),
         }
     }
 
@@ -15,6 +15,8 @@ mod forward;
 pub(crate) use backward::BackwardSequenceAwareSyncCursor;
 pub(crate) use forward::ForwardSequenceAwareSyncCursor;
 
+use crate::settings::ChunkSizeBounds;
+
 use super::{CursorMetrics, Indexable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,6 +91,7 @@ impl<T: Debug + Indexable + Clone + Sync + Send + 'static>
         latest_sequence_querier: Arc<dyn SequenceAwareIndexer<T>>,
         store: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
         chunk_size: u32,
+        chunk_size_bounds: Option<ChunkSizeBounds>,
         mode: IndexMode,
     ) -> Result<Self> {
         let (sequence_count, tip) = latest_sequence_querier
@@ -109,7 +112,8 @@ impl<T: Debug + Indexable + Clone + Sync + Send + 'static>
             tip,
             mode,
             metrics_data.clone(),
-        );
+        )
+        .with_chunk_size_bounds(chunk_size_bounds);
         let backward_cursor = BackwardSequenceAwareSyncCursor::new(
             chunk_size,
             store,
@@ -117,7 +121,8 @@ impl<T: Debug + Indexable + Clone + Sync + Send + 'static>
             tip,
             mode,
             metrics_data,
-        );
+        )
+        .with_chunk_size_bounds(chunk_size_bounds);
         Ok(Self {
             forward: forward_cursor,
             backward: backward_cursor,
@@ -161,4 +166,18 @@ impl<T: Send + Sync + Clone + Debug + 'static + Indexable> ContractSyncCursor<T>
             SyncDirection::Backward => self.backward.update(logs, range).await,
         }
     }
+
+    fn on_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        match self.last_direction {
+            SyncDirection::Forward => self.forward.on_query_error(range, err),
+            SyncDirection::Backward => self.backward.on_query_error(range, err),
+        }
+    }
+
+    fn on_logs_fetched(&mut self, range: &RangeInclusive<u32>, logs_fetched: usize) {
+        match self.last_direction {
+            SyncDirection::Forward => self.forward.on_logs_fetched(range, logs_fetched),
+            SyncDirection::Backward => self.backward.on_logs_fetched(range, logs_fetched),
+        }
+    }
 }
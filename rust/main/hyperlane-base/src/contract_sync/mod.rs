@@ -22,6 +22,8 @@ use tracing::{debug, info, instrument, trace, warn};
 
 use crate::settings::IndexSettings;
 
+/// Indexing from archived log exports
+pub mod archive;
 /// Broadcast channel utility, with async interface for `send`
 pub mod broadcast;
 pub(crate) mod cursors;
@@ -200,9 +202,11 @@ where
                     Ok(logs) => logs,
                     Err(err) => {
                         warn!(?err, ?range, "Error fetching logs in range");
+                        cursor.on_query_error(&range, &err);
                         break Some(SLEEP_DURATION);
                     }
                 };
+                cursor.on_logs_fetched(&range, logs.len());
 
                 let logs = self.dedupe_and_store_logs(logs, stored_logs_metric).await;
                 let logs_found = logs.len() as u64;
@@ -333,6 +337,8 @@ where
             from: watermark.unwrap_or(index_settings.from),
             chunk_size: index_settings.chunk_size,
             mode: index_settings.mode,
+            archive: index_settings.archive,
+            chunk_size_bounds: index_settings.chunk_size_bounds,
         };
         Ok(Box::new(
             RateLimitedContractSyncCursor::new(
@@ -343,7 +349,8 @@ where
                 index_settings.chunk_size,
                 index_settings.from,
             )
-            .await?,
+            .await?
+            .with_chunk_size_bounds(index_settings.chunk_size_bounds),
         ))
     }
 
@@ -384,6 +391,7 @@ where
                 self.indexer.clone(),
                 Arc::new(self.store.clone()),
                 index_settings.chunk_size,
+                index_settings.chunk_size_bounds,
                 index_settings.mode,
             )
             .await?,
@@ -65,6 +65,19 @@ impl DB {
             .map(Into::into)
     }
 
+    /// Opens an existing db at `db_path` without taking its lock, so that it can
+    /// be read while an agent has it open. Writes made afterwards aren't seen.
+    #[tracing::instrument(err)]
+    pub fn from_path_read_only(db_path: &Path) -> Result<DB> {
+        Rocks::open_for_read_only(&Options::default(), db_path, false)
+            .map_err(|e| DbError::OpeningError {
+                source: e,
+                path: db_path.into(),
+                canonicalized: db_path.into(),
+            })
+            .map(Into::into)
+    }
+
     /// Store a value in the DB
     pub fn store(&self, key: &[u8], value: &[u8]) -> Result<()> {
         Ok(self.0.put(key, value)?)
@@ -74,4 +87,9 @@ impl DB {
     pub fn retrieve(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         Ok(self.0.get(key)?)
     }
+
+    /// Delete a value from the DB
+    pub fn delete(&self, key: &[u8]) -> Result<()> {
+        Ok(self.0.delete(key)?)
+    }
 }
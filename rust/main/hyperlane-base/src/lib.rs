@@ -17,6 +17,8 @@ pub mod db;
 
 mod metadata;
 
+pub mod sink;
+
 pub mod metrics;
 pub use metrics::*;
 
@@ -40,6 +40,7 @@ pub struct CoreMetrics {
     last_known_message_nonce: IntGaugeVec,
     latest_tree_insertion_index: IntGaugeVec,
     submitter_queue_length: IntGaugeVec,
+    cosmos_gas_price: GaugeVec,
 
     operations_processed_count: IntCounterVec,
     messages_processed_count: IntCounterVec,
@@ -148,6 +149,16 @@ impl CoreMetrics {
             registry
         )?;
 
+        let cosmos_gas_price = register_gauge_vec_with_registry!(
+            opts!(
+                namespaced!("cosmos_gas_price"),
+                "Gas price paid for txs on Cosmos chains, unrounded, in the lowest denomination of the fee token",
+                const_labels_ref
+            ),
+            &["chain"],
+            registry
+        )?;
+
         let latest_checkpoint = register_int_gauge_vec_with_registry!(
             opts!(
                 namespaced!("latest_checkpoint"),
@@ -191,6 +202,7 @@ impl CoreMetrics {
             latest_tree_insertion_index,
 
             submitter_queue_length,
+            cosmos_gas_price,
 
             operations_processed_count,
             messages_processed_count,
@@ -348,6 +360,16 @@ impl CoreMetrics {
         self.submitter_queue_length.clone()
     }
 
+    /// Gas price paid for txs on Cosmos chains. Unlike the `gas_price` chain
+    /// metric, this isn't rounded to an integer, as gas prices are
+    /// fractional on many Cosmos chains.
+    ///
+    /// Labels:
+    /// - `chain`: Chain the gas price is for.
+    pub fn cosmos_gas_price(&self) -> GaugeVec {
+        self.cosmos_gas_price.clone()
+    }
+
     /// The number of operations successfully submitted by this process during
     /// its lifetime.
     ///
@@ -1,17 +1,18 @@
 use axum::async_trait;
 use ethers::prelude::Selector;
 use h_cosmos::CosmosProvider;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use eyre::{eyre, Context, Result};
 
 use ethers_prometheus::middleware::{ChainInfo, ContractInfo, PrometheusMiddlewareConf};
 use hyperlane_core::{
-    config::OperationBatchConfig, AggregationIsm, CcipReadIsm, ContractLocator, HyperlaneAbi,
-    HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneMessage, HyperlaneProvider, IndexMode,
-    InterchainGasPaymaster, InterchainGasPayment, InterchainSecurityModule, Mailbox,
-    MerkleTreeHook, MerkleTreeInsertion, MultisigIsm, ReorgPeriod, RoutingIsm,
-    SequenceAwareIndexer, ValidatorAnnounce, H256,
+    config::OperationBatchConfig, AggregationIsm, ArbL2ToL1Ism, ArbitrumOutboxProver, CcipReadIsm,
+    ContractLocator, HyperlaneAbi, HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneMessage,
+    HyperlaneProvider, IndexMode, InterchainGasPaymaster, InterchainGasPayment,
+    InterchainSecurityModule, Mailbox, MerkleTreeHook, MerkleTreeInsertion, MultisigIsm, NullIsm,
+    OpL2ToL1Ism, OpWithdrawalProver, ReorgPeriod, RoutingIsm, SequenceAwareIndexer,
+    ValidatorAnnounce, WeightedMultisigIsm, H256,
 };
 use hyperlane_cosmos as h_cosmos;
 use hyperlane_ethereum::{
@@ -20,8 +21,13 @@ use hyperlane_ethereum::{
 };
 use hyperlane_fuel as h_fuel;
 use hyperlane_sealevel as h_sealevel;
+use hyperlane_starknet as h_starknet;
 
 use crate::{
+    archive::{
+        ArchiveBackedIndexer, ArchiveEvent, ArchiveEventKind, ArchiveFormat, ArchiveIndexer,
+        ArchivePosition,
+    },
     metrics::AgentMetricsConf,
     settings::signers::{BuildableWithSignerConf, SignerConf},
     CoreMetrics,
@@ -81,9 +87,10 @@ impl TryFromWithMetrics<ChainConf> for MessageIndexer {
         metrics: &CoreMetrics,
         advanced_log_meta: bool,
     ) -> Result<Self> {
-        conf.build_message_indexer(metrics, advanced_log_meta)
-            .await
-            .map(Into::into)
+        let indexer = conf
+            .build_message_indexer(metrics, advanced_log_meta)
+            .await?;
+        conf.with_archive(indexer.into()).await
     }
 }
 
@@ -94,9 +101,10 @@ impl TryFromWithMetrics<ChainConf> for DeliveryIndexer {
         metrics: &CoreMetrics,
         advanced_log_meta: bool,
     ) -> Result<Self> {
-        conf.build_delivery_indexer(metrics, advanced_log_meta)
-            .await
-            .map(Into::into)
+        let indexer = conf
+            .build_delivery_indexer(metrics, advanced_log_meta)
+            .await?;
+        conf.with_archive(indexer.into()).await
     }
 }
 
@@ -107,9 +115,10 @@ impl TryFromWithMetrics<ChainConf> for IgpIndexer {
         metrics: &CoreMetrics,
         advanced_log_meta: bool,
     ) -> Result<Self> {
-        conf.build_interchain_gas_payment_indexer(metrics, advanced_log_meta)
-            .await
-            .map(Into::into)
+        let indexer = conf
+            .build_interchain_gas_payment_indexer(metrics, advanced_log_meta)
+            .await?;
+        conf.with_archive(indexer.into()).await
     }
 }
 
@@ -120,9 +129,10 @@ impl TryFromWithMetrics<ChainConf> for MerkleTreeHookIndexer {
         metrics: &CoreMetrics,
         advanced_log_meta: bool,
     ) -> Result<Self> {
-        conf.build_merkle_tree_hook_indexer(metrics, advanced_log_meta)
-            .await
-            .map(Into::into)
+        let indexer = conf
+            .build_merkle_tree_hook_indexer(metrics, advanced_log_meta)
+            .await?;
+        conf.with_archive(indexer.into()).await
     }
 }
 
@@ -137,6 +147,8 @@ pub enum ChainConnectionConf {
     Sealevel(h_sealevel::ConnectionConf),
     /// Cosmos configuration.
     Cosmos(h_cosmos::ConnectionConf),
+    /// Starknet configuration.
+    Starknet(h_starknet::ConnectionConf),
 }
 
 impl ChainConnectionConf {
@@ -147,6 +159,7 @@ impl ChainConnectionConf {
             Self::Fuel(_) => HyperlaneDomainProtocol::Fuel,
             Self::Sealevel(_) => HyperlaneDomainProtocol::Sealevel,
             Self::Cosmos(_) => HyperlaneDomainProtocol::Cosmos,
+            Self::Starknet(_) => HyperlaneDomainProtocol::Starknet,
         }
     }
 
@@ -183,6 +196,36 @@ pub struct IndexSettings {
     pub chunk_size: u32,
     /// The indexing mode.
     pub mode: IndexMode,
+    /// Optional archive of exported logs to serve historical ranges from.
+    pub archive: Option<ArchiveSettings>,
+    /// If set, cursors adapt the number of blocks they query at once within
+    /// these bounds, starting from `chunk_size`.
+    pub chunk_size_bounds: Option<ChunkSizeBounds>,
+}
+
+/// Bounds within which cursors may adapt the size of the block ranges they query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkSizeBounds {
+    /// The smallest number of blocks to query at once.
+    pub min: u32,
+    /// The largest number of blocks to query at once.
+    pub max: u32,
+    /// Full-sized ranges that yield fewer logs than this are considered sparse,
+    /// and the chunk size is grown.
+    pub sparse_range_log_count: usize,
+}
+
+/// Settings for backfilling from archived log exports
+#[derive(Debug, Clone)]
+pub struct ArchiveSettings {
+    /// Path to an export file, or to a directory of export files.
+    pub path: PathBuf,
+    /// The format of the export files.
+    pub format: ArchiveFormat,
+    /// Blocks strictly below this height are served from the archive, all
+    /// others from the chain's RPC. On Sealevel, whose indexers query ranges of
+    /// sequences, this is a sequence instead.
+    pub handoff_block: u32,
 }
 
 impl ChainConf {
@@ -203,10 +246,16 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::HyperlaneProviderBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                h_fuel::FuelProvider::new(locator.domain.clone(), conf)
+                    .await
+                    .map(|p| Box::new(p) as Box<dyn HyperlaneProvider>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => Ok(Box::new(h_sealevel::SealevelProvider::new(
                 locator.domain.clone(),
                 conf,
+                &Self::sealevel_rpc_metrics(metrics),
             )) as Box<dyn HyperlaneProvider>),
             ChainConnectionConf::Cosmos(conf) => {
                 let provider = CosmosProvider::new(
@@ -214,9 +263,20 @@ impl ChainConf {
                     conf.clone(),
                     locator.clone(),
                     None,
-                )?;
+                )?
+                .with_gas_price_metric(
+                    metrics
+                        .cosmos_gas_price()
+                        .with_label_values(&[locator.domain.name()]),
+                );
                 Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
             }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetProvider::new(locator.domain.clone(), conf, None)
+                    .await
+                    .map(|p| Box::new(p) as Box<dyn HyperlaneProvider>)
+                    .map_err(Into::into)
+            }
         }
         .context(ctx)
     }
@@ -244,13 +304,29 @@ impl ChainConf {
                     conf,
                     locator,
                     keypair.map(h_sealevel::SealevelKeypair::new),
+                    &Self::sealevel_rpc_metrics(metrics),
                 )
                 .map(|m| Box::new(m) as Box<dyn Mailbox>)
                 .map_err(Into::into)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                h_cosmos::CosmosMailbox::new(conf.clone(), locator.clone(), signer.clone())
+                match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => {
+                        h_cosmos::CosmosMailbox::new(conf.clone(), locator.clone(), signer)
+                            .map(|m| Box::new(m) as Box<dyn Mailbox>)
+                    }
+                    h_cosmos::CosmosConnectionMode::NativeModule => {
+                        h_cosmos::CosmosNativeMailbox::new(conf.clone(), locator.clone(), signer)
+                            .map(|m| Box::new(m) as Box<dyn Mailbox>)
+                    }
+                }
+                .map_err(Into::into)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                let signer = self.starknet_signer().await.context(ctx)?;
+                h_starknet::StarknetMailbox::new(conf, locator, signer)
+                    .await
                     .map(|m| Box::new(m) as Box<dyn Mailbox>)
                     .map_err(Into::into)
             }
@@ -271,20 +347,43 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MerkleTreeHookBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_conf) => {
-                todo!("Fuel does not support merkle tree hooks yet")
-            }
-            ChainConnectionConf::Sealevel(conf) => {
-                h_sealevel::SealevelMailbox::new(conf, locator, None)
-                    .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelMerkleTreeHook::new(conf, locator, wallet)
+                    .await
+                    .map(|h| Box::new(h) as Box<dyn MerkleTreeHook>)
                     .map_err(Into::into)
             }
+            ChainConnectionConf::Sealevel(conf) => h_sealevel::SealevelMailbox::new(
+                conf,
+                locator,
+                None,
+                &Self::sealevel_rpc_metrics(metrics),
+            )
+            .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
+            .map_err(Into::into),
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let hook =
-                    h_cosmos::CosmosMerkleTreeHook::new(conf.clone(), locator.clone(), signer)?;
-
-                Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
+                let hook: Box<dyn MerkleTreeHook> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => Box::new(
+                        h_cosmos::CosmosMerkleTreeHook::new(conf.clone(), locator.clone(), signer)?,
+                    ),
+                    h_cosmos::CosmosConnectionMode::NativeModule => {
+                        Box::new(h_cosmos::CosmosNativeMerkleTreeHook::new(
+                            conf.clone(),
+                            locator.clone(),
+                            signer,
+                        )?)
+                    }
+                };
+
+                Ok(hook)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetMerkleTreeHook::new(conf, locator)
+                    .await
+                    .map(|h| Box::new(h) as Box<dyn MerkleTreeHook>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -311,25 +410,54 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelMailboxIndexer::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(
                     conf,
                     locator,
+                    &self.reorg_period,
                     advanced_log_meta,
+                    &Self::sealevel_rpc_metrics(metrics),
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let reorg_period = self.reorg_period.as_blocks().context(ctx)?;
-                let indexer = Box::new(h_cosmos::CosmosMailboxDispatchIndexer::new(
-                    conf.clone(),
-                    locator,
-                    signer,
-                    reorg_period,
-                )?);
-                Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+                let reorg_period = h_cosmos::CosmosReorgPeriod::try_from(&self.reorg_period)
+                    .context(ctx)?
+                    .blocks();
+                let indexer: Box<dyn SequenceAwareIndexer<HyperlaneMessage>> =
+                    match conf.get_connection_mode() {
+                        h_cosmos::CosmosConnectionMode::CosmWasm => {
+                            Box::new(h_cosmos::CosmosMailboxDispatchIndexer::new(
+                                conf.clone(),
+                                locator,
+                                signer,
+                                reorg_period,
+                            )?)
+                        }
+                        h_cosmos::CosmosConnectionMode::NativeModule => {
+                            Box::new(h_cosmos::CosmosNativeMailboxDispatchIndexer::new(
+                                conf.clone(),
+                                locator,
+                                signer,
+                                reorg_period,
+                            )?)
+                        }
+                    };
+                Ok(indexer)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetMailboxIndexer::new(conf, locator, &self.reorg_period)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -356,25 +484,53 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelMailboxIndexer::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<H256>>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(
                     conf,
                     locator,
+                    &self.reorg_period,
                     advanced_log_meta,
+                    &Self::sealevel_rpc_metrics(metrics),
                 )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let reorg_period = self.reorg_period.as_blocks().context(ctx)?;
-                let indexer = Box::new(h_cosmos::CosmosMailboxDeliveryIndexer::new(
-                    conf.clone(),
-                    locator,
-                    signer,
-                    reorg_period,
-                )?);
-                Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
+                let reorg_period = h_cosmos::CosmosReorgPeriod::try_from(&self.reorg_period)
+                    .context(ctx)?
+                    .blocks();
+                let indexer: Box<dyn SequenceAwareIndexer<H256>> = match conf.get_connection_mode()
+                {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => {
+                        Box::new(h_cosmos::CosmosMailboxDeliveryIndexer::new(
+                            conf.clone(),
+                            locator,
+                            signer,
+                            reorg_period,
+                        )?)
+                    }
+                    h_cosmos::CosmosConnectionMode::NativeModule => {
+                        Box::new(h_cosmos::CosmosNativeMailboxDeliveryIndexer::new(
+                            conf.clone(),
+                            locator,
+                            reorg_period,
+                        )?)
+                    }
+                };
+                Ok(indexer)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetMailboxIndexer::new(conf, locator, &self.reorg_period)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<H256>>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -399,21 +555,49 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelInterchainGasPaymaster::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn InterchainGasPaymaster>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let paymaster = Box::new(
-                    h_sealevel::SealevelInterchainGasPaymaster::new(conf, &locator).await?,
+                    h_sealevel::SealevelInterchainGasPaymaster::new(
+                        conf,
+                        &locator,
+                        &Self::sealevel_rpc_metrics(metrics),
+                    )
+                    .await?,
                 );
                 Ok(paymaster as Box<dyn InterchainGasPaymaster>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let paymaster = Box::new(h_cosmos::CosmosInterchainGasPaymaster::new(
-                    conf.clone(),
-                    locator.clone(),
-                    signer,
-                )?);
-                Ok(paymaster as Box<dyn InterchainGasPaymaster>)
+                let paymaster: Box<dyn InterchainGasPaymaster> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => {
+                        Box::new(h_cosmos::CosmosInterchainGasPaymaster::new(
+                            conf.clone(),
+                            locator.clone(),
+                            signer,
+                        )?)
+                    }
+                    h_cosmos::CosmosConnectionMode::NativeModule => {
+                        Box::new(h_cosmos::CosmosNativeInterchainGasPaymaster::new(
+                            conf.clone(),
+                            locator.clone(),
+                            signer,
+                        )?)
+                    }
+                };
+                Ok(paymaster)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetInterchainGasPaymaster::new(conf, locator)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn InterchainGasPaymaster>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -443,26 +627,58 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelInterchainGasPaymasterIndexer::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(
                     h_sealevel::SealevelInterchainGasPaymasterIndexer::new(
                         conf,
                         locator,
+                        &self.reorg_period,
                         advanced_log_meta,
+                        &Self::sealevel_rpc_metrics(metrics),
                     )
                     .await?,
                 );
                 Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
             }
             ChainConnectionConf::Cosmos(conf) => {
-                let reorg_period = self.reorg_period.as_blocks().context(ctx)?;
-                let indexer = Box::new(h_cosmos::CosmosInterchainGasPaymasterIndexer::new(
-                    conf.clone(),
+                let reorg_period = h_cosmos::CosmosReorgPeriod::try_from(&self.reorg_period)
+                    .context(ctx)?
+                    .blocks();
+                let indexer: Box<dyn SequenceAwareIndexer<InterchainGasPayment>> =
+                    match conf.get_connection_mode() {
+                        h_cosmos::CosmosConnectionMode::CosmWasm => {
+                            Box::new(h_cosmos::CosmosInterchainGasPaymasterIndexer::new(
+                                conf.clone(),
+                                locator,
+                                reorg_period,
+                            )?)
+                        }
+                        h_cosmos::CosmosConnectionMode::NativeModule => {
+                            Box::new(h_cosmos::CosmosNativeInterchainGasPaymasterIndexer::new(
+                                conf.clone(),
+                                locator,
+                                reorg_period,
+                            )?)
+                        }
+                    };
+                Ok(indexer)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetInterchainGasPaymasterIndexer::new(
+                    conf,
                     locator,
-                    reorg_period,
-                )?);
-                Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
+                    &self.reorg_period,
+                )
+                .await
+                .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
+                .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -489,12 +705,20 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelMerkleTreeHookIndexer::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let mailbox_indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(
                     conf,
                     locator,
+                    &self.reorg_period,
                     advanced_log_meta,
+                    &Self::sealevel_rpc_metrics(metrics),
                 )?);
                 let indexer = Box::new(h_sealevel::SealevelMerkleTreeHookIndexer::new(
                     *mailbox_indexer,
@@ -503,15 +727,36 @@ impl ChainConf {
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let reorg_period = self.reorg_period.as_blocks().context(ctx)?;
-                let indexer = Box::new(h_cosmos::CosmosMerkleTreeHookIndexer::new(
-                    conf.clone(),
-                    locator,
-                    // TODO: remove signer requirement entirely
-                    signer,
-                    reorg_period,
-                )?);
-                Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
+                let reorg_period = h_cosmos::CosmosReorgPeriod::try_from(&self.reorg_period)
+                    .context(ctx)?
+                    .blocks();
+                let indexer: Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>> =
+                    match conf.get_connection_mode() {
+                        h_cosmos::CosmosConnectionMode::CosmWasm => {
+                            Box::new(h_cosmos::CosmosMerkleTreeHookIndexer::new(
+                                conf.clone(),
+                                locator,
+                                // TODO: remove signer requirement entirely
+                                signer,
+                                reorg_period,
+                            )?)
+                        }
+                        h_cosmos::CosmosConnectionMode::NativeModule => {
+                            Box::new(h_cosmos::CosmosNativeMerkleTreeHookIndexer::new(
+                                conf.clone(),
+                                locator,
+                                signer,
+                                reorg_period,
+                            )?)
+                        }
+                    };
+                Ok(indexer)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetMerkleTreeHookIndexer::new(conf, locator, &self.reorg_period)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -529,20 +774,41 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::ValidatorAnnounceBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelValidatorAnnounce::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn ValidatorAnnounce>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
-                let va = Box::new(h_sealevel::SealevelValidatorAnnounce::new(conf, locator));
+                let va = Box::new(h_sealevel::SealevelValidatorAnnounce::new(
+                    conf,
+                    locator,
+                    &Self::sealevel_rpc_metrics(metrics),
+                ));
                 Ok(va as Box<dyn ValidatorAnnounce>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let va = Box::new(h_cosmos::CosmosValidatorAnnounce::new(
-                    conf.clone(),
-                    locator.clone(),
-                    signer,
-                )?);
-
-                Ok(va as Box<dyn ValidatorAnnounce>)
+                let va: Box<dyn ValidatorAnnounce> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => {
+                        Box::new(h_cosmos::CosmosValidatorAnnounce::new(
+                            conf.clone(),
+                            locator.clone(),
+                            signer,
+                        )?)
+                    }
+                    h_cosmos::CosmosConnectionMode::NativeModule => {
+                        Box::new(h_cosmos::CosmosNativeValidatorAnnounce::new(
+                            conf.clone(),
+                            locator.clone(),
+                            signer,
+                        )?)
+                    }
+                };
+
+                Ok(va)
             }
         }
         .context("Building ValidatorAnnounce")
@@ -568,22 +834,47 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelInterchainSecurityModule::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn InterchainSecurityModule>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelInterchainSecurityModule::new(
                     conf,
                     locator,
                     keypair.map(h_sealevel::SealevelKeypair::new),
+                    &Self::sealevel_rpc_metrics(metrics),
                 ));
                 Ok(ism as Box<dyn InterchainSecurityModule>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let ism = Box::new(h_cosmos::CosmosInterchainSecurityModule::new(
-                    conf, locator, signer,
-                )?);
-                Ok(ism as Box<dyn InterchainSecurityModule>)
+                let ism: Box<dyn InterchainSecurityModule> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => Box::new(
+                        h_cosmos::CosmosInterchainSecurityModule::new(conf, locator, signer)?,
+                    ),
+                    h_cosmos::CosmosConnectionMode::NativeModule => Box::new(
+                        h_cosmos::CosmosNativeIsm::new(conf.clone(), locator, signer)?,
+                    ),
+                };
+                Ok(ism)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                let signer = self.starknet_signer().await.context(ctx)?;
+                h_starknet::StarknetValidatorAnnounce::new(conf, locator, signer)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn ValidatorAnnounce>)
+                    .map_err(Into::into)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetInterchainSecurityModule::new(conf, locator)
+                    .await
+                    .map(|i| Box::new(i) as Box<dyn InterchainSecurityModule>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -604,29 +895,220 @@ impl ChainConf {
                     .await
             }
 
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelMultisigIsm::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn MultisigIsm>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelMultisigIsm::new(
                     conf,
                     locator,
                     keypair.map(h_sealevel::SealevelKeypair::new),
+                    &Self::sealevel_rpc_metrics(metrics),
                 ));
                 Ok(ism as Box<dyn MultisigIsm>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let ism = Box::new(h_cosmos::CosmosMultisigIsm::new(
-                    conf.clone(),
-                    locator.clone(),
-                    signer,
-                )?);
-                Ok(ism as Box<dyn MultisigIsm>)
+                let ism: Box<dyn MultisigIsm> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => Box::new(
+                        h_cosmos::CosmosMultisigIsm::new(conf.clone(), locator.clone(), signer)?,
+                    ),
+                    h_cosmos::CosmosConnectionMode::NativeModule => Box::new(
+                        h_cosmos::CosmosNativeIsm::new(conf.clone(), locator.clone(), signer)?,
+                    ),
+                };
+                Ok(ism)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetMultisigIsm::new(conf, locator)
+                    .await
+                    .map(|i| Box::new(i) as Box<dyn MultisigIsm>)
+                    .map_err(Into::into)
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into a WeightedMultisigIsm contract
+    pub async fn build_weighted_multisig_ism(
+        &self,
+        address: H256,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn WeightedMultisigIsm>> {
+        let ctx = "Building weighted multisig ISM";
+        let locator = self.locator(address);
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::WeightedMultisigIsmBuilder {},
+                )
+                .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support weighted multisig ISM")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(_) => {
+                Err(eyre!("Sealevel does not support weighted multisig ISM yet")).context(ctx)
+            }
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support weighted multisig ISM yet")).context(ctx)
+            }
+            ChainConnectionConf::Starknet(_) => {
+                Err(eyre!("Starknet does not support weighted multisig ISM")).context(ctx)
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into an ArbL2ToL1Ism contract
+    pub async fn build_arb_l2_to_l1_ism(
+        &self,
+        address: H256,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn ArbL2ToL1Ism>> {
+        let ctx = "Building ArbL2ToL1 ISM";
+        let locator = self.locator(address);
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(conf, &locator, metrics, h_eth::ArbL2ToL1IsmBuilder {})
+                    .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support ArbL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(_) => {
+                Err(eyre!("Sealevel does not support ArbL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support ArbL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Starknet(_) => {
+                Err(eyre!("Starknet does not support ArbL2ToL1 ISM")).context(ctx)
+            }
+        }
+        .context(ctx)
+    }
+
+    /// Try to build a prover for L2 to L1 transactions sent from this chain,
+    /// which must be an Arbitrum Nitro chain
+    pub async fn build_arbitrum_outbox_prover(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn ArbitrumOutboxProver>> {
+        let ctx = "Building Arbitrum outbox prover";
+        // The prover only talks to precompiles, so no address is needed
+        let locator = self.locator(H256::zero());
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) if self.domain.is_arbitrum_nitro() => {
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::ArbitrumOutboxProverBuilder {},
+                )
+                .await
+            }
+            _ => Err(eyre!("{} is not an Arbitrum Nitro chain", self.domain)).context(ctx),
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into an OPL2ToL1Ism contract
+    pub async fn build_op_l2_to_l1_ism(
+        &self,
+        address: H256,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn OpL2ToL1Ism>> {
+        let ctx = "Building OPL2ToL1 ISM";
+        let locator = self.locator(address);
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(conf, &locator, metrics, h_eth::OpL2ToL1IsmBuilder {})
+                    .await
+            }
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support OPL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(_) => {
+                Err(eyre!("Sealevel does not support OPL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Cosmos(_) => {
+                Err(eyre!("Cosmos does not support OPL2ToL1 ISM")).context(ctx)
+            }
+            ChainConnectionConf::Starknet(_) => {
+                Err(eyre!("Starknet does not support OPL2ToL1 ISM")).context(ctx)
             }
         }
         .context(ctx)
     }
 
+    /// Try to build a prover for withdrawals sent from this chain, which must
+    /// be an OP Stack chain
+    pub async fn build_op_withdrawal_prover(
+        &self,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn OpWithdrawalProver>> {
+        let ctx = "Building OP Stack withdrawal prover";
+        // The prover only talks to predeploys, so no address is needed
+        let locator = self.locator(H256::zero());
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) if self.domain.is_op_stack() => {
+                self.build_ethereum(conf, &locator, metrics, h_eth::OpWithdrawalProverBuilder {})
+                    .await
+            }
+            _ => Err(eyre!("{} is not an OP Stack chain", self.domain)).context(ctx),
+        }
+        .context(ctx)
+    }
+
+    /// Try to convert the chain setting into an ISM that needs no metadata,
+    /// to check whether it rejects every message
+    pub async fn build_null_ism(
+        &self,
+        address: H256,
+        metrics: &CoreMetrics,
+    ) -> Result<Box<dyn NullIsm>> {
+        let ctx = "Building null ISM";
+        let locator = self.locator(address);
+
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) => {
+                self.build_ethereum(conf, &locator, metrics, h_eth::NullIsmBuilder {})
+                    .await
+            }
+            ChainConnectionConf::Fuel(_) => Err(eyre!(
+                "Fuel does not support pausable or trusted relayer ISMs"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Sealevel(_) => Err(eyre!(
+                "Sealevel does not support pausable or trusted relayer ISMs"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Cosmos(_) => Err(eyre!(
+                "Cosmos does not support pausable or trusted relayer ISMs"
+            ))
+            .context(ctx),
+            ChainConnectionConf::Starknet(_) => Err(eyre!(
+                "Starknet does not support pausable or trusted relayer ISMs"
+            ))
+            .context(ctx),
+        }
+        .context(ctx)
+    }
+
     /// Try to convert the chain setting into a RoutingIsm Ism contract
     pub async fn build_routing_ism(
         &self,
@@ -644,18 +1126,33 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::RoutingIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_read_only_wallet().await.context(ctx)?;
+                h_fuel::FuelRoutingIsm::new(conf, locator, wallet)
+                    .await
+                    .map(|c| Box::new(c) as Box<dyn RoutingIsm>)
+                    .map_err(Into::into)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support routing ISM yet")).context(ctx)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
-                let ism = Box::new(h_cosmos::CosmosRoutingIsm::new(
-                    &conf.clone(),
-                    locator.clone(),
-                    signer,
-                )?);
-                Ok(ism as Box<dyn RoutingIsm>)
+                let ism: Box<dyn RoutingIsm> = match conf.get_connection_mode() {
+                    h_cosmos::CosmosConnectionMode::CosmWasm => Box::new(
+                        h_cosmos::CosmosRoutingIsm::new(&conf.clone(), locator.clone(), signer)?,
+                    ),
+                    h_cosmos::CosmosConnectionMode::NativeModule => Box::new(
+                        h_cosmos::CosmosNativeIsm::new(conf.clone(), locator.clone(), signer)?,
+                    ),
+                };
+                Ok(ism)
+            }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetRoutingIsm::new(conf, locator)
+                    .await
+                    .map(|i| Box::new(i) as Box<dyn RoutingIsm>)
+                    .map_err(Into::into)
             }
         }
         .context(ctx)
@@ -678,10 +1175,20 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::AggregationIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support aggregation ISM")).context(ctx)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support aggregation ISM yet")).context(ctx)
             }
+            ChainConnectionConf::Cosmos(conf)
+                if conf.get_connection_mode() == h_cosmos::CosmosConnectionMode::NativeModule =>
+            {
+                Err(eyre!(
+                    "Cosmos native modules do not support aggregation ISM yet"
+                ))
+                .context(ctx)
+            }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
                 let ism = Box::new(h_cosmos::CosmosAggregationIsm::new(
@@ -692,6 +1199,12 @@ impl ChainConf {
 
                 Ok(ism as Box<dyn AggregationIsm>)
             }
+            ChainConnectionConf::Starknet(conf) => {
+                h_starknet::StarknetAggregationIsm::new(conf, locator)
+                    .await
+                    .map(|i| Box::new(i) as Box<dyn AggregationIsm>)
+                    .map_err(Into::into)
+            }
         }
         .context(ctx)
     }
@@ -713,13 +1226,18 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::CcipReadIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support CCIP read ISM")).context(ctx)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support CCIP read ISM yet")).context(ctx)
             }
             ChainConnectionConf::Cosmos(_) => {
                 Err(eyre!("Cosmos does not support CCIP read ISM yet")).context(ctx)
             }
+            ChainConnectionConf::Starknet(_) => {
+                Err(eyre!("Starknet does not support CCIP read ISM")).context(ctx)
+            }
         }
         .context(ctx)
     }
@@ -744,6 +1262,9 @@ impl ChainConf {
                     Box::new(conf.build::<h_sealevel::Keypair>().await?)
                 }
                 ChainConnectionConf::Cosmos(_) => Box::new(conf.build::<h_cosmos::Signer>().await?),
+                ChainConnectionConf::Starknet(_) => {
+                    Box::new(conf.build::<h_starknet::Signer>().await?)
+                }
             };
             Ok(Some(chain_signer))
         } else {
@@ -761,6 +1282,16 @@ impl ChainConf {
         })
     }
 
+    /// Fuel contract instances always need a wallet, but reads and indexing
+    /// only simulate calls so an unfunded random wallet is enough when no
+    /// signer is configured.
+    async fn fuel_read_only_wallet(&self) -> Result<fuels::prelude::WalletUnlocked> {
+        Ok(self
+            .signer()
+            .await?
+            .unwrap_or_else(|| fuels::prelude::WalletUnlocked::new_random(None)))
+    }
+
     async fn sealevel_signer(&self) -> Result<Option<h_sealevel::Keypair>> {
         self.signer().await
     }
@@ -769,6 +1300,10 @@ impl ChainConf {
         self.signer().await
     }
 
+    async fn starknet_signer(&self) -> Result<Option<h_starknet::Signer>> {
+        self.signer().await
+    }
+
     /// Try to build an agent metrics configuration from the chain config
     pub async fn agent_metrics_conf(&self, agent_name: String) -> Result<AgentMetricsConf> {
         let chain_signer_address = self.chain_signer().await?.map(|s| s.address_string());
@@ -833,6 +1368,43 @@ impl ChainConf {
         }
     }
 
+    /// Wraps a live indexer so that ranges below the configured handoff block are
+    /// served from the log archive, if one is configured.
+    async fn with_archive<T: ArchiveEvent>(
+        &self,
+        indexer: Arc<dyn SequenceAwareIndexer<T>>,
+    ) -> Result<Arc<dyn SequenceAwareIndexer<T>>> {
+        let Some(archive) = &self.index.archive else {
+            return Ok(indexer);
+        };
+        let position = match self.domain.domain_protocol() {
+            HyperlaneDomainProtocol::Sealevel => ArchivePosition::Sequence,
+            _ => ArchivePosition::Block,
+        };
+        let contract = match T::KIND {
+            ArchiveEventKind::Dispatch | ArchiveEventKind::Delivery => self.addresses.mailbox,
+            ArchiveEventKind::GasPayment => self.addresses.interchain_gas_paymaster,
+            ArchiveEventKind::MerkleTreeInsertion => self.addresses.merkle_tree_hook,
+        };
+        let archive_indexer =
+            ArchiveIndexer::<T>::load(&archive.path, archive.format, position, contract)
+                .await
+                .with_context(|| format!("Loading log archive for {}", self.domain))?;
+        Ok(Arc::new(ArchiveBackedIndexer::new(
+            archive_indexer,
+            indexer,
+            archive.handoff_block,
+        )))
+    }
+
+    fn sealevel_rpc_metrics(metrics: &CoreMetrics) -> h_sealevel::SealevelRpcMetrics {
+        let rpc_metrics = metrics.json_rpc_client_metrics();
+        h_sealevel::SealevelRpcMetrics {
+            request_count: rpc_metrics.request_count().cloned(),
+            request_duration_seconds: rpc_metrics.request_duration_seconds().cloned(),
+        }
+    }
+
     async fn build_ethereum<B>(
         &self,
         conf: &h_eth::ConnectionConf,
@@ -73,6 +73,7 @@ mod envs {
     pub use hyperlane_ethereum as h_eth;
     pub use hyperlane_fuel as h_fuel;
     pub use hyperlane_sealevel as h_sealevel;
+    pub use hyperlane_starknet as h_starknet;
 }
 
 /// AWS Credentials provider.
@@ -1,13 +1,18 @@
+use std::str::FromStr;
+
 use eyre::eyre;
 use hyperlane_sealevel::{
     HeliusPriorityFeeLevel, HeliusPriorityFeeOracleConfig, PriorityFeeOracleConfig,
+    RecentFeesPriorityFeeOracleConfig,
 };
 use url::Url;
 
 use h_eth::TransactionOverrides;
 
 use hyperlane_core::config::{ConfigErrResultExt, OperationBatchConfig};
-use hyperlane_core::{config::ConfigParsingError, HyperlaneDomainProtocol, NativeToken};
+use hyperlane_core::{
+    config::ConfigParsingError, FixedPointNumber, HyperlaneDomainProtocol, NativeToken,
+};
 
 use crate::settings::envs::*;
 use crate::settings::ChainConnectionConf;
@@ -71,10 +76,17 @@ pub fn build_ethereum_connection_conf(
         })
         .unwrap_or_default();
 
+    let arb_send_root_lookback_blocks = chain
+        .chain(err)
+        .get_opt_key("arbSendRootLookbackBlocks")
+        .parse_u64()
+        .end();
+
     Some(ChainConnectionConf::Ethereum(h_eth::ConnectionConf {
         rpc_connection: rpc_connection_conf?,
         transaction_overrides,
         operation_batch,
+        arb_send_root_lookback_blocks,
     }))
 }
 
@@ -142,6 +154,27 @@ pub fn build_cosmos_connection_conf(
 
     let native_token = parse_native_token(chain, err, 18);
 
+    let connection_mode = match chain
+        .chain(err)
+        .get_opt_key("connectionMode")
+        .parse_string()
+        .end()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        None | Some("cosmwasm") => Some(h_cosmos::CosmosConnectionMode::CosmWasm),
+        Some("native") => Some(h_cosmos::CosmosConnectionMode::NativeModule),
+        Some(_) => {
+            local_err.push(
+                &chain.cwp + "connection_mode",
+                eyre!("Unknown cosmos connection mode, expected `cosmwasm` or `native`"),
+            );
+            None
+        }
+    };
+
+    let gas_price_oracle = parse_cosmos_gas_price_oracle_config(chain, &mut local_err);
+
     if !local_err.is_ok() {
         err.merge(local_err);
         None
@@ -156,12 +189,67 @@ pub fn build_cosmos_connection_conf(
             contract_address_bytes.unwrap().try_into().unwrap(),
             operation_batch,
             native_token,
+            connection_mode.unwrap(),
+            gas_price_oracle.unwrap(),
         )))
     }
 }
 
+fn parse_cosmos_gas_price_oracle_config(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<h_cosmos::CosmosGasPriceOracleConfig> {
+    let Some(value_parser) = chain.chain(err).get_opt_key("gasPriceOracle").end() else {
+        // If not specified at all, use default
+        return Some(h_cosmos::CosmosGasPriceOracleConfig::default());
+    };
+
+    let oracle_type = value_parser
+        .chain(err)
+        .get_key("type")
+        .parse_string()
+        .end()
+        .or_else(|| {
+            err.push(
+                &value_parser.cwp + "type",
+                eyre!("Missing gas price oracle type"),
+            );
+            None
+        })
+        .unwrap_or_default();
+
+    let multiplier = value_parser
+        .chain(err)
+        .get_opt_key("multiplier")
+        .parse_f64()
+        .end()
+        .map(|multiplier| FixedPointNumber::from_str(&multiplier.to_string()))
+        .transpose()
+        .unwrap_or_else(|e| {
+            err.push(
+                &value_parser.cwp + "multiplier",
+                eyre!("Invalid gas price multiplier: {e}"),
+            );
+            None
+        })
+        .unwrap_or_else(|| FixedPointNumber::from(1u64));
+
+    match oracle_type {
+        "static" => Some(h_cosmos::CosmosGasPriceOracleConfig::Static),
+        "feemarket" => Some(h_cosmos::CosmosGasPriceOracleConfig::FeeMarket { multiplier }),
+        "osmosisTxFees" => Some(h_cosmos::CosmosGasPriceOracleConfig::OsmosisTxFees { multiplier }),
+        _ => {
+            err.push(
+                &value_parser.cwp + "type",
+                eyre!("Unknown gas price oracle type"),
+            );
+            None
+        }
+    }
+}
+
 fn build_sealevel_connection_conf(
-    url: &Url,
+    urls: &[Url],
     chain: &ValueParser,
     err: &mut ConfigParsingError,
     operation_batch: OperationBatchConfig,
@@ -171,21 +259,53 @@ fn build_sealevel_connection_conf(
     let native_token = parse_native_token(chain, err, 9);
     let priority_fee_oracle = parse_sealevel_priority_fee_oracle_config(chain, &mut local_err);
     let transaction_submitter = parse_transaction_submitter_config(chain, &mut local_err);
+    let address_lookup_tables = parse_sealevel_address_lookup_tables(chain, &mut local_err);
 
     if !local_err.is_ok() {
         err.merge(local_err);
         None
     } else {
         Some(ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
-            url: url.clone(),
+            urls: urls.to_owned(),
             operation_batch,
             native_token,
             priority_fee_oracle: priority_fee_oracle.unwrap(),
             transaction_submitter: transaction_submitter.unwrap(),
+            address_lookup_tables,
         }))
     }
 }
 
+fn parse_sealevel_address_lookup_tables(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Vec<h_sealevel::Pubkey> {
+    // A comma separated list, like `transactionSubmitter.urls`
+    chain
+        .chain(err)
+        .get_opt_key("addressLookupTables")
+        .parse_string()
+        .end()
+        .map(|tables| {
+            tables
+                .split(',')
+                .map(str::trim)
+                .filter(|table| !table.is_empty())
+                .filter_map(|table| {
+                    h_sealevel::Pubkey::from_str(table)
+                        .map_err(|e| {
+                            err.push(
+                                &chain.cwp + "addressLookupTables",
+                                eyre!("Invalid address lookup table `{table}`: {e}"),
+                            )
+                        })
+                        .ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn parse_native_token(
     chain: &ValueParser,
     err: &mut ConfigParsingError,
@@ -258,6 +378,8 @@ fn parse_sealevel_priority_fee_oracle_config(
                 };
                 Some(PriorityFeeOracleConfig::Helius(config))
             }
+            "recentFees" => parse_recent_fees_priority_fee_oracle_config(&value_parser, err)
+                .map(PriorityFeeOracleConfig::RecentFees),
             _ => {
                 err.push(
                     &value_parser.cwp + "type",
@@ -274,6 +396,58 @@ fn parse_sealevel_priority_fee_oracle_config(
     priority_fee_oracle
 }
 
+fn parse_recent_fees_priority_fee_oracle_config(
+    value_parser: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<RecentFeesPriorityFeeOracleConfig> {
+    let default = RecentFeesPriorityFeeOracleConfig::default();
+
+    let percentile = match value_parser
+        .chain(err)
+        .get_opt_key("percentile")
+        .parse_u32()
+        .end()
+    {
+        None => default.percentile,
+        Some(percentile) if percentile <= 100 => percentile as u8,
+        Some(_) => {
+            err.push(
+                &value_parser.cwp + "percentile",
+                eyre!("Percentile must be between 0 and 100"),
+            );
+            return None;
+        }
+    };
+    let floor = value_parser
+        .chain(err)
+        .get_opt_key("floor")
+        .parse_u64()
+        .end()
+        .unwrap_or(default.floor);
+    let cap = value_parser.chain(err).get_opt_key("cap").parse_u64().end();
+    let escalation_percent = value_parser
+        .chain(err)
+        .get_opt_key("escalationPercent")
+        .parse_u64()
+        .end()
+        .unwrap_or(default.escalation_percent);
+
+    if cap.is_some_and(|cap| cap < floor) {
+        err.push(
+            &value_parser.cwp + "cap",
+            eyre!("Priority fee cap must not be below the floor"),
+        );
+        return None;
+    }
+
+    Some(RecentFeesPriorityFeeOracleConfig {
+        percentile,
+        floor,
+        cap,
+        escalation_percent,
+    })
+}
+
 fn parse_helius_priority_fee_level(
     value_parser: &ValueParser,
     err: &mut ConfigParsingError,
@@ -338,6 +512,40 @@ fn parse_transaction_submitter_config(
                     .end();
                 Some(h_sealevel::TransactionSubmitterConfig::Jito { url })
             }
+            "jitobundle" => {
+                let url = chain
+                    .chain(err)
+                    .get_opt_key("transactionSubmitter")
+                    .get_opt_key("url")
+                    .parse_from_str("Invalid url")
+                    .end();
+                Some(h_sealevel::TransactionSubmitterConfig::JitoBundle { url })
+            }
+            "multirpc" => {
+                // A comma separated list, like `customRpcUrls`
+                let urls = chain
+                    .chain(err)
+                    .get_key("transactionSubmitter")
+                    .get_key("urls")
+                    .parse_string()
+                    .end()
+                    .map(|urls| {
+                        urls.split(',')
+                            .map(str::trim)
+                            .filter(|url| !url.is_empty())
+                            .map(|url| {
+                                Url::parse(url).map_err(|e| {
+                                    err.push(
+                                        &chain.cwp + "transactionSubmitter.urls",
+                                        eyre!("Invalid url `{url}`: {e}"),
+                                    )
+                                })
+                            })
+                            .filter_map(Result::ok)
+                            .collect()
+                    })?;
+                Some(h_sealevel::TransactionSubmitterConfig::MultiRpc { urls })
+            }
             _ => {
                 err.push(
                     &chain.cwp + "transactionSubmitter.type",
@@ -372,12 +580,17 @@ pub fn build_connection_conf(
             .iter()
             .next()
             .map(|url| ChainConnectionConf::Fuel(h_fuel::ConnectionConf { url: url.clone() })),
-        HyperlaneDomainProtocol::Sealevel => rpcs
-            .iter()
-            .next()
-            .and_then(|url| build_sealevel_connection_conf(url, chain, err, operation_batch)),
+        HyperlaneDomainProtocol::Sealevel => {
+            if rpcs.is_empty() {
+                return None;
+            }
+            build_sealevel_connection_conf(rpcs, chain, err, operation_batch)
+        }
         HyperlaneDomainProtocol::Cosmos => {
             build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
         }
+        HyperlaneDomainProtocol::Starknet => rpcs.iter().next().map(|url| {
+            ChainConnectionConf::Starknet(h_starknet::ConnectionConf { url: url.clone() })
+        }),
     }
 }
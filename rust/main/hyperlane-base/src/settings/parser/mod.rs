@@ -7,6 +7,7 @@
 use std::{
     collections::{HashMap, HashSet},
     default::Default,
+    path::PathBuf,
 };
 
 use convert_case::{Case, Casing};
@@ -23,7 +24,9 @@ use hyperlane_core::{
 };
 
 use crate::settings::{
-    chains::IndexSettings, parser::connection_parser::build_connection_conf, trace::TracingConfig,
+    chains::{ArchiveSettings, ChunkSizeBounds, IndexSettings},
+    parser::connection_parser::build_connection_conf,
+    trace::TracingConfig,
     ChainConf, CoreContractAddresses, Settings, SignerConf,
 };
 
@@ -35,6 +38,7 @@ mod connection_parser;
 mod json_value_parser;
 
 const DEFAULT_CHUNK_SIZE: u32 = 1999;
+const DEFAULT_SPARSE_RANGE_LOG_COUNT: u64 = 10;
 
 /// The base agent config
 #[derive(Debug, Deserialize)]
@@ -155,6 +159,31 @@ fn parse_chain(
         .get_opt_key("chunk")
         .parse_u32()
         .unwrap_or(DEFAULT_CHUNK_SIZE);
+    let min_chunk_size = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("minChunk")
+        .parse_u32()
+        .end();
+    let max_chunk_size = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("maxChunk")
+        .parse_u32()
+        .end();
+    let sparse_range_log_count = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("sparseRangeLogCount")
+        .parse_u64()
+        .unwrap_or(DEFAULT_SPARSE_RANGE_LOG_COUNT);
+    // Adaptive chunk sizing is opt-in, by configuring either bound.
+    let chunk_size_bounds =
+        (min_chunk_size.is_some() || max_chunk_size.is_some()).then(|| ChunkSizeBounds {
+            min: min_chunk_size.unwrap_or(1),
+            max: max_chunk_size.unwrap_or(chunk_size),
+            sparse_range_log_count: sparse_range_log_count as usize,
+        });
     let mode = chain
         .chain(&mut err)
         .get_opt_key("index")
@@ -171,6 +200,8 @@ fn parse_chain(
                 .unwrap_or_default()
         });
 
+    let archive = parse_archive_settings(&chain, &mut err);
+
     let mailbox = chain
         .chain(&mut err)
         .get_key("mailbox")
@@ -234,10 +265,44 @@ fn parse_chain(
             from,
             chunk_size,
             mode,
+            archive,
+            chunk_size_bounds,
         },
     })
 }
 
+/// Expects an optional `index.archive` object with a `path`, a `handoffBlock` and
+/// optionally a `format`.
+fn parse_archive_settings(
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<ArchiveSettings> {
+    let archive = chain
+        .chain(err)
+        .get_opt_key("index")
+        .get_opt_key("archive")
+        .end()?;
+
+    let path = archive
+        .chain(err)
+        .get_key("path")
+        .parse_string()
+        .end()
+        .map(PathBuf::from);
+    let format = archive
+        .chain(err)
+        .get_opt_key("format")
+        .parse_value("Invalid archive format")
+        .unwrap_or_default();
+    let handoff_block = archive.chain(err).get_key("handoffBlock").parse_u32().end();
+
+    Some(ArchiveSettings {
+        path: path?,
+        format,
+        handoff_block: handoff_block?,
+    })
+}
+
 /// Expects ChainMetadata
 fn parse_domain(chain: ValueParser, name: &str) -> ConfigResult<HyperlaneDomain> {
     let mut err = ConfigParsingError::default();
@@ -346,12 +411,35 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
                 account_address_type,
             })
         }};
+        (starkKey) => {{
+            let key = signer
+                .chain(&mut err)
+                .get_key("key")
+                .parse_private_key()
+                .unwrap_or_default();
+            let address = signer
+                .chain(&mut err)
+                .get_key("address")
+                .parse_address_hash()
+                .unwrap_or_default();
+            let is_legacy = signer
+                .chain(&mut err)
+                .get_opt_key("isLegacy")
+                .parse_bool()
+                .unwrap_or(false);
+            err.into_result(SignerConf::StarkKey {
+                key,
+                address,
+                is_legacy,
+            })
+        }};
     }
 
     match signer_type {
         Some("hexKey") => parse_signer!(hexKey),
         Some("aws") => parse_signer!(aws),
         Some("cosmosKey") => parse_signer!(cosmosKey),
+        Some("starkKey") => parse_signer!(starkKey),
         Some(t) => {
             Err(eyre!("Unknown signer type `{t}`")).into_config_result(|| &signer.cwp + "type")
         }
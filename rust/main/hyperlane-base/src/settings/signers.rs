@@ -37,6 +37,15 @@ pub enum SignerConf {
         /// Account address type for cosmos address
         account_address_type: AccountAddressType,
     },
+    /// Starknet Specific key
+    StarkKey {
+        /// Private key value
+        key: H256,
+        /// Address of the account contract
+        address: H256,
+        /// Whether the account uses the legacy (Cairo 0) calldata encoding
+        is_legacy: bool,
+    },
     /// Assume node will sign on RPC calls
     #[default]
     Node,
@@ -88,6 +97,9 @@ impl BuildableWithSignerConf for hyperlane_ethereum::Signers {
             SignerConf::CosmosKey { .. } => {
                 bail!("cosmosKey signer is not supported by Ethereum")
             }
+            SignerConf::StarkKey { .. } => {
+                bail!("starkKey signer is not supported by Ethereum")
+            }
             SignerConf::Node => bail!("Node signer"),
         })
     }
@@ -166,3 +178,25 @@ impl ChainSigner for hyperlane_cosmos::Signer {
         self.address.clone()
     }
 }
+
+#[async_trait]
+impl BuildableWithSignerConf for hyperlane_starknet::Signer {
+    async fn build(conf: &SignerConf) -> Result<Self, Report> {
+        if let SignerConf::StarkKey {
+            key,
+            address,
+            is_legacy,
+        } = conf
+        {
+            Ok(hyperlane_starknet::Signer::new(key, address, *is_legacy)?)
+        } else {
+            bail!(format!("{conf:?} key is not supported by starknet"));
+        }
+    }
+}
+
+impl ChainSigner for hyperlane_starknet::Signer {
+    fn address_string(&self) -> String {
+        format!("{:#x}", self.address)
+    }
+}
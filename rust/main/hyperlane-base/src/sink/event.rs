@@ -0,0 +1,132 @@
+use hyperlane_core::{
+    Delivery, HyperlaneDomain, HyperlaneMessage, Indexed, InterchainGasPayment, LogMeta, H256, U256,
+};
+use serde::{Deserialize, Serialize};
+
+/// An indexed event, as published to event sinks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SinkEvent {
+    /// A message was dispatched from the origin mailbox
+    #[serde(rename_all = "camelCase")]
+    Dispatch {
+        /// Id of the message
+        message_id: H256,
+        /// Message nonce
+        nonce: u32,
+        /// Origin domain ID
+        origin: u32,
+        /// Sender address in origin convention
+        sender: H256,
+        /// Destination domain ID
+        destination: u32,
+        /// Recipient address in destination convention
+        recipient: H256,
+        /// Hex encoded message contents
+        body: String,
+    },
+    /// A message was delivered by the destination mailbox
+    #[serde(rename_all = "camelCase")]
+    Delivery {
+        /// Id of the message
+        message_id: H256,
+    },
+    /// The gas costs of a message were paid
+    #[serde(rename_all = "camelCase")]
+    GasPayment {
+        /// Id of the message
+        message_id: H256,
+        /// Destination domain paid for
+        destination: u32,
+        /// Amount of native tokens paid
+        payment: U256,
+        /// Amount of destination gas paid for
+        gas_amount: U256,
+    },
+}
+
+impl SinkEvent {
+    /// Id of the message the event relates to
+    pub fn message_id(&self) -> H256 {
+        match self {
+            SinkEvent::Dispatch { message_id, .. }
+            | SinkEvent::Delivery { message_id }
+            | SinkEvent::GasPayment { message_id, .. } => *message_id,
+        }
+    }
+}
+
+/// An event together with where it was indexed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SinkEnvelope {
+    /// Position of the event in the outbox of its domain. Events may be
+    /// published more than once, so consumers should deduplicate on
+    /// `(domain, meta.transaction_id, meta.log_index)` rather than on this.
+    pub id: u64,
+    /// Name of the domain the event was indexed on
+    pub domain: String,
+    /// Id of the domain the event was indexed on
+    pub domain_id: u32,
+    /// Where the event was emitted
+    pub meta: LogMeta,
+    /// The event itself
+    pub event: SinkEvent,
+}
+
+impl SinkEnvelope {
+    /// Wrap an event indexed on `domain`. The id is assigned when the
+    /// envelope is enqueued in an outbox.
+    pub fn new(domain: &HyperlaneDomain, meta: LogMeta, event: SinkEvent) -> Self {
+        Self {
+            id: 0,
+            domain: domain.name().to_owned(),
+            domain_id: domain.id(),
+            meta,
+            event,
+        }
+    }
+}
+
+/// Indexed types that can be published to event sinks.
+pub trait ToSinkEvent {
+    /// Convert an indexed value into a sink event
+    fn to_sink_event(indexed: &Indexed<Self>) -> SinkEvent
+    where
+        Self: Sized;
+}
+
+impl ToSinkEvent for HyperlaneMessage {
+    fn to_sink_event(indexed: &Indexed<Self>) -> SinkEvent {
+        let message = indexed.inner();
+        SinkEvent::Dispatch {
+            message_id: message.id(),
+            nonce: message.nonce,
+            origin: message.origin,
+            sender: message.sender,
+            destination: message.destination,
+            recipient: message.recipient,
+            body: hex::encode(&message.body),
+        }
+    }
+}
+
+impl ToSinkEvent for Delivery {
+    fn to_sink_event(indexed: &Indexed<Self>) -> SinkEvent {
+        SinkEvent::Delivery {
+            message_id: *indexed.inner(),
+        }
+    }
+}
+
+impl ToSinkEvent for InterchainGasPayment {
+    fn to_sink_event(indexed: &Indexed<Self>) -> SinkEvent {
+        let payment = indexed.inner();
+        SinkEvent::GasPayment {
+            message_id: payment.message_id,
+            destination: payment.destination,
+            payment: payment.payment,
+            gas_amount: payment.gas_amount,
+        }
+    }
+}
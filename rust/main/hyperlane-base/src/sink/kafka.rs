@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use eyre::{bail, Result};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::Serialize;
+use url::Url;
+
+use super::{EventSink, SinkEnvelope};
+
+const KAFKA_JSON_CONTENT_TYPE: &str = "application/vnd.kafka.json.v2+json";
+
+/// Publishes events to a Kafka topic through a Kafka REST proxy.
+///
+/// Records are keyed by message id so that all the events of a message land
+/// on the same partition and are consumed in order.
+#[derive(Debug, Clone)]
+pub struct KafkaRestSink {
+    client: Client,
+    url: Url,
+}
+
+#[derive(Serialize)]
+struct KafkaRecords<'a> {
+    records: Vec<KafkaRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct KafkaRecord<'a> {
+    key: String,
+    value: &'a SinkEnvelope,
+}
+
+impl KafkaRestSink {
+    /// Create a sink producing to `topic` through the REST proxy at `proxy_url`
+    pub fn new(proxy_url: Url, topic: &str) -> Result<Self> {
+        let url = proxy_url.join(&format!("topics/{topic}"))?;
+        Ok(Self {
+            client: Client::new(),
+            url,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaRestSink {
+    async fn publish(&self, events: &[SinkEnvelope]) -> Result<()> {
+        let records = KafkaRecords {
+            records: events
+                .iter()
+                .map(|event| KafkaRecord {
+                    key: format!("{:?}", event.event.message_id()),
+                    value: event,
+                })
+                .collect(),
+        };
+        let response = self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, KAFKA_JSON_CONTENT_TYPE)
+            .body(serde_json::to_vec(&records)?)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("Kafka REST proxy {} responded with {status}", self.url);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_topic_url() {
+        let sink =
+            KafkaRestSink::new("http://localhost:8082/".parse().unwrap(), "hyperlane").unwrap();
+        assert_eq!(sink.url.as_str(), "http://localhost:8082/topics/hyperlane");
+    }
+}
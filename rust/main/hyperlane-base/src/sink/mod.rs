@@ -0,0 +1,184 @@
+//! Publishing of indexed events to external systems.
+//!
+//! Stores wrapped in a [`SinkedLogStore`] enqueue every log they store in a
+//! persisted [`SinkOutbox`], which a [`SinkPublisher`] drains into an
+//! [`EventSink`] such as a webhook, a Kafka topic or a NATS subject.
+
+use std::{fmt::Debug, path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use eyre::Result;
+use url::Url;
+
+pub use event::*;
+pub use kafka::*;
+pub use nats::*;
+pub use outbox::*;
+pub use publisher::*;
+pub use store::*;
+pub use webhook::*;
+
+mod event;
+mod kafka;
+mod nats;
+mod outbox;
+mod publisher;
+mod store;
+mod webhook;
+
+/// A destination for indexed events.
+#[async_trait]
+pub trait EventSink: Send + Sync + Debug {
+    /// Publish a batch of events. The batch is retried in full if this fails,
+    /// so implementations must tolerate events being published again.
+    async fn publish(&self, events: &[SinkEnvelope]) -> Result<()>;
+}
+
+/// Settings for publishing indexed events to an event sink
+#[derive(Debug, Clone)]
+pub struct SinkSettings {
+    /// Path to the database holding the outbox of events yet to be published
+    pub db: PathBuf,
+    /// Maximum number of events published at once
+    pub batch_size: usize,
+    /// Where to publish events
+    pub conf: SinkConf,
+}
+
+/// Where to publish indexed events
+#[derive(Debug, Clone)]
+pub enum SinkConf {
+    /// POST batches of events to a webhook
+    Webhook {
+        /// The webhook url
+        url: Url,
+    },
+    /// Produce events to a Kafka topic through a Kafka REST proxy
+    Kafka {
+        /// Base url of the REST proxy
+        url: Url,
+        /// Topic to produce to
+        topic: String,
+    },
+    /// Publish events to a NATS subject captured by a JetStream stream
+    Nats {
+        /// Url of the NATS server
+        url: Url,
+        /// Subject to publish to
+        subject: String,
+    },
+}
+
+impl SinkConf {
+    /// Build the configured sink
+    pub fn build(&self) -> Result<Arc<dyn EventSink>> {
+        Ok(match self {
+            SinkConf::Webhook { url } => Arc::new(WebhookSink::new(url.clone())),
+            SinkConf::Kafka { url, topic } => Arc::new(KafkaRestSink::new(url.clone(), topic)?),
+            SinkConf::Nats { url, subject } => Arc::new(NatsSink::new(url.clone(), subject)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use eyre::eyre;
+    use hyperlane_core::{
+        Delivery, HyperlaneDomain, HyperlaneLogStore, Indexed, KnownHyperlaneDomain, LogMeta, H256,
+    };
+
+    use crate::db::test_utils;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct NoopStore;
+
+    #[async_trait]
+    impl HyperlaneLogStore<Delivery> for NoopStore {
+        async fn store_logs(&self, logs: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+            Ok(logs.len() as u32)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct FailingStore;
+
+    #[async_trait]
+    impl HyperlaneLogStore<Delivery> for FailingStore {
+        async fn store_logs(&self, _logs: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+            Err(eyre!("db unavailable"))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct TestSink {
+        fail: Mutex<bool>,
+        published: Mutex<Vec<SinkEnvelope>>,
+    }
+
+    #[async_trait]
+    impl EventSink for TestSink {
+        async fn publish(&self, events: &[SinkEnvelope]) -> Result<()> {
+            if *self.fail.lock().unwrap() {
+                return Err(eyre!("sink unavailable"));
+            }
+            self.published.lock().unwrap().extend_from_slice(events);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_logs_are_published_at_least_once() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+            let outbox = Arc::new(SinkOutbox::new(&domain, db).unwrap());
+            let store = SinkedLogStore::new(NoopStore, domain.clone(), outbox.clone());
+            let sink = Arc::new(TestSink::default());
+            let publisher = SinkPublisher::new(outbox.clone(), sink.clone(), 2);
+
+            let logs = (1..=3)
+                .map(|i| (Indexed::new(H256::repeat_byte(i)), LogMeta::default()))
+                .collect::<Vec<_>>();
+            assert_eq!(store.store_logs(&logs).await.unwrap(), 3);
+
+            // a failed batch stays in the outbox
+            *sink.fail.lock().unwrap() = true;
+            assert!(publisher.publish_pending().await.is_err());
+            assert_eq!(outbox.len().unwrap(), 3);
+
+            *sink.fail.lock().unwrap() = false;
+            assert_eq!(publisher.publish_pending().await.unwrap(), 2);
+            assert_eq!(publisher.publish_pending().await.unwrap(), 1);
+            assert_eq!(publisher.publish_pending().await.unwrap(), 0);
+
+            let published = sink.published.lock().unwrap();
+            assert_eq!(
+                published
+                    .iter()
+                    .map(|e| e.event.message_id())
+                    .collect::<Vec<_>>(),
+                (1..=3).map(H256::repeat_byte).collect::<Vec<_>>()
+            );
+            assert!(published.iter().all(|e| e.domain == "arbitrum"));
+        })
+        .await
+    }
+    #[tokio::test]
+    async fn test_logs_are_enqueued_before_they_are_stored() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+            let outbox = Arc::new(SinkOutbox::new(&domain, db).unwrap());
+            let store = SinkedLogStore::new(FailingStore, domain, outbox.clone());
+
+            let logs = vec![(Indexed::new(H256::repeat_byte(1)), LogMeta::default())];
+            assert!(store.store_logs(&logs).await.is_err());
+
+            // the logs will be indexed and enqueued again, which sinks tolerate
+            assert_eq!(outbox.len().unwrap(), 1);
+        })
+        .await
+    }
+}
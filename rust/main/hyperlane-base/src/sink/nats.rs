@@ -0,0 +1,99 @@
+use async_nats::jetstream::{self, context::Publish};
+use async_trait::async_trait;
+use eyre::Result;
+use tokio::sync::OnceCell;
+use url::Url;
+
+use super::{EventSink, SinkEnvelope};
+
+/// Publishes events to a NATS subject captured by a JetStream stream.
+///
+/// Each event is only considered published once JetStream acknowledges it.
+/// Events carry a `Nats-Msg-Id` derived from where they were emitted, so
+/// republished events are dropped by the stream's duplicate window.
+#[derive(Debug)]
+pub struct NatsSink {
+    url: Url,
+    subject: String,
+    // Connected on first publish, since building a sink is synchronous. The
+    // client reconnects by itself afterwards.
+    context: OnceCell<jetstream::Context>,
+}
+
+impl NatsSink {
+    /// Create a sink publishing to `subject` on the NATS server at `url`
+    pub fn new(url: Url, subject: &str) -> Self {
+        Self {
+            url,
+            subject: subject.to_owned(),
+            context: OnceCell::new(),
+        }
+    }
+
+    async fn context(&self) -> Result<&jetstream::Context> {
+        self.context
+            .get_or_try_init(|| async {
+                let client = async_nats::connect(self.url.as_str()).await?;
+                Ok(jetstream::new(client))
+            })
+            .await
+    }
+}
+
+/// Id JetStream deduplicates an event on
+fn message_id(event: &SinkEnvelope) -> String {
+    format!(
+        "{}-{:?}-{}",
+        event.domain_id, event.meta.transaction_id, event.meta.log_index
+    )
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&self, events: &[SinkEnvelope]) -> Result<()> {
+        let context = self.context().await?;
+        let mut acks = Vec::with_capacity(events.len());
+        for event in events {
+            let publish = Publish::build()
+                .payload(serde_json::to_vec(event)?.into())
+                .message_id(message_id(event));
+            acks.push(context.send_publish(self.subject.clone(), publish).await?);
+        }
+        for ack in acks {
+            ack.await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, LogMeta, H256, H512, U256};
+
+    use crate::sink::SinkEvent;
+
+    use super::*;
+
+    #[test]
+    fn test_message_id_ignores_outbox_id() {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let meta = LogMeta {
+            transaction_id: H512::repeat_byte(1),
+            log_index: U256::from(3),
+            ..Default::default()
+        };
+        let event = SinkEnvelope::new(
+            &domain,
+            meta,
+            SinkEvent::Delivery {
+                message_id: H256::zero(),
+            },
+        );
+        let mut republished = event.clone();
+        republished.id = 7;
+
+        assert_eq!(message_id(&event), message_id(&republished));
+        assert!(message_id(&event).starts_with("42161-0x0101"));
+        assert!(message_id(&event).ends_with("-3"));
+    }
+}
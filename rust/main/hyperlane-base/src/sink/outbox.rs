@@ -0,0 +1,169 @@
+use std::sync::Mutex;
+
+use eyre::Result;
+use hyperlane_core::{Decode, Encode, HyperlaneDomain};
+
+use crate::db::DB;
+
+use super::SinkEnvelope;
+
+const OUTBOX_EVENT: &str = "sink_outbox_event_";
+const OUTBOX_NEXT_ID: &str = "sink_outbox_next_id";
+const OUTBOX_CURSOR: &str = "sink_outbox_cursor";
+
+/// A persisted queue of events waiting to be published to an event sink.
+///
+/// Events are enqueued with contiguous ids once they have been indexed, and
+/// the cursor is only advanced past them once the sink has acknowledged them,
+/// so every event is published at least once even across restarts.
+#[derive(Debug)]
+pub struct SinkOutbox {
+    domain_prefix: String,
+    db: DB,
+    // Guards id assignment, since the outbox is shared by the stores of every
+    // event type indexed on the domain.
+    next_id: Mutex<u64>,
+}
+
+impl SinkOutbox {
+    /// Open the outbox of `domain` in `db`.
+    pub fn new(domain: &HyperlaneDomain, db: DB) -> Result<Self> {
+        let domain_prefix = format!("{}_", domain.name());
+        let next_id = Self::retrieve_u64(&db, &format!("{domain_prefix}{OUTBOX_NEXT_ID}"))?;
+        Ok(Self {
+            domain_prefix,
+            db,
+            next_id: Mutex::new(next_id.unwrap_or_default()),
+        })
+    }
+
+    /// Persist `events`, assigning them the next ids in the outbox.
+    pub fn enqueue(&self, events: Vec<SinkEnvelope>) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let mut next_id = self.next_id.lock().expect("outbox lock poisoned");
+        let mut id = *next_id;
+        for mut event in events {
+            event.id = id;
+            self.db
+                .store(&self.event_key(id), &serde_json::to_vec(&event)?)?;
+            id += 1;
+        }
+        // Only make the events visible once all of them were written
+        self.db.store(&self.key(OUTBOX_NEXT_ID), &id.to_vec())?;
+        *next_id = id;
+        Ok(())
+    }
+
+    /// Up to `limit` events that have not been acknowledged yet, oldest first.
+    pub fn pending(&self, limit: usize) -> Result<Vec<SinkEnvelope>> {
+        let cursor = self.cursor()?;
+        let next_id = *self.next_id.lock().expect("outbox lock poisoned");
+        (cursor..next_id)
+            .take(limit)
+            .filter_map(|id| self.db.retrieve(&self.event_key(id)).transpose())
+            .map(|bytes| Ok(serde_json::from_slice(&bytes?)?))
+            .collect()
+    }
+
+    /// Mark every event up to and including `id` as published.
+    pub fn acknowledge(&self, id: u64) -> Result<()> {
+        let cursor = self.cursor()?;
+        if id < cursor {
+            return Ok(());
+        }
+        self.db
+            .store(&self.key(OUTBOX_CURSOR), &(id + 1).to_vec())?;
+        for acknowledged in cursor..=id {
+            self.db.delete(&self.event_key(acknowledged))?;
+        }
+        Ok(())
+    }
+
+    /// The id of the oldest event that has not been acknowledged yet.
+    pub fn cursor(&self) -> Result<u64> {
+        Ok(Self::retrieve_u64(&self.db, &self.key(OUTBOX_CURSOR))?.unwrap_or_default())
+    }
+
+    /// The number of events that have not been acknowledged yet.
+    pub fn len(&self) -> Result<u64> {
+        let next_id = *self.next_id.lock().expect("outbox lock poisoned");
+        Ok(next_id.saturating_sub(self.cursor()?))
+    }
+
+    /// Whether every event has been acknowledged.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    fn key(&self, name: &str) -> Vec<u8> {
+        format!("{}{name}", self.domain_prefix).into_bytes()
+    }
+
+    fn event_key(&self, id: u64) -> Vec<u8> {
+        let mut key = self.key(OUTBOX_EVENT);
+        key.extend(id.to_vec());
+        key
+    }
+
+    fn retrieve_u64(db: &DB, key: &str) -> Result<Option<u64>> {
+        db.retrieve(key.as_bytes())?
+            .map(|v| u64::read_from(&mut v.as_slice()))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{KnownHyperlaneDomain, LogMeta, H256};
+
+    use crate::{db::test_utils, sink::SinkEvent};
+
+    use super::*;
+
+    fn envelope(domain: &HyperlaneDomain, nonce: u8) -> SinkEnvelope {
+        SinkEnvelope::new(
+            domain,
+            LogMeta::default(),
+            SinkEvent::Delivery {
+                message_id: H256::repeat_byte(nonce),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_events_are_pending_until_acknowledged() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+            let outbox = SinkOutbox::new(&domain, db.clone()).unwrap();
+
+            outbox
+                .enqueue((1..=3).map(|i| envelope(&domain, i)).collect())
+                .unwrap();
+            let pending = outbox.pending(10).unwrap();
+            assert_eq!(
+                pending.iter().map(|e| e.id).collect::<Vec<_>>(),
+                vec![0, 1, 2]
+            );
+            assert_eq!(outbox.pending(2).unwrap().len(), 2);
+
+            outbox.acknowledge(1).unwrap();
+            let pending = outbox.pending(10).unwrap();
+            assert_eq!(pending.len(), 1);
+            assert_eq!(pending[0].event.message_id(), H256::repeat_byte(3));
+
+            // ids keep increasing when the outbox is reopened
+            let reopened = SinkOutbox::new(&domain, db).unwrap();
+            assert_eq!(reopened.cursor().unwrap(), 2);
+            reopened.enqueue(vec![envelope(&domain, 4)]).unwrap();
+            let pending = reopened.pending(10).unwrap();
+            assert_eq!(pending.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+
+            reopened.acknowledge(3).unwrap();
+            assert!(reopened.is_empty().unwrap());
+        })
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+use std::{sync::Arc, time::Duration};
+
+use derive_new::new;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+use super::{EventSink, SinkOutbox};
+
+/// How long to wait before checking the outbox again once it is drained.
+const IDLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Backoff bounds when the sink fails to accept a batch.
+const MIN_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Drains an outbox into an event sink.
+///
+/// Batches are retried until the sink accepts them, and the outbox cursor is
+/// only advanced afterwards, so delivery is at-least-once.
+#[derive(Debug, new)]
+pub struct SinkPublisher {
+    outbox: Arc<SinkOutbox>,
+    sink: Arc<dyn EventSink>,
+    batch_size: usize,
+}
+
+impl SinkPublisher {
+    /// Publish events as they are enqueued, forever.
+    pub async fn run(self) {
+        let mut backoff = MIN_RETRY_BACKOFF;
+        loop {
+            match self.publish_pending().await {
+                Ok(0) => sleep(IDLE_INTERVAL).await,
+                Ok(published) => {
+                    debug!(published, "Published events to sink");
+                    backoff = MIN_RETRY_BACKOFF;
+                }
+                Err(err) => {
+                    warn!(?err, ?backoff, "Failed to publish events to sink, retrying");
+                    sleep(backoff).await;
+                    backoff = Duration::min(backoff * 2, MAX_RETRY_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Publish a single batch of pending events, returning how many were published.
+    pub async fn publish_pending(&self) -> eyre::Result<usize> {
+        let events = self.outbox.pending(self.batch_size)?;
+        let Some(last) = events.last() else {
+            return Ok(0);
+        };
+        self.sink.publish(&events).await?;
+        self.outbox.acknowledge(last.id)?;
+        Ok(events.len())
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use derive_new::new;
+use eyre::Result;
+use hyperlane_core::{
+    HyperlaneDomain, HyperlaneLogStore, HyperlaneSequenceAwareIndexerStoreReader,
+    HyperlaneWatermarkedLogStore, Indexed, LogMeta,
+};
+
+use super::{SinkEnvelope, SinkOutbox, ToSinkEvent};
+
+/// A log store that enqueues logs in an outbox to be published to an event
+/// sink before storing them in the wrapped store.
+///
+/// Enqueueing first means a failure between the two writes can only publish
+/// an event twice, once the logs are indexed again, rather than lose it after
+/// the wrapped store has moved on.
+#[derive(Debug, Clone, new)]
+pub struct SinkedLogStore<S> {
+    inner: S,
+    domain: HyperlaneDomain,
+    outbox: Arc<SinkOutbox>,
+}
+
+#[async_trait]
+impl<T, S> HyperlaneLogStore<T> for SinkedLogStore<S>
+where
+    T: ToSinkEvent + Send + Sync + 'static,
+    S: HyperlaneLogStore<T>,
+{
+    async fn store_logs(&self, logs: &[(Indexed<T>, LogMeta)]) -> Result<u32> {
+        let events = logs
+            .iter()
+            .map(|(log, meta)| SinkEnvelope::new(&self.domain, meta.clone(), T::to_sink_event(log)))
+            .collect();
+        self.outbox.enqueue(events)?;
+        self.inner.store_logs(logs).await
+    }
+}
+
+#[async_trait]
+impl<T, S> HyperlaneSequenceAwareIndexerStoreReader<T> for SinkedLogStore<S>
+where
+    T: Send + Sync + 'static,
+    S: HyperlaneSequenceAwareIndexerStoreReader<T>,
+{
+    async fn retrieve_by_sequence(&self, sequence: u32) -> Result<Option<T>> {
+        self.inner.retrieve_by_sequence(sequence).await
+    }
+
+    async fn retrieve_log_block_number_by_sequence(&self, sequence: u32) -> Result<Option<u64>> {
+        self.inner
+            .retrieve_log_block_number_by_sequence(sequence)
+            .await
+    }
+}
+
+#[async_trait]
+impl<T, S> HyperlaneWatermarkedLogStore<T> for SinkedLogStore<S>
+where
+    T: ToSinkEvent + Send + Sync + 'static,
+    S: HyperlaneWatermarkedLogStore<T>,
+{
+    async fn retrieve_high_watermark(&self) -> Result<Option<u32>> {
+        self.inner.retrieve_high_watermark().await
+    }
+
+    async fn store_high_watermark(&self, block_number: u32) -> Result<()> {
+        self.inner.store_high_watermark(block_number).await
+    }
+}
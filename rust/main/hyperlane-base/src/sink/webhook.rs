@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use eyre::{bail, Result};
+use reqwest::{header::CONTENT_TYPE, Client};
+use url::Url;
+
+use super::{EventSink, SinkEnvelope};
+
+/// Publishes events by POSTing them as a JSON array to a webhook.
+/// Any non-2xx response is treated as a failure and the batch is retried.
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    client: Client,
+    url: Url,
+}
+
+impl WebhookSink {
+    /// Create a sink posting to `url`
+    pub fn new(url: Url) -> Self {
+        Self {
+            client: Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    async fn publish(&self, events: &[SinkEnvelope]) -> Result<()> {
+        let response = self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(events)?)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("Webhook {} responded with {status}", self.url);
+        }
+        Ok(())
+    }
+}
@@ -11,6 +11,77 @@ use hyperlane_core::{
 
 use crate::{CheckpointSyncer, CoreMetrics};
 
+/// The quorum that signatures over a checkpoint need to reach for a
+/// validator set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidatorQuorum {
+    /// At least this many validators need to sign
+    Threshold(usize),
+    /// The summed weight of the validators that signed needs to reach
+    /// `threshold_weight`
+    Weighted {
+        /// Weight of each validator, in the order of the validator set
+        weights: Vec<u128>,
+        /// Total weight needed
+        threshold_weight: u128,
+    },
+}
+
+impl ValidatorQuorum {
+    /// The weight of the signature of the validator at `validator_index` in
+    /// the validator set
+    pub fn weight(&self, validator_index: usize) -> u128 {
+        match self {
+            ValidatorQuorum::Threshold(_) => 1,
+            ValidatorQuorum::Weighted { weights, .. } => {
+                weights.get(validator_index).copied().unwrap_or_default()
+            }
+        }
+    }
+
+    /// The total weight needed to reach the quorum
+    pub fn threshold(&self) -> u128 {
+        match self {
+            ValidatorQuorum::Threshold(threshold) => *threshold as u128,
+            ValidatorQuorum::Weighted {
+                threshold_weight, ..
+            } => *threshold_weight,
+        }
+    }
+
+    /// The number of signatures a checkpoint whose last signer is at
+    /// `last_signer_index` in the validator set must carry to verify.
+    ///
+    /// Weighted multisig ISMs only look for signers among the first
+    /// `min(validators, signatures)` validators, so signatures by validators
+    /// further down the set are rejected unless the metadata carries enough
+    /// (padding) signatures.
+    pub fn min_signature_count(&self, last_signer_index: usize) -> usize {
+        match self {
+            ValidatorQuorum::Threshold(_) => 0,
+            ValidatorQuorum::Weighted { .. } => last_signer_index + 1,
+        }
+    }
+
+    /// Given the latest index signed by each validator, keyed by position in
+    /// the validator set, returns the highest index for which the quorum can
+    /// (supposedly) be reached.
+    pub fn highest_quorum_index(&self, latest_indices: &[(usize, u32)]) -> Option<u32> {
+        let mut latest_indices = latest_indices.to_vec();
+        // Sort in descending order of index, so that after accumulating the
+        // n'th entry we have the weight of every validator that signed it
+        latest_indices.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut weight: u128 = 0;
+        for (validator_index, index) in latest_indices {
+            weight = weight.saturating_add(self.weight(validator_index));
+            if weight >= self.threshold() {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
 /// For a particular validator set, fetches signed checkpoints from multiple
 /// validators to create MultisigSignedCheckpoints.
 #[derive(Clone, Debug, new)]
@@ -32,6 +103,23 @@ impl MultisigCheckpointSyncer {
         origin: &HyperlaneDomain,
         destination: &HyperlaneDomain,
     ) -> Vec<u32> {
+        // Filter out any validators that did not return a latest index
+        self.get_latest_indices_and_update_metrics(validators, origin, destination)
+            .await
+            .values()
+            .copied()
+            .flatten()
+            .collect()
+    }
+
+    /// Gets the latest checkpoint index from each validator's checkpoint syncer,
+    /// keyed by validator, and updates the validator latest checkpoint metrics.
+    pub async fn get_latest_indices_and_update_metrics(
+        &self,
+        validators: &[H256],
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+    ) -> HashMap<H160, Option<u32>> {
         // Get the latest_index from each validator's checkpoint syncer.
         // If a validator does not return a latest index, None is recorded so
         // this can be surfaced in the metrics.
@@ -77,16 +165,15 @@ impl MultisigCheckpointSyncer {
                 .await;
         }
 
-        // Filter out any validators that did not return a latest index
-        latest_indices.values().copied().flatten().collect()
+        latest_indices
     }
 
     /// Attempts to get the latest checkpoint with a quorum of signatures among
     /// validators.
     ///
     /// First iterates through the `latest_index` of each validator's checkpoint
-    /// syncer, looking for the highest index that >= `threshold` validators
-    /// have returned.
+    /// syncer, looking for the highest index that validators reaching the
+    /// `quorum` have returned.
     ///
     /// Attempts to find a quorum of signed checkpoints from that index,
     /// iterating backwards if unsuccessful, until the (optional) index is
@@ -97,15 +184,23 @@ impl MultisigCheckpointSyncer {
     pub async fn fetch_checkpoint_in_range(
         &self,
         validators: &[H256],
-        threshold: usize,
+        quorum: &ValidatorQuorum,
         minimum_index: u32,
         maximum_index: u32,
         origin: &HyperlaneDomain,
         destination: &HyperlaneDomain,
     ) -> Result<Option<MultisigSignedCheckpoint>> {
-        let mut latest_indices = self
-            .get_validator_latest_checkpoints_and_update_metrics(validators, origin, destination)
+        let latest_indices = self
+            .get_latest_indices_and_update_metrics(validators, origin, destination)
             .await;
+        let latest_indices: Vec<(usize, u32)> = validators
+            .iter()
+            .enumerate()
+            .filter_map(|(validator_index, validator)| {
+                let index = (*latest_indices.get(&H160::from(*validator))?)?;
+                Some((validator_index, index))
+            })
+            .collect();
 
         debug!(
             ?latest_indices,
@@ -117,10 +212,7 @@ impl MultisigCheckpointSyncer {
             return Ok(None);
         }
 
-        // Sort in descending order. The n'th index will represent
-        // the highest index for which we (supposedly) have (n+1) signed checkpoints
-        latest_indices.sort_by(|a, b| b.cmp(a));
-        if let Some(&highest_quorum_index) = latest_indices.get(threshold - 1) {
+        if let Some(highest_quorum_index) = quorum.highest_quorum_index(&latest_indices) {
             // The highest viable checkpoint index is the minimum of the highest index
             // we (supposedly) have a quorum for, and the maximum index for which we can
             // generate a proof.
@@ -130,8 +222,7 @@ impl MultisigCheckpointSyncer {
                 return Ok(None);
             }
             for index in (minimum_index..=start_index).rev() {
-                if let Ok(Some(checkpoint)) =
-                    self.fetch_checkpoint(validators, threshold, index).await
+                if let Ok(Some(checkpoint)) = self.fetch_checkpoint(validators, quorum, index).await
                 {
                     return Ok(Some(checkpoint));
                 }
@@ -148,7 +239,7 @@ impl MultisigCheckpointSyncer {
     pub async fn fetch_checkpoint(
         &self,
         validators: &[H256],
-        threshold: usize,
+        quorum: &ValidatorQuorum,
         index: u32,
     ) -> Result<Option<MultisigSignedCheckpoint>> {
         // Keeps track of signed validator checkpoints for a particular root.
@@ -156,8 +247,9 @@ impl MultisigCheckpointSyncer {
         // particular index, but we'd like to be robust to this not being the case
         let mut signed_checkpoints_per_root: HashMap<H256, Vec<SignedCheckpointWithMessageId>> =
             HashMap::new();
+        let mut signed_weight_per_root: HashMap<H256, u128> = HashMap::new();
 
-        for validator in validators.iter() {
+        for (validator_index, validator) in validators.iter().enumerate() {
             let addr = H160::from(*validator);
             if let Some(checkpoint_syncer) = self.checkpoint_syncers.get(&addr) {
                 // Gracefully ignore an error fetching the checkpoint from a validator's
@@ -193,19 +285,31 @@ impl MultisigCheckpointSyncer {
                     let signed_checkpoints = signed_checkpoints_per_root.entry(root).or_default();
                     signed_checkpoints.push(signed_checkpoint);
 
-                    // Count the number of signatures for this signed checkpoint
-                    let signature_count = signed_checkpoints.len();
+                    // Sum the weight of the signatures for this signed checkpoint
+                    let signed_weight = signed_weight_per_root.entry(root).or_default();
+                    *signed_weight = signed_weight.saturating_add(quorum.weight(validator_index));
                     debug!(
                         validator = format!("{:#x}", validator),
                         index = index,
                         root = format!("{:#x}", root),
-                        signature_count = signature_count,
+                        signature_count = signed_checkpoints.len(),
+                        signed_weight = *signed_weight,
                         "Found signed checkpoint"
                     );
 
                     // If we've hit a quorum, create a MultisigSignedCheckpoint
-                    if signature_count >= threshold {
-                        let checkpoint: MultisigSignedCheckpoint = signed_checkpoints.try_into()?;
+                    if *signed_weight >= quorum.threshold() {
+                        let mut checkpoint: MultisigSignedCheckpoint =
+                            signed_checkpoints.try_into()?;
+                        // The ISM stops reading signatures once the quorum is
+                        // reached, so repeating the last one only extends the
+                        // range of validators it looks for signers in
+                        if let Some(&last) = checkpoint.signatures.last() {
+                            let count = quorum
+                                .min_signature_count(validator_index)
+                                .max(checkpoint.signatures.len());
+                            checkpoint.signatures.resize(count, last);
+                        }
                         debug!(checkpoint=?checkpoint, "Fetched multisig checkpoint");
                         return Ok(Some(checkpoint));
                     }
@@ -225,3 +329,110 @@ impl MultisigCheckpointSyncer {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::signers::LocalWallet;
+    use hyperlane_core::{
+        Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt,
+    };
+    use hyperlane_ethereum::Signers;
+    use prometheus::Registry;
+
+    use crate::LocalStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_threshold_quorum_index() {
+        let quorum = ValidatorQuorum::Threshold(2);
+        assert_eq!(
+            quorum.highest_quorum_index(&[(0, 5), (1, 9), (2, 7)]),
+            Some(7)
+        );
+        assert_eq!(quorum.highest_quorum_index(&[(1, 9)]), None);
+    }
+
+    #[test]
+    fn test_weighted_quorum_index() {
+        let quorum = ValidatorQuorum::Weighted {
+            weights: vec![6, 1, 3],
+            threshold_weight: 7,
+        };
+        // The two validators with the highest indices only have a weight of 4
+        assert_eq!(
+            quorum.highest_quorum_index(&[(0, 5), (1, 9), (2, 7)]),
+            Some(5)
+        );
+        assert_eq!(quorum.highest_quorum_index(&[(1, 9), (2, 7)]), None);
+        // Validators outside of the set carry no weight
+        assert_eq!(quorum.weight(3), 0);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_signatures_cover_last_signer() {
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(1),
+                mailbox_domain: 1,
+                root: H256::repeat_byte(2),
+                index: 0,
+            },
+            message_id: H256::repeat_byte(3),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let signers: Vec<Signers> = (1..=3)
+            .map(|key| Signers::from(format!("{key:064x}").parse::<LocalWallet>().unwrap()))
+            .collect();
+        let validators: Vec<H256> = signers.iter().map(|s| s.eth_address().into()).collect();
+        let mut checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>> = HashMap::new();
+        for (i, signer) in signers.iter().enumerate() {
+            let storage = LocalStorage::new(dir.path().join(i.to_string()), None).unwrap();
+            // Only the last validator in the set signs, its weight alone
+            // reaches the quorum
+            if i == 2 {
+                let signed = signer.sign(checkpoint).await.unwrap();
+                storage.write_checkpoint(&signed).await.unwrap();
+            }
+            checkpoint_syncers.insert(signer.eth_address(), Arc::new(storage));
+        }
+        let metrics = Arc::new(CoreMetrics::new("test", 8080, Registry::new()).unwrap());
+        let syncer = MultisigCheckpointSyncer::new(checkpoint_syncers, metrics, None);
+        let quorum = ValidatorQuorum::Weighted {
+            weights: vec![1, 1, 5],
+            threshold_weight: 5,
+        };
+
+        let signed = syncer
+            .fetch_checkpoint(&validators, &quorum, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(signed.signatures.len(), 3);
+
+        // Verify the way the ISM does, which only looks for signers among the
+        // first `min(validators, signatures)` validators
+        let validator_count = validators.len().min(signed.signatures.len());
+        let (mut validator_index, mut weight) = (0, 0);
+        for signature in &signed.signatures {
+            if weight >= quorum.threshold() {
+                break;
+            }
+            let signer = SignedCheckpointWithMessageId {
+                value: signed.checkpoint,
+                signature: *signature,
+            }
+            .recover()
+            .unwrap();
+            while validator_index < validator_count
+                && H256::from(signer) != validators[validator_index]
+            {
+                validator_index += 1;
+            }
+            assert!(validator_index < validator_count, "Invalid signer");
+            weight += quorum.weight(validator_index);
+            validator_index += 1;
+        }
+        assert!(weight >= quorum.threshold());
+    }
+}
@@ -193,6 +193,8 @@ pub enum KnownHyperlaneDomain {
     SealevelTest2 = 13376,
     CosmosTest99990 = 99990,
     CosmosTest99991 = 99991,
+    StarknetTest23448593 = 23448593,
+    StarknetTest23448594 = 23448594,
 
     // -- Test chains --
     //
@@ -276,6 +278,8 @@ pub enum HyperlaneDomainProtocol {
     Sealevel,
     /// A Cosmos-based chain type which uses hyperlane-cosmos.
     Cosmos,
+    /// A Starknet-based chain type which uses hyperlane-starknet.
+    Starknet,
 }
 
 impl HyperlaneDomainProtocol {
@@ -286,6 +290,7 @@ impl HyperlaneDomainProtocol {
             Fuel => format!("{:?}", addr),
             Sealevel => format!("{:?}", addr),
             Cosmos => format!("{:?}", addr),
+            Starknet => format!("{:?}", addr),
         }
     }
 }
@@ -334,7 +339,7 @@ impl KnownHyperlaneDomain {
             ],
             LocalTestChain: [
                 Test1, Test2, Test3, FuelTest1, SealevelTest1, SealevelTest2, CosmosTest99990,
-                CosmosTest99991
+                CosmosTest99991, StarknetTest23448593, StarknetTest23448594
             ],
         })
     }
@@ -367,6 +372,7 @@ impl KnownHyperlaneDomain {
                 // Local chains
                 CosmosTest99990, CosmosTest99991,
             ],
+            HyperlaneDomainProtocol::Starknet: [StarknetTest23448593, StarknetTest23448594],
         })
     }
 
@@ -399,8 +405,8 @@ impl KnownHyperlaneDomain {
                 Sei, SolanaMainnet, Taiko, Viction, Zetachain,
 
                 // Local chains
-                CosmosTest99990, CosmosTest99991, FuelTest1, SealevelTest1, SealevelTest2, Test1,
-                Test2, Test3,
+                CosmosTest99990, CosmosTest99991, FuelTest1, SealevelTest1, SealevelTest2,
+                StarknetTest23448593, StarknetTest23448594, Test1, Test2, Test3,
 
                 // Test chains
                 Alfajores, BinanceSmartChainTestnet, Chiado, Fuji, Holesky, MoonbaseAlpha, ScrollSepolia,
@@ -581,6 +587,13 @@ impl HyperlaneDomain {
         matches!(self, Self::Known(KnownHyperlaneDomain::Injective))
     }
 
+    pub const fn is_op_stack(&self) -> bool {
+        matches!(
+            self.domain_technical_stack(),
+            HyperlaneDomainTechnicalStack::OpStack
+        )
+    }
+
     pub const fn is_zksync_stack(&self) -> bool {
         matches!(
             self.domain_technical_stack(),
@@ -592,7 +605,7 @@ impl HyperlaneDomain {
         use HyperlaneDomainProtocol::*;
         let protocol = self.domain_protocol();
         many_to_one!(match protocol {
-            IndexMode::Block: [Ethereum, Cosmos],
+            IndexMode::Block: [Ethereum, Cosmos, Starknet],
             IndexMode::Sequence : [Sealevel, Fuel],
         })
     }
@@ -0,0 +1,74 @@
+use crate::HyperlaneMessage;
+
+use super::{IsmConfig, IsmVerificationError};
+
+/// Bytes used to store one member of the (start, end) range tuple of each
+/// sub-module, see `AggregationIsmMetadata.sol`
+const METADATA_RANGE_SIZE: usize = 4;
+
+/// Returns the metadata of each sub-module of an aggregation ISM, or `None`
+/// for sub-modules without metadata
+pub fn aggregation_sub_metadata(
+    metadata: &[u8],
+    ism_count: usize,
+) -> Result<Vec<Option<&[u8]>>, IsmVerificationError> {
+    let read_u32 = |offset: usize| -> Result<usize, IsmVerificationError> {
+        metadata
+            .get(offset..offset + METADATA_RANGE_SIZE)
+            .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()) as usize)
+            .ok_or(IsmVerificationError::MalformedMetadata(
+                "too short for the ranges of all sub-modules",
+            ))
+    };
+    (0..ism_count)
+        .map(|index| {
+            let range_offset = METADATA_RANGE_SIZE * 2 * index;
+            let start = read_u32(range_offset)?;
+            if start == 0 {
+                return Ok(None);
+            }
+            let end = read_u32(range_offset + METADATA_RANGE_SIZE)?;
+            metadata
+                .get(start..end)
+                .map(Some)
+                .ok_or(IsmVerificationError::MalformedMetadata(
+                    "sub-module range out of bounds",
+                ))
+        })
+        .collect()
+}
+
+/// Verifies metadata of an `AggregationIsm`: exactly `threshold` sub-modules
+/// must have metadata, and all of them must verify the message with it, like
+/// `AbstractAggregationIsm.verify` requires. It verifies every sub-module
+/// with metadata, and reverts if there are more than `threshold`.
+pub fn verify_aggregation(
+    modules: &[IsmConfig],
+    threshold: u8,
+    metadata: &[u8],
+    message: &HyperlaneMessage,
+) -> Result<(), IsmVerificationError> {
+    let threshold = threshold as usize;
+    let sub_metadata = aggregation_sub_metadata(metadata, modules.len())?;
+    let with_metadata: Vec<_> = modules
+        .iter()
+        .zip(sub_metadata)
+        .enumerate()
+        .filter_map(|(index, (module, metadata))| Some((index, module, metadata?)))
+        .collect();
+    if with_metadata.len() != threshold {
+        return Err(IsmVerificationError::ThresholdNotMet {
+            found: with_metadata.len(),
+            threshold,
+        });
+    }
+    for (index, module, metadata) in with_metadata {
+        module
+            .verify(metadata, message)
+            .map_err(|source| IsmVerificationError::SubModule {
+                index,
+                source: Box::new(source),
+            })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,242 @@
+//! Verification of ISM metadata without calling the destination chain.
+//!
+//! Given the configuration of an ISM, e.g. as read from the destination
+//! chain with the ISM traits, [`IsmConfig::verify`] checks metadata the way
+//! the Solidity ISMs do. This lets obviously-bad metadata be rejected
+//! without RPC calls, and historical deliveries be audited offline.
+
+use std::collections::HashMap;
+
+use crate::{HyperlaneMessage, H160, H256};
+
+pub use aggregation::*;
+pub use multisig::*;
+
+mod aggregation;
+mod multisig;
+
+/// The configuration of an ISM, as far as verification is concerned
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IsmConfig {
+    /// A `MessageIdMultisigIsm`, possibly a static one
+    MessageIdMultisig {
+        /// The validators, in the order of the ISM
+        validators: Vec<H256>,
+        /// How many of the validators need to sign
+        threshold: u8,
+    },
+    /// A `MerkleRootMultisigIsm`, possibly a static one
+    MerkleRootMultisig {
+        /// The validators, in the order of the ISM
+        validators: Vec<H256>,
+        /// How many of the validators need to sign
+        threshold: u8,
+    },
+    /// An `AggregationIsm`, possibly a static one
+    Aggregation {
+        /// The sub-modules, in the order of the ISM
+        modules: Vec<IsmConfig>,
+        /// How many of the sub-modules need to verify the message
+        threshold: u8,
+    },
+    /// A `DomainRoutingIsm`, routing messages by origin
+    DomainRouting {
+        /// The ISM of each origin domain
+        domains: HashMap<u32, IsmConfig>,
+    },
+}
+
+/// Why an ISM rejects metadata
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IsmVerificationError {
+    /// The metadata can't be decoded
+    #[error("Malformed metadata: {0}")]
+    MalformedMetadata(&'static str),
+    /// A signature can't be recovered, or isn't in its canonical form
+    #[error("Signature {index} is invalid")]
+    InvalidSignature {
+        /// The position of the signature in the metadata
+        index: usize,
+    },
+    /// A signature is by an address that isn't one of the remaining
+    /// validators, i.e. it's not a validator, a duplicate, or out of order
+    #[error("Signature {index} by {signer:?} is not by a validator, or out of order")]
+    UnexpectedSigner {
+        /// The position of the signature in the metadata
+        index: usize,
+        /// The address that signed
+        signer: H160,
+    },
+    /// The signed checkpoint can't include the message
+    #[error("Checkpoint index {checkpoint_index} is before message index {message_index}")]
+    CheckpointBeforeMessage {
+        /// The index of the signed checkpoint
+        checkpoint_index: u32,
+        /// The index of the message in the merkle tree
+        message_index: u32,
+    },
+    /// More or fewer sub-modules have metadata than the threshold
+    #[error("{found} sub-modules have metadata but exactly {threshold} are required")]
+    ThresholdNotMet {
+        /// The number of sub-modules with metadata
+        found: usize,
+        /// The number of sub-modules with metadata needed
+        threshold: usize,
+    },
+    /// A sub-module of an aggregation rejects its metadata
+    #[error("Sub-module {index} rejects its metadata: {source}")]
+    SubModule {
+        /// The index of the sub-module
+        index: usize,
+        /// Why it rejects its metadata
+        source: Box<IsmVerificationError>,
+    },
+    /// A routing ISM has no route for the message
+    #[error("No ISM for origin {0}")]
+    NoRoute(u32),
+}
+
+impl IsmConfig {
+    /// Verifies `metadata` for `message` like the ISM would onchain
+    pub fn verify(
+        &self,
+        metadata: &[u8],
+        message: &HyperlaneMessage,
+    ) -> Result<(), IsmVerificationError> {
+        match self {
+            Self::MessageIdMultisig {
+                validators,
+                threshold,
+            } => verify_message_id_multisig(validators, *threshold, metadata, message),
+            Self::MerkleRootMultisig {
+                validators,
+                threshold,
+            } => verify_merkle_root_multisig(validators, *threshold, metadata, message),
+            Self::Aggregation { modules, threshold } => {
+                verify_aggregation(modules, *threshold, metadata, message)
+            }
+            Self::DomainRouting { domains } => domains
+                .get(&message.origin)
+                .ok_or(IsmVerificationError::NoRoute(message.origin))?
+                .verify(metadata, message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Accepts any message id multisig metadata, as it needs no signatures
+    fn accepting() -> IsmConfig {
+        IsmConfig::MessageIdMultisig {
+            validators: vec![],
+            threshold: 0,
+        }
+    }
+
+    /// Rejects any metadata, as it lacks the signature
+    fn rejecting() -> IsmConfig {
+        IsmConfig::MessageIdMultisig {
+            validators: vec![H256::repeat_byte(1)],
+            threshold: 1,
+        }
+    }
+
+    /// Formats aggregation metadata like `AggregationIsmMetadata.sol`
+    fn aggregation_metadata(sub_metadata: &[Option<Vec<u8>>]) -> Vec<u8> {
+        let mut ranges = vec![];
+        let mut data = vec![];
+        let mut offset = (sub_metadata.len() * 8) as u32;
+        for metadata in sub_metadata {
+            match metadata {
+                Some(metadata) => {
+                    ranges.extend(offset.to_be_bytes());
+                    offset += metadata.len() as u32;
+                    ranges.extend(offset.to_be_bytes());
+                    data.extend(metadata);
+                }
+                None => ranges.extend([0; 8]),
+            }
+        }
+        [ranges, data].concat()
+    }
+
+    #[test]
+    fn test_verify_aggregation() {
+        let message = HyperlaneMessage::default();
+        let config = IsmConfig::Aggregation {
+            modules: vec![rejecting(), accepting(), accepting()],
+            threshold: 2,
+        };
+        let sub_metadata = Some(vec![0; 68]);
+
+        let metadata = aggregation_metadata(&[None, sub_metadata.clone(), sub_metadata.clone()]);
+        assert_eq!(config.verify(&metadata, &message), Ok(()));
+
+        let metadata = aggregation_metadata(&[None, sub_metadata.clone(), None]);
+        assert_eq!(
+            config.verify(&metadata, &message),
+            Err(IsmVerificationError::ThresholdNotMet {
+                found: 1,
+                threshold: 2
+            })
+        );
+
+        // Every sub-module with metadata is verified, so there can't be more
+        // than the threshold, even if they all verify
+        let config = IsmConfig::Aggregation {
+            modules: vec![accepting(), accepting(), accepting()],
+            threshold: 2,
+        };
+        let metadata = aggregation_metadata(&[
+            sub_metadata.clone(),
+            sub_metadata.clone(),
+            sub_metadata.clone(),
+        ]);
+        assert_eq!(
+            config.verify(&metadata, &message),
+            Err(IsmVerificationError::ThresholdNotMet {
+                found: 3,
+                threshold: 2
+            })
+        );
+
+        let config = IsmConfig::Aggregation {
+            modules: vec![rejecting(), accepting(), accepting()],
+            threshold: 2,
+        };
+        let metadata = aggregation_metadata(&[sub_metadata.clone(), sub_metadata, None]);
+        assert!(matches!(
+            config.verify(&metadata, &message),
+            Err(IsmVerificationError::SubModule { index: 0, .. })
+        ));
+
+        assert!(matches!(
+            config.verify(&[0; 4], &message),
+            Err(IsmVerificationError::MalformedMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_domain_routing() {
+        let config = IsmConfig::DomainRouting {
+            domains: HashMap::from([(1, accepting()), (2, rejecting())]),
+        };
+        let message = |origin| HyperlaneMessage {
+            origin,
+            ..Default::default()
+        };
+        let metadata = vec![0; 68];
+
+        assert_eq!(config.verify(&metadata, &message(1)), Ok(()));
+        assert!(matches!(
+            config.verify(&metadata, &message(2)),
+            Err(IsmVerificationError::MalformedMetadata(_))
+        ));
+        assert_eq!(
+            config.verify(&metadata, &message(3)),
+            Err(IsmVerificationError::NoRoute(3))
+        );
+    }
+}
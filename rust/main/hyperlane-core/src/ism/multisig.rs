@@ -0,0 +1,330 @@
+use ethers_core::types::Signature as EthersSignature;
+
+use crate::{
+    accumulator::{merkle::merkle_root_from_branch, TREE_DEPTH},
+    Checkpoint, CheckpointWithMessageId, HyperlaneMessage, Signable, H160, H256, U256,
+};
+
+use super::IsmVerificationError;
+
+/// The length of a signature in multisig metadata
+const SIGNATURE_LENGTH: usize = 65;
+/// Where the signatures start in `MessageIdMultisigIsmMetadata`
+const MESSAGE_ID_SIGNATURES_OFFSET: usize = 68;
+/// Where the signatures start in `MerkleRootMultisigIsmMetadata`
+const MERKLE_ROOT_SIGNATURES_OFFSET: usize = 1096;
+/// Half the order of the secp256k1 curve, the largest `s` OpenZeppelin's
+/// `ECDSA.recover` accepts
+const SECP256K1_HALF_N: U256 = U256([
+    0xdfe9_2f46_681b_20a0,
+    0x5d57_6e73_57a4_501d,
+    0xffff_ffff_ffff_ffff,
+    0x7fff_ffff_ffff_ffff,
+]);
+
+/// Verifies metadata of a `MessageIdMultisigIsm`, laid out as:
+/// `[0:32]` origin merkle tree hook, `[32:64]` signed root, `[64:68]` signed
+/// index and `[68:]` the signatures.
+pub fn verify_message_id_multisig(
+    validators: &[H256],
+    threshold: u8,
+    metadata: &[u8],
+    message: &HyperlaneMessage,
+) -> Result<(), IsmVerificationError> {
+    if metadata.len() < MESSAGE_ID_SIGNATURES_OFFSET {
+        return Err(IsmVerificationError::MalformedMetadata(
+            "too short for message id multisig metadata",
+        ));
+    }
+    let checkpoint = CheckpointWithMessageId {
+        checkpoint: Checkpoint {
+            merkle_tree_hook_address: H256::from_slice(&metadata[0..32]),
+            mailbox_domain: message.origin,
+            root: H256::from_slice(&metadata[32..64]),
+            index: read_u32(&metadata[64..68]),
+        },
+        message_id: message.id(),
+    };
+    verify_signatures(
+        validators,
+        threshold,
+        &metadata[MESSAGE_ID_SIGNATURES_OFFSET..],
+        &checkpoint,
+    )
+}
+
+/// Verifies metadata of a `MerkleRootMultisigIsm`, laid out as:
+/// `[0:32]` origin merkle tree hook, `[32:36]` message index, `[36:68]`
+/// signed message id, `[68:1092]` merkle proof of the message, `[1092:1096]`
+/// signed index and `[1096:]` the signatures.
+pub fn verify_merkle_root_multisig(
+    validators: &[H256],
+    threshold: u8,
+    metadata: &[u8],
+    message: &HyperlaneMessage,
+) -> Result<(), IsmVerificationError> {
+    if metadata.len() < MERKLE_ROOT_SIGNATURES_OFFSET {
+        return Err(IsmVerificationError::MalformedMetadata(
+            "too short for merkle root multisig metadata",
+        ));
+    }
+    let message_index = read_u32(&metadata[32..36]);
+    let checkpoint_index = read_u32(&metadata[1092..1096]);
+    if checkpoint_index < message_index {
+        return Err(IsmVerificationError::CheckpointBeforeMessage {
+            checkpoint_index,
+            message_index,
+        });
+    }
+    let proof: Vec<H256> = metadata[68..1092]
+        .chunks(32)
+        .map(H256::from_slice)
+        .collect();
+    // The validators sign a root the message's proof must lead to
+    let root = merkle_root_from_branch(message.id(), &proof, TREE_DEPTH, message_index as usize);
+    let checkpoint = CheckpointWithMessageId {
+        checkpoint: Checkpoint {
+            merkle_tree_hook_address: H256::from_slice(&metadata[0..32]),
+            mailbox_domain: message.origin,
+            root,
+            index: checkpoint_index,
+        },
+        message_id: H256::from_slice(&metadata[36..68]),
+    };
+    verify_signatures(
+        validators,
+        threshold,
+        &metadata[MERKLE_ROOT_SIGNATURES_OFFSET..],
+        &checkpoint,
+    )
+}
+
+/// Checks that the first `threshold` signatures are over the checkpoint and
+/// by validators, in the order of the validator set, like
+/// `AbstractMultisigIsm.verify` does. Like OpenZeppelin's `ECDSA.recover`,
+/// only signatures with `v` of 27 or 28 and a low `s` are accepted, so that
+/// a signature can't be made valid in another form.
+fn verify_signatures(
+    validators: &[H256],
+    threshold: u8,
+    signatures: &[u8],
+    checkpoint: &CheckpointWithMessageId,
+) -> Result<(), IsmVerificationError> {
+    let threshold = threshold as usize;
+    if signatures.len() < threshold * SIGNATURE_LENGTH {
+        return Err(IsmVerificationError::MalformedMetadata(
+            "fewer signatures than the threshold",
+        ));
+    }
+    let digest = ethers_core::types::H256::from(checkpoint.eth_signed_message_hash());
+
+    let mut remaining_validators = validators.iter();
+    for (index, signature) in signatures
+        .chunks(SIGNATURE_LENGTH)
+        .take(threshold)
+        .enumerate()
+    {
+        let s = U256::from_big_endian(&signature[32..64]);
+        if !matches!(signature[64], 27 | 28) || s > SECP256K1_HALF_N {
+            return Err(IsmVerificationError::InvalidSignature { index });
+        }
+        let signer: H160 = EthersSignature::try_from(signature)
+            .and_then(|signature| signature.recover(digest))
+            .map_err(|_| IsmVerificationError::InvalidSignature { index })?
+            .into();
+        // Validators can only sign once, and in order
+        if !remaining_validators.any(|validator| *validator == H256::from(signer)) {
+            return Err(IsmVerificationError::UnexpectedSigner { index, signer });
+        }
+    }
+    Ok(())
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes.try_into().expect("slice of 4 bytes"))
+}
+
+#[cfg(test)]
+mod test {
+    use ethers_core::{k256::ecdsa::SigningKey, utils::hash_message};
+
+    use crate::{accumulator::merkle::MerkleTree, Signature, U256};
+
+    use super::*;
+
+    /// Signs like validators do, i.e. the EIP-191 hash of the signing hash
+    fn sign(key: &SigningKey, checkpoint: &CheckpointWithMessageId) -> Vec<u8> {
+        let hash = hash_message(checkpoint.signing_hash());
+        let (signature, recovery_id) = key.sign_prehash_recoverable(hash.as_bytes()).unwrap();
+        let bytes = signature.to_bytes();
+        Signature {
+            r: U256::from_big_endian(&bytes[..32]),
+            s: U256::from_big_endian(&bytes[32..]),
+            v: 27 + recovery_id.to_byte() as u64,
+        }
+        .to_vec()
+    }
+
+    fn address(key: &SigningKey) -> H256 {
+        H160::from(ethers_core::utils::secret_key_to_address(key)).into()
+    }
+
+    fn keys() -> Vec<SigningKey> {
+        (1u8..=3)
+            .map(|i| SigningKey::from_slice(&[i; 32]).unwrap())
+            .collect()
+    }
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: 1,
+            destination: 2,
+            nonce: 1,
+            body: vec![1, 2, 3],
+            ..Default::default()
+        }
+    }
+
+    fn message_id_metadata(
+        checkpoint: &CheckpointWithMessageId,
+        signers: &[&SigningKey],
+    ) -> Vec<u8> {
+        [
+            checkpoint.merkle_tree_hook_address.as_bytes(),
+            checkpoint.root.as_bytes(),
+            &checkpoint.index.to_be_bytes(),
+        ]
+        .concat()
+        .into_iter()
+        .chain(signers.iter().flat_map(|key| sign(key, checkpoint)))
+        .collect()
+    }
+
+    #[test]
+    fn test_verify_message_id_multisig() {
+        let keys = keys();
+        let validators: Vec<H256> = keys.iter().map(address).collect();
+        let message = message();
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(9),
+                mailbox_domain: message.origin,
+                root: H256::repeat_byte(8),
+                index: 1,
+            },
+            message_id: message.id(),
+        };
+
+        let metadata = message_id_metadata(&checkpoint, &[&keys[0], &keys[2]]);
+        assert_eq!(
+            verify_message_id_multisig(&validators, 2, &metadata, &message),
+            Ok(())
+        );
+
+        // Out of order
+        let metadata = message_id_metadata(&checkpoint, &[&keys[2], &keys[0]]);
+        assert!(matches!(
+            verify_message_id_multisig(&validators, 2, &metadata, &message),
+            Err(IsmVerificationError::UnexpectedSigner { index: 1, .. })
+        ));
+
+        // Duplicate signer
+        let metadata = message_id_metadata(&checkpoint, &[&keys[0], &keys[0]]);
+        assert!(matches!(
+            verify_message_id_multisig(&validators, 2, &metadata, &message),
+            Err(IsmVerificationError::UnexpectedSigner { index: 1, .. })
+        ));
+
+        // Not enough signatures
+        let metadata = message_id_metadata(&checkpoint, &[&keys[0]]);
+        assert!(matches!(
+            verify_message_id_multisig(&validators, 2, &metadata, &message),
+            Err(IsmVerificationError::MalformedMetadata(_))
+        ));
+
+        // The same signatures in their malleable forms, with `s` of `n - s`
+        // and the other `v`, or `v` of 0 or 1
+        let metadata = message_id_metadata(&checkpoint, &[&keys[0], &keys[2]]);
+        let n = SECP256K1_HALF_N * 2 + 1;
+        let mut high_s = metadata.clone();
+        let second = MESSAGE_ID_SIGNATURES_OFFSET + SIGNATURE_LENGTH;
+        let s = U256::from_big_endian(&high_s[second + 32..second + 64]);
+        (n - s).to_big_endian(&mut high_s[second + 32..second + 64]);
+        high_s[second + 64] ^= 27 ^ 28;
+        assert_eq!(
+            verify_message_id_multisig(&validators, 2, &high_s, &message),
+            Err(IsmVerificationError::InvalidSignature { index: 1 })
+        );
+        let mut low_v = metadata;
+        low_v[MESSAGE_ID_SIGNATURES_OFFSET + 64] -= 27;
+        assert_eq!(
+            verify_message_id_multisig(&validators, 2, &low_v, &message),
+            Err(IsmVerificationError::InvalidSignature { index: 0 })
+        );
+
+        // Signatures over another message
+        let other = HyperlaneMessage {
+            nonce: 2,
+            ..message.clone()
+        };
+        let metadata = message_id_metadata(&checkpoint, &[&keys[0], &keys[1]]);
+        assert!(matches!(
+            verify_message_id_multisig(&validators, 2, &metadata, &other),
+            Err(IsmVerificationError::UnexpectedSigner { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_merkle_root_multisig() {
+        let keys = keys();
+        let validators: Vec<H256> = keys.iter().map(address).collect();
+        let message = message();
+        let later_message_id = H256::repeat_byte(7);
+
+        // The message is at index 1, the validators signed the tree at index 2
+        let tree = MerkleTree::create(
+            &[H256::repeat_byte(6), message.id(), later_message_id],
+            TREE_DEPTH,
+        );
+        let (_, proof) = tree.generate_proof(1, TREE_DEPTH);
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(9),
+                mailbox_domain: message.origin,
+                root: tree.hash(),
+                index: 2,
+            },
+            message_id: later_message_id,
+        };
+        let metadata = |message_index: u32| -> Vec<u8> {
+            [
+                checkpoint.merkle_tree_hook_address.as_bytes(),
+                &message_index.to_be_bytes(),
+                checkpoint.message_id.as_bytes(),
+                &proof.iter().flat_map(|node| node.0).collect::<Vec<_>>(),
+                &checkpoint.index.to_be_bytes(),
+            ]
+            .concat()
+            .into_iter()
+            .chain(sign(&keys[1], &checkpoint))
+            .collect()
+        };
+
+        assert_eq!(
+            verify_merkle_root_multisig(&validators, 1, &metadata(1), &message),
+            Ok(())
+        );
+        // The proof doesn't lead to the signed root from another index
+        assert!(matches!(
+            verify_merkle_root_multisig(&validators, 1, &metadata(0), &message),
+            Err(IsmVerificationError::UnexpectedSigner { .. })
+        ));
+        assert_eq!(
+            verify_merkle_root_multisig(&validators, 1, &metadata(3), &message),
+            Err(IsmVerificationError::CheckpointBeforeMessage {
+                checkpoint_index: 2,
+                message_index: 3
+            })
+        );
+    }
+}
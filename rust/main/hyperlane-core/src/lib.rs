@@ -17,6 +17,9 @@ pub use types::*;
 /// Accumulator management
 pub mod accumulator;
 
+#[cfg(feature = "ethers")]
+pub mod ism;
+
 /// Async Traits for contract instances for use in applications
 mod traits;
 /// Utilities to match contract values
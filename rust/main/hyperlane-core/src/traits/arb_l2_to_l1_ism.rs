@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneContract, HyperlaneMessage, H256, U256};
+
+/// An L2 to L1 transaction sent through Arbitrum's `ArbSys` precompile, as
+/// emitted in its `L2ToL1Tx` event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArbL2ToL1Tx {
+    /// The L2 sender of the transaction
+    pub caller: H256,
+    /// The L1 target of the transaction
+    pub destination: H256,
+    /// Position of the transaction in the outbox merkle tree
+    pub position: U256,
+    /// The L2 block number the transaction was sent in
+    pub arb_block_num: U256,
+    /// The L1 block number at the time the transaction was sent
+    pub eth_block_num: U256,
+    /// The L2 timestamp the transaction was sent at
+    pub timestamp: U256,
+    /// Value sent along with the transaction
+    pub callvalue: U256,
+    /// Calldata of the transaction
+    pub data: Vec<u8>,
+}
+
+/// Interface for the ArbL2ToL1Ism chain contract, which verifies messages
+/// through the Arbitrum outbox on L1. Allows abstraction over different chains
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait ArbL2ToL1Ism: HyperlaneContract + Send + Sync + Debug {
+    /// Whether the message was already verified, i.e. its outbox
+    /// transaction was executed
+    async fn is_verified(&self, message: &HyperlaneMessage) -> ChainResult<bool>;
+
+    /// The hook on the Arbitrum chain that is authorized to send messages to
+    /// the ISM, as the L2 sender of their outbox transactions
+    async fn authorized_hook(&self) -> ChainResult<H256>;
+
+    /// Whether the outbox transaction at `position` was already executed
+    async fn is_spent(&self, position: U256) -> ChainResult<bool>;
+
+    /// The L2 block hash of the latest send root confirmed in the outbox,
+    /// if any was confirmed recently
+    async fn latest_confirmed_block(&self) -> ChainResult<Option<H256>>;
+}
+
+/// Interface to the Arbitrum L2 precompiles needed to prove an L2 to L1
+/// transaction in the outbox on L1.
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait ArbitrumOutboxProver: Send + Sync + Debug {
+    /// Finds the L2 to L1 transaction sent by `caller` in block `block_number`
+    /// whose calldata carries `message_id`
+    async fn l2_to_l1_tx(
+        &self,
+        block_number: u64,
+        caller: H256,
+        message_id: H256,
+    ) -> ChainResult<Option<ArbL2ToL1Tx>>;
+
+    /// The number of L2 to L1 transactions sent up to and including the L2
+    /// block with hash `block_hash`
+    async fn send_count(&self, block_hash: H256) -> ChainResult<u64>;
+
+    /// The proof of the L2 to L1 transaction at `leaf` in the outbox merkle
+    /// tree of size `size`
+    async fn outbox_proof(&self, size: u64, leaf: u64) -> ChainResult<Vec<H256>>;
+}
@@ -3,12 +3,54 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 
-use crate::{ChainResult, HyperlaneContract};
+use crate::{ChainResult, HyperlaneContract, HyperlaneMessage, H160};
+
+/// The `OffchainLookup` error of EIP-3668, telling clients which gateways
+/// to query for offchain data and how to use the response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OffchainLookup {
+    /// The contract that reverted, which must be the contract that was called
+    pub sender: H160,
+    /// The gateway URL templates, to be tried in order
+    pub urls: Vec<String>,
+    /// The data to send to the gateway
+    pub call_data: Vec<u8>,
+    /// The selector of the function to call with the gateway response
+    pub callback_function: [u8; 4],
+    /// Data to pass to the callback along with the gateway response
+    pub extra_data: Vec<u8>,
+}
+
+/// The outcome of dry-running the verification of a message with the data
+/// returned by a gateway
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OffchainVerification {
+    /// The ISM accepts the data
+    Verified,
+    /// The ISM rejects the data
+    Rejected,
+    /// The ISM needs more offchain data, as EIP-3668 allows callbacks to
+    /// revert with another `OffchainLookup`
+    Lookup(OffchainLookup),
+}
 
 /// Interface for the CcipReadIsm chain contract
 #[async_trait]
 #[auto_impl(&, Box, Arc)]
 pub trait CcipReadIsm: HyperlaneContract + Send + Sync + Debug {
-    /// Reverts with a custom error specifying how to query for offchain information
-    async fn get_offchain_verify_info(&self, message: Vec<u8>) -> ChainResult<()>;
+    /// Returns how to query for offchain information, as specified by the
+    /// custom error `getOffchainVerifyInfo` reverts with. Returns `None` if
+    /// it doesn't revert with an `OffchainLookup`, i.e. if the ISM is
+    /// misconfigured.
+    async fn get_offchain_verify_info(
+        &self,
+        message: Vec<u8>,
+    ) -> ChainResult<Option<OffchainLookup>>;
+
+    /// Dry-runs the verification of a message with the given metadata
+    async fn verify(
+        &self,
+        metadata: &[u8],
+        message: &HyperlaneMessage,
+    ) -> ChainResult<OffchainVerification>;
 }
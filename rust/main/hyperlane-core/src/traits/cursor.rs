@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use eyre::Result;
 
-use crate::{Indexed, LogMeta};
+use crate::{ChainCommunicationError, Indexed, LogMeta};
 
 /// A cursor governs event indexing for a contract.
 #[async_trait]
@@ -33,6 +33,15 @@ pub trait ContractSyncCursor<T>: Debug + Send + Sync + 'static {
         logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()>;
+
+    /// Notifies the cursor that querying `range` failed, so that it may adjust
+    /// the ranges it queries next (e.g. by shrinking them). Does nothing by default.
+    fn on_query_error(&mut self, _range: &RangeInclusive<u32>, _err: &ChainCommunicationError) {}
+
+    /// Notifies the cursor that querying `range` returned `logs_fetched` logs, before
+    /// any deduplication, so that it may adjust the ranges it queries next (e.g. by
+    /// growing them). Does nothing by default.
+    fn on_logs_fetched(&mut self, _range: &RangeInclusive<u32>, _logs_fetched: usize) {}
 }
 
 /// The action that should be taken by the contract sync loop
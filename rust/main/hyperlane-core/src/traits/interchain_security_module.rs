@@ -41,6 +41,14 @@ pub enum ModuleType {
     Null,
     /// Ccip Read ISM (accepts offchain signature information)
     CcipRead,
+    /// Arbitrum L2 to L1 ISM (verifies messages sent via the Arbitrum outbox)
+    ArbL2ToL1,
+    /// Weighted Merkle Proof ISM (stake-weighted validator set)
+    WeightedMerkleRootMultisig,
+    /// Weighted Message ID ISM (stake-weighted validator set)
+    WeightedMessageIdMultisig,
+    /// OP Stack L2 to L1 ISM (verifies messages sent via the OP Stack portal)
+    OpL2ToL1,
 }
 
 /// Interface for the InterchainSecurityModule chain contract. Allows abstraction over
@@ -1,4 +1,5 @@
 pub use aggregation_ism::*;
+pub use arb_l2_to_l1_ism::*;
 pub use ccip_read_ism::*;
 pub use cursor::*;
 pub use db::*;
@@ -10,6 +11,8 @@ pub use interchain_security_module::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
 pub use multisig_ism::*;
+pub use null_ism::*;
+pub use op_l2_to_l1_ism::*;
 pub use pending_operation::*;
 pub use provider::*;
 pub use routing_ism::*;
@@ -19,6 +22,7 @@ pub use validator_announce::*;
 use crate::{FixedPointNumber, H512, U256};
 
 mod aggregation_ism;
+mod arb_l2_to_l1_ism;
 mod ccip_read_ism;
 mod cursor;
 mod db;
@@ -30,6 +34,8 @@ mod interchain_security_module;
 mod mailbox;
 mod merkle_tree_hook;
 mod multisig_ism;
+mod null_ism;
+mod op_l2_to_l1_ism;
 mod pending_operation;
 mod provider;
 mod routing_ism;
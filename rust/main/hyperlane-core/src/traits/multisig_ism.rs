@@ -16,3 +16,16 @@ pub trait MultisigIsm: HyperlaneContract + Send + Sync + Debug {
         message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)>;
 }
+
+/// Interface for the WeightedMultisigIsm chain contract. Allows abstraction
+/// over different chains
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait WeightedMultisigIsm: HyperlaneContract + Send + Sync + Debug {
+    /// Returns the validators with their weights, and the total weight
+    /// their signatures need to reach to verify message
+    async fn validators_and_threshold_weight(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<(H256, u128)>, u128)>;
+}
@@ -0,0 +1,25 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneContract, H256};
+
+/// Interface for ISMs that don't need metadata but can still reject every
+/// message based on their own state, like the PausableIsm and the
+/// TrustedRelayerIsm. Allows abstraction over different chains
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait NullIsm: HyperlaneContract + Send + Sync + Debug {
+    /// Returns whether the ISM is paused. ISMs that can't be paused are
+    /// never paused.
+    async fn paused(&self) -> ChainResult<bool>;
+
+    /// Returns the only relayer the ISM accepts messages from, if it
+    /// restricts who can relay
+    async fn trusted_relayer(&self) -> ChainResult<Option<H256>>;
+
+    /// The address messages are relayed from when using this ISM's signer,
+    /// if it has one
+    fn relayer(&self) -> Option<H256>;
+}
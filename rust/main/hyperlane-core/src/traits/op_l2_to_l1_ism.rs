@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+
+use crate::{ChainResult, HyperlaneContract, HyperlaneMessage, TxOutcome, H256, U256};
+
+/// A withdrawal sent through the OP Stack `L2ToL1MessagePasser`, as emitted
+/// in its `MessagePassed` event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpWithdrawal {
+    /// Nonce of the withdrawal
+    pub nonce: U256,
+    /// The L2 sender of the withdrawal
+    pub sender: H256,
+    /// The L1 target of the withdrawal
+    pub target: H256,
+    /// Value sent along with the withdrawal
+    pub value: U256,
+    /// Gas limit of the withdrawal on L1
+    pub gas_limit: U256,
+    /// Calldata of the withdrawal
+    pub data: Vec<u8>,
+    /// Hash identifying the withdrawal
+    pub withdrawal_hash: H256,
+}
+
+/// An L2 output root proposed to L1 by a dispute game, which withdrawals
+/// up to its L2 block can be proven against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpDisputeGame {
+    /// Index of the game in the dispute game factory
+    pub index: U256,
+    /// The L2 block the proposed output root is for
+    pub l2_block_number: u64,
+}
+
+/// Proof that a withdrawal is included in an L2 output root.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpWithdrawalProof {
+    /// Version of the output root
+    pub version: H256,
+    /// State root of the L2 block
+    pub state_root: H256,
+    /// Storage root of the `L2ToL1MessagePasser` in the L2 block
+    pub message_passer_storage_root: H256,
+    /// Hash of the L2 block
+    pub latest_blockhash: H256,
+    /// Merkle proof of the withdrawal in the `L2ToL1MessagePasser` storage
+    pub withdrawal_proof: Vec<Vec<u8>>,
+}
+
+/// Interface for the OPL2ToL1Ism chain contract, which verifies messages
+/// finalized through the OP Stack portal on L1. Proving and finalizing
+/// withdrawals are sent from the signer of the chain. Allows abstraction over
+/// different chains
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait OpL2ToL1Ism: HyperlaneContract + Send + Sync + Debug {
+    /// Whether the message was already verified, i.e. its withdrawal was
+    /// finalized
+    async fn is_verified(&self, message: &HyperlaneMessage) -> ChainResult<bool>;
+
+    /// The latest dispute game of the type respected by the portal, if any
+    async fn latest_dispute_game(&self) -> ChainResult<Option<OpDisputeGame>>;
+
+    /// Whether the signer already proved the withdrawal
+    async fn is_proven(&self, withdrawal_hash: H256) -> ChainResult<bool>;
+
+    /// Whether the withdrawal proven by the signer can be finalized, i.e.
+    /// its dispute game was resolved and the finalization period has passed
+    async fn is_finalizable(&self, withdrawal_hash: H256) -> ChainResult<bool>;
+
+    /// Prove the withdrawal against the output root of the dispute game
+    async fn prove_withdrawal(
+        &self,
+        withdrawal: &OpWithdrawal,
+        dispute_game: &OpDisputeGame,
+        proof: &OpWithdrawalProof,
+    ) -> ChainResult<TxOutcome>;
+
+    /// Finalize the withdrawal proven by the signer
+    async fn finalize_withdrawal(&self, withdrawal: &OpWithdrawal) -> ChainResult<TxOutcome>;
+}
+
+/// Interface to the OP Stack L2 contracts needed to prove withdrawals on L1.
+#[async_trait]
+#[auto_impl(&, Box, Arc)]
+pub trait OpWithdrawalProver: Send + Sync + Debug {
+    /// Finds the withdrawal sent in block `block_number` whose calldata
+    /// carries `message_id`
+    async fn withdrawal(
+        &self,
+        block_number: u64,
+        message_id: H256,
+    ) -> ChainResult<Option<OpWithdrawal>>;
+
+    /// Proves the withdrawal against the output root of L2 block
+    /// `l2_block_number`
+    async fn withdrawal_proof(
+        &self,
+        withdrawal_hash: H256,
+        l2_block_number: u64,
+    ) -> ChainResult<OpWithdrawalProof>;
+}
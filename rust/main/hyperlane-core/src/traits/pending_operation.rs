@@ -253,6 +253,13 @@ pub enum ReprepareReason {
     /// The metadata building was refused for the message.
     #[strum(to_string = "Message metadata refused")]
     MessageMetadataRefused,
+    #[strum(to_string = "Recipient ISM is paused")]
+    /// The recipient ISM, or one of the sub-modules it needs, is paused
+    IsmPaused,
+    #[strum(to_string = "Recipient ISM only accepts messages from another relayer")]
+    /// The recipient ISM, or one of the sub-modules it needs, only accepts
+    /// messages delivered by a relayer other than this one
+    RelayerNotTrustedByIsm,
 }
 
 #[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -58,7 +58,10 @@ pub type SignedCheckpointWithMessageId = SignedType<CheckpointWithMessageId>;
 pub struct MultisigSignedCheckpoint {
     /// The checkpoint
     pub checkpoint: CheckpointWithMessageId,
-    /// Signatures over the checkpoint ordered by validator index, length == threshold
+    /// Signatures over the checkpoint ordered by validator index, length ==
+    /// threshold. For weighted quorums, the last signature may be repeated so
+    /// that there are at least as many signatures as the position of the last
+    /// signer in the validator set.
     pub signatures: Vec<Signature>,
 }
 
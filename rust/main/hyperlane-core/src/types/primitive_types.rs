@@ -12,7 +12,7 @@ use bigdecimal::{BigDecimal, RoundingMode};
 use borsh::{BorshDeserialize, BorshSerialize};
 use fixed_hash::impl_fixed_hash_conversions;
 use num::CheckedDiv;
-use num_traits::Zero;
+use num_traits::{ToPrimitive, Zero};
 use uint::construct_uint;
 
 use crate::{types::serialize, ChainCommunicationError};
@@ -368,6 +368,11 @@ impl FixedPointNumber {
                 .with_scale_round(fractional_digit_count, bigdecimal::RoundingMode::Ceiling),
         )
     }
+
+    /// Lossy conversion to `f64`, e.g. for metrics
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
 }
 
 impl Default for FixedPointNumber {
@@ -477,4 +482,16 @@ mod test {
             FixedPointNumber::from_str("1234").unwrap(),
         );
     }
+
+    #[test]
+    fn test_fixed_point_number_to_f64_lossy() {
+        use super::FixedPointNumber;
+        use std::str::FromStr;
+
+        assert_eq!(
+            FixedPointNumber::from_str("0.0125").unwrap().to_f64_lossy(),
+            0.0125
+        );
+        assert_eq!(FixedPointNumber::from(7u64).to_f64_lossy(), 7.0);
+    }
 }
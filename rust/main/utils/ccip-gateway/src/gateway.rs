@@ -0,0 +1,266 @@
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing, Json, Router,
+};
+use ethers::utils::hex;
+use hyperlane_core::{utils::bytes_to_hex, H160};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// A request for the offchain data of a CCIP read ISM
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CcipReadRequest {
+    /// The ISM that reverted with `OffchainLookup`
+    pub sender: H160,
+    /// The `callData` of the `OffchainLookup` error
+    pub call_data: Vec<u8>,
+}
+
+/// Why a request couldn't be resolved
+#[derive(Debug, thiserror::Error)]
+pub enum ResolverError {
+    /// The request is malformed and retrying won't help
+    #[error("{0}")]
+    BadRequest(String),
+    /// The data isn't available, at least not yet
+    #[error("{0}")]
+    NotFound(String),
+    /// Resolving failed, e.g. because of the RPC
+    #[error(transparent)]
+    Other(#[from] eyre::Report),
+}
+
+impl ResolverError {
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Resolves the offchain data of requests from CCIP read ISMs
+#[async_trait]
+pub trait Resolver: Send + Sync + Debug {
+    /// Returns the data the relayer passes to the ISM as metadata
+    async fn resolve(&self, request: &CcipReadRequest) -> Result<Vec<u8>, ResolverError>;
+}
+
+/// Serves CCIP read requests, dispatching them to resolvers by sender
+#[derive(Clone, Debug, Default)]
+pub struct Gateway {
+    resolvers: HashMap<H160, Arc<dyn Resolver>>,
+    fallback: Option<Arc<dyn Resolver>>,
+}
+
+/// Body of `POST` requests
+#[derive(Debug, Deserialize)]
+struct PostRequest {
+    sender: String,
+    data: String,
+}
+
+/// Body of successful responses, as defined by EIP-3668
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GatewayResponse {
+    /// The 0x-prefixed hex encoded data
+    pub data: String,
+}
+
+/// Body of error responses, as defined by EIP-3668
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GatewayErrorResponse {
+    /// What went wrong
+    pub message: String,
+}
+
+impl IntoResponse for ResolverError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let message = match &self {
+            // Don't leak internal errors
+            Self::Other(_) => "Failed to resolve request".to_owned(),
+            _ => self.to_string(),
+        };
+        (status, Json(GatewayErrorResponse { message })).into_response()
+    }
+}
+
+impl Gateway {
+    /// Creates a gateway without resolvers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves requests from the ISM at `sender` with `resolver`
+    pub fn with_resolver(mut self, sender: H160, resolver: Arc<dyn Resolver>) -> Self {
+        self.resolvers.insert(sender, resolver);
+        self
+    }
+
+    /// Resolves requests from ISMs without a dedicated resolver with
+    /// `resolver`
+    pub fn with_fallback_resolver(mut self, resolver: Arc<dyn Resolver>) -> Self {
+        self.fallback = Some(resolver);
+        self
+    }
+
+    /// The routes of the gateway. ISMs should use `<base url>/{sender}/{data}`
+    /// or `<base url>` as their `OffchainLookup` URL, for `GET` and `POST`
+    /// requests respectively.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/", routing::post(post_request))
+            .route("/:sender/:data", routing::get(get_request))
+            .with_state(self)
+    }
+
+    async fn resolve(
+        &self,
+        sender: &str,
+        data: &str,
+    ) -> Result<Json<GatewayResponse>, ResolverError> {
+        let request = CcipReadRequest {
+            sender: decode_hex(sender)
+                .filter(|sender| sender.len() == H160::len_bytes())
+                .map(|sender| H160::from_slice(&sender))
+                .ok_or_else(|| ResolverError::BadRequest(format!("Invalid sender {sender}")))?,
+            call_data: decode_hex(data)
+                .ok_or_else(|| ResolverError::BadRequest("Invalid call data".to_owned()))?,
+        };
+        let resolver = self
+            .resolvers
+            .get(&request.sender)
+            .or(self.fallback.as_ref())
+            .ok_or_else(|| {
+                ResolverError::NotFound(format!("No resolver for sender {:?}", request.sender))
+            })?;
+
+        debug!(?request, "Resolving CCIP read request");
+        match resolver.resolve(&request).await {
+            Ok(data) => Ok(Json(GatewayResponse {
+                data: bytes_to_hex(&data),
+            })),
+            Err(err) => {
+                warn!(?request, error = ?err, "Failed to resolve CCIP read request");
+                Err(err)
+            }
+        }
+    }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).ok()
+}
+
+async fn get_request(
+    State(gateway): State<Gateway>,
+    Path((sender, data)): Path<(String, String)>,
+) -> Result<Json<GatewayResponse>, ResolverError> {
+    // Some clients require URLs to end with `.json`
+    let data = data.strip_suffix(".json").unwrap_or(&data);
+    gateway.resolve(&sender, data).await
+}
+
+async fn post_request(
+    State(gateway): State<Gateway>,
+    Json(request): Json<PostRequest>,
+) -> Result<Json<GatewayResponse>, ResolverError> {
+    gateway.resolve(&request.sender, &request.data).await
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    /// Echoes the call data back, reversed
+    #[derive(Debug)]
+    pub(crate) struct ReverseResolver;
+
+    #[async_trait]
+    impl Resolver for ReverseResolver {
+        async fn resolve(&self, request: &CcipReadRequest) -> Result<Vec<u8>, ResolverError> {
+            if request.call_data.is_empty() {
+                return Err(ResolverError::NotFound("Nothing to reverse".to_owned()));
+            }
+            Ok(request.call_data.iter().rev().copied().collect())
+        }
+    }
+
+    fn serve(gateway: Gateway) -> SocketAddr {
+        let server = axum::Server::bind(&"127.0.0.1:0".parse().unwrap())
+            .serve(gateway.router().into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_get_and_post_requests() {
+        let sender = H160::repeat_byte(1);
+        let addr = serve(Gateway::new().with_resolver(sender, Arc::new(ReverseResolver)));
+
+        let response: GatewayResponse = reqwest::get(format!(
+            "http://{addr}/{}/0x0102.json",
+            bytes_to_hex(sender.as_bytes())
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(response.data, "0x0201");
+
+        let response: GatewayResponse = reqwest::Client::new()
+            .post(format!("http://{addr}/"))
+            .json(&serde_json::json!({
+                "sender": bytes_to_hex(sender.as_bytes()),
+                "data": "0x010203",
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response.data, "0x030201");
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let sender = H160::repeat_byte(1);
+        let addr = serve(Gateway::new().with_resolver(sender, Arc::new(ReverseResolver)));
+        let sender = bytes_to_hex(sender.as_bytes());
+
+        // Unknown sender
+        let response = reqwest::get(format!(
+            "http://{addr}/{}/0x01",
+            bytes_to_hex(H160::repeat_byte(2).as_bytes())
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Malformed call data
+        let response = reqwest::get(format!("http://{addr}/{sender}/0xzz"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Resolver error
+        let response = reqwest::get(format!("http://{addr}/{sender}/0x"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response: GatewayErrorResponse = response.json().await.unwrap();
+        assert_eq!(response.message, "Nothing to reverse");
+    }
+}
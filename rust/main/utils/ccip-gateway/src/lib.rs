@@ -0,0 +1,15 @@
+//! A CCIP-read (EIP-3668) gateway for CCIP read ISMs.
+//!
+//! CCIP read ISMs revert `getOffchainVerifyInfo` with an `OffchainLookup`
+//! error pointing the relayer to a gateway, whose response is used as the
+//! message's metadata. The gateway answers both the `GET` and `POST` forms
+//! of the protocol and hands each request to the [`Resolver`] configured
+//! for the ISM that sent it.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+pub use gateway::*;
+
+mod gateway;
+pub mod resolvers;
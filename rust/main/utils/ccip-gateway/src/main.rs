@@ -0,0 +1,58 @@
+//! Serves CCIP read requests by attesting to messages dispatched on an
+//! origin chain.
+//!
+//! Reads the same configuration as the agents, plus the `origin` chain and
+//! the `attestationSigner`. See `settings.rs` for all options.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::{net::SocketAddr, sync::Arc};
+
+use ccip_gateway::{resolvers::MessageAttestationResolver, Gateway};
+use eyre::{Context, Result};
+use hyperlane_base::LoadableFromSettings;
+use hyperlane_core::HyperlaneSigner;
+use tracing::info;
+
+use crate::settings::GatewaySettings;
+
+mod settings;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let settings = GatewaySettings::load()?;
+    let metrics = settings.metrics("ccip_gateway")?;
+
+    let indexer = settings
+        .chain_setup(&settings.origin)?
+        .build_message_indexer(&metrics, false)
+        .await?;
+    let signer: hyperlane_ethereum::Signers = settings
+        .attestation_signer
+        .build()
+        .await
+        .context("Building attestation signer")?;
+    info!(signer = ?signer.eth_address(), origin = %settings.origin, "Attesting to messages");
+
+    let mut resolver = MessageAttestationResolver::new(
+        settings.origin.clone(),
+        settings.origin.index_mode(),
+        indexer,
+        Box::new(signer),
+        settings.max_lookback_blocks,
+    );
+    if let Some(db) = &settings.db {
+        resolver = resolver.with_db(db.clone());
+    }
+    let router = Gateway::new()
+        .with_fallback_resolver(Arc::new(resolver))
+        .router();
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], settings.port));
+    info!(%addr, "Serving CCIP read requests");
+    axum::Server::bind(&addr)
+        .serve(router.into_make_service())
+        .await?;
+    Ok(())
+}
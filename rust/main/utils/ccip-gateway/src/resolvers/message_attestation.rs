@@ -0,0 +1,373 @@
+use std::{
+    fmt::{Debug, Formatter},
+    path::PathBuf,
+};
+
+use async_trait::async_trait;
+use ethers::utils::keccak256;
+use eyre::Context;
+use hyperlane_base::db::{HyperlaneDb, HyperlaneRocksDB, DB};
+use hyperlane_core::{
+    Decode, HyperlaneDomain, HyperlaneMessage, HyperlaneSigner, HyperlaneSignerExt, IndexMode,
+    Indexed, SequenceAwareIndexer, Signable, H256,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::{CcipReadRequest, Resolver, ResolverError};
+
+/// The default number of blocks searched back from the finalized block for
+/// the dispatch of a message
+pub const DEFAULT_MAX_LOOKBACK_BLOCKS: u32 = 10_000;
+/// How many blocks are fetched per RPC call when searching for a dispatch
+const LOOKBACK_CHUNK_SIZE: u32 = 1_000;
+
+/// An attestation that a message was dispatched on its origin chain, for
+/// the ISM that requested it. Binding the attestation to the ISM and its
+/// chain keeps it from being replayed to other ISMs trusting the same signer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageAttestation {
+    /// The domain of the ISM, i.e. the destination of the message
+    pub destination: u32,
+    /// The ISM the attestation is for
+    pub ism: H256,
+    /// The origin domain of the message
+    pub origin: u32,
+    /// The id of the message
+    pub message_id: H256,
+}
+
+impl Signable for MessageAttestation {
+    /// A hash of `abi.encodePacked(destination, ism, origin, messageId)`
+    fn signing_hash(&self) -> H256 {
+        let mut packed = Vec::with_capacity(72);
+        packed.extend_from_slice(&self.destination.to_be_bytes());
+        packed.extend_from_slice(self.ism.as_bytes());
+        packed.extend_from_slice(&self.origin.to_be_bytes());
+        packed.extend_from_slice(self.message_id.as_bytes());
+        H256::from(keccak256(packed))
+    }
+}
+
+/// Attests to messages having been dispatched on the origin chain, after
+/// finding their dispatch with the origin's message indexer.
+///
+/// For chains indexed by block, dispatches are searched for in the latest
+/// finalized blocks. Older dispatches are found through the block an agent
+/// indexed them at, if the database of an agent indexing the origin is set.
+///
+/// The call data of requests must be the message itself, and the response is
+/// the 65 byte EIP-191 signature of the [`MessageAttestation`].
+pub struct MessageAttestationResolver {
+    origin: HyperlaneDomain,
+    index_mode: IndexMode,
+    indexer: Box<dyn SequenceAwareIndexer<HyperlaneMessage>>,
+    signer: Box<dyn HyperlaneSigner>,
+    max_lookback_blocks: u32,
+    db_path: Option<PathBuf>,
+}
+
+impl Debug for MessageAttestationResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MessageAttestationResolver")
+            .field("origin", &self.origin)
+            .field("signer", &self.signer.eth_address())
+            .field("max_lookback_blocks", &self.max_lookback_blocks)
+            .field("db_path", &self.db_path)
+            .finish()
+    }
+}
+
+impl MessageAttestationResolver {
+    /// Creates a resolver for messages from `origin`
+    pub fn new(
+        origin: HyperlaneDomain,
+        index_mode: IndexMode,
+        indexer: Box<dyn SequenceAwareIndexer<HyperlaneMessage>>,
+        signer: Box<dyn HyperlaneSigner>,
+        max_lookback_blocks: u32,
+    ) -> Self {
+        Self {
+            origin,
+            index_mode,
+            indexer,
+            signer,
+            max_lookback_blocks,
+            db_path: None,
+        }
+    }
+
+    /// Looks up dispatches older than the lookback in the database of an
+    /// agent indexing the origin
+    pub fn with_db(mut self, db_path: PathBuf) -> Self {
+        self.db_path = Some(db_path);
+        self
+    }
+
+    /// The block the message was indexed as dispatched at by the agent
+    /// whose database is set, if any
+    fn indexed_dispatch_block(&self, message: &HyperlaneMessage) -> eyre::Result<Option<u32>> {
+        let Some(db_path) = &self.db_path else {
+            return Ok(None);
+        };
+        // Opened for each lookup, since a read-only database doesn't see
+        // what the agent writes after it's opened
+        let db = HyperlaneRocksDB::new(&self.origin, DB::from_path_read_only(db_path)?);
+        let block = db.retrieve_dispatched_block_number_by_nonce(&message.nonce)?;
+        Ok(block.map(u32::try_from).transpose()?)
+    }
+
+    /// Whether the message was dispatched on the origin chain
+    #[instrument(err, skip(self, message), fields(message_id = ?message.id()))]
+    async fn is_dispatched(&self, message: &HyperlaneMessage) -> eyre::Result<bool> {
+        let contains = |logs: Vec<(Indexed<HyperlaneMessage>, _)>| {
+            logs.iter().any(|(log, _)| log.inner() == message)
+        };
+        match self.index_mode {
+            IndexMode::Sequence => {
+                let logs = self
+                    .indexer
+                    .fetch_logs_in_range(message.nonce..=message.nonce)
+                    .await?;
+                Ok(contains(logs))
+            }
+            IndexMode::Block => {
+                let finalized = self.indexer.get_finalized_block_number().await?;
+                let lowest = finalized.saturating_sub(self.max_lookback_blocks);
+                let mut to = finalized;
+                loop {
+                    let from = to.saturating_sub(LOOKBACK_CHUNK_SIZE - 1).max(lowest);
+                    debug!(from, to, "Searching for message dispatch");
+                    if contains(self.indexer.fetch_logs_in_range(from..=to).await?) {
+                        return Ok(true);
+                    }
+                    if from == lowest {
+                        break;
+                    }
+                    to = from - 1;
+                }
+                // The indexed block is only a hint, the dispatch is still
+                // checked on chain
+                match self.indexed_dispatch_block(message)? {
+                    Some(block) if block < lowest => {
+                        debug!(block, "Checking message dispatch indexed by the agent");
+                        Ok(contains(
+                            self.indexer.fetch_logs_in_range(block..=block).await?,
+                        ))
+                    }
+                    _ => Ok(false),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Resolver for MessageAttestationResolver {
+    async fn resolve(&self, request: &CcipReadRequest) -> Result<Vec<u8>, ResolverError> {
+        let message = HyperlaneMessage::read_from(&mut request.call_data.as_slice())
+            .map_err(|_| ResolverError::BadRequest("Call data is not a message".to_owned()))?;
+        if message.origin != self.origin.id() {
+            return Err(ResolverError::BadRequest(format!(
+                "Message origin {} is not {}",
+                message.origin,
+                self.origin.id()
+            )));
+        }
+        if !self
+            .is_dispatched(&message)
+            .await
+            .context("When searching for the message dispatch")?
+        {
+            let indexed = if self.db_path.is_some() {
+                " or at the block it was indexed at"
+            } else {
+                ""
+            };
+            return Err(ResolverError::NotFound(format!(
+                "Message {:?} was not dispatched in the last {} finalized blocks{indexed}",
+                message.id(),
+                self.max_lookback_blocks
+            )));
+        }
+        let attestation = MessageAttestation {
+            destination: message.destination,
+            ism: request.sender.into(),
+            origin: message.origin,
+            message_id: message.id(),
+        };
+        let signed = self
+            .signer
+            .sign(attestation)
+            .await
+            .context("When signing the message attestation")?;
+        Ok(signed.signature.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{ops::RangeInclusive, str::FromStr};
+
+    use ethers::signers::{LocalWallet, Signer};
+    use hyperlane_core::{
+        ChainResult, Indexer, KnownHyperlaneDomain, LogMeta, RawHyperlaneMessage, SignedType, H160,
+    };
+    use hyperlane_ethereum::Signers;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Serves a single message dispatched at `block`
+    #[derive(Debug)]
+    struct MockIndexer {
+        message: HyperlaneMessage,
+        block: u32,
+        finalized: u32,
+    }
+
+    #[async_trait]
+    impl Indexer<HyperlaneMessage> for MockIndexer {
+        async fn fetch_logs_in_range(
+            &self,
+            range: RangeInclusive<u32>,
+        ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
+            if !range.contains(&self.block) {
+                return Ok(vec![]);
+            }
+            let meta = LogMeta {
+                block_number: self.block as u64,
+                ..Default::default()
+            };
+            Ok(vec![(self.message.clone().into(), meta)])
+        }
+
+        async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+            Ok(self.finalized)
+        }
+    }
+
+    #[async_trait]
+    impl SequenceAwareIndexer<HyperlaneMessage> for MockIndexer {
+        async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+            Ok((None, self.finalized))
+        }
+    }
+
+    fn wallet() -> LocalWallet {
+        LocalWallet::from_str("45bde8e8ae6b1d7b8e4f3ec4b0c3f1b0a8e08ad7d6ae5d5c9b0c3a2f1e0d9c8b")
+            .unwrap()
+    }
+
+    fn setup(block: u32, finalized: u32) -> (MessageAttestationResolver, HyperlaneMessage) {
+        let message = HyperlaneMessage {
+            origin: 1,
+            destination: 10,
+            nonce: 7,
+            body: vec![1, 2, 3],
+            ..Default::default()
+        };
+        let indexer = MockIndexer {
+            message: message.clone(),
+            block,
+            finalized,
+        };
+        let resolver = MessageAttestationResolver::new(
+            HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum),
+            IndexMode::Block,
+            Box::new(indexer),
+            Box::new(Signers::Local(wallet())),
+            DEFAULT_MAX_LOOKBACK_BLOCKS,
+        );
+        (resolver, message)
+    }
+
+    fn request(message: &HyperlaneMessage) -> CcipReadRequest {
+        CcipReadRequest {
+            sender: H160::repeat_byte(0xaa),
+            call_data: RawHyperlaneMessage::from(message),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attests_dispatched_message() {
+        // Dispatched a few chunks below the finalized block
+        let (resolver, message) = setup(5_500, 9_000);
+        let signature = resolver.resolve(&request(&message)).await.unwrap();
+        assert_eq!(signature.len(), 65);
+
+        let attestation = MessageAttestation {
+            destination: 10,
+            ism: H160::repeat_byte(0xaa).into(),
+            origin: 1,
+            message_id: message.id(),
+        };
+        // Attestations for another ISM must not verify with this signature
+        let other_ism = MessageAttestation {
+            ism: H160::repeat_byte(0xbb).into(),
+            ..attestation
+        };
+        assert_ne!(attestation.signing_hash(), other_ism.signing_hash());
+
+        let signed = SignedType {
+            value: attestation,
+            signature: ethers::types::Signature::try_from(signature.as_slice())
+                .unwrap()
+                .into(),
+        };
+        assert_eq!(signed.recover().unwrap(), H160::from(wallet().address()));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_unknown_messages() {
+        // Dispatched before the lookback window
+        let (resolver, message) = setup(100, 20_000);
+        assert!(matches!(
+            resolver.resolve(&request(&message)).await,
+            Err(ResolverError::NotFound(_))
+        ));
+
+        let (resolver, mut message) = setup(5_500, 9_000);
+        message.origin = 2;
+        assert!(matches!(
+            resolver.resolve(&request(&message)).await,
+            Err(ResolverError::BadRequest(_))
+        ));
+
+        let malformed = CcipReadRequest {
+            sender: H160::zero(),
+            call_data: vec![1, 2, 3],
+        };
+        assert!(matches!(
+            resolver.resolve(&malformed).await,
+            Err(ResolverError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_attests_message_indexed_by_agent() {
+        // Dispatched before the lookback window, but indexed by an agent
+        let (resolver, message) = setup(100, 20_000);
+        let dir = TempDir::new().unwrap();
+        {
+            let db = DB::from_path(dir.path()).unwrap();
+            HyperlaneRocksDB::new(&resolver.origin, db)
+                .store_message(&message, 100)
+                .unwrap();
+        }
+        let resolver = resolver.with_db(dir.path().to_path_buf());
+        assert_eq!(
+            resolver.resolve(&request(&message)).await.unwrap().len(),
+            65
+        );
+
+        // The indexed block must still contain the dispatch on chain
+        let (resolver, mut other) = setup(100, 20_000);
+        other.body = vec![4, 5, 6];
+        let resolver = resolver.with_db(dir.path().to_path_buf());
+        assert!(matches!(
+            resolver.resolve(&request(&other)).await,
+            Err(ResolverError::NotFound(_))
+        ));
+    }
+}
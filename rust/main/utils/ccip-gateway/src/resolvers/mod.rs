@@ -0,0 +1,5 @@
+//! Resolvers shipped with the gateway
+
+pub use message_attestation::*;
+
+mod message_attestation;
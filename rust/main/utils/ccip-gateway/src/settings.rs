@@ -0,0 +1,115 @@
+//! Gateway configuration.
+//!
+//! The gateway reads the same chain configuration as the agents, along with
+//! the origin chain whose messages it attests to and the key it signs with,
+//! e.g. `--origin ethereum --attestationSigner.type hexKey --attestationSigner.key 0x...`.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use ccip_gateway::resolvers::DEFAULT_MAX_LOOKBACK_BLOCKS;
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, RawAgentSignerConf, ValueParser},
+        Settings, SignerConf,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Settings for the CCIP read gateway
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct GatewaySettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    pub base: Settings,
+
+    /// The chain whose messages are attested to
+    pub origin: HyperlaneDomain,
+    /// The key attestations are signed with
+    pub attestation_signer: SignerConf,
+    /// The port the gateway listens on
+    pub port: u16,
+    /// How many blocks to search back from the finalized block for the
+    /// dispatch of a message
+    pub max_lookback_blocks: u32,
+    /// The database of an agent indexing the origin, used to find the
+    /// dispatch of messages older than the lookback
+    pub db: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawGatewaySettings(Value);
+
+impl_loadable_from_settings!(Gateway, RawGatewaySettings -> GatewaySettings);
+
+impl FromRawConf<RawGatewaySettings> for GatewaySettings {
+    fn from_config_filtered(
+        raw: RawGatewaySettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                None,
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let origin_name = p.chain(&mut err).get_key("origin").parse_string().end();
+
+        let attestation_signer = p
+            .chain(&mut err)
+            .get_key("attestationSigner")
+            .parse_from_raw_config::<SignerConf, RawAgentSignerConf, NoFilter>(
+                (),
+                "Expected valid attestation signer configuration",
+            )
+            .end();
+
+        let port = p
+            .chain(&mut err)
+            .get_opt_key("port")
+            .parse_u16()
+            .unwrap_or(3000);
+
+        let max_lookback_blocks = p
+            .chain(&mut err)
+            .get_opt_key("maxLookbackBlocks")
+            .parse_u32()
+            .unwrap_or(DEFAULT_MAX_LOOKBACK_BLOCKS);
+
+        let db = p
+            .chain(&mut err)
+            .get_opt_key("db")
+            .parse_from_str("Expected db file path")
+            .end();
+
+        cfg_unwrap_all!(&p.cwp, err: [base, origin_name]);
+
+        let origin = base
+            .lookup_domain(origin_name)
+            .into_config_result(|| &p.cwp + "origin")
+            .take_config_err(&mut err);
+
+        cfg_unwrap_all!(&p.cwp, err: [origin, attestation_signer]);
+
+        err.into_result(Self {
+            base,
+            origin,
+            attestation_signer,
+            port,
+            max_lookback_blocks,
+            db,
+        })
+    }
+}
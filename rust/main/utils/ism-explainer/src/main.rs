@@ -0,0 +1,205 @@
+//! Prints the ISM tree a relayer resolves for a message, or for the
+//! recipient of an (origin, recipient) pair, using the relayer's
+//! `/ism_explain` endpoint.
+//!
+//! ```sh
+//! ism-explainer --origin-domain 1 --message-id 0x...
+//! ism-explainer --origin-domain 1 --destination-domain 42161 --recipient 0x...
+//! ```
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use clap::Parser;
+use eyre::{eyre, Result};
+use serde_json::Value;
+
+#[derive(Debug, Parser)]
+#[command(about = "Explain the ISM configuration a relayer resolves for a message")]
+struct Args {
+    /// Base URL of the relayer's server
+    #[arg(long, default_value = "http://localhost:9090")]
+    relayer_url: String,
+    /// Origin domain id of the message
+    #[arg(long)]
+    origin_domain: u32,
+    /// Id of a dispatched message
+    #[arg(long, conflicts_with_all = ["destination_domain", "recipient", "sender"])]
+    message_id: Option<String>,
+    /// Destination domain id, when explaining an (origin, recipient) pair
+    #[arg(long, requires = "recipient")]
+    destination_domain: Option<u32>,
+    /// Recipient address as 32 bytes hex, when explaining an (origin,
+    /// recipient) pair
+    #[arg(long, requires = "destination_domain")]
+    recipient: Option<String>,
+    /// Sender address as 32 bytes hex, used to resolve routing ISMs that
+    /// route on the sender
+    #[arg(long)]
+    sender: Option<String>,
+    /// Print the raw JSON response instead of a tree
+    #[arg(long)]
+    json: bool,
+}
+
+impl Args {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("origin_domain", self.origin_domain.to_string())];
+        if let Some(message_id) = &self.message_id {
+            query.push(("message_id", message_id.clone()));
+        }
+        if let Some(destination_domain) = self.destination_domain {
+            query.push(("destination_domain", destination_domain.to_string()));
+        }
+        if let Some(recipient) = &self.recipient {
+            query.push(("recipient", recipient.clone()));
+        }
+        if let Some(sender) = &self.sender {
+            query.push(("sender", sender.clone()));
+        }
+        query
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.message_id.is_none() && args.recipient.is_none() {
+        return Err(eyre!(
+            "Expected either `--message-id`, or `--destination-domain` and `--recipient`"
+        ));
+    }
+
+    let response = reqwest::Client::new()
+        .get(format!(
+            "{}/ism_explain",
+            args.relayer_url.trim_end_matches('/')
+        ))
+        .query(&args.query())
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(eyre!(
+            "Relayer responded {status}: {}",
+            response.text().await?
+        ));
+    }
+    let explanation: Value = response.json().await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+    } else {
+        print_explanation(&explanation);
+    }
+    Ok(())
+}
+
+fn print_explanation(explanation: &Value) {
+    match explanation.get("message_id").and_then(Value::as_str) {
+        Some(message_id) => println!("Message {message_id}"),
+        None => println!("No message, resolved with an empty body"),
+    }
+    println!(
+        "{} -> {}, recipient {}",
+        explanation["origin_domain"],
+        explanation["destination_domain"],
+        as_str(&explanation["recipient"]),
+    );
+    let mut lines = vec![];
+    format_node(&explanation["ism"], 0, &mut lines);
+    for line in lines {
+        println!("{line}");
+    }
+}
+
+/// Formats an ISM node and its children, one line per node and validator
+fn format_node(node: &Value, depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    let chosen = match node["chosen"].as_bool() {
+        Some(true) => "* ",
+        Some(false) => "- ",
+        None => "",
+    };
+    let module_type = node["module_type"].as_str().unwrap_or("unknown type");
+    let mut line = format!("{indent}{chosen}{module_type} {}", as_str(&node["address"]));
+    if let Some(threshold) = node["threshold"].as_u64() {
+        line.push_str(&format!(", threshold {threshold}"));
+    }
+    if let Some(metadata) = node["metadata"].as_object() {
+        let status = metadata.get("status").map(as_str).unwrap_or_default();
+        line.push_str(&format!(", metadata {status}"));
+        if let Some(reason) = metadata.get("reason") {
+            line.push_str(&format!(" ({})", as_str(reason)));
+        }
+    }
+    if let Some(error) = node["error"].as_str() {
+        line.push_str(&format!(", error: {error}"));
+    }
+    lines.push(line);
+
+    for validator in node["validators"].as_array().into_iter().flatten() {
+        let mut line = format!("{indent}    validator {}", as_str(&validator["address"]));
+        if let Some(weight) = validator["weight"].as_u64() {
+            line.push_str(&format!(", weight {weight}"));
+        }
+        match validator["latest_checkpoint_index"].as_u64() {
+            Some(index) => line.push_str(&format!(", latest checkpoint {index}")),
+            None => line.push_str(", no checkpoint"),
+        }
+        lines.push(line);
+    }
+    for child in node["children"].as_array().into_iter().flatten() {
+        format_node(child, depth + 1, lines);
+    }
+}
+
+fn as_str(value: &Value) -> String {
+    value
+        .as_str()
+        .map(str::to_owned)
+        .unwrap_or_else(|| value.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_format_node_marks_chosen_branches() {
+        let node = json!({
+            "address": "0x01",
+            "module_type": "Aggregation",
+            "threshold": 1,
+            "metadata": { "status": "found" },
+            "children": [
+                {
+                    "address": "0x02",
+                    "module_type": "MessageIdMultisig",
+                    "threshold": 1,
+                    "chosen": true,
+                    "validators": [{ "address": "0x03", "latest_checkpoint_index": 7 }],
+                },
+                {
+                    "address": "0x04",
+                    "module_type": "Null",
+                    "chosen": false,
+                    "metadata": { "status": "blocked", "reason": "Recipient ISM is paused" },
+                },
+            ],
+        });
+        let mut lines = vec![];
+        format_node(&node, 0, &mut lines);
+        assert_eq!(
+            lines,
+            vec![
+                "Aggregation 0x01, threshold 1, metadata found",
+                "  * MessageIdMultisig 0x02, threshold 1",
+                "        validator 0x03, latest checkpoint 7",
+                "  - Null 0x04, metadata blocked (Recipient ISM is paused)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Traces a message across chains and diagnoses where it is stuck.
+//!
+//! Reads the same configuration as the agents, plus either `origin` and
+//! `originTxHash` (and optionally `messageId`, to pick a single message out of
+//! the transaction), or just a `messageId`. Messages traced by id are looked up
+//! in the relayer database at `db` if set, or else in the latest
+//! `dispatchLookback` blocks of every configured chain.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use eyre::Result;
+use hyperlane_base::LoadableFromSettings;
+
+use crate::{settings::TracerSettings, trace::Tracer};
+
+mod report;
+mod settings;
+mod trace;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let settings = TracerSettings::load()?;
+    let metrics = settings.metrics("tracer")?;
+
+    let traces = Tracer::new(settings, metrics).trace().await?;
+    for trace in traces {
+        println!("{trace}\n");
+    }
+    Ok(())
+}
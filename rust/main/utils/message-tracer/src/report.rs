@@ -0,0 +1,176 @@
+//! The outcome of tracing a message, and the diagnosis derived from it.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use hyperlane_core::H256;
+
+/// The status of a single step along a message's path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    /// The step has completed
+    Done,
+    /// Something looks off, but it doesn't necessarily block the message
+    Warning,
+    /// The step hasn't completed yet
+    Pending,
+    /// The step could not be checked
+    Failed,
+    /// The step is not checked for this message
+    Skipped,
+}
+
+impl StepStatus {
+    fn symbol(&self) -> &'static str {
+        match self {
+            StepStatus::Done => "✔",
+            StepStatus::Warning => "!",
+            StepStatus::Pending => "…",
+            StepStatus::Failed => "✘",
+            StepStatus::Skipped => "-",
+        }
+    }
+
+    /// Whether the message cannot progress past a step with this status
+    fn is_blocking(&self) -> bool {
+        matches!(self, StepStatus::Pending | StepStatus::Failed)
+    }
+}
+
+/// A single step along a message's path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// What the step is
+    pub name: &'static str,
+    /// Whether the step completed
+    pub status: StepStatus,
+    /// What was found
+    pub details: String,
+}
+
+impl Step {
+    /// Create a new step
+    pub fn new(name: &'static str, status: StepStatus, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            details: details.into(),
+        }
+    }
+}
+
+/// Everything found while tracing a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTrace {
+    /// Id of the traced message
+    pub message_id: H256,
+    /// The steps along the message's path, in order
+    pub steps: Vec<Step>,
+}
+
+impl MessageTrace {
+    /// Create a trace without any steps yet
+    pub fn new(message_id: H256) -> Self {
+        Self {
+            message_id,
+            steps: vec![],
+        }
+    }
+
+    /// Record a step
+    pub fn push(&mut self, name: &'static str, status: StepStatus, details: impl Into<String>) {
+        self.steps.push(Step::new(name, status, details));
+    }
+
+    /// Whether the message was delivered
+    pub fn delivered(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| step.name == DELIVERY && step.status == StepStatus::Done)
+    }
+
+    /// The first step the message is stuck at, if it isn't delivered yet
+    pub fn stuck_at(&self) -> Option<&Step> {
+        if self.delivered() {
+            return None;
+        }
+        self.steps.iter().find(|step| step.status.is_blocking())
+    }
+
+    /// A one line diagnosis of the message's state
+    pub fn diagnosis(&self) -> String {
+        if self.delivered() {
+            return "Message was delivered".to_owned();
+        }
+        match self.stuck_at() {
+            Some(step) => format!("Message is stuck at `{}`: {}", step.name, step.details),
+            None => "Message is not delivered yet, but no step is known to be blocking it; \
+                 it may still be in the relayer's queue"
+                .to_owned(),
+        }
+    }
+}
+
+impl Display for MessageTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "Message {:?}", self.message_id)?;
+        for step in &self.steps {
+            writeln!(
+                f,
+                "  {} {:<22} {}",
+                step.status.symbol(),
+                step.name,
+                step.details
+            )?;
+        }
+        write!(f, "Diagnosis: {}", self.diagnosis())
+    }
+}
+
+/// Dispatch of the message on the origin chain
+pub const DISPATCH: &str = "dispatch";
+/// Payment for the message's destination gas
+pub const GAS_PAYMENT: &str = "gas payment";
+/// Finality of the message's merkle tree insertion on the origin chain
+pub const ORIGIN_FINALITY: &str = "origin finality";
+/// The ISM the recipient uses to verify the message
+pub const ISM: &str = "ism";
+/// Validator signatures over the message's checkpoint
+pub const VALIDATOR_SIGNATURES: &str = "validator signatures";
+/// Delivery of the message on the destination chain
+pub const DELIVERY: &str = "delivery";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diagnosis_points_at_first_blocking_step() {
+        let mut trace = MessageTrace::new(H256::zero());
+        trace.push(DISPATCH, StepStatus::Done, "nonce 1");
+        trace.push(GAS_PAYMENT, StepStatus::Warning, "no payment found");
+        trace.push(ORIGIN_FINALITY, StepStatus::Done, "final");
+        trace.push(VALIDATOR_SIGNATURES, StepStatus::Pending, "1/3 signed");
+        trace.push(DELIVERY, StepStatus::Pending, "not delivered");
+
+        assert_eq!(trace.stuck_at().unwrap().name, VALIDATOR_SIGNATURES);
+        assert_eq!(
+            trace.diagnosis(),
+            "Message is stuck at `validator signatures`: 1/3 signed"
+        );
+    }
+
+    #[test]
+    fn test_delivered_message_is_not_stuck() {
+        let mut trace = MessageTrace::new(H256::zero());
+        trace.push(DISPATCH, StepStatus::Done, "nonce 1");
+        trace.push(
+            VALIDATOR_SIGNATURES,
+            StepStatus::Failed,
+            "bucket unreachable",
+        );
+        trace.push(DELIVERY, StepStatus::Done, "delivered");
+
+        assert!(trace.stuck_at().is_none());
+        assert_eq!(trace.diagnosis(), "Message was delivered");
+    }
+}
@@ -0,0 +1,135 @@
+//! Tracer configuration.
+//!
+//! The tracer reads the same chain configuration as the agents, along with
+//! the message to trace, which is usually passed as command line arguments,
+//! e.g. `--origin ethereum --originTxHash 0x...` or `--messageId 0x...`.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::eyre;
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, H256, H512};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Settings for the message tracer
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct TracerSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    pub base: Settings,
+
+    /// What to trace
+    pub target: TraceTarget,
+    /// A relayer database to look up messages traced by id in. It is opened
+    /// read-only, so the relayer can keep running.
+    pub db: Option<PathBuf>,
+    /// How many of the latest blocks (or nonces, for chains indexed by
+    /// sequence) to scan for a message traced by id that isn't in `db`
+    pub dispatch_lookback: u32,
+}
+
+/// Default number of blocks scanned for the dispatch of a message traced by id
+const DEFAULT_DISPATCH_LOOKBACK: u32 = 10_000;
+
+/// The message(s) to trace
+#[derive(Debug, Clone)]
+pub enum TraceTarget {
+    /// The messages dispatched in a transaction on the origin chain,
+    /// optionally narrowed down to a single message id
+    OriginTx {
+        origin: HyperlaneDomain,
+        tx_hash: H512,
+        message_id: Option<H256>,
+    },
+    /// A message of which only the id is known
+    MessageId(H256),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawTracerSettings(Value);
+
+impl_loadable_from_settings!(Tracer, RawTracerSettings -> TracerSettings);
+
+impl FromRawConf<RawTracerSettings> for TracerSettings {
+    fn from_config_filtered(
+        raw: RawTracerSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                None,
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let origin_name = p.chain(&mut err).get_opt_key("origin").parse_string().end();
+        let tx_hash = p
+            .chain(&mut err)
+            .get_opt_key("originTxHash")
+            .parse_from_str::<H512>("Invalid origin tx hash")
+            .end();
+        let message_id = p
+            .chain(&mut err)
+            .get_opt_key("messageId")
+            .parse_address_hash()
+            .end();
+        let db = p
+            .chain(&mut err)
+            .get_opt_key("db")
+            .parse_string()
+            .end()
+            .map(PathBuf::from);
+        let dispatch_lookback = p
+            .chain(&mut err)
+            .get_opt_key("dispatchLookback")
+            .parse_u32()
+            .unwrap_or(DEFAULT_DISPATCH_LOOKBACK);
+
+        cfg_unwrap_all!(&p.cwp, err: [base]);
+
+        let target = match (origin_name, tx_hash, message_id) {
+            (Some(origin_name), Some(tx_hash), message_id) => base
+                .lookup_domain(origin_name)
+                .into_config_result(|| &p.cwp + "origin")
+                .take_config_err(&mut err)
+                .map(|origin| TraceTarget::OriginTx {
+                    origin,
+                    tx_hash,
+                    message_id,
+                }),
+            (None, None, Some(message_id)) => Some(TraceTarget::MessageId(message_id)),
+            _ => {
+                err.push(
+                    &p.cwp + "origin_tx_hash",
+                    eyre!("Expected either `origin` and `originTxHash`, or a `messageId`"),
+                );
+                None
+            }
+        };
+
+        cfg_unwrap_all!(&p.cwp, err: [target]);
+
+        err.into_result(Self {
+            base,
+            target,
+            db,
+            dispatch_lookback,
+        })
+    }
+}
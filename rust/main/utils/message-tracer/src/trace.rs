@@ -0,0 +1,666 @@
+//! Walks a message's path across chains using the agents' chain builders.
+
+use std::{ops::RangeInclusive, str::FromStr, sync::Arc};
+
+use eyre::{bail, eyre, Result};
+use futures::future::{join_all, BoxFuture};
+use hyperlane_base::{
+    db::{HyperlaneDb, HyperlaneRocksDB, DB},
+    settings::{ChainConf, CheckpointSyncerConf},
+    CoreMetrics, ValidatorQuorum,
+};
+use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, IndexMode, ModuleType, H256, H512, U256};
+
+use crate::{
+    report::{
+        MessageTrace, StepStatus, DELIVERY, DISPATCH, GAS_PAYMENT, ISM, ORIGIN_FINALITY,
+        VALIDATOR_SIGNATURES,
+    },
+    settings::{TraceTarget, TracerSettings},
+};
+
+/// Routing and aggregation ISMs are followed at most this deep, to guard against cycles.
+const MAX_ISM_DEPTH: usize = 8;
+
+/// Traces messages across the configured chains
+#[derive(Debug)]
+pub struct Tracer {
+    settings: TracerSettings,
+    metrics: Arc<CoreMetrics>,
+}
+
+impl Tracer {
+    /// Create a new tracer
+    pub fn new(settings: TracerSettings, metrics: Arc<CoreMetrics>) -> Self {
+        Self { settings, metrics }
+    }
+
+    /// Trace the configured target
+    pub async fn trace(&self) -> Result<Vec<MessageTrace>> {
+        match &self.settings.target {
+            TraceTarget::OriginTx {
+                origin,
+                tx_hash,
+                message_id,
+            } => {
+                let messages = self.dispatched_messages(origin, *tx_hash).await?;
+                let messages = messages
+                    .into_iter()
+                    .filter(|message| message_id.map_or(true, |id| id == message.id()))
+                    .collect::<Vec<_>>();
+                if messages.is_empty() {
+                    return Err(eyre!(
+                        "No matching message was dispatched in {tx_hash:?} on {origin}"
+                    ));
+                }
+                let mut traces = Vec::with_capacity(messages.len());
+                for message in messages {
+                    traces.push(self.trace_message(origin, *tx_hash, &message).await);
+                }
+                Ok(traces)
+            }
+            TraceTarget::MessageId(message_id) => {
+                Ok(vec![self.trace_message_id(*message_id).await])
+            }
+        }
+    }
+
+    async fn dispatched_messages(
+        &self,
+        origin: &HyperlaneDomain,
+        tx_hash: H512,
+    ) -> Result<Vec<HyperlaneMessage>> {
+        let indexer = self
+            .chain_conf(origin)?
+            .build_message_indexer(&self.metrics, false)
+            .await?;
+        let logs = indexer.fetch_logs_by_tx_hash(tx_hash).await?;
+        Ok(logs
+            .into_iter()
+            .map(|(message, _)| message.inner().clone())
+            .collect())
+    }
+
+    /// Trace a message of which only the id is known, by finding its dispatch
+    /// on one of the configured chains and tracing it from there.
+    async fn trace_message_id(&self, message_id: H256) -> MessageTrace {
+        match self.find_dispatch(message_id).await {
+            Ok((origin, tx_hash, message)) => self.trace_message(&origin, tx_hash, &message).await,
+            Err(err) => {
+                let mut trace = MessageTrace::new(message_id);
+                trace.push(
+                    DISPATCH,
+                    StepStatus::Failed,
+                    format!("{err}; pass `origin` and `originTxHash` to trace it"),
+                );
+                trace
+            }
+        }
+    }
+
+    /// The origin, dispatch transaction and contents of a message
+    async fn find_dispatch(
+        &self,
+        message_id: H256,
+    ) -> Result<(HyperlaneDomain, H512, HyperlaneMessage)> {
+        let db = self
+            .settings
+            .db
+            .as_deref()
+            .map(DB::from_path_read_only)
+            .transpose()?;
+        let searches = self.settings.chains.values().map(|conf| {
+            let db = db.clone();
+            async move {
+                let found = self.find_dispatch_on(conf, db, message_id).await;
+                (conf.domain.clone(), found)
+            }
+        });
+        let mut failed = vec![];
+        for (domain, found) in join_all(searches).await {
+            match found {
+                Ok(Some((tx_hash, message))) => return Ok((domain, tx_hash, message)),
+                Ok(None) => {}
+                Err(err) => failed.push(format!("{domain} ({err})")),
+            }
+        }
+        let searched = if db.is_some() {
+            "the relayer database or "
+        } else {
+            ""
+        };
+        let lookback = self.settings.dispatch_lookback;
+        if failed.is_empty() {
+            Err(eyre!(
+                "the dispatch was not found in {searched}the latest {lookback} blocks of any configured chain"
+            ))
+        } else {
+            Err(eyre!(
+                "the dispatch was not found in {searched}the latest {lookback} blocks of any reachable chain; could not search {}",
+                failed.join(", ")
+            ))
+        }
+    }
+
+    /// Looks for the dispatch of a message on a single chain, in the ranges the
+    /// relayer database places it in or else the latest ones.
+    async fn find_dispatch_on(
+        &self,
+        conf: &ChainConf,
+        db: Option<DB>,
+        message_id: H256,
+    ) -> Result<Option<(H512, HyperlaneMessage)>> {
+        let indexer = conf.build_message_indexer(&self.metrics, false).await?;
+        let db_range = db
+            .map(|db| {
+                dispatch_range_from_db(conf, HyperlaneRocksDB::new(&conf.domain, db), message_id)
+            })
+            .transpose()?
+            .flatten();
+        let ranges = match db_range {
+            Some(range) => vec![range],
+            None => {
+                let (count, tip) = indexer.latest_sequence_count_and_tip().await?;
+                let end = match conf.index.mode {
+                    IndexMode::Block => Some(tip),
+                    IndexMode::Sequence => count.and_then(|count| count.checked_sub(1)),
+                };
+                match end {
+                    Some(end) => {
+                        latest_ranges(end, self.settings.dispatch_lookback, conf.index.chunk_size)
+                    }
+                    None => vec![],
+                }
+            }
+        };
+        for range in ranges {
+            let found = indexer
+                .fetch_logs_in_range(range)
+                .await?
+                .into_iter()
+                .find(|(message, _)| message.inner().id() == message_id);
+            if let Some((message, meta)) = found {
+                return Ok(Some((meta.transaction_id, message.inner().clone())));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn trace_message(
+        &self,
+        origin: &HyperlaneDomain,
+        tx_hash: H512,
+        message: &HyperlaneMessage,
+    ) -> MessageTrace {
+        let message_id = message.id();
+        let mut trace = MessageTrace::new(message_id);
+        trace.push(
+            DISPATCH,
+            StepStatus::Done,
+            format!(
+                "nonce {} from {:?} on {origin} to {:?} on domain {}",
+                message.nonce, message.sender, message.recipient, message.destination
+            ),
+        );
+
+        let origin_conf = match self.chain_conf(origin) {
+            Ok(conf) => conf,
+            Err(err) => {
+                trace.push(GAS_PAYMENT, StepStatus::Failed, err.to_string());
+                return trace;
+            }
+        };
+
+        match self.gas_payment(origin_conf, tx_hash, message_id).await {
+            Ok(Some((payment, gas_amount))) => trace.push(
+                GAS_PAYMENT,
+                StepStatus::Done,
+                format!("paid {payment} for {gas_amount} gas in the dispatch transaction"),
+            ),
+            Ok(None) => trace.push(
+                GAS_PAYMENT,
+                StepStatus::Warning,
+                "no payment found in the dispatch transaction; relayers enforcing gas payments won't deliver the message unless it was paid for separately",
+            ),
+            Err(err) => trace.push(GAS_PAYMENT, StepStatus::Failed, err.to_string()),
+        }
+
+        let leaf_index = match self.origin_finality(origin_conf, tx_hash, message).await {
+            Ok((leaf_index, status, details)) => {
+                trace.push(ORIGIN_FINALITY, status, details);
+                Some(leaf_index)
+            }
+            Err(err) => {
+                trace.push(ORIGIN_FINALITY, StepStatus::Failed, err.to_string());
+                None
+            }
+        };
+
+        let destination_conf = match self
+            .settings
+            .chains
+            .values()
+            .find(|conf| conf.domain.id() == message.destination)
+        {
+            Some(conf) => conf,
+            None => {
+                trace.push(
+                    ISM,
+                    StepStatus::Failed,
+                    format!(
+                        "destination domain {} is not configured",
+                        message.destination
+                    ),
+                );
+                return trace;
+            }
+        };
+
+        match self.resolve_ism(destination_conf, message).await {
+            Ok(isms) => {
+                let description = isms
+                    .iter()
+                    .map(|(address, module_type)| format!("{module_type:?} at {address:?}"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                trace.push(ISM, StepStatus::Done, description);
+
+                let multisig_isms = match isms.last() {
+                    Some(&(address, module_type)) => {
+                        self.multisig_isms(destination_conf, message, address, module_type, 0)
+                            .await
+                    }
+                    None => Ok(vec![]),
+                };
+                match (multisig_isms, leaf_index) {
+                    (Ok(multisig_isms), _) if multisig_isms.is_empty() => trace.push(
+                        VALIDATOR_SIGNATURES,
+                        StepStatus::Skipped,
+                        "the ISM does not use validator signatures",
+                    ),
+                    (Ok(multisig_isms), Some(leaf_index)) => {
+                        for (ism, module_type) in multisig_isms {
+                            let (status, details) = self
+                                .validator_signatures(
+                                    origin_conf,
+                                    destination_conf,
+                                    ism,
+                                    module_type,
+                                    message,
+                                    leaf_index,
+                                )
+                                .await
+                                .unwrap_or_else(|err| (StepStatus::Failed, err.to_string()));
+                            trace.push(VALIDATOR_SIGNATURES, status, details);
+                        }
+                    }
+                    (Ok(_), None) => trace.push(
+                        VALIDATOR_SIGNATURES,
+                        StepStatus::Failed,
+                        "the message's merkle tree leaf index is unknown",
+                    ),
+                    (Err(err), _) => {
+                        trace.push(VALIDATOR_SIGNATURES, StepStatus::Failed, err.to_string())
+                    }
+                }
+            }
+            Err(err) => trace.push(ISM, StepStatus::Failed, err.to_string()),
+        }
+
+        let delivered = match destination_conf.build_mailbox(&self.metrics).await {
+            Ok(mailbox) => mailbox.delivered(message_id).await.map_err(Into::into),
+            Err(err) => Err(err),
+        };
+        match delivered {
+            Ok(true) => trace.push(DELIVERY, StepStatus::Done, "delivered"),
+            Ok(false) => trace.push(DELIVERY, StepStatus::Pending, "not delivered yet"),
+            Err(err) => trace.push(DELIVERY, StepStatus::Failed, err.to_string()),
+        }
+
+        trace
+    }
+
+    /// The total payment and gas amount paid for the message in the dispatch tx, if any.
+    async fn gas_payment(
+        &self,
+        origin_conf: &ChainConf,
+        tx_hash: H512,
+        message_id: H256,
+    ) -> Result<Option<(U256, U256)>> {
+        let indexer = origin_conf
+            .build_interchain_gas_payment_indexer(&self.metrics, false)
+            .await?;
+        let payments = indexer
+            .fetch_logs_by_tx_hash(tx_hash)
+            .await?
+            .into_iter()
+            .map(|(payment, _)| *payment.inner())
+            .filter(|payment| payment.message_id == message_id)
+            .collect::<Vec<_>>();
+        if payments.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(payments.iter().fold(
+            (U256::zero(), U256::zero()),
+            |(payment, gas_amount), p| (payment + p.payment, gas_amount + p.gas_amount),
+        )))
+    }
+
+    /// Whether the message's merkle tree insertion is final on the origin, and
+    /// hence can be signed by validators.
+    async fn origin_finality(
+        &self,
+        origin_conf: &ChainConf,
+        tx_hash: H512,
+        message: &HyperlaneMessage,
+    ) -> Result<(u32, StepStatus, String)> {
+        let message_id = message.id();
+        let insertion = origin_conf
+            .build_merkle_tree_hook_indexer(&self.metrics, false)
+            .await?
+            .fetch_logs_by_tx_hash(tx_hash)
+            .await?
+            .into_iter()
+            .map(|(insertion, _)| *insertion.inner())
+            .find(|insertion| insertion.message_id() == message_id);
+        let Some(insertion) = insertion else {
+            return Ok((
+                message.nonce,
+                StepStatus::Pending,
+                "the message was not inserted into the origin's merkle tree hook; validators won't sign it".to_owned(),
+            ));
+        };
+        let leaf_index = insertion.index();
+
+        let checkpoint = origin_conf
+            .build_merkle_tree_hook(&self.metrics)
+            .await?
+            .latest_checkpoint(&origin_conf.reorg_period)
+            .await?;
+        if checkpoint.index >= leaf_index {
+            Ok((
+                leaf_index,
+                StepStatus::Done,
+                format!(
+                    "leaf {leaf_index} is final (latest final checkpoint index {})",
+                    checkpoint.index
+                ),
+            ))
+        } else {
+            Ok((
+                leaf_index,
+                StepStatus::Pending,
+                format!(
+                    "leaf {leaf_index} is not final yet (latest final checkpoint index {}, reorg period {:?})",
+                    checkpoint.index, origin_conf.reorg_period
+                ),
+            ))
+        }
+    }
+
+    /// The ISMs the message is verified by, following routing ISMs.
+    async fn resolve_ism(
+        &self,
+        destination_conf: &ChainConf,
+        message: &HyperlaneMessage,
+    ) -> Result<Vec<(H256, ModuleType)>> {
+        let mailbox = destination_conf.build_mailbox(&self.metrics).await?;
+        let mut address = mailbox.recipient_ism(message.recipient).await?;
+        let mut path = vec![];
+        for _ in 0..MAX_ISM_DEPTH {
+            let module_type = destination_conf
+                .build_ism(address, &self.metrics)
+                .await?
+                .module_type()
+                .await?;
+            path.push((address, module_type));
+            if module_type != ModuleType::Routing {
+                return Ok(path);
+            }
+            address = destination_conf
+                .build_routing_ism(address, &self.metrics)
+                .await?
+                .route(message)
+                .await?;
+        }
+        Err(eyre!(
+            "Routing ISMs nest deeper than {MAX_ISM_DEPTH} levels"
+        ))
+    }
+
+    /// The multisig ISMs that verify the message under the ISM at `address`,
+    /// following routing ISMs and the modules of aggregation ISMs.
+    fn multisig_isms<'a>(
+        &'a self,
+        destination_conf: &'a ChainConf,
+        message: &'a HyperlaneMessage,
+        address: H256,
+        module_type: ModuleType,
+        depth: usize,
+    ) -> BoxFuture<'a, Result<Vec<(H256, ModuleType)>>> {
+        Box::pin(async move {
+            if depth >= MAX_ISM_DEPTH {
+                bail!("ISMs nest deeper than {MAX_ISM_DEPTH} levels");
+            }
+            let modules = match module_type {
+                ModuleType::Routing => vec![
+                    destination_conf
+                        .build_routing_ism(address, &self.metrics)
+                        .await?
+                        .route(message)
+                        .await?,
+                ],
+                ModuleType::Aggregation => {
+                    destination_conf
+                        .build_aggregation_ism(address, &self.metrics)
+                        .await?
+                        .modules_and_threshold(message)
+                        .await?
+                        .0
+                }
+                module_type if is_multisig(module_type) => return Ok(vec![(address, module_type)]),
+                _ => return Ok(vec![]),
+            };
+            let mut multisig_isms = vec![];
+            for module in modules {
+                let module_type = destination_conf
+                    .build_ism(module, &self.metrics)
+                    .await?
+                    .module_type()
+                    .await?;
+                multisig_isms.extend(
+                    self.multisig_isms(destination_conf, message, module, module_type, depth + 1)
+                        .await?,
+                );
+            }
+            Ok(multisig_isms)
+        })
+    }
+
+    /// Whether the multisig ISM's validators that signed a checkpoint for the
+    /// message reach its quorum.
+    async fn validator_signatures(
+        &self,
+        origin_conf: &ChainConf,
+        destination_conf: &ChainConf,
+        multisig_ism: H256,
+        module_type: ModuleType,
+        message: &HyperlaneMessage,
+        leaf_index: u32,
+    ) -> Result<(StepStatus, String)> {
+        let (validators, quorum) = match module_type {
+            ModuleType::WeightedMerkleRootMultisig | ModuleType::WeightedMessageIdMultisig => {
+                let (validators, threshold_weight) = destination_conf
+                    .build_weighted_multisig_ism(multisig_ism, &self.metrics)
+                    .await?
+                    .validators_and_threshold_weight(message)
+                    .await?;
+                let (validators, weights): (Vec<_>, _) = validators.into_iter().unzip();
+                (
+                    validators,
+                    ValidatorQuorum::Weighted {
+                        weights,
+                        threshold_weight,
+                    },
+                )
+            }
+            _ => {
+                let (validators, threshold) = destination_conf
+                    .build_multisig_ism(multisig_ism, &self.metrics)
+                    .await?
+                    .validators_and_threshold(message)
+                    .await?;
+                (validators, ValidatorQuorum::Threshold(threshold as usize))
+            }
+        };
+        let locations = origin_conf
+            .build_validator_announce(&self.metrics)
+            .await?
+            .get_announced_storage_locations(&validators)
+            .await?;
+
+        let checks = validators
+            .iter()
+            .zip(locations)
+            .map(|(validator, locations)| async move {
+                (
+                    *validator,
+                    signed_checkpoint_at_any(&locations, leaf_index, message.id()).await,
+                )
+            });
+        let mut signed = 0;
+        let mut signed_weight: u128 = 0;
+        let mut missing = vec![];
+        for (validator_index, (validator, result)) in join_all(checks).await.into_iter().enumerate()
+        {
+            match result {
+                Ok(true) => {
+                    signed += 1;
+                    signed_weight = signed_weight.saturating_add(quorum.weight(validator_index));
+                }
+                Ok(false) => missing.push(format!("{validator:?}")),
+                Err(err) => missing.push(format!("{validator:?} ({err})")),
+            }
+        }
+
+        let summary = match quorum {
+            ValidatorQuorum::Threshold(threshold) => format!(
+                "{signed}/{} validators of the ISM at {multisig_ism:?} signed leaf {leaf_index}, threshold {threshold}",
+                validators.len()
+            ),
+            ValidatorQuorum::Weighted {
+                threshold_weight, ..
+            } => format!(
+                "{signed}/{} validators of the ISM at {multisig_ism:?} signed leaf {leaf_index}, weight {signed_weight} of threshold {threshold_weight}",
+                validators.len()
+            ),
+        };
+        if signed_weight >= quorum.threshold() {
+            Ok((StepStatus::Done, summary))
+        } else {
+            Ok((
+                StepStatus::Pending,
+                format!("{summary}; missing {}", missing.join(", ")),
+            ))
+        }
+    }
+
+    fn chain_conf(&self, domain: &HyperlaneDomain) -> Result<&ChainConf> {
+        self.settings.chain_setup(domain)
+    }
+}
+
+/// Whether the ISM verifies messages with validator signatures.
+fn is_multisig(module_type: ModuleType) -> bool {
+    matches!(
+        module_type,
+        ModuleType::MerkleRootMultisig
+            | ModuleType::MessageIdMultisig
+            | ModuleType::WeightedMerkleRootMultisig
+            | ModuleType::WeightedMessageIdMultisig
+    )
+}
+
+/// Whether any of the validator's announced checkpoint storages holds a
+/// checkpoint for the message, trying the latest announced one first.
+async fn signed_checkpoint_at_any(
+    locations: &[String],
+    leaf_index: u32,
+    message_id: H256,
+) -> Result<bool> {
+    let mut errors = vec![];
+    for location in locations.iter().rev() {
+        match signed_checkpoint(location, leaf_index, message_id).await {
+            Ok(true) => return Ok(true),
+            Ok(false) => {}
+            Err(err) => errors.push(format!("{location}: {err}")),
+        }
+    }
+    if locations.is_empty() {
+        bail!("no announced storage location");
+    }
+    if errors.len() == locations.len() {
+        bail!("{}", errors.join("; "));
+    }
+    Ok(false)
+}
+
+/// Whether the validator's checkpoint storage holds a checkpoint for the message.
+async fn signed_checkpoint(location: &str, leaf_index: u32, message_id: H256) -> Result<bool> {
+    let syncer = CheckpointSyncerConf::from_str(location)?
+        .build_and_validate(None)
+        .await?;
+    let checkpoint = syncer.fetch_checkpoint(leaf_index).await?;
+    Ok(checkpoint.map_or(false, |checkpoint| {
+        checkpoint.value.message_id == message_id
+    }))
+}
+
+/// Where the relayer database says the message was dispatched, in the unit
+/// the chain is indexed by
+fn dispatch_range_from_db(
+    conf: &ChainConf,
+    db: HyperlaneRocksDB,
+    message_id: H256,
+) -> Result<Option<RangeInclusive<u32>>> {
+    let Some(message) = db.retrieve_message_by_id(&message_id)? else {
+        return Ok(None);
+    };
+    let position = match conf.index.mode {
+        IndexMode::Sequence => message.nonce,
+        IndexMode::Block => match db.retrieve_dispatched_block_number_by_nonce(&message.nonce)? {
+            Some(block) => u32::try_from(block)?,
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(position..=position))
+}
+
+/// Ranges of at most `chunk_size` covering the `lookback` positions up to and
+/// including `end`, newest first
+fn latest_ranges(end: u32, lookback: u32, chunk_size: u32) -> Vec<RangeInclusive<u32>> {
+    let start = end.saturating_sub(lookback.saturating_sub(1));
+    let chunk_size = chunk_size.max(1);
+    let mut ranges = vec![];
+    let mut to = end;
+    loop {
+        let from = to.saturating_sub(chunk_size - 1).max(start);
+        ranges.push(from..=to);
+        if from == start {
+            return ranges;
+        }
+        to = from - 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_ranges() {
+        assert_eq!(latest_ranges(100, 25, 10), vec![91..=100, 81..=90, 76..=80]);
+        assert_eq!(latest_ranges(5, 25, 10), vec![0..=5]);
+        assert_eq!(latest_ranges(100, 1, 10), vec![100..=100]);
+    }
+}
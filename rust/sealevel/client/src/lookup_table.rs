@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, system_program};
+
+use hyperlane_sealevel_mailbox::{
+    mailbox_inbox_pda_seeds, mailbox_process_authority_pda_seeds, spl_noop,
+};
+
+use crate::{Context, LookupTableAccounts, LookupTableCmd, LookupTableSubCmd};
+
+/// How many addresses are added per extend transaction, to stay within the
+/// transaction size limit.
+const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+pub(crate) fn process_lookup_table_cmd(ctx: Context, cmd: LookupTableCmd) {
+    match cmd.cmd {
+        LookupTableSubCmd::Create(create) => {
+            let recent_slot = ctx
+                .client
+                .get_slot_with_commitment(CommitmentConfig::finalized())
+                .unwrap();
+            let (instruction, lookup_table) =
+                create_lookup_table(ctx.payer_pubkey, ctx.payer_pubkey, recent_slot);
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Create address lookup table {}", lookup_table),
+                )
+                .send_with_payer();
+            println!("Created address lookup table {}", lookup_table);
+
+            extend(&ctx, lookup_table, vec![], &create.accounts);
+        }
+        LookupTableSubCmd::Extend(args) => {
+            let existing = fetch_lookup_table_addresses(&ctx, &args.address);
+            extend(&ctx, args.address, existing, &args.accounts);
+        }
+        LookupTableSubCmd::Query(query) => {
+            let addresses = fetch_lookup_table_addresses(&ctx, &query.address);
+            println!(
+                "Address lookup table {} has {} addresses:",
+                query.address,
+                addresses.len()
+            );
+            for address in addresses {
+                println!("\t{}", address);
+            }
+        }
+    }
+}
+
+/// Adds the accounts specified by `args` that are not in `existing` to the
+/// lookup table.
+fn extend(ctx: &Context, lookup_table: Pubkey, existing: Vec<Pubkey>, args: &LookupTableAccounts) {
+    let mut seen: HashSet<Pubkey> = existing.into_iter().collect();
+    let new_addresses = accounts_to_add(args)
+        .into_iter()
+        .filter(|address| seen.insert(*address))
+        .collect::<Vec<_>>();
+
+    if new_addresses.is_empty() {
+        println!("No new addresses to add to {}", lookup_table);
+        return;
+    }
+
+    for chunk in new_addresses.chunks(MAX_ADDRESSES_PER_EXTEND) {
+        ctx.new_txn()
+            .add_with_description(
+                extend_lookup_table(
+                    lookup_table,
+                    ctx.payer_pubkey,
+                    Some(ctx.payer_pubkey),
+                    chunk.to_vec(),
+                ),
+                format!(
+                    "Add {} addresses to address lookup table {}",
+                    chunk.len(),
+                    lookup_table
+                ),
+            )
+            .send_with_payer();
+    }
+    println!(
+        "Added {} addresses to address lookup table {}",
+        new_addresses.len(),
+        lookup_table
+    );
+}
+
+/// The accounts to add to a lookup table. If a recipient is given, these
+/// include the mailbox accounts that every delivery to it uses.
+fn accounts_to_add(args: &LookupTableAccounts) -> Vec<Pubkey> {
+    let mut accounts = vec![];
+    if let Some(recipient) = args.recipient {
+        let (inbox, _inbox_bump) =
+            Pubkey::find_program_address(mailbox_inbox_pda_seeds!(), &args.mailbox);
+        let (process_authority, _process_authority_bump) = Pubkey::find_program_address(
+            mailbox_process_authority_pda_seeds!(&recipient),
+            &args.mailbox,
+        );
+        accounts.extend([
+            system_program::id(),
+            inbox,
+            process_authority,
+            spl_noop::id(),
+            recipient,
+        ]);
+    }
+    accounts.extend(args.accounts.iter().copied());
+    accounts
+}
+
+fn fetch_lookup_table_addresses(ctx: &Context, lookup_table: &Pubkey) -> Vec<Pubkey> {
+    let account = ctx
+        .client
+        .get_account_with_commitment(lookup_table, ctx.commitment)
+        .unwrap()
+        .value
+        .expect("Address lookup table not found");
+    AddressLookupTable::deserialize(&account.data)
+        .unwrap()
+        .addresses
+        .to_vec()
+}
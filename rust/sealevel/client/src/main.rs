@@ -66,6 +66,7 @@ mod context;
 mod r#core;
 mod helloworld;
 mod igp;
+mod lookup_table;
 mod multisig_ism;
 mod router;
 mod serde;
@@ -73,6 +74,7 @@ mod warp_route;
 
 use crate::helloworld::process_helloworld_cmd;
 use crate::igp::process_igp_cmd;
+use crate::lookup_table::process_lookup_table_cmd;
 use crate::multisig_ism::process_multisig_ism_message_id_cmd;
 use crate::warp_route::process_warp_route_cmd;
 pub(crate) use crate::{context::*, core::*};
@@ -113,6 +115,7 @@ enum HyperlaneSealevelCmd {
     MultisigIsmMessageId(MultisigIsmMessageIdCmd),
     WarpRoute(WarpRouteCmd),
     HelloWorld(HelloWorldCmd),
+    LookupTable(LookupTableCmd),
 }
 
 #[derive(Args)]
@@ -700,6 +703,52 @@ pub(crate) struct HelloWorldQuery {
     program_id: Pubkey,
 }
 
+#[derive(Args)]
+pub(crate) struct LookupTableCmd {
+    #[command(subcommand)]
+    cmd: LookupTableSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum LookupTableSubCmd {
+    Create(LookupTableCreate),
+    Extend(LookupTableExtend),
+    Query(LookupTableQuery),
+}
+
+/// The accounts to add to an address lookup table.
+#[derive(Args)]
+pub(crate) struct LookupTableAccounts {
+    /// Adds the mailbox accounts used when delivering to this recipient
+    #[arg(long)]
+    recipient: Option<Pubkey>,
+    #[arg(long, default_value_t = MAILBOX_PROG_ID)]
+    mailbox: Pubkey,
+    /// Additional accounts, e.g. the ISM's and the recipient's handle accounts
+    #[arg(long, value_delimiter = ',')]
+    accounts: Vec<Pubkey>,
+}
+
+#[derive(Args)]
+pub(crate) struct LookupTableCreate {
+    #[command(flatten)]
+    accounts: LookupTableAccounts,
+}
+
+#[derive(Args)]
+pub(crate) struct LookupTableExtend {
+    #[arg(long)]
+    address: Pubkey,
+    #[command(flatten)]
+    accounts: LookupTableAccounts,
+}
+
+#[derive(Args)]
+pub(crate) struct LookupTableQuery {
+    #[arg(long)]
+    address: Pubkey,
+}
+
 fn main() {
     pretty_env_logger::init();
 
@@ -776,6 +825,7 @@ fn main() {
         HyperlaneSealevelCmd::WarpRoute(cmd) => process_warp_route_cmd(ctx, cmd),
         HyperlaneSealevelCmd::HelloWorld(cmd) => process_helloworld_cmd(ctx, cmd),
         HyperlaneSealevelCmd::Igp(cmd) => process_igp_cmd(ctx, cmd),
+        HyperlaneSealevelCmd::LookupTable(cmd) => process_lookup_table_cmd(ctx, cmd),
     }
 }
 
@@ -44,16 +44,26 @@ export enum AgentIndexMode {
   Sequence = 'sequence',
 }
 
+export enum AgentArchiveFormat {
+  Hyperlane = 'hyperlane',
+  EthereumLogs = 'ethereumLogs',
+  EthereumLogsParquet = 'ethereumLogsParquet',
+  SealevelTransactions = 'sealevelTransactions',
+  SealevelAccounts = 'sealevelAccounts',
+}
+
 export enum AgentSignerKeyType {
   Aws = 'aws',
   Hex = 'hexKey',
   Node = 'node',
   Cosmos = 'cosmosKey',
+  Starknet = 'starkKey',
 }
 
 export enum AgentSealevelPriorityFeeOracleType {
   Helius = 'helius',
   Constant = 'constant',
+  RecentFees = 'recentFees',
 }
 
 export enum AgentSealevelHeliusFeeLevel {
@@ -66,9 +76,22 @@ export enum AgentSealevelHeliusFeeLevel {
   UnsafeMax = 'unsafeMax',
 }
 
+export enum AgentCosmosConnectionMode {
+  CosmWasm = 'cosmwasm',
+  Native = 'native',
+}
+
+export enum AgentCosmosGasPriceOracleType {
+  Static = 'static',
+  FeeMarket = 'feemarket',
+  OsmosisTxFees = 'osmosisTxFees',
+}
+
 export enum AgentSealevelTransactionSubmitterType {
   Rpc = 'rpc',
   Jito = 'jito',
+  JitoBundle = 'jitoBundle',
+  MultiRpc = 'multiRpc',
 }
 
 const AgentSignerHexKeySchema = z
@@ -93,6 +116,17 @@ const AgentSignerCosmosKeySchema = z
     key: ZHash,
   })
   .describe('Cosmos key');
+const AgentSignerStarknetKeySchema = z
+  .object({
+    type: z.literal(AgentSignerKeyType.Starknet),
+    key: ZHash,
+    address: ZHash.describe('The address of the account contract'),
+    isLegacy: z
+      .boolean()
+      .optional()
+      .describe('Whether the account uses the legacy Cairo 0 calldata encoding'),
+  })
+  .describe('Starknet key');
 const AgentSignerNodeSchema = z
   .object({
     type: z.literal(AgentSignerKeyType.Node),
@@ -103,12 +137,16 @@ const AgentSignerSchema = z.union([
   AgentSignerHexKeySchema,
   AgentSignerAwsKeySchema,
   AgentSignerCosmosKeySchema,
+  AgentSignerStarknetKeySchema,
   AgentSignerNodeSchema,
 ]);
 
 export type AgentSignerHexKey = z.infer<typeof AgentSignerHexKeySchema>;
 export type AgentSignerAwsKey = z.infer<typeof AgentSignerAwsKeySchema>;
 export type AgentSignerCosmosKey = z.infer<typeof AgentSignerNodeSchema>;
+export type AgentSignerStarknetKey = z.infer<
+  typeof AgentSignerStarknetKeySchema
+>;
 export type AgentSignerNode = z.infer<typeof AgentSignerNodeSchema>;
 export type AgentSigner = z.infer<typeof AgentSignerSchema>;
 
@@ -134,6 +172,27 @@ const AgentCosmosChainMetadataSchema = z.object({
     .positive()
     .lte(32)
     .describe('The number of bytes used to represent a contract address.'),
+  connectionMode: z
+    .nativeEnum(AgentCosmosConnectionMode)
+    .optional()
+    .describe(
+      'Whether Hyperlane is deployed as CosmWasm contracts or as native Cosmos SDK modules. Defaults to cosmwasm.',
+    ),
+  gasPriceOracle: z
+    .object({
+      type: z.nativeEnum(AgentCosmosGasPriceOracleType),
+      multiplier: z
+        .number()
+        .positive()
+        .optional()
+        .describe(
+          'Multiplier applied to the queried gas price. Defaults to 1.',
+        ),
+    })
+    .optional()
+    .describe(
+      'How the gas price is determined. Dynamic prices never go below gasPrice. Defaults to static.',
+    ),
 });
 
 export type AgentCosmosGasPrice = z.infer<
@@ -154,14 +213,36 @@ const AgentSealevelChainMetadataSchema = z.object({
         // In microlamports
         fee: ZUWei,
       }),
+      z.object({
+        type: z.literal(AgentSealevelPriorityFeeOracleType.RecentFees),
+        percentile: z.number().int().min(0).max(100).optional(),
+        // In microlamports
+        floor: ZUWei.optional(),
+        // In microlamports
+        cap: ZUWei.optional(),
+        // Percent increase per resubmission after a blockhash expiry
+        escalationPercent: z.number().int().nonnegative().optional(),
+      }),
     ])
     .optional(),
   transactionSubmitter: z
     .object({
       type: z.nativeEnum(AgentSealevelTransactionSubmitterType),
       url: z.string().optional(),
+      urls: z
+        .string()
+        .optional()
+        .describe(
+          'Comma separated list of additional RPC URLs to send transactions to, for the multiRpc submitter.',
+        ),
     })
     .optional(),
+  addressLookupTables: z
+    .string()
+    .optional()
+    .describe(
+      'Comma separated list of address lookup tables to use for transactions that are too large to be sent as legacy transactions.',
+    ),
 });
 
 export type AgentSealevelChainMetadata = z.infer<
@@ -188,6 +269,9 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
       .nativeEnum(RpcConsensusType)
       .describe('The consensus type to use when multiple RPCs are configured.')
       .optional(),
+    arbSendRootLookbackBlocks: ZNzUint.optional().describe(
+      'How many blocks to search back for the latest confirmed Arbitrum send root when relaying to ArbL2ToL1 ISMs on this chain. Defaults to 7200.',
+    ),
     signer: AgentSignerSchema.optional().describe(
       'The signer to use for this chain',
     ),
@@ -199,12 +283,42 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
         chunk: ZNzUint.optional().describe(
           'The number of blocks to index at a time.',
         ),
+        minChunk: ZNzUint.optional().describe(
+          'Enables adaptive chunk sizing; the smallest number of blocks to index at a time.',
+        ),
+        maxChunk: ZNzUint.optional().describe(
+          'Enables adaptive chunk sizing; the largest number of blocks to index at a time.',
+        ),
+        sparseRangeLogCount: ZUint.optional().describe(
+          'With adaptive chunk sizing, full ranges that yield fewer logs than this grow the chunk size. Defaults to 10.',
+        ),
         mode: z
           .nativeEnum(AgentIndexMode)
           .optional()
           .describe(
             'The indexing method to use for this chain; will attempt to choose a suitable default if not specified.',
           ),
+        archive: z
+          .object({
+            path: z
+              .string()
+              .describe(
+                'Path to a log export file, or a directory of export files.',
+              ),
+            format: z
+              .nativeEnum(AgentArchiveFormat)
+              .optional()
+              .describe(
+                'The format of the export files; defaults to Hyperlane JSONL.',
+              ),
+            handoffBlock: ZUint.describe(
+              'Blocks below this height are indexed from the archive, all others from the RPC. On Sealevel this is a sequence.',
+            ),
+          })
+          .optional()
+          .describe(
+            'Backfill historical ranges from archived log exports instead of the RPC.',
+          ),
       })
       .optional(),
   })
@@ -398,6 +512,50 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .describe(
       'A list of app contexts and their matching lists to use for metrics. A message will be classified as the first matching app context.',
     ),
+  ismCacheTtlSeconds: ZUint.optional().describe(
+    'How long ISM types, routes, modules and validator sets are cached for. Defaults to 600 seconds, 0 disables the cache.',
+  ),
+  ccipRead: z
+    .object({
+      timeoutSeconds: ZUint.optional().describe(
+        'How long a single CCIP read gateway request may take. Defaults to 10 seconds.',
+      ),
+      maxResponseBytes: ZUint.optional().describe(
+        'Gateway responses larger than this are discarded. Defaults to 256 KiB.',
+      ),
+      maxRetries: ZUint.optional().describe(
+        'How many times a gateway request is retried after a transient failure. Defaults to 2.',
+      ),
+      retryBackoffMs: ZUint.optional().describe(
+        'The delay before the first retry, doubled for every following one. Defaults to 500ms.',
+      ),
+      maxUrls: ZUint.optional().describe(
+        'How many of the gateway URLs of a lookup are tried. Defaults to 5.',
+      ),
+      totalTimeoutSeconds: ZUint.optional().describe(
+        'How long fetching the data of a lookup may take across all of its gateways and nested lookups. Defaults to 60 seconds.',
+      ),
+      allowedHosts: z
+        .string()
+        .optional()
+        .describe(
+          'Comma separated list of hosts. If set, only gateways on these hosts or their subdomains are queried. Otherwise gateways on private, loopback and link-local addresses are never queried.',
+        ),
+      deniedHosts: z
+        .string()
+        .optional()
+        .describe(
+          'Comma separated list of hosts. Gateways on these hosts or their subdomains are never queried.',
+        ),
+    })
+    .optional()
+    .describe('How the offchain gateways of CCIP read ISMs are queried.'),
+  aggregationMaxWaitSeconds: ZUint.optional().describe(
+    'How long aggregation ISM metadata may wait for sub-modules that are usually cheaper but whose metadata is not available yet. Defaults to 0, which disables waiting.',
+  ),
+  blockedIsmRetryDelaySeconds: ZUint.optional().describe(
+    'How long to wait before retrying a message whose ISM rejects every message, e.g. because it is paused or trusts another relayer. Defaults to 3600 seconds.',
+  ),
 });
 
 export type RelayerConfig = z.infer<typeof RelayerAgentConfigSchema>;
@@ -407,6 +565,41 @@ export const ScraperAgentConfigSchema = AgentConfigSchema.extend({
   chainsToScrape: CommaSeparatedChainList.describe(
     'Comma separated list of chain names to scrape',
   ),
+  sink: z
+    .object({
+      type: z
+        .enum(['webhook', 'kafka', 'nats'])
+        .describe('The kind of system indexed events are published to.'),
+      url: z
+        .string()
+        .url()
+        .describe(
+          'The webhook url, the base url of the Kafka REST proxy, or the url of the NATS server.',
+        ),
+      topic: z
+        .string()
+        .optional()
+        .describe('The Kafka topic to produce to. Required for kafka sinks.'),
+      subject: z
+        .string()
+        .optional()
+        .describe(
+          'The NATS subject to publish to, which must be captured by a JetStream stream. Required for nats sinks.',
+        ),
+      db: z
+        .string()
+        .min(1)
+        .describe(
+          'Path to the database persisting events yet to be published.',
+        ),
+      batchSize: ZUint.optional().describe(
+        'The maximum number of events published at once.',
+      ),
+    })
+    .optional()
+    .describe(
+      'Publish indexed dispatches, deliveries and gas payments to an external system, in addition to the database.',
+    ),
 });
 
 export type ScraperConfig = z.infer<typeof ScraperAgentConfigSchema>;